@@ -15,25 +15,47 @@
 //     implemented") and return CG_NULL_ID / CG_ERR_NO_RESULT.
 
 // ── OCCT includes ────────────────────────────────────────────────────────────
+#include <BRepAdaptor_Curve.hxx>
+#include <BRepAdaptor_Surface.hxx>
+#include <BRepAlgoAPI_Common.hxx>
+#include <BRepAlgoAPI_Cut.hxx>
+#include <BRepAlgoAPI_Fuse.hxx>
+#include <BRepAlgoAPI_Section.hxx>
 #include <BRepBndLib.hxx>
+#include <BRepGProp.hxx>
 #include <BRepMesh_IncrementalMesh.hxx>
+#include <BRepPrimAPI_MakeBox.hxx>
+#include <BRepPrimAPI_MakeCylinder.hxx>
 #include <BRep_Tool.hxx>
 #include <Bnd_Box.hxx>
+#include <GCPnts_TangentialDeflection.hxx>
+#include <GProp_GProps.hxx>
 #include <IFSelect_ReturnStatus.hxx>
+#include <IMeshTools_Parameters.hxx>
+#include <Message_ProgressIndicator.hxx>
+#include <Message_ProgressScope.hxx>
 #include <Poly_Triangulation.hxx>
 #include <RWStl.hxx>
 #include <STEPControl_Reader.hxx>
 #include <ShapeFix_Shape.hxx>
 #include <Standard_Failure.hxx>
 #include <TopAbs_Orientation.hxx>
+#include <TopExp.hxx>
 #include <TopExp_Explorer.hxx>
+#include <TopTools_IndexedMapOfShape.hxx>
 #include <TopoDS.hxx>
+#include <TopoDS_Edge.hxx>
 #include <TopoDS_Face.hxx>
+#include <TopoDS_Vertex.hxx>
 #include <TopLoc_Location.hxx>
+#include <gp_Ax2.hxx>
+#include <gp_Cylinder.hxx>
+#include <gp_Pln.hxx>
 #include <gp_Pnt.hxx>
 #include <gp_Vec.hxx>
 
 // ── Standard library includes ────────────────────────────────────────────────
+#include <algorithm>
 #include <atomic>
 #include <cmath>
 #include <cstring>
@@ -322,9 +344,33 @@ CgBbox cg_shape_bounding_box(CgShapeId id) {
     }
 }
 
-size_t cg_shape_faces(CgShapeId /*id*/, CgFaceId* /*out_faces*/, size_t /*capacity*/) {
-    set_last_error("not implemented");
-    return 0;
+size_t cg_shape_faces(CgShapeId id, CgFaceId* out_faces, size_t capacity) {
+    if (id == CG_NULL_ID) {
+        set_last_error("cg_shape_faces: null handle");
+        return 0;
+    }
+    try {
+        // The indexed map visits each face once, in a stable order.
+        TopTools_IndexedMapOfShape faces;
+        TopExp::MapShapes(registry_get_shape(id), TopAbs_FACE, faces);
+        const size_t count = static_cast<size_t>(faces.Extent());
+        if (out_faces) {
+            const size_t n = std::min(count, capacity);
+            for (size_t i = 0; i < n; ++i) {
+                out_faces[i] = registry_store_shape(faces(static_cast<int>(i) + 1));
+            }
+        }
+        return count;
+    } catch (const std::out_of_range&) {
+        set_last_error("cg_shape_faces: invalid shape ID");
+        return 0;
+    } catch (const Standard_Failure& ex) {
+        set_last_error(std::string("Face traversal exception: ") + ex.GetMessageString());
+        return 0;
+    } catch (...) {
+        set_last_error("Face traversal: unknown exception");
+        return 0;
+    }
 }
 
 size_t cg_shape_edges(CgShapeId /*id*/, CgEdgeId* /*out_edges*/, size_t /*capacity*/) {
@@ -344,7 +390,30 @@ void cg_edge_free(CgEdgeId id) {
 
 /* ── Tessellation ────────────────────────────────────────────────────────── */
 
+// Forwards OCCT progress to a CgProgressFn.  OCCT calls Show() with its own
+// mutex held, so the callback is never entered concurrently.
+class CallbackProgress : public Message_ProgressIndicator {
+public:
+    CallbackProgress(CgProgressFn fn, void* user_data)
+        : fn_(fn), user_data_(user_data) {}
+
+protected:
+    void Show(const Message_ProgressScope& /*scope*/,
+              const Standard_Boolean /*force*/) override {
+        if (fn_) fn_(user_data_, GetPosition());
+    }
+
+private:
+    CgProgressFn fn_;
+    void*        user_data_;
+};
+
 CgMeshId cg_shape_tessellate(CgShapeId id, double chord_tol, double angle_tol) {
+    return cg_shape_tessellate_progress(id, chord_tol, angle_tol, nullptr, nullptr);
+}
+
+CgMeshId cg_shape_tessellate_progress(CgShapeId id, double chord_tol, double angle_tol,
+                                      CgProgressFn progress, void* user_data) {
     if (id == CG_NULL_ID) {
         set_last_error("cg_shape_tessellate: null handle");
         return CG_NULL_ID;
@@ -353,10 +422,15 @@ CgMeshId cg_shape_tessellate(CgShapeId id, double chord_tol, double angle_tol) {
         const TopoDS_Shape& shape = registry_get_shape(id);
 
         // Mesh the shape (stores triangulations inside the shape's topology).
-        BRepMesh_IncrementalMesh mesher(shape, chord_tol,
-                                        /*isRelative=*/Standard_False,
-                                        angle_tol,
-                                        /*isParallel=*/Standard_True);
+        // Meshing dominates the run time, so it accounts for the whole range
+        // reported through `progress`; merging finishes with 1.0.
+        IMeshTools_Parameters params;
+        params.Deflection = chord_tol;
+        params.Angle      = angle_tol;
+        params.Relative   = Standard_False;
+        params.InParallel = Standard_True;
+        Handle(CallbackProgress) indicator = new CallbackProgress(progress, user_data);
+        BRepMesh_IncrementalMesh mesher(shape, params, indicator->Start());
         if (!mesher.IsDone()) {
             set_last_error("cg_shape_tessellate: mesher did not complete");
             return CG_NULL_ID;
@@ -381,7 +455,9 @@ CgMeshId cg_shape_tessellate(CgShapeId id, double chord_tol, double angle_tol) {
         }
 
         normalize_normals(*data);
-        return mesh_store_insert(std::move(data));
+        const CgMeshId mesh_id = mesh_store_insert(std::move(data));
+        if (progress) progress(user_data, 1.0);
+        return mesh_id;
 
     } catch (const std::out_of_range&) {
         set_last_error("cg_shape_tessellate: invalid shape ID");
@@ -459,13 +535,61 @@ void cg_mesh_free(CgMeshId id) {
     mesh_store_erase(id);
 }
 
-/* ── Surface evaluation (stubs) ──────────────────────────────────────────── */
+/* ── Surface evaluation ──────────────────────────────────────────────────── */
 
-CgSurfaceType cg_face_surface_type(CgFaceId /*id*/) {
-    set_last_error("not implemented");
-    return CG_SURF_OTHER;
+CgSurfaceType cg_face_surface_type(CgFaceId id) {
+    if (id == CG_NULL_ID) {
+        set_last_error("cg_face_surface_type: null handle");
+        return CG_SURF_OTHER;
+    }
+    try {
+        const BRepAdaptor_Surface surf(TopoDS::Face(registry_get_shape(id)));
+        switch (surf.GetType()) {
+            case GeomAbs_Plane:           return CG_SURF_PLANE;
+            case GeomAbs_Cylinder:        return CG_SURF_CYLINDER;
+            case GeomAbs_Cone:            return CG_SURF_CONE;
+            case GeomAbs_Sphere:          return CG_SURF_SPHERE;
+            case GeomAbs_Torus:           return CG_SURF_TORUS;
+            case GeomAbs_BSplineSurface:  return CG_SURF_BSPLINE;
+            case GeomAbs_BezierSurface:   return CG_SURF_BEZIER;
+            case GeomAbs_OffsetSurface:   return CG_SURF_OFFSET;
+            default:                      return CG_SURF_OTHER;
+        }
+    } catch (const std::out_of_range&) {
+        set_last_error("cg_face_surface_type: invalid face ID");
+        return CG_SURF_OTHER;
+    } catch (const Standard_Failure& ex) {
+        set_last_error(std::string("Surface type exception: ") + ex.GetMessageString());
+        return CG_SURF_OTHER;
+    } catch (...) {
+        set_last_error("Surface type: unknown exception");
+        return CG_SURF_OTHER;
+    }
 }
 
+double cg_face_area(CgFaceId id) {
+    if (id == CG_NULL_ID) {
+        set_last_error("cg_face_area: null handle");
+        return -1.0;
+    }
+    try {
+        GProp_GProps props;
+        BRepGProp::SurfaceProperties(registry_get_shape(id), props);
+        return props.Mass();
+    } catch (const std::out_of_range&) {
+        set_last_error("cg_face_area: invalid face ID");
+        return -1.0;
+    } catch (const Standard_Failure& ex) {
+        set_last_error(std::string("Face area exception: ") + ex.GetMessageString());
+        return -1.0;
+    } catch (...) {
+        set_last_error("Face area: unknown exception");
+        return -1.0;
+    }
+}
+
+/* ── Surface evaluation (stubs) ──────────────────────────────────────────── */
+
 CgUVBounds cg_face_uv_bounds(CgFaceId /*id*/) {
     set_last_error("not implemented");
     return CgUVBounds{0, 0, 0, 0};
@@ -536,39 +660,206 @@ int cg_edge_is_circle(CgEdgeId /*id*/, CgPoint3* /*out_center*/,
     return 0;
 }
 
-/* ── Geometric queries (stubs) ───────────────────────────────────────────── */
+/* ── Geometric queries ───────────────────────────────────────────────────── */
 
-double cg_shape_distance(CgShapeId /*a*/, CgShapeId /*b*/) {
-    set_last_error("not implemented");
-    return -1.0;
-}
-
-CgError cg_shape_section_at_z(CgShapeId /*id*/, double /*z_value*/,
+CgError cg_shape_section_at_z(CgShapeId id, double z_value,
                                CgPoint3** out_points, size_t* out_count) {
-    set_last_error("not implemented");
     if (out_points) *out_points = nullptr;
     if (out_count)  *out_count  = 0;
-    return CG_ERR_NO_RESULT;
+    if (!out_points || !out_count) {
+        set_last_error("cg_shape_section_at_z: null output pointer");
+        return CG_ERR_INVALID_ARG;
+    }
+    if (id == CG_NULL_ID) {
+        set_last_error("cg_shape_section_at_z: null handle");
+        return CG_ERR_NULL_HANDLE;
+    }
+    try {
+        const TopoDS_Shape& shape = registry_get_shape(id);
+        const gp_Pln plane(gp_Pnt(0.0, 0.0, z_value), gp_Dir(0.0, 0.0, 1.0));
+        BRepAlgoAPI_Section section(shape, plane, Standard_False);
+        section.Approximation(Standard_True);
+        section.Build();
+        if (!section.IsDone()) {
+            set_last_error("cg_shape_section_at_z: section failed");
+            return CG_ERR_OCCT_EXCEPTION;
+        }
+
+        // Straight edges yield one segment; curved edges are split so the
+        // chord never strays more than 0.01 from the curve.
+        std::vector<CgPoint3> points;
+        for (TopExp_Explorer ex(section.Shape(), TopAbs_EDGE); ex.More(); ex.Next()) {
+            const BRepAdaptor_Curve curve(TopoDS::Edge(ex.Current()));
+            const GCPnts_TangentialDeflection sampler(curve, 0.1, 0.01);
+            for (int i = 1; i < sampler.NbPoints(); ++i) {
+                const gp_Pnt a = sampler.Value(i);
+                const gp_Pnt b = sampler.Value(i + 1);
+                points.push_back(CgPoint3{a.X(), a.Y(), a.Z()});
+                points.push_back(CgPoint3{b.X(), b.Y(), b.Z()});
+            }
+        }
+        if (points.empty()) {
+            set_last_error("cg_shape_section_at_z: plane misses the shape");
+            return CG_ERR_NO_RESULT;
+        }
+
+        CgPoint3* result = new CgPoint3[points.size()];
+        std::copy(points.begin(), points.end(), result);
+        *out_points = result;
+        *out_count = points.size();
+        return CG_OK;
+    } catch (const std::out_of_range&) {
+        set_last_error("cg_shape_section_at_z: invalid shape ID");
+        return CG_ERR_NULL_HANDLE;
+    } catch (const Standard_Failure& ex) {
+        set_last_error(std::string("Section exception: ") + ex.GetMessageString());
+        return CG_ERR_OCCT_EXCEPTION;
+    } catch (...) {
+        set_last_error("Section: unknown exception");
+        return CG_ERR_OCCT_EXCEPTION;
+    }
 }
 
 void cg_section_free(CgPoint3* points) {
     delete[] points;
 }
 
-/* ── Feature detection (stubs) ───────────────────────────────────────────── */
+/* ── Geometric queries (stubs) ───────────────────────────────────────────── */
 
-size_t cg_shape_find_holes(CgShapeId /*id*/,
-                            double /*min_diameter*/, double /*max_diameter*/,
-                            CgHoleInfo** out_holes) {
+double cg_shape_distance(CgShapeId /*a*/, CgShapeId /*b*/) {
     set_last_error("not implemented");
+    return -1.0;
+}
+
+/* ── Feature detection ───────────────────────────────────────────────────── */
+
+// Range of vertex projections of `shape` onto the line through `origin`
+// along unit vector `dir`.
+static void axial_extent(const TopoDS_Shape& shape, const gp_Pnt& origin,
+                         const gp_Dir& dir, double& tmin, double& tmax) {
+    tmin = INFINITY;
+    tmax = -INFINITY;
+    for (TopExp_Explorer ex(shape, TopAbs_VERTEX); ex.More(); ex.Next()) {
+        const gp_Pnt p = BRep_Tool::Pnt(TopoDS::Vertex(ex.Current()));
+        const double t = gp_Vec(origin, p).Dot(gp_Vec(dir));
+        tmin = std::min(tmin, t);
+        tmax = std::max(tmax, t);
+    }
+}
+
+// One hole being accumulated from its cylindrical faces (a hole is often
+// split into two half-cylinders along a seam).
+struct HoleAccum {
+    gp_Pnt origin;
+    gp_Dir dir;
+    double radius;
+    double tmin;
+    double tmax;
+};
+
+size_t cg_shape_find_holes(CgShapeId id,
+                            double min_diameter, double max_diameter,
+                            CgHoleInfo** out_holes) {
     if (out_holes) *out_holes = nullptr;
-    return 0;
+    if (!out_holes) {
+        set_last_error("cg_shape_find_holes: null output pointer");
+        return 0;
+    }
+    if (id == CG_NULL_ID) {
+        set_last_error("cg_shape_find_holes: null handle");
+        return 0;
+    }
+    try {
+        const TopoDS_Shape& shape = registry_get_shape(id);
+        const double tol = 1e-6;
+        std::vector<HoleAccum> holes;
+
+        for (TopExp_Explorer ex(shape, TopAbs_FACE); ex.More(); ex.Next()) {
+            const TopoDS_Face& face = TopoDS::Face(ex.Current());
+            BRepAdaptor_Surface surf(face);
+            if (surf.GetType() != GeomAbs_Cylinder) continue;
+
+            // A hole wall faces its own axis: the surface normal of a direct
+            // cylinder points away from the axis, so the face must be reversed.
+            const gp_Cylinder cyl = surf.Cylinder();
+            const bool reversed = face.Orientation() == TopAbs_REVERSED;
+            if (reversed != cyl.Direct()) continue;
+
+            const double diameter = 2.0 * cyl.Radius();
+            if (diameter < min_diameter || diameter > max_diameter) continue;
+
+            const gp_Pnt origin = cyl.Location();
+            const gp_Dir dir = cyl.Axis().Direction();
+            double tmin, tmax;
+            axial_extent(face, origin, dir, tmin, tmax);
+            if (!(tmax > tmin)) continue;
+
+            // Merge with a previous face of the same hole: parallel axis on
+            // the same line, same radius.
+            bool merged = false;
+            for (auto& h : holes) {
+                if (!h.dir.IsParallel(dir, 1e-6)) continue;
+                if (std::abs(h.radius - cyl.Radius()) > tol) continue;
+                const gp_Vec offset(h.origin, origin);
+                const double along = offset.Dot(gp_Vec(h.dir));
+                if ((offset - gp_Vec(h.dir) * along).Magnitude() > tol) continue;
+                // Re-express this face's range in the stored hole's frame.
+                const double sign = h.dir.Dot(dir) > 0 ? 1.0 : -1.0;
+                const double a = along + sign * tmin;
+                const double b = along + sign * tmax;
+                h.tmin = std::min(h.tmin, std::min(a, b));
+                h.tmax = std::max(h.tmax, std::max(a, b));
+                merged = true;
+                break;
+            }
+            if (!merged) {
+                holes.push_back({origin, dir, cyl.Radius(), tmin, tmax});
+            }
+        }
+
+        if (holes.empty()) return 0;
+
+        CgHoleInfo* result = new CgHoleInfo[holes.size()];
+        for (size_t i = 0; i < holes.size(); ++i) {
+            const HoleAccum& h = holes[i];
+            double smin, smax;
+            axial_extent(shape, h.origin, h.dir, smin, smax);
+            const bool open_max = h.tmax >= smax - tol;
+            const bool open_min = h.tmin <= smin + tol;
+
+            // Point the axis out of the hole's open end; with both or neither
+            // end open, prefer the end facing up (+Z).
+            bool up = open_max;
+            if (open_max == open_min) up = h.dir.Z() >= 0.0;
+            const gp_Dir axis = up ? h.dir : h.dir.Reversed();
+            const gp_Pnt top = h.origin.Translated(gp_Vec(h.dir) * (up ? h.tmax : h.tmin));
+
+            result[i].center = CgPoint3{top.X(), top.Y(), top.Z()};
+            result[i].axis = CgVec3{axis.X(), axis.Y(), axis.Z()};
+            result[i].diameter = 2.0 * h.radius;
+            result[i].depth = h.tmax - h.tmin;
+            result[i].is_through = (open_max && open_min) ? 1 : 0;
+        }
+        *out_holes = result;
+        return holes.size();
+    } catch (const std::out_of_range&) {
+        set_last_error("cg_shape_find_holes: invalid shape ID");
+        return 0;
+    } catch (const Standard_Failure& ex) {
+        set_last_error(std::string("Hole detection exception: ") + ex.GetMessageString());
+        return 0;
+    } catch (...) {
+        set_last_error("Hole detection: unknown exception");
+        return 0;
+    }
 }
 
 void cg_holes_free(CgHoleInfo* holes) {
     delete[] holes;
 }
 
+/* ── Feature detection (stubs) ───────────────────────────────────────────── */
+
 size_t cg_shape_find_planar_faces(CgShapeId /*id*/, CgPlanarFaceInfo** out_faces) {
     set_last_error("not implemented");
     if (out_faces) *out_faces = nullptr;
@@ -604,4 +895,94 @@ CgError cg_poly_boolean(const double* /*a_points*/, size_t /*a_count*/,
     return CG_ERR_NO_RESULT;
 }
 
+/* ── Solid modelling ─────────────────────────────────────────────────────── */
+
+CgShapeId cg_shape_make_box(CgPoint3 origin, double dx, double dy, double dz) {
+    if (!(dx > 0.0 && dy > 0.0 && dz > 0.0)) {
+        set_last_error("cg_shape_make_box: box sizes must be positive");
+        return CG_NULL_ID;
+    }
+    try {
+        BRepPrimAPI_MakeBox maker(gp_Pnt(origin.x, origin.y, origin.z), dx, dy, dz);
+        return registry_store_shape(maker.Shape());
+    } catch (const Standard_Failure& ex) {
+        set_last_error(std::string("MakeBox exception: ") + ex.GetMessageString());
+        return CG_NULL_ID;
+    } catch (...) {
+        set_last_error("MakeBox: unknown exception");
+        return CG_NULL_ID;
+    }
+}
+
+CgShapeId cg_shape_make_cylinder(CgPoint3 origin, CgVec3 axis,
+                                 double radius, double height) {
+    if (!(radius > 0.0 && height > 0.0)) {
+        set_last_error("cg_shape_make_cylinder: radius and height must be positive");
+        return CG_NULL_ID;
+    }
+    try {
+        // gp_Dir throws on a zero-length vector; the catch below reports it.
+        const gp_Ax2 placement(gp_Pnt(origin.x, origin.y, origin.z),
+                               gp_Dir(axis.x, axis.y, axis.z));
+        BRepPrimAPI_MakeCylinder maker(placement, radius, height);
+        return registry_store_shape(maker.Shape());
+    } catch (const Standard_Failure& ex) {
+        set_last_error(std::string("MakeCylinder exception: ") + ex.GetMessageString());
+        return CG_NULL_ID;
+    } catch (...) {
+        set_last_error("MakeCylinder: unknown exception");
+        return CG_NULL_ID;
+    }
+}
+
+CgShapeId cg_shape_boolean(CgShapeId a, CgShapeId b, CgBoolOp op) {
+    if (a == CG_NULL_ID || b == CG_NULL_ID) {
+        set_last_error("cg_shape_boolean: null handle");
+        return CG_NULL_ID;
+    }
+    try {
+        const TopoDS_Shape& sa = registry_get_shape(a);
+        const TopoDS_Shape& sb = registry_get_shape(b);
+        TopoDS_Shape result;
+        bool done = false;
+        switch (op) {
+        case CG_BOOL_UNION: {
+            BRepAlgoAPI_Fuse algo(sa, sb);
+            done = algo.IsDone();
+            if (done) result = algo.Shape();
+            break;
+        }
+        case CG_BOOL_DIFFERENCE: {
+            BRepAlgoAPI_Cut algo(sa, sb);
+            done = algo.IsDone();
+            if (done) result = algo.Shape();
+            break;
+        }
+        case CG_BOOL_INTERSECTION: {
+            BRepAlgoAPI_Common algo(sa, sb);
+            done = algo.IsDone();
+            if (done) result = algo.Shape();
+            break;
+        }
+        default:
+            set_last_error("cg_shape_boolean: unknown operation");
+            return CG_NULL_ID;
+        }
+        if (!done || result.IsNull()) {
+            set_last_error("cg_shape_boolean: boolean operation failed");
+            return CG_NULL_ID;
+        }
+        return registry_store_shape(result);
+    } catch (const std::out_of_range&) {
+        set_last_error("cg_shape_boolean: invalid shape ID");
+        return CG_NULL_ID;
+    } catch (const Standard_Failure& ex) {
+        set_last_error(std::string("Boolean exception: ") + ex.GetMessageString());
+        return CG_NULL_ID;
+    } catch (...) {
+        set_last_error("Boolean: unknown exception");
+        return CG_NULL_ID;
+    }
+}
+
 } // extern "C"
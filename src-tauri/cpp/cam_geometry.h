@@ -128,6 +128,16 @@ void cg_edge_free(CgEdgeId id);
 // Returns CG_NULL_ID on failure.
 CgMeshId cg_shape_tessellate(CgShapeId id, double chord_tol, double angle_tol);
 
+// Progress callback: `fraction` rises from 0.0 to 1.0.  May be invoked from
+// mesher worker threads, but never concurrently.
+typedef void (*CgProgressFn)(void* user_data, double fraction);
+
+// As cg_shape_tessellate(), reporting progress through `progress` (which may
+// be NULL) with `user_data` passed back unchanged.  The last report is 1.0
+// on success.
+CgMeshId cg_shape_tessellate_progress(CgShapeId id, double chord_tol, double angle_tol,
+                                      CgProgressFn progress, void* user_data);
+
 // Return the number of vertices in the mesh (each vertex is 3 doubles).
 size_t cg_mesh_vertex_count(CgMeshId id);
 
@@ -157,6 +167,9 @@ void cg_mesh_free(CgMeshId id);
 // Return the surface type of a face.
 CgSurfaceType cg_face_surface_type(CgFaceId id);
 
+// Return the surface area of a face, or -1.0 on failure.
+double cg_face_area(CgFaceId id);
+
 // Return the UV parameter bounds of a face.
 CgUVBounds cg_face_uv_bounds(CgFaceId id);
 
@@ -290,6 +303,23 @@ CgError cg_poly_boolean(const double* a_points, size_t a_count,
                          CgBoolOp op,
                          double** out_points, size_t* out_count);
 
+/* ── Solid modelling ─────────────────────────────────────────────────────── */
+
+// Build an axis-aligned box solid with minimum corner `origin` and size
+// dx × dy × dz mm.  Returns CG_NULL_ID on failure (e.g. a non-positive size).
+CgShapeId cg_shape_make_box(CgPoint3 origin, double dx, double dy, double dz);
+
+// Build a cylinder solid whose bottom face is centred on `origin`, extruded
+// `height` mm along `axis` (need not be normalised).
+// Returns CG_NULL_ID on failure.
+CgShapeId cg_shape_make_cylinder(CgPoint3 origin, CgVec3 axis,
+                                 double radius, double height);
+
+// Boolean between two solids: a ∪ b, a − b, or a ∩ b.  The inputs are left
+// unchanged; the result is a new handle the caller must free.
+// Returns CG_NULL_ID on failure.
+CgShapeId cg_shape_boolean(CgShapeId a, CgShapeId b, CgBoolOp op);
+
 #ifdef __cplusplus
 }
 #endif
@@ -64,6 +64,10 @@ CgMeshId cg_shape_tessellate(CgShapeId id, double /*c*/, double /*a*/) {
     set_error("not implemented");
     return CG_NULL_ID;
 }
+CgMeshId cg_shape_tessellate_progress(CgShapeId id, double c, double a,
+                                      CgProgressFn /*fn*/, void* /*ud*/) {
+    return cg_shape_tessellate(id, c, a);
+}
 size_t  cg_mesh_vertex_count(CgMeshId /*id*/)   { return 0; }
 size_t  cg_mesh_triangle_count(CgMeshId /*id*/) { return 0; }
 CgError cg_mesh_copy_vertices(CgMeshId id, double* /*out*/) {
@@ -81,6 +85,7 @@ CgError cg_mesh_copy_indices(CgMeshId id, uint32_t* /*out*/) {
 void cg_mesh_free(CgMeshId /*id*/) {}
 
 CgSurfaceType cg_face_surface_type(CgFaceId /*id*/) { set_error("not implemented"); return CG_SURF_OTHER; }
+double        cg_face_area(CgFaceId /*id*/)         { set_error("not implemented"); return -1.0; }
 CgUVBounds    cg_face_uv_bounds(CgFaceId /*id*/)    { set_error("not implemented"); return CgUVBounds{0,0,0,0}; }
 CgPoint3 cg_face_eval_point(CgFaceId /*id*/, double /*u*/, double /*v*/)  { set_error("not implemented"); return CgPoint3{0,0,0}; }
 CgVec3   cg_face_eval_normal(CgFaceId /*id*/, double /*u*/, double /*v*/) { set_error("not implemented"); return CgVec3{0,0,0}; }
@@ -124,4 +129,14 @@ CgError cg_poly_boolean(const double* /*a*/, size_t /*na*/, const double* /*b*/,
     set_error("not implemented"); if (out) *out=nullptr; if (cnt) *cnt=0; return CG_ERR_NO_RESULT;
 }
 
+CgShapeId cg_shape_make_box(CgPoint3 /*o*/, double /*dx*/, double /*dy*/, double /*dz*/) {
+    set_error("not implemented"); return CG_NULL_ID;
+}
+CgShapeId cg_shape_make_cylinder(CgPoint3 /*o*/, CgVec3 /*ax*/, double /*r*/, double /*h*/) {
+    set_error("not implemented"); return CG_NULL_ID;
+}
+CgShapeId cg_shape_boolean(CgShapeId /*a*/, CgShapeId /*b*/, CgBoolOp /*op*/) {
+    set_error("not implemented"); return CG_NULL_ID;
+}
+
 } // extern "C"
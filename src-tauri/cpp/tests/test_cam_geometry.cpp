@@ -138,6 +138,17 @@ TEST(tessellate_null_handle) {
                 std::string(cg_last_error_message()).size() > 0);
 }
 
+static void count_progress(void* user_data, double /*fraction*/) {
+    ++*static_cast<int*>(user_data);
+}
+
+TEST(tessellate_progress_null_handle) {
+    int calls = 0;
+    CgMeshId id = cg_shape_tessellate_progress(CG_NULL_ID, 0.1, 0.1, count_progress, &calls);
+    ASSERT_EQ("cg_shape_tessellate_progress(null) == CG_NULL_ID", id, CG_NULL_ID);
+    ASSERT_EQ("cg_shape_tessellate_progress(null) reports no progress", calls, 0);
+}
+
 TEST(shape_bounding_box_null) {
     CgBbox b = cg_shape_bounding_box(CG_NULL_ID);
     // Must not crash; all zeros is the documented sentinel.
@@ -192,6 +203,11 @@ TEST(face_surface_type_stub) {
     ASSERT_EQ("cg_face_surface_type stub returns CG_SURF_OTHER", (int)t, (int)CG_SURF_OTHER);
 }
 
+TEST(face_area_stub) {
+    double a = cg_face_area(1);
+    ASSERT_EQ("cg_face_area stub returns -1.0", a, -1.0);
+}
+
 TEST(face_plane_stub) {
     CgVec3 n; CgPoint3 o;
     CgError e = cg_face_plane(1, &n, &o);
@@ -232,6 +248,13 @@ TEST(find_holes_stub) {
     ASSERT_EQ("cg_shape_find_holes stub sets *out to null", holes, (CgHoleInfo*)nullptr);
 }
 
+TEST(shape_boolean_stub) {
+    CgShapeId id = cg_shape_boolean(1, 2, CG_BOOL_DIFFERENCE);
+    ASSERT_EQ("cg_shape_boolean stub returns CG_NULL_ID", id, CG_NULL_ID);
+    ASSERT_TRUE("cg_shape_boolean stub sets an error",
+                std::strlen(cg_last_error_message()) > 0);
+}
+
 TEST(find_planar_faces_stub) {
     CgPlanarFaceInfo* faces = nullptr;
     size_t n = cg_shape_find_planar_faces(1, &faces);
@@ -325,6 +348,7 @@ int main() {
     test_mesh_vertex_count_null();
     test_mesh_triangle_count_null();
     test_tessellate_null_handle();
+    test_tessellate_progress_null_handle();
     test_shape_bounding_box_null();
 
     // Group 5: Copy functions with null handle
@@ -336,6 +360,7 @@ int main() {
     test_load_iges_stub();
     test_shape_heal_stub();
     test_face_surface_type_stub();
+    test_face_area_stub();
     test_face_plane_stub();
     test_face_cylinder_stub();
     test_edge_is_circle_stub();
@@ -343,6 +368,7 @@ int main() {
     test_shape_section_at_z_stub();
     test_find_holes_stub();
     test_find_planar_faces_stub();
+    test_shape_boolean_stub();
     test_poly_offset_stub();
     test_poly_boolean_stub();
 
@@ -0,0 +1,175 @@
+//! Demo model IPC command handlers.
+//!
+//! The test fixtures under `tests/fixtures/` are embedded into the binary so
+//! users can try the viewer without a model of their own. Loading one writes
+//! the embedded bytes to a temp file and goes through the normal
+//! [`open_model_inner`] import path.
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::geometry::{MeshData, TessellationOptions};
+use crate::state::{AppState, Project};
+
+use super::file::open_model_inner;
+
+/// A bundled model compiled into the binary.
+struct EmbeddedModel {
+    name: &'static str,
+    description: &'static str,
+    bytes: &'static [u8],
+}
+
+const DEMO_MODELS: &[EmbeddedModel] = &[
+    EmbeddedModel {
+        name: "box.step",
+        description: "Simple box (STEP B-rep)",
+        bytes: include_bytes!("../../../tests/fixtures/box.step"),
+    },
+    EmbeddedModel {
+        name: "box.stl",
+        description: "Simple box (STL mesh)",
+        bytes: include_bytes!("../../../tests/fixtures/box.stl"),
+    },
+];
+
+/// Entry returned by [`list_demo_models`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DemoModelInfo {
+    /// File name, passed back to [`load_demo_model`].
+    pub name: String,
+    pub description: String,
+    /// Size of the embedded file in bytes.
+    pub size_bytes: usize,
+}
+
+// ── list_demo_models ──────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`list_demo_models`].
+pub(crate) fn list_demo_models_inner() -> Vec<DemoModelInfo> {
+    DEMO_MODELS
+        .iter()
+        .map(|m| DemoModelInfo {
+            name: m.name.to_string(),
+            description: m.description.to_string(),
+            size_bytes: m.bytes.len(),
+        })
+        .collect()
+}
+
+// ── load_demo_model ───────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`load_demo_model`].
+///
+/// 1. Returns [`AppError::NotFound`] if `name` is not a bundled model.
+/// 2. Returns [`AppError::GeometryImport`] if this build lacks the OCCT
+///    geometry kernel, which every demo model needs.
+/// 3. Materializes the embedded bytes under the system temp directory.
+/// 4. Imports the file via [`open_model_inner`], replacing the loaded model.
+pub(crate) async fn load_demo_model_inner(
+    name: &str,
+    project_lock: &RwLock<Project>,
+) -> Result<MeshData, AppError> {
+    let model = DEMO_MODELS
+        .iter()
+        .find(|m| m.name == name)
+        .ok_or_else(|| AppError::NotFound(format!("no demo model named '{name}'")))?;
+
+    if !cfg!(cam_geometry_bindings) {
+        return Err(AppError::GeometryImport(
+            "demo models need the OCCT geometry kernel, which this build does not include"
+                .to_string(),
+        ));
+    }
+
+    let path = materialize(model)?;
+    open_model_inner(
+        &path.to_string_lossy(),
+        TessellationOptions::default(),
+        None,
+        |_| {},
+        project_lock,
+        None,
+    )
+    .await
+}
+
+/// Writes `model` to `<temp>/jamiecam-demo/<name>` and returns the path.
+fn materialize(model: &EmbeddedModel) -> Result<PathBuf, AppError> {
+    let dir = std::env::temp_dir().join("jamiecam-demo");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(model.name);
+    std::fs::write(&path, model.bytes)?;
+    Ok(path)
+}
+
+// ── Tauri command wrappers ────────────────────────────────────────────────────
+
+/// List the demo models bundled with the application.
+#[tauri::command]
+pub async fn list_demo_models() -> Result<Vec<DemoModelInfo>, AppError> {
+    Ok(list_demo_models_inner())
+}
+
+/// Load a bundled demo model into the active project.
+///
+/// Returns the [`MeshData`] for the frontend to render, like `open_model`.
+#[tauri::command]
+pub async fn load_demo_model(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<MeshData, AppError> {
+    load_demo_model_inner(&name, &state.project).await
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_demo_models_returns_bundled_fixtures() {
+        let models = list_demo_models_inner();
+        assert!(!models.is_empty());
+        assert!(models.iter().any(|m| m.name == "box.step"));
+        assert!(models.iter().all(|m| m.size_bytes > 0));
+    }
+
+    #[tokio::test]
+    async fn load_demo_model_unknown_name_returns_not_found() {
+        let state = AppState::default();
+        let result = load_demo_model_inner("no-such-model.step", &state.project).await;
+        assert!(
+            matches!(result, Err(AppError::NotFound(_))),
+            "expected NotFound, got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(not(cam_geometry_bindings))]
+    async fn load_demo_model_without_occt_explains_missing_kernel() {
+        let state = AppState::default();
+        let result = load_demo_model_inner("box.step", &state.project).await;
+        match result {
+            Err(AppError::GeometryImport(msg)) => assert!(msg.contains("OCCT"), "{msg}"),
+            other => panic!("expected GeometryImport, got: {other:?}"),
+        }
+        assert!(state.project.read().unwrap().source_model.is_none());
+    }
+
+    #[tokio::test]
+    #[cfg(cam_geometry_bindings)]
+    async fn load_demo_model_box_step_stores_model() {
+        let state = AppState::default();
+        let mesh = load_demo_model_inner("box.step", &state.project)
+            .await
+            .expect("demo model should load");
+        assert!(!mesh.vertices.is_empty());
+        assert!(state.project.read().unwrap().source_model.is_some());
+    }
+}
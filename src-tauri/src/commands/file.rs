@@ -8,31 +8,55 @@
 //! Every fallible path returns `Result<_, AppError>`. No `unwrap()` or
 //! `expect()` calls are present outside of `#[cfg(test)]`.
 
-use std::path::PathBuf;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
-use sha2::Digest as _;
-
 use crate::error::AppError;
-use crate::geometry::MeshData;
-use crate::state::{AppState, LoadedModel, Project};
+use crate::geometry::{FaceInfo, HoleInfo, MeshCache, MeshData, TessellationOptions};
+use crate::project::diff::ProjectDiff;
+use crate::state::{AppState, LoadedModel, Project, UserPreferences};
 
-use crate::postprocessor::{program::GenerateOptions, PostProcessor};
+use crate::postprocessor::{
+    program::{CommentLevel, GenerateOptions},
+    GenerateSummary, PostProcessor, PostProcessorError, ToolInfo,
+};
+use crate::toolpath::Toolpath;
 
 use super::project::ProjectSnapshot;
-use super::{build_tool_infos, parse_entity_id, read_project, write_project};
+use super::{
+    build_tool_infos, cached_toolpath, parse_entity_id, read_project, remember_recent_file,
+    user_post_dir, write_project,
+};
 
 // ── open_model ────────────────────────────────────────────────────────────────
 
+/// Event emitted by [`open_model`] with the fraction (0.0–1.0) of the import
+/// done.
+pub const TESSELLATION_PROGRESS_EVENT: &str = "tessellation-progress";
+
 /// Testable inner logic for [`open_model`].
 ///
 /// 1. Returns [`AppError::FileNotFound`] if `path_str` does not exist.
-/// 2. Offloads tessellation + checksum computation to a blocking thread pool.
-/// 3. Stores the resulting [`LoadedModel`] in `project_lock`.
-/// 4. Returns the [`MeshData`] for the frontend to render.
+/// 2. Offloads checksum computation + tessellation (with `tessellation`
+///    tolerances) to a blocking thread pool, reporting the fraction done to
+///    `progress` (see [`crate::geometry::import`]).
+/// 3. When re-opening the model the project already references (e.g. after
+///    loading a project, whose mesh is not persisted), returns
+///    [`AppError::ModelChanged`] before tessellating if the file's checksum
+///    differs from the stored one. [`refresh_model_reference`] accepts the
+///    edited file.
+/// 4. Stores the resulting [`LoadedModel`] in `project_lock`. With a `cache`,
+///    unchanged content is read from it instead of being re-tessellated.
+/// 5. Adds the file to the recent files in `prefs_lock`, when given.
+/// 6. Returns the [`MeshData`] for the frontend to render.
 pub(crate) async fn open_model_inner(
     path_str: &str,
+    tessellation: TessellationOptions,
+    cache: Option<MeshCache>,
+    mut progress: impl FnMut(f32) + Send + 'static,
     project_lock: &RwLock<Project>,
+    prefs_lock: Option<&RwLock<UserPreferences>>,
 ) -> Result<MeshData, AppError> {
     let path_buf = PathBuf::from(path_str);
 
@@ -40,14 +64,28 @@ pub(crate) async fn open_model_inner(
         return Err(AppError::FileNotFound);
     }
 
+    let expected = read_project(project_lock)?
+        .source_model
+        .as_ref()
+        .filter(|m| m.path == path_buf)
+        .map(|m| m.checksum.clone());
+
     // Tessellation is CPU-bound; run it on the blocking thread pool so the
     // async runtime is not starved.
     let path_clone = path_buf.clone();
     let blocking_result = tokio::task::spawn_blocking(move || {
-        let mesh = crate::geometry::import(&path_clone).map_err(AppError::from)?;
-        let bytes = std::fs::read(&path_clone).map_err(|e| AppError::Io(e.to_string()))?;
-        let digest = sha2::Sha256::digest(&bytes);
-        Ok::<(MeshData, String), AppError>((mesh, format!("{digest:x}")))
+        let checksum = file_checksum(&path_clone)?;
+        if expected.is_some_and(|e| e != checksum) {
+            return Err(model_changed(&path_clone));
+        }
+        let mesh = crate::geometry::import(
+            &path_clone,
+            &tessellation,
+            cache.as_ref(),
+            Some(&mut progress),
+        )
+        .map_err(AppError::from)?;
+        Ok::<(MeshData, String), AppError>((mesh, checksum))
     })
     .await
     .map_err(|e| AppError::GeometryImport(format!("import task panicked: {e}")))?;
@@ -57,21 +95,232 @@ pub(crate) async fn open_model_inner(
     let mut project = write_project(project_lock)?;
     project.source_model = Some(LoadedModel {
         path: path_buf,
+        original_path: None,
         checksum,
         mesh_data: mesh.clone(),
     });
+    drop(project);
 
+    if let Some(prefs_lock) = prefs_lock {
+        remember_recent_file(prefs_lock, path_str)?;
+    }
     Ok(mesh)
 }
 
+fn model_changed(path: &Path) -> AppError {
+    AppError::ModelChanged(format!(
+        "model file {} has changed since it was referenced",
+        path.display()
+    ))
+}
+
+/// Returns the lowercase hex SHA-256 digest of the file at `path`.
+fn file_checksum(path: &Path) -> Result<String, AppError> {
+    crate::geometry::mesh_cache::file_checksum(path).map_err(AppError::from)
+}
+
+// ── refresh_model_reference ───────────────────────────────────────────────────
+
+/// Testable inner logic for [`refresh_model_reference`].
+///
+/// Recomputes the SHA-256 of the loaded model's file, stores it in
+/// [`LoadedModel::checksum`], and returns it. Use after the model file was
+/// intentionally edited so the stale checksum stops being reported as a
+/// mismatch. The mesh is not re-imported.
+///
+/// Returns [`AppError::NotFound`] if no model is loaded, or
+/// [`AppError::FileNotFound`] if the model file no longer exists.
+pub(crate) fn refresh_model_reference_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<String, AppError> {
+    let path = read_project(project_lock)?
+        .source_model
+        .as_ref()
+        .map(|m| m.path.clone())
+        .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+
+    if !path.exists() {
+        return Err(AppError::FileNotFound);
+    }
+    let checksum = file_checksum(&path)?;
+
+    let mut project = write_project(project_lock)?;
+    // The model may have been replaced while the file was being hashed.
+    match project.source_model.as_mut() {
+        Some(model) if model.path == path => model.checksum = checksum.clone(),
+        _ => {
+            return Err(AppError::NotFound(
+                "model changed during refresh".to_string(),
+            ))
+        }
+    }
+    Ok(checksum)
+}
+
+// ── verify_model_checksum ─────────────────────────────────────────────────────
+
+/// Result of [`verify_model_checksum`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelChecksumStatus {
+    /// The file on disk matches the stored checksum.
+    Match,
+    /// The file on disk differs from the stored checksum.
+    Mismatch,
+    /// The model file no longer exists.
+    Missing,
+}
+
+/// Testable inner logic for [`verify_model_checksum`].
+///
+/// Hashes the loaded model's file and compares it with
+/// [`LoadedModel::checksum`] without changing the project. Returns
+/// [`AppError::NotFound`] if no model is loaded.
+pub(crate) fn verify_model_checksum_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<ModelChecksumStatus, AppError> {
+    let (path, expected) = read_project(project_lock)?
+        .source_model
+        .as_ref()
+        .map(|m| (m.path.clone(), m.checksum.clone()))
+        .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+
+    if !path.exists() {
+        return Ok(ModelChecksumStatus::Missing);
+    }
+    Ok(if file_checksum(&path)? == expected {
+        ModelChecksumStatus::Match
+    } else {
+        ModelChecksumStatus::Mismatch
+    })
+}
+
+// ── clear_mesh_cache ──────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`clear_mesh_cache`].
+///
+/// Removes every entry of `cache` and returns how many were removed. Without
+/// a cache (no platform cache dir) there is nothing to remove.
+pub(crate) fn clear_mesh_cache_inner(cache: Option<&MeshCache>) -> Result<usize, AppError> {
+    cache.map_or(Ok(0), |c| c.clear().map_err(AppError::from))
+}
+
+// ── detect_holes ──────────────────────────────────────────────────────────────
+
+/// Holes in the B-rep at `path`, loaded through OCCT. Sources without a
+/// B-rep (STL) have no holes to report.
+#[cfg(cam_geometry_bindings)]
+fn brep_holes(path: &Path) -> Result<Vec<HoleInfo>, AppError> {
+    use super::{has_brep, load_brep};
+
+    if !has_brep(path) {
+        return Ok(Vec::new());
+    }
+    Ok(load_brep(path)?.detect_holes()?)
+}
+
+#[cfg(not(cam_geometry_bindings))]
+fn brep_holes(_path: &Path) -> Result<Vec<HoleInfo>, AppError> {
+    Ok(Vec::new())
+}
+
+/// Testable inner logic for [`detect_holes`].
+///
+/// Reloads the model's STEP/IGES source and lists its cylindrical holes.
+/// Returns [`AppError::NotFound`] if no model is loaded; without OCCT the
+/// list is always empty.
+pub(crate) fn detect_holes_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<HoleInfo>, AppError> {
+    let path = read_project(project_lock)?
+        .source_model
+        .as_ref()
+        .map(|m| m.path.clone())
+        .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+    brep_holes(&path)
+}
+
+// ── list_faces ────────────────────────────────────────────────────────────────
+
+/// Faces of the B-rep at `path`, loaded through OCCT. Sources without a
+/// B-rep (STL) have no faces to report.
+#[cfg(cam_geometry_bindings)]
+fn brep_faces(path: &Path) -> Result<Vec<FaceInfo>, AppError> {
+    use super::{has_brep, load_brep};
+
+    if !has_brep(path) {
+        return Ok(Vec::new());
+    }
+    Ok(load_brep(path)?.faces()?)
+}
+
+#[cfg(not(cam_geometry_bindings))]
+fn brep_faces(_path: &Path) -> Result<Vec<FaceInfo>, AppError> {
+    Ok(Vec::new())
+}
+
+/// Testable inner logic for [`list_faces`].
+///
+/// Reloads the model's STEP/IGES source and lists its faces with surface
+/// type and area. Returns [`AppError::NotFound`] if no model is loaded;
+/// without OCCT the list is always empty.
+pub(crate) fn list_faces_inner(project_lock: &RwLock<Project>) -> Result<Vec<FaceInfo>, AppError> {
+    let path = read_project(project_lock)?
+        .source_model
+        .as_ref()
+        .map(|m| m.path.clone())
+        .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+    brep_faces(&path)
+}
+
+// ── section_model_at_z ────────────────────────────────────────────────────────
+
+/// Closed XY loops where the plane at height `z` cuts the B-rep at `path`.
+/// STL sources have no B-rep to section.
+#[cfg(cam_geometry_bindings)]
+fn brep_section(path: &Path, z: f64) -> Result<Vec<Vec<(f64, f64)>>, AppError> {
+    use super::load_brep;
+
+    Ok(load_brep(path)?.section_z(z)?)
+}
+
+#[cfg(not(cam_geometry_bindings))]
+fn brep_section(_path: &Path, _z: f64) -> Result<Vec<Vec<(f64, f64)>>, AppError> {
+    Err(crate::geometry::GeometryError::TessellationFailed {
+        message: "OCCT not available".into(),
+    }
+    .into())
+}
+
+/// Testable inner logic for [`section_model_at_z`].
+///
+/// Reloads the model's STEP/IGES source and slices it at height `z`.
+/// Returns [`AppError::NotFound`] if no model is loaded and
+/// [`AppError::InvalidInput`] for an STL model; without OCCT it always fails
+/// with [`AppError::GeometryImport`].
+pub(crate) fn section_model_at_z_inner(
+    z: f64,
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<Vec<(f64, f64)>>, AppError> {
+    let path = read_project(project_lock)?
+        .source_model
+        .as_ref()
+        .map(|m| m.path.clone())
+        .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+    brep_section(&path, z)
+}
+
 // ── save_project ──────────────────────────────────────────────────────────────
 
 /// Testable inner logic for [`save_project`].
 ///
 /// Updates `modified_at` (and `created_at` on first save) to the current UTC
-/// time, then serialises the project to `path_str`.
+/// time, then serialises the project to `path_str`, embedding the source
+/// model file when `embed_model` is set. On success `path_str` becomes the
+/// project's [`Project::path`].
 pub(crate) fn save_project_inner(
     path_str: &str,
+    embed_model: bool,
     project_lock: &RwLock<Project>,
 ) -> Result<(), AppError> {
     let path_buf = PathBuf::from(path_str);
@@ -86,24 +335,104 @@ pub(crate) fn save_project_inner(
     }
 
     let project = read_project(project_lock)?;
-    crate::project::serialization::save(&project, &path_buf)
+    crate::project::serialization::save(&project, &path_buf, embed_model)?;
+    drop(project);
+    write_project(project_lock)?.path = Some(path_buf);
+    Ok(())
 }
 
 // ── load_project ──────────────────────────────────────────────────────────────
 
 /// Testable inner logic for [`load_project`].
 ///
-/// Loads the `.jcam` file, replaces the active project in `project_lock`, and
-/// returns a [`ProjectSnapshot`] for immediate display.
+/// Loads the `.jcam` file, replaces the active project in `project_lock`,
+/// adds the file to the recent files in `prefs_lock`, and returns a
+/// [`ProjectSnapshot`] for immediate display.
 pub(crate) fn load_project_inner(
     path_str: &str,
     project_lock: &RwLock<Project>,
+    prefs_lock: &RwLock<UserPreferences>,
 ) -> Result<ProjectSnapshot, AppError> {
     let path_buf = PathBuf::from(path_str);
-    let new_project = crate::project::serialization::load(&path_buf)?;
+    let mut new_project = crate::project::serialization::load(&path_buf)?;
+    new_project.path = Some(path_buf);
     let snapshot = ProjectSnapshot::from(&new_project);
-    let mut project = write_project(project_lock)?;
-    *project = new_project;
+    *write_project(project_lock)? = new_project;
+    remember_recent_file(prefs_lock, path_str)?;
+    Ok(snapshot)
+}
+
+// ── autosave ──────────────────────────────────────────────────────────────────
+
+/// Number of autosaves kept per project; older ones are deleted.
+pub const AUTOSAVE_KEEP: usize = 3;
+
+/// Stand-in project path for autosaves of a project that was never saved.
+fn untitled_project_path() -> PathBuf {
+    std::env::temp_dir().join("jamiecam-untitled.jcam")
+}
+
+/// Returns the autosave slots for a project saved at `project_path`, newest
+/// first: `<project_path>.autosave.jcam`, then `.autosave.1.jcam` and so on
+/// up to [`AUTOSAVE_KEEP`] files.
+fn autosave_paths(project_path: &Path) -> Vec<PathBuf> {
+    let name = project_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    (0..AUTOSAVE_KEEP)
+        .map(|slot| {
+            let suffix = match slot {
+                0 => "autosave.jcam".to_string(),
+                n => format!("autosave.{n}.jcam"),
+            };
+            project_path.with_file_name(format!("{name}.{suffix}"))
+        })
+        .collect()
+}
+
+/// Testable inner logic for [`autosave`].
+///
+/// Shifts the existing autosaves of the active project one slot older,
+/// dropping the oldest, then writes the project to the newest slot with
+/// [`crate::project::serialization::save`]. The slots sit beside
+/// [`Project::path`], or in the temp directory for a never-saved project.
+/// Unlike [`save_project_inner`], the project itself is not touched: its
+/// timestamps and path stay as they were. Returns the autosave path.
+pub(crate) fn autosave_inner(project_lock: &RwLock<Project>) -> Result<PathBuf, AppError> {
+    let project = read_project(project_lock)?;
+    let slots = autosave_paths(project.path.as_deref().unwrap_or(&untitled_project_path()));
+
+    for pair in slots.windows(2).rev() {
+        if pair[0].exists() {
+            std::fs::rename(&pair[0], &pair[1])
+                .map_err(|e| AppError::ProjectSave(format!("cannot rotate autosave: {e}")))?;
+        }
+    }
+    crate::project::serialization::save(&project, &slots[0], false)?;
+    Ok(slots[0].clone())
+}
+
+/// Testable inner logic for [`restore_autosave`].
+///
+/// Loads the newest autosave of the active project (see [`autosave_inner`])
+/// and installs it as the active project, keeping the current
+/// [`Project::path`] so the next save goes to the real file rather than the
+/// autosave. Returns [`AppError::FileNotFound`] when there is no autosave.
+pub(crate) fn restore_autosave_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<ProjectSnapshot, AppError> {
+    let path = read_project(project_lock)?.path.clone();
+    let newest = autosave_paths(path.as_deref().unwrap_or(&untitled_project_path()))
+        .into_iter()
+        .find(|p| p.exists())
+        .ok_or(AppError::FileNotFound)?;
+
+    let mut restored = crate::project::serialization::load(&newest)?;
+    restored.path = path;
+    let snapshot = ProjectSnapshot::from(&restored);
+    *write_project(project_lock)? = restored;
     Ok(snapshot)
 }
 
@@ -123,25 +452,116 @@ pub(crate) fn new_project_inner(
     Ok(snapshot)
 }
 
+// ── new_project_from_template ─────────────────────────────────────────────────
+
+/// Testable inner logic for [`new_project_from_template`].
+///
+/// Loads the `.jcam` at `template_path` and installs it as the active project
+/// with its source model and timestamps cleared, so the new job starts from
+/// the template's tools, work offsets, and stock without inheriting its part
+/// or creation date. Returns a [`ProjectSnapshot`] for immediate display.
+pub(crate) fn new_project_from_template_inner(
+    template_path: &Path,
+    project_lock: &RwLock<Project>,
+) -> Result<ProjectSnapshot, AppError> {
+    let mut new_project = crate::project::serialization::load(template_path)?;
+    new_project.source_model = None;
+    new_project.created_at.clear();
+    new_project.modified_at.clear();
+    let snapshot = ProjectSnapshot::from(&new_project);
+    let mut project = write_project(project_lock)?;
+    *project = new_project;
+    Ok(snapshot)
+}
+
+// ── project_diff ──────────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`project_diff`].
+///
+/// Loads both `.jcam` files (without touching the active project) and returns
+/// the tools, operations, and metadata that differ from `path_a` to `path_b`.
+pub(crate) fn project_diff_inner(path_a: &str, path_b: &str) -> Result<ProjectDiff, AppError> {
+    let a = crate::project::serialization::load(&PathBuf::from(path_a))?;
+    let b = crate::project::serialization::load(&PathBuf::from(path_b))?;
+    Ok(crate::project::diff::diff(&a, &b))
+}
+
+// ── get_recent_files ──────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`get_recent_files`].
+pub(crate) fn get_recent_files_inner(
+    prefs_lock: &RwLock<UserPreferences>,
+) -> Result<Vec<PathBuf>, AppError> {
+    let prefs = prefs_lock
+        .read()
+        .map_err(|e| AppError::Io(format!("preferences lock poisoned: {e}")))?;
+    Ok(prefs.recent_files.iter().cloned().collect())
+}
+
 // ── Tauri command wrappers ────────────────────────────────────────────────────
 
 /// Open a 3D model file, tessellate it, and store it in the active project.
 ///
 /// Tessellation is offloaded to a blocking thread pool because it is
 /// CPU-bound. Returns the [`MeshData`] so the frontend can begin rendering
-/// immediately.
+/// immediately. Omitted `tessellation` tolerances are derived from the part.
 #[tauri::command]
 pub async fn open_model(
     path: String,
+    tessellation: Option<TessellationOptions>,
+    app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
 ) -> Result<MeshData, AppError> {
-    open_model_inner(&path, &state.project).await
+    use tauri::Emitter as _;
+
+    let progress = move |fraction: f32| {
+        // Progress is advisory; a closed window must not fail the import.
+        let _ = app.emit(TESSELLATION_PROGRESS_EVENT, fraction);
+    };
+    open_model_inner(
+        &path,
+        tessellation.unwrap_or_default(),
+        MeshCache::user(),
+        progress,
+        &state.project,
+        Some(&state.preferences),
+    )
+    .await
+}
+
+/// List the cylindrical holes in the loaded model, so the UI can seed drill
+/// operations from them.
+#[tauri::command]
+pub async fn detect_holes(state: tauri::State<'_, AppState>) -> Result<Vec<HoleInfo>, AppError> {
+    detect_holes_inner(&state.project)
+}
+
+/// List the faces of the loaded model with their surface type and area, so
+/// the UI can pick flat faces for facing operations.
+#[tauri::command]
+pub async fn list_faces(state: tauri::State<'_, AppState>) -> Result<Vec<FaceInfo>, AppError> {
+    list_faces_inner(&state.project)
+}
+
+/// Slice the loaded model at height `z` into closed XY loops (outer
+/// boundaries and holes), so the UI can draw the cross-section.
+#[tauri::command]
+pub async fn section_model_at_z(
+    z: f64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Vec<(f64, f64)>>, AppError> {
+    section_model_at_z_inner(z, &state.project)
 }
 
-/// Serialize the active project to a `.jcam` file at `path`.
+/// Serialize the active project to a `.jcam` file at `path`, embedding the
+/// source model file when `embed_model` is set.
 #[tauri::command]
-pub async fn save_project(path: String, state: tauri::State<'_, AppState>) -> Result<(), AppError> {
-    save_project_inner(&path, &state.project)
+pub async fn save_project(
+    path: String,
+    embed_model: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    save_project_inner(&path, embed_model.unwrap_or(false), &state.project)
 }
 
 /// Load a `.jcam` file and replace the active project.
@@ -152,7 +572,30 @@ pub async fn load_project(
     path: String,
     state: tauri::State<'_, AppState>,
 ) -> Result<ProjectSnapshot, AppError> {
-    load_project_inner(&path, &state.project)
+    load_project_inner(&path, &state.project, &state.preferences)
+}
+
+/// Write the active project to its newest autosave slot, rotating older
+/// autosaves. Returns the path written.
+#[tauri::command]
+pub async fn autosave(state: tauri::State<'_, AppState>) -> Result<PathBuf, AppError> {
+    autosave_inner(&state.project)
+}
+
+/// Replace the active project with its newest autosave.
+///
+/// Returns a [`ProjectSnapshot`] for immediate display in the frontend.
+#[tauri::command]
+pub async fn restore_autosave(
+    state: tauri::State<'_, AppState>,
+) -> Result<ProjectSnapshot, AppError> {
+    restore_autosave_inner(&state.project)
+}
+
+/// List recently opened models and projects, newest first.
+#[tauri::command]
+pub async fn get_recent_files(state: tauri::State<'_, AppState>) -> Result<Vec<PathBuf>, AppError> {
+    get_recent_files_inner(&state.preferences)
 }
 
 /// Reset the active project to a fresh default state.
@@ -163,31 +606,224 @@ pub async fn new_project(state: tauri::State<'_, AppState>) -> Result<ProjectSna
     new_project_inner(&state.project)
 }
 
+/// Start a new project from the `.jcam` template at `path`.
+///
+/// Unlike [`load_project`], the template's model reference and timestamps are
+/// dropped and the template is not added to the recent files list.
+#[tauri::command]
+pub async fn new_project_from_template(
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ProjectSnapshot, AppError> {
+    new_project_from_template_inner(Path::new(&path), &state.project)
+}
+
+/// Recompute the loaded model's checksum from the file on disk.
+///
+/// Returns the new SHA-256 hex digest.
+#[tauri::command]
+pub async fn refresh_model_reference(
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    refresh_model_reference_inner(&state.project)
+}
+
+/// Check whether the loaded model's file still matches its stored checksum.
+///
+/// Returns `match`, `mismatch`, or `missing`, or [`AppError::NotFound`] if no
+/// model is loaded.
+#[tauri::command]
+pub async fn verify_model_checksum(
+    state: tauri::State<'_, AppState>,
+) -> Result<ModelChecksumStatus, AppError> {
+    verify_model_checksum_inner(&state.project)
+}
+
+/// Delete all cached tessellations, forcing the next import to re-mesh.
+///
+/// Returns the number of cache entries removed.
+#[tauri::command]
+pub async fn clear_mesh_cache() -> Result<usize, AppError> {
+    clear_mesh_cache_inner(MeshCache::user().as_ref())
+}
+
+/// Validate (and optionally create the directory for) an output file path.
+///
+/// Returns the path with `~` expanded, ready to write to.
+#[tauri::command]
+pub async fn prepare_output_path(path: String, create_dirs: bool) -> Result<PathBuf, AppError> {
+    prepare_output_path_inner(&path, create_dirs)
+}
+
+/// Compare two `.jcam` files and return their structural differences.
+#[tauri::command]
+pub async fn project_diff(path_a: String, path_b: String) -> Result<ProjectDiff, AppError> {
+    project_diff_inner(&path_a, &path_b)
+}
+
+// ── prepare_output_path ───────────────────────────────────────────────────────
+
+/// Characters Windows does not allow in a file name. They are rejected on
+/// every platform: programs are often copied to Windows-based controllers.
+const ILLEGAL_FILENAME_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Testable inner logic for [`prepare_output_path`].
+///
+/// 1. Expands a leading `~` to the user's home directory.
+/// 2. Returns [`AppError::InvalidPath`] if the file name is missing or
+///    contains a control character or one Windows reserves.
+/// 3. Creates the parent directory when `create_dirs` is set; otherwise
+///    returns [`AppError::Io`] if it does not exist.
+/// 4. Returns [`AppError::Io`] if the parent directory is not writable.
+pub(crate) fn prepare_output_path_inner(
+    path_str: &str,
+    create_dirs: bool,
+) -> Result<PathBuf, AppError> {
+    let path = expand_home(path_str)?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| AppError::InvalidPath(format!("'{path_str}' has no file name")))?;
+    if let Some(c) = file_name
+        .chars()
+        .find(|c| c.is_control() || ILLEGAL_FILENAME_CHARS.contains(c))
+    {
+        return Err(AppError::InvalidPath(format!(
+            "file name '{file_name}' contains illegal character {c:?}"
+        )));
+    }
+
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    if !parent.is_dir() {
+        if create_dirs {
+            std::fs::create_dir_all(&parent)?;
+        } else {
+            return Err(AppError::Io(format!(
+                "directory '{}' does not exist",
+                parent.display()
+            )));
+        }
+    }
+    check_writable(&parent)?;
+
+    Ok(path)
+}
+
+fn expand_home(path_str: &str) -> Result<PathBuf, AppError> {
+    let rest = match path_str.strip_prefix('~') {
+        None => return Ok(PathBuf::from(path_str)),
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        // `~user` forms are not expanded.
+        Some(_) => return Ok(PathBuf::from(path_str)),
+    };
+    let home = dirs::home_dir()
+        .ok_or_else(|| AppError::InvalidPath("cannot resolve home directory".to_string()))?;
+    Ok(home.join(rest.trim_start_matches(['/', '\\'])))
+}
+
+/// Probes `dir` by creating and removing an empty file; permission bits alone
+/// do not account for ACLs or read-only mounts.
+fn check_writable(dir: &Path) -> Result<(), AppError> {
+    let probe = dir.join(format!(".jamiecam-write-test-{}", uuid::Uuid::new_v4()));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|e| {
+            AppError::Io(format!(
+                "directory '{}' is not writable: {e}",
+                dir.display()
+            ))
+        })?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
 // ── export_gcode ──────────────────────────────────────────────────────────────
 
-/// Input parameters for [`export_gcode`].
+/// What to generate: the operations, post-processor and output options
+/// shared by [`export_gcode`] and [`export_gcode_string`].
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ExportParams {
+pub struct ProgramParams {
     pub operation_ids: Vec<String>,
     pub post_processor_id: String,
-    pub output_path: String,
     pub program_number: Option<u32>,
-    pub include_comments: bool,
+    /// Comment density: `"none"`, `"operations"` or `"verbose"`.
+    #[serde(default)]
+    pub comments: Option<CommentLevel>,
+    /// Legacy all-or-nothing flag, used when `comments` is absent:
+    /// `false` → none, `true` → verbose.
+    #[serde(default)]
+    pub include_comments: Option<bool>,
+    /// Insert an optional stop (M01) between operations.
+    #[serde(default)]
+    pub optional_stop_between_ops: bool,
+    /// Safe Z for retracts between passes and operations; `None` keeps the
+    /// toolpaths' own rapids.
+    #[serde(default)]
+    pub clearance_z: Option<f64>,
+    /// Open the program with post / program / runtime comments; needs
+    /// program-level comments.
+    #[serde(default)]
+    pub emit_stats_header: bool,
 }
 
-/// Testable inner logic for [`export_gcode`].
+impl ProgramParams {
+    /// The requested comment density, falling back to the legacy
+    /// `include_comments` flag and then to no comments.
+    fn comment_level(&self) -> CommentLevel {
+        self.comments
+            .unwrap_or_else(|| self.include_comments.unwrap_or(false).into())
+    }
+}
+
+/// Input parameters for [`export_gcode`]: [`ProgramParams`] plus where to
+/// write the program.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportParams {
+    #[serde(flatten)]
+    pub program: ProgramParams,
+    pub output_path: String,
+    /// Create missing parent directories of `output_path`.
+    #[serde(default)]
+    pub create_dirs: bool,
+}
+
+/// A program ready to generate: the loaded post, the toolpaths in program
+/// order and the options, all resolved from [`ProgramParams`].
+struct PreparedProgram {
+    post: PostProcessor,
+    toolpaths: Vec<Toolpath>,
+    tool_infos: Vec<ToolInfo>,
+    options: GenerateOptions,
+}
+
+impl PreparedProgram {
+    /// Streams the generated program into `writer`.
+    fn write_to<W: Write>(self, writer: W) -> Result<GenerateSummary, PostProcessorError> {
+        self.post
+            .generate_to_writer(&self.toolpaths, &self.tool_infos, self.options, writer)
+    }
+}
+
+/// Resolves `params` against the project, before anything is written:
 ///
 /// 1. Parses all operation UUIDs.
 /// 2. Verifies each operation exists in the project.
 /// 3. Looks up each toolpath by operation UUID.
-/// 4. Builds [`crate::postprocessor::ToolInfo`] from matching operations and tools.
-/// 5. Loads the named builtin post-processor.
-/// 6. Generates G-code and writes it to `params.output_path`.
-pub(crate) fn export_gcode_inner(
-    params: ExportParams,
+/// 4. Builds [`ToolInfo`] from matching operations and tools.
+/// 5. Loads the named post-processor, preferring a user post in `user_dir`.
+fn prepare_program(
+    params: &ProgramParams,
+    user_dir: Option<&Path>,
     project_lock: &RwLock<Project>,
-) -> Result<(), AppError> {
+) -> Result<PreparedProgram, AppError> {
     let op_uuids = params
         .operation_ids
         .iter()
@@ -202,12 +838,7 @@ pub(crate) fn export_gcode_inner(
             if !project.operations.iter().any(|op| op.id == *op_uuid) {
                 return Err(AppError::NotFound(format!("operation {op_uuid} not found")));
             }
-            let toolpath = project
-                .toolpaths
-                .get(op_uuid)
-                .ok_or_else(|| AppError::NotFound(format!("no toolpath for operation {op_uuid}")))?
-                .clone();
-            toolpaths.push(toolpath);
+            toolpaths.push(cached_toolpath(&project, op_uuid)?);
         }
 
         let tool_infos = build_tool_infos(&toolpaths, &project);
@@ -215,32 +846,241 @@ pub(crate) fn export_gcode_inner(
         (toolpaths, tool_infos)
     }; // read lock released here
 
-    let pp = PostProcessor::builtin(&params.post_processor_id)
-        .map_err(|e| AppError::PostProcessor(e.to_string()))?;
+    let post = PostProcessor::load(&params.post_processor_id, user_dir)?;
+
+    Ok(PreparedProgram {
+        post,
+        toolpaths,
+        tool_infos,
+        options: GenerateOptions {
+            program_number: params.program_number,
+            comments: params.comment_level(),
+            optional_stop_between_ops: params.optional_stop_between_ops,
+            clearance_z: params.clearance_z,
+            emit_stats_header: params.emit_stats_header,
+            ..Default::default()
+        },
+    })
+}
 
-    let gcode = pp
-        .generate(
-            &toolpaths,
-            &tool_infos,
-            GenerateOptions {
-                program_number: params.program_number,
-                include_comments: params.include_comments,
-            },
-        )
-        .map_err(|e| AppError::PostProcessor(e.to_string()))?;
+/// Testable inner logic for [`export_gcode`].
+///
+/// Validates `params.output_path` with [`prepare_output_path_inner`] and
+/// resolves the program with [`prepare_program`], then streams the generated
+/// G-code into `<path>.tmp` beside the prepared path and renames it over the
+/// target. If generation fails part-way the temp file is removed and any
+/// existing program at the path is left untouched.
+///
+/// Returns the generation warnings so the UI can show them after the write.
+pub(crate) fn export_gcode_inner(
+    params: ExportParams,
+    user_dir: Option<&Path>,
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<String>, AppError> {
+    let output_path = prepare_output_path_inner(&params.output_path, params.create_dirs)?;
+    let program = prepare_program(&params.program, user_dir, project_lock)?;
+
+    let file_name = output_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let tmp_path = output_path.with_file_name(format!("{file_name}.tmp"));
+
+    let written = std::fs::File::create(&tmp_path)
+        .map_err(AppError::from)
+        .and_then(|file| Ok(program.write_to(BufWriter::new(file))?));
+    let summary = match written {
+        Ok(summary) => summary,
+        Err(e) => {
+            // Don't leave a truncated program behind for someone to run.
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(e);
+        }
+    };
 
-    std::fs::write(&params.output_path, gcode).map_err(AppError::from)?;
+    std::fs::rename(&tmp_path, &output_path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        AppError::from(e)
+    })?;
 
-    Ok(())
+    Ok(summary.warnings)
+}
+
+/// Testable inner logic for [`export_gcode_string`].
+///
+/// Runs the same pipeline as [`export_gcode_inner`] but returns the program
+/// text instead of writing it, so a multi-operation program can be previewed
+/// before it is saved.
+pub(crate) fn export_gcode_string_inner(
+    params: ProgramParams,
+    user_dir: Option<&Path>,
+    project_lock: &RwLock<Project>,
+) -> Result<String, AppError> {
+    let mut buf = Vec::new();
+    prepare_program(&params, user_dir, project_lock)?.write_to(&mut buf)?;
+    String::from_utf8(buf)
+        .map_err(|e| AppError::PostProcessor(format!("generated G-code is not UTF-8: {e}")))
 }
 
 /// Generate G-code for the given operations and write it to the output path.
+///
+/// Returns any generation warnings.
 #[tauri::command]
 pub async fn export_gcode(
     params: ExportParams,
     state: tauri::State<'_, AppState>,
+) -> Result<Vec<String>, AppError> {
+    let user_dir = user_post_dir(&state.preferences)?;
+    export_gcode_inner(params, user_dir.as_deref(), &state.project)
+}
+
+/// Generate G-code for the given operations and return it without writing a
+/// file.
+#[tauri::command]
+pub async fn export_gcode_string(
+    params: ProgramParams,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let user_dir = user_post_dir(&state.preferences)?;
+    export_gcode_string_inner(params, user_dir.as_deref(), &state.project)
+}
+
+// ── export_operations_summary_json ────────────────────────────────────────────
+
+/// One entry of the operations summary written by
+/// [`export_operations_summary_json`]. Missing data is omitted, not nulled.
+#[derive(Debug, serde::Serialize)]
+pub struct OperationSummaryEntry {
+    pub id: uuid::Uuid,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub operation_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<SummaryTool>,
+    pub feeds: SummaryFeeds,
+    pub depth: f64,
+    pub enabled: bool,
+}
+
+/// The tool referenced by an [`OperationSummaryEntry`].
+#[derive(Debug, serde::Serialize)]
+pub struct SummaryTool {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<u32>,
+    pub name: String,
+    pub diameter: f64,
+}
+
+/// Spindle speed (RPM) and feed rate of an [`OperationSummaryEntry`].
+#[derive(Debug, Default, serde::Serialize)]
+pub struct SummaryFeeds {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spindle: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed: Option<f64>,
+}
+
+/// Testable inner logic for [`export_operations_summary_json`].
+///
+/// Writes one [`OperationSummaryEntry`] per project operation, in list order.
+/// Feeds and the tool number come from the operation's cached toolpath when
+/// there is one, otherwise from the tool's defaults; an operation whose tool
+/// is no longer in the library is written without a `tool` object.
+///
+/// `path` is checked with [`prepare_output_path_inner`] first.
+pub(crate) fn export_operations_summary_json_inner(
+    path: &str,
+    project_lock: &RwLock<Project>,
+) -> Result<(), AppError> {
+    use crate::models::operation::OperationParams;
+
+    let path = prepare_output_path_inner(path, false)?;
+    let entries: Vec<OperationSummaryEntry> = {
+        let project = read_project(project_lock)?;
+        project
+            .operations
+            .iter()
+            .map(|op| {
+                let tool = project.tools.iter().find(|t| t.id == op.tool_id);
+                let toolpath = project.toolpaths.get(&op.id);
+                let (operation_type, depth) = match &op.params {
+                    OperationParams::Profile(p) => ("profile", p.depth),
+                    OperationParams::Pocket(p) => ("pocket", p.depth),
+                    OperationParams::Drill(p) => ("drill", p.depth),
+                };
+                let feeds = match toolpath {
+                    Some(tp) => SummaryFeeds {
+                        spindle: Some(tp.spindle_speed),
+                        feed: Some(tp.feed_rate),
+                    },
+                    None => SummaryFeeds {
+                        spindle: tool.and_then(|t| t.default_spindle_speed.map(f64::from)),
+                        feed: tool.and_then(|t| t.default_feed_rate),
+                    },
+                };
+                OperationSummaryEntry {
+                    id: op.id,
+                    name: op.name.clone(),
+                    operation_type,
+                    tool: tool.map(|t| SummaryTool {
+                        number: toolpath.map(|tp| tp.tool_number),
+                        name: t.name.clone(),
+                        diameter: t.diameter,
+                    }),
+                    feeds,
+                    depth,
+                    enabled: op.enabled,
+                }
+            })
+            .collect()
+    }; // read lock released here
+
+    let json = serde_json::to_string_pretty(&entries).map_err(|e| AppError::Io(e.to_string()))?;
+    std::fs::write(path, json).map_err(AppError::from)
+}
+
+/// Write a machine-readable JSON summary of the project's operations to `path`.
+#[tauri::command]
+pub async fn export_operations_summary_json(
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    export_operations_summary_json_inner(&path, &state.project)
+}
+
+// ── export_mesh_stl ───────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`export_mesh_stl`].
+///
+/// Writes the loaded model's tessellated mesh to `path` as binary or ASCII
+/// STL. Returns [`AppError::NotFound`] if no model is loaded; `path` is
+/// checked with [`prepare_output_path_inner`] first.
+pub(crate) fn export_mesh_stl_inner(
+    path: &str,
+    binary: bool,
+    project_lock: &RwLock<Project>,
+) -> Result<(), AppError> {
+    let path = prepare_output_path_inner(path, false)?;
+    let project = read_project(project_lock)?;
+    let model = project
+        .source_model
+        .as_ref()
+        .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+    model
+        .mesh_data
+        .write_stl(&path, binary)
+        .map_err(AppError::from)
+}
+
+/// Write the loaded model's mesh to `path` as STL for external tools.
+#[tauri::command]
+pub async fn export_mesh_stl(
+    path: String,
+    binary: bool,
+    state: tauri::State<'_, AppState>,
 ) -> Result<(), AppError> {
-    export_gcode_inner(params, &state.project)
+    export_mesh_stl_inner(&path, binary, &state.project)
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -280,7 +1120,8 @@ mod tests {
         }
 
         let tmp = std::env::temp_dir().join("jcam_cmd_test_round_trip.jcam");
-        save_project_inner(&tmp.to_string_lossy(), &state.project).expect("save should succeed");
+        save_project_inner(&tmp.to_string_lossy(), false, &state.project)
+            .expect("save should succeed");
 
         // After save, both timestamps must be non-empty ISO-8601 strings.
         {
@@ -298,11 +1139,15 @@ mod tests {
         // Reset state, then load the saved file.
         new_project_inner(&state.project).expect("new_project should succeed");
 
-        let snap = load_project_inner(&tmp.to_string_lossy(), &state.project)
+        let snap = load_project_inner(&tmp.to_string_lossy(), &state.project, &state.preferences)
             .expect("load should succeed");
         let _ = std::fs::remove_file(&tmp);
 
         assert_eq!(snap.project_name, "Round Trip");
+        assert_eq!(
+            get_recent_files_inner(&state.preferences).expect("recent files"),
+            vec![tmp.clone()]
+        );
         let project = state.project.read().expect("read lock");
         assert_eq!(project.name, "Round Trip");
         assert_eq!(project.schema_version, 1);
@@ -322,12 +1167,12 @@ mod tests {
         let tmp = std::env::temp_dir().join("jcam_cmd_test_created_at.jcam");
 
         // First save: sets created_at.
-        save_project_inner(&tmp.to_string_lossy(), &state.project).expect("first save");
+        save_project_inner(&tmp.to_string_lossy(), false, &state.project).expect("first save");
         let created_at_1 = state.project.read().expect("read").created_at.clone();
         assert!(!created_at_1.is_empty());
 
         // Second save: created_at must not change; modified_at may change.
-        save_project_inner(&tmp.to_string_lossy(), &state.project).expect("second save");
+        save_project_inner(&tmp.to_string_lossy(), false, &state.project).expect("second save");
         let _ = std::fs::remove_file(&tmp);
         let created_at_2 = state.project.read().expect("read").created_at.clone();
 
@@ -340,23 +1185,199 @@ mod tests {
     #[test]
     fn load_project_returns_err_for_missing_file() {
         let state = AppState::default();
-        let result = load_project_inner("/nonexistent/path/project.jcam", &state.project);
+        let result = load_project_inner(
+            "/nonexistent/path/project.jcam",
+            &state.project,
+            &state.preferences,
+        );
         assert!(matches!(result, Err(AppError::ProjectLoad(_))));
     }
 
     #[test]
     fn save_project_to_invalid_path_returns_err() {
         let state = AppState::default();
-        let result = save_project_inner("/nonexistent_dir_jamiecam/project.jcam", &state.project);
+        let result = save_project_inner(
+            "/nonexistent_dir_jamiecam/project.jcam",
+            false,
+            &state.project,
+        );
         assert!(matches!(result, Err(AppError::ProjectSave(_))));
     }
 
+    // ── autosave / restore_autosave ───────────────────────────────────────
+
+    fn state_saved_in(dir_name: &str) -> (AppState, PathBuf) {
+        let dir = std::env::temp_dir().join(dir_name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let state = AppState::default();
+        {
+            let mut p = state.project.write().expect("write lock");
+            p.name = "Autosaved".to_string();
+            p.created_at = "2026-01-01T00:00:00Z".to_string();
+            p.path = Some(dir.join("job.jcam"));
+        }
+        (state, dir)
+    }
+
+    #[test]
+    fn autosave_writes_loadable_archive_without_touching_project() {
+        let (state, dir) = state_saved_in("jcam_cmd_test_autosave");
+
+        let written = autosave_inner(&state.project).expect("autosave");
+        assert_eq!(written, dir.join("job.jcam.autosave.jcam"));
+
+        let loaded = crate::project::serialization::load(&written).expect("load autosave");
+        assert_eq!(loaded.name, "Autosaved");
+        let p = state.project.read().expect("read lock");
+        assert_eq!(p.created_at, "2026-01-01T00:00:00Z");
+        assert!(p.modified_at.is_empty());
+        assert_eq!(p.path, Some(dir.join("job.jcam")));
+        drop(p);
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn autosave_keeps_only_the_newest_slots() {
+        let (state, dir) = state_saved_in("jcam_cmd_test_autosave_rotate");
+
+        for _ in 0..AUTOSAVE_KEEP + 2 {
+            autosave_inner(&state.project).expect("autosave");
+        }
+
+        let count = std::fs::read_dir(&dir).expect("read dir").count();
+        assert_eq!(count, AUTOSAVE_KEEP);
+        assert!(dir.join("job.jcam.autosave.2.jcam").exists());
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn restore_autosave_loads_newest_and_keeps_project_path() {
+        let (state, dir) = state_saved_in("jcam_cmd_test_autosave_restore");
+        autosave_inner(&state.project).expect("autosave");
+        state.project.write().expect("write lock").name = "Edited".to_string();
+
+        let snap = restore_autosave_inner(&state.project).expect("restore");
+        assert_eq!(snap.project_name, "Autosaved");
+        let p = state.project.read().expect("read lock");
+        assert_eq!(p.path, Some(dir.join("job.jcam")));
+        drop(p);
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn restore_autosave_without_autosave_is_file_not_found() {
+        let (state, dir) = state_saved_in("jcam_cmd_test_autosave_none");
+        let result = restore_autosave_inner(&state.project);
+        assert!(matches!(result, Err(AppError::FileNotFound)));
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    // ── new_project_from_template ─────────────────────────────────────────
+
+    #[test]
+    fn new_project_from_template_keeps_tools_and_clears_model() {
+        let state = AppState::default();
+        {
+            let mut p = state.project.write().expect("write lock");
+            p.name = "Shop Template".to_string();
+            p.tools.push(crate::models::Tool {
+                id: uuid::Uuid::new_v4(),
+                name: "6mm Flat".to_string(),
+                tool_type: crate::models::ToolType::FlatEndmill,
+                material: "carbide".to_string(),
+                diameter: 6.0,
+                flute_count: 3,
+                default_spindle_speed: None,
+                default_feed_rate: None,
+                tip_angle: None,
+            });
+            p.source_model = Some(LoadedModel {
+                path: PathBuf::from("/parts/old-job.step"),
+                original_path: None,
+                checksum: "abc123".to_string(),
+                mesh_data: MeshData {
+                    vertices: vec![],
+                    normals: vec![],
+                    indices: vec![],
+                },
+            });
+        }
+        let tmp = std::env::temp_dir().join("jcam_cmd_test_template.jcam");
+        save_project_inner(&tmp.to_string_lossy(), false, &state.project).expect("save template");
+        new_project_inner(&state.project).expect("reset");
+
+        let snap =
+            new_project_from_template_inner(&tmp, &state.project).expect("template should load");
+        let _ = std::fs::remove_file(&tmp);
+
+        assert!(snap.model_path.is_none());
+        let project = state.project.read().expect("read lock");
+        assert_eq!(project.tools.len(), 1);
+        assert_eq!(project.tools[0].name, "6mm Flat");
+        assert!(project.source_model.is_none());
+        assert!(project.created_at.is_empty());
+        assert!(project.modified_at.is_empty());
+    }
+
+    #[test]
+    fn new_project_from_template_missing_file_keeps_active_project() {
+        let state = AppState::default();
+        state.project.write().expect("write lock").name = "Current".to_string();
+
+        let result = new_project_from_template_inner(
+            Path::new("/nonexistent/template.jcam"),
+            &state.project,
+        );
+        assert!(matches!(result, Err(AppError::ProjectLoad(_))));
+        assert_eq!(state.project.read().expect("read lock").name, "Current");
+    }
+
+    // ── project_diff ──────────────────────────────────────────────────────
+
+    #[test]
+    fn project_diff_reports_one_added_operation() {
+        let (state, _) = make_export_state();
+        let path_a = std::env::temp_dir().join("jcam_cmd_test_diff_a.jcam");
+        let path_b = std::env::temp_dir().join("jcam_cmd_test_diff_b.jcam");
+        save_project_inner(&path_a.to_string_lossy(), false, &state.project).expect("save a");
+
+        let added_id = {
+            let mut p = state.project.write().expect("write lock");
+            let mut extra = p.operations[0].clone();
+            extra.id = uuid::Uuid::new_v4();
+            extra.name = "Finish Pocket".to_string();
+            p.operations.push(extra);
+            p.operations[1].id
+        };
+        save_project_inner(&path_b.to_string_lossy(), false, &state.project).expect("save b");
+
+        let diff = project_diff_inner(&path_a.to_string_lossy(), &path_b.to_string_lossy());
+        let _ = std::fs::remove_file(&path_a);
+        let _ = std::fs::remove_file(&path_b);
+        let diff = diff.expect("diff should succeed");
+
+        assert_eq!(diff.operations.added.len(), 1);
+        assert_eq!(diff.operations.added[0].id, added_id);
+        assert!(diff.operations.removed.is_empty());
+        assert!(diff.operations.changed.is_empty());
+        assert!(diff.tools.is_empty());
+    }
+
     // ── open_model ────────────────────────────────────────────────────────
 
     #[tokio::test]
     async fn open_model_returns_file_not_found_for_missing_path() {
         let state = AppState::default();
-        let result = open_model_inner("/nonexistent/path/model.step", &state.project).await;
+        let result = open_model_inner(
+            "/nonexistent/path/model.step",
+            TessellationOptions::default(),
+            None,
+            |_| {},
+            &state.project,
+            Some(&state.preferences),
+        )
+        .await;
         assert!(matches!(result, Err(AppError::FileNotFound)));
     }
 
@@ -373,7 +1394,15 @@ mod tests {
             return; // fixture absent in this environment — skip
         }
         let state = AppState::default();
-        let result = open_model_inner(&fixture.to_string_lossy(), &state.project).await;
+        let result = open_model_inner(
+            &fixture.to_string_lossy(),
+            TessellationOptions::default(),
+            None,
+            |_| {},
+            &state.project,
+            Some(&state.preferences),
+        )
+        .await;
         assert!(
             matches!(result, Err(AppError::GeometryImport(_))),
             "expected GeometryImport, got: {result:?}",
@@ -393,9 +1422,16 @@ mod tests {
             "/../tests/fixtures/box.step",
         ));
         let state = AppState::default();
-        let mesh = open_model_inner(&fixture.to_string_lossy(), &state.project)
-            .await
-            .expect("open_model should succeed with OCCT");
+        let mesh = open_model_inner(
+            &fixture.to_string_lossy(),
+            TessellationOptions::default(),
+            None,
+            |_| {},
+            &state.project,
+            Some(&state.preferences),
+        )
+        .await
+        .expect("open_model should succeed with OCCT");
         assert!(!mesh.vertices.is_empty(), "vertices must not be empty");
         assert_eq!(
             mesh.vertices.len(),
@@ -429,7 +1465,7 @@ mod tests {
 
     fn make_export_state() -> (AppState, uuid::Uuid) {
         use crate::models::{
-            operation::{OperationParams, PocketParams},
+            operation::{CoolantMode, FeedMode, LeadType, OperationParams, PocketParams},
             tool::ToolType,
             Operation, Tool, Vec3,
         };
@@ -450,6 +1486,7 @@ mod tests {
             flute_count: 4,
             default_spindle_speed: None,
             default_feed_rate: None,
+            tip_angle: None,
         };
 
         let operation = Operation {
@@ -461,7 +1498,12 @@ mod tests {
                 depth: 10.0,
                 stepdown: 2.0,
                 stepover_percent: 50.0,
+                lead_in: LeadType::None,
+                stock_to_leave: 0.0,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         };
 
         let toolpath = Toolpath {
@@ -491,7 +1533,13 @@ mod tests {
                         tool_orientation: None,
                     },
                 ],
+                feed_rate: None,
             }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
         };
 
         {
@@ -509,14 +1557,26 @@ mod tests {
         let (state, op_id) = make_export_state();
         let tmp = std::env::temp_dir().join("jcam_export_gcode_test.nc");
         let params = ExportParams {
-            operation_ids: vec![op_id.to_string()],
-            post_processor_id: "fanuc-0i".to_string(),
+            program: ProgramParams {
+                operation_ids: vec![op_id.to_string()],
+                post_processor_id: "fanuc-0i".to_string(),
+                program_number: Some(1),
+                comments: Some(CommentLevel::Verbose),
+                include_comments: None,
+                optional_stop_between_ops: false,
+                clearance_z: None,
+                emit_stats_header: false,
+            },
             output_path: tmp.to_string_lossy().to_string(),
-            program_number: Some(1),
-            include_comments: true,
+            create_dirs: false,
         };
 
-        export_gcode_inner(params, &state.project).expect("export should succeed");
+        let warnings =
+            export_gcode_inner(params, None, &state.project).expect("export should succeed");
+        assert!(
+            warnings.is_empty(),
+            "clean export must not warn: {warnings:?}"
+        );
 
         assert!(tmp.exists(), "output file must exist after export");
         let content = std::fs::read_to_string(&tmp).expect("read output file");
@@ -524,10 +1584,127 @@ mod tests {
         let _ = std::fs::remove_file(&tmp);
     }
 
+    #[test]
+    fn failed_export_keeps_existing_program() {
+        use crate::models::operation::FeedMode;
+
+        let (state, op_id) = make_export_state();
+        {
+            // Feed per revolution without a spindle speed fails mid-program.
+            let mut project = state.project.write().expect("write lock");
+            project.operations[0].feed_mode = FeedMode::PerRev;
+            project
+                .toolpaths
+                .get_mut(&op_id)
+                .expect("toolpath")
+                .spindle_speed = 0.0;
+        }
+        let tmp = std::env::temp_dir().join("jcam_export_gcode_keep_test.nc");
+        std::fs::write(&tmp, "previous program\n").expect("seed existing program");
+        let params = ExportParams {
+            program: ProgramParams {
+                operation_ids: vec![op_id.to_string()],
+                post_processor_id: "fanuc-0i".to_string(),
+                program_number: None,
+                comments: None,
+                include_comments: None,
+                optional_stop_between_ops: false,
+                clearance_z: None,
+                emit_stats_header: false,
+            },
+            output_path: tmp.to_string_lossy().to_string(),
+            create_dirs: false,
+        };
+
+        let result = export_gcode_inner(params, None, &state.project);
+        let content = std::fs::read_to_string(&tmp).expect("existing program remains");
+        let _ = std::fs::remove_file(&tmp);
+        assert!(result.is_err(), "expected failure, got {result:?}");
+        assert_eq!(content, "previous program\n");
+        assert!(!tmp
+            .with_file_name("jcam_export_gcode_keep_test.nc.tmp")
+            .exists());
+    }
+
+    #[test]
+    fn export_gcode_string_matches_exported_file() {
+        let (state, op_id) = make_export_state();
+        let tmp = std::env::temp_dir().join("jcam_export_gcode_string_test.nc");
+        let program = || ProgramParams {
+            operation_ids: vec![op_id.to_string()],
+            post_processor_id: "fanuc-0i".to_string(),
+            program_number: Some(1),
+            comments: Some(CommentLevel::Verbose),
+            include_comments: None,
+            optional_stop_between_ops: false,
+            clearance_z: None,
+            emit_stats_header: false,
+        };
+
+        let gcode =
+            export_gcode_string_inner(program(), None, &state.project).expect("dry run succeeds");
+        let params = ExportParams {
+            program: program(),
+            output_path: tmp.to_string_lossy().to_string(),
+            create_dirs: false,
+        };
+        export_gcode_inner(params, None, &state.project).expect("export should succeed");
+
+        let written = std::fs::read_to_string(&tmp).expect("read output file");
+        let _ = std::fs::remove_file(&tmp);
+        assert!(!gcode.is_empty());
+        assert_eq!(gcode, written);
+    }
+
+    #[test]
+    fn export_gcode_string_unknown_operation_returns_not_found() {
+        let (state, _) = make_export_state();
+        let params = ProgramParams {
+            operation_ids: vec![uuid::Uuid::new_v4().to_string()],
+            post_processor_id: "fanuc-0i".to_string(),
+            program_number: None,
+            comments: None,
+            include_comments: None,
+            optional_stop_between_ops: false,
+            clearance_z: None,
+            emit_stats_header: false,
+        };
+
+        let result = export_gcode_string_inner(params, None, &state.project);
+        assert!(
+            matches!(result, Err(AppError::NotFound(_))),
+            "expected NotFound, got: {result:?}"
+        );
+    }
+
+    #[test]
+    fn export_params_comment_level_prefers_level_over_legacy_flag() {
+        let params = |comments, include_comments| ProgramParams {
+            operation_ids: Vec::new(),
+            post_processor_id: "fanuc-0i".to_string(),
+            program_number: None,
+            comments,
+            include_comments,
+            optional_stop_between_ops: false,
+            clearance_z: None,
+            emit_stats_header: false,
+        };
+
+        assert_eq!(params(None, None).comment_level(), CommentLevel::None);
+        assert_eq!(
+            params(None, Some(true)).comment_level(),
+            CommentLevel::Verbose
+        );
+        assert_eq!(
+            params(Some(CommentLevel::Operations), Some(true)).comment_level(),
+            CommentLevel::Operations
+        );
+    }
+
     #[test]
     fn export_gcode_inner_returns_not_found_when_toolpath_absent() {
         use crate::models::{
-            operation::{OperationParams, PocketParams},
+            operation::{CoolantMode, FeedMode, LeadType, OperationParams, PocketParams},
             Operation,
         };
         use uuid::Uuid;
@@ -544,7 +1721,12 @@ mod tests {
                 depth: 10.0,
                 stepdown: 2.0,
                 stepover_percent: 50.0,
+                lead_in: LeadType::None,
+                stock_to_leave: 0.0,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         };
 
         {
@@ -554,14 +1736,21 @@ mod tests {
         }
 
         let params = ExportParams {
-            operation_ids: vec![op_id.to_string()],
-            post_processor_id: "fanuc-0i".to_string(),
+            program: ProgramParams {
+                operation_ids: vec![op_id.to_string()],
+                post_processor_id: "fanuc-0i".to_string(),
+                program_number: None,
+                comments: None,
+                include_comments: None,
+                optional_stop_between_ops: false,
+                clearance_z: None,
+                emit_stats_header: false,
+            },
             output_path: "/tmp/should_not_be_created.nc".to_string(),
-            program_number: None,
-            include_comments: false,
+            create_dirs: false,
         };
 
-        let result = export_gcode_inner(params, &state.project);
+        let result = export_gcode_inner(params, None, &state.project);
         assert!(
             matches!(result, Err(AppError::NotFound(_))),
             "expected NotFound, got: {result:?}"
@@ -572,17 +1761,415 @@ mod tests {
     fn export_gcode_inner_returns_io_error_for_unwritable_path() {
         let (state, op_id) = make_export_state();
         let params = ExportParams {
-            operation_ids: vec![op_id.to_string()],
-            post_processor_id: "fanuc-0i".to_string(),
+            program: ProgramParams {
+                operation_ids: vec![op_id.to_string()],
+                post_processor_id: "fanuc-0i".to_string(),
+                program_number: None,
+                comments: None,
+                include_comments: None,
+                optional_stop_between_ops: false,
+                clearance_z: None,
+                emit_stats_header: false,
+            },
             output_path: "/nonexistent_dir_jamiecam/output.nc".to_string(),
-            program_number: None,
-            include_comments: false,
+            create_dirs: false,
         };
 
-        let result = export_gcode_inner(params, &state.project);
+        let result = export_gcode_inner(params, None, &state.project);
         assert!(
             matches!(result, Err(AppError::Io(_))),
             "expected Io error, got: {result:?}"
         );
     }
+
+    #[test]
+    fn export_gcode_inner_rejects_invalid_file_name() {
+        let (state, op_id) = make_export_state();
+        let params = ExportParams {
+            program: ProgramParams {
+                operation_ids: vec![op_id.to_string()],
+                post_processor_id: "fanuc-0i".to_string(),
+                program_number: None,
+                comments: None,
+                include_comments: None,
+                optional_stop_between_ops: false,
+                clearance_z: None,
+                emit_stats_header: false,
+            },
+            output_path: std::env::temp_dir()
+                .join("bad|name.nc")
+                .to_string_lossy()
+                .into_owned(),
+            create_dirs: false,
+        };
+
+        let result = export_gcode_inner(params, None, &state.project);
+        assert!(
+            matches!(result, Err(AppError::InvalidPath(_))),
+            "{result:?}"
+        );
+    }
+
+    // ── clear_mesh_cache ──────────────────────────────────────────────────
+
+    #[test]
+    fn clear_mesh_cache_removes_entries() {
+        let dir = std::env::temp_dir().join("jcam_cmd_test_clear_mesh_cache");
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = MeshCache::new(&dir);
+        let mesh = MeshData {
+            vertices: vec![0.0; 9],
+            normals: vec![0.0; 9],
+            indices: vec![0, 1, 2],
+        };
+        cache
+            .store("abc", &TessellationOptions::default(), &mesh)
+            .unwrap();
+
+        assert_eq!(clear_mesh_cache_inner(Some(&cache)).unwrap(), 1);
+        assert!(!cache.entry_path("abc").exists());
+        assert_eq!(clear_mesh_cache_inner(None).unwrap(), 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // ── refresh_model_reference ───────────────────────────────────────────
+
+    #[test]
+    fn refresh_model_reference_updates_checksum_after_edit() {
+        let tmp = std::env::temp_dir().join("jcam_refresh_model_test.stl");
+        std::fs::write(&tmp, b"solid original").expect("write model");
+        let state = AppState::default();
+        state.project.write().expect("write lock").source_model = Some(LoadedModel {
+            path: tmp.clone(),
+            original_path: None,
+            checksum: file_checksum(&tmp).expect("checksum"),
+            mesh_data: MeshData {
+                vertices: vec![],
+                normals: vec![],
+                indices: vec![],
+            },
+        });
+        let before = state
+            .project
+            .read()
+            .unwrap()
+            .source_model
+            .as_ref()
+            .unwrap()
+            .checksum
+            .clone();
+
+        std::fs::write(&tmp, b"solid edited").expect("rewrite model");
+        let returned = refresh_model_reference_inner(&state.project).expect("refresh");
+
+        let stored = state
+            .project
+            .read()
+            .unwrap()
+            .source_model
+            .as_ref()
+            .unwrap()
+            .checksum
+            .clone();
+        assert_eq!(returned, stored);
+        assert_ne!(stored, before, "checksum must change with the file bytes");
+        let _ = std::fs::remove_file(&tmp);
+    }
+
+    // ── verify_model_checksum ─────────────────────────────────────────────
+
+    fn reference_model(state: &AppState, path: &Path, checksum: &str) {
+        state.project.write().expect("write lock").source_model = Some(LoadedModel {
+            path: path.to_path_buf(),
+            original_path: None,
+            checksum: checksum.to_string(),
+            mesh_data: MeshData {
+                vertices: vec![],
+                normals: vec![],
+                indices: vec![],
+            },
+        });
+    }
+
+    #[test]
+    fn verify_model_checksum_reports_match_mismatch_and_missing() {
+        let tmp = std::env::temp_dir().join("jcam_verify_checksum_test.stl");
+        std::fs::write(&tmp, b"solid part").expect("write model");
+        let state = AppState::default();
+
+        reference_model(&state, &tmp, &file_checksum(&tmp).expect("checksum"));
+        assert_eq!(
+            verify_model_checksum_inner(&state.project).unwrap(),
+            ModelChecksumStatus::Match
+        );
+
+        reference_model(&state, &tmp, "bogus");
+        assert_eq!(
+            verify_model_checksum_inner(&state.project).unwrap(),
+            ModelChecksumStatus::Mismatch
+        );
+
+        let _ = std::fs::remove_file(&tmp);
+        assert_eq!(
+            verify_model_checksum_inner(&state.project).unwrap(),
+            ModelChecksumStatus::Missing
+        );
+    }
+
+    #[test]
+    fn detect_holes_without_model_returns_not_found() {
+        let state = AppState::default();
+        let result = detect_holes_inner(&state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    #[test]
+    fn detect_holes_without_occt_returns_empty() {
+        let state = AppState::default();
+        reference_model(&state, Path::new("/parts/plate.step"), "");
+        assert!(detect_holes_inner(&state.project).unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_faces_without_model_returns_not_found() {
+        let state = AppState::default();
+        let result = list_faces_inner(&state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    #[test]
+    fn list_faces_without_occt_returns_empty() {
+        let state = AppState::default();
+        reference_model(&state, Path::new("/parts/plate.step"), "");
+        assert!(list_faces_inner(&state.project).unwrap().is_empty());
+    }
+
+    #[test]
+    fn section_model_at_z_without_model_returns_not_found() {
+        let state = AppState::default();
+        let result = section_model_at_z_inner(5.0, &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    #[test]
+    fn section_model_at_z_without_occt_fails() {
+        let state = AppState::default();
+        reference_model(&state, Path::new("/parts/plate.step"), "");
+        let result = section_model_at_z_inner(5.0, &state.project);
+        assert!(
+            matches!(result, Err(AppError::GeometryImport(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn verify_model_checksum_without_model_returns_not_found() {
+        let state = AppState::default();
+        let result = verify_model_checksum_inner(&state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn open_model_rejects_referenced_file_with_changed_checksum() {
+        let tmp = std::env::temp_dir().join("jcam_open_model_changed.stl");
+        std::fs::write(&tmp, b"solid edited").expect("write model");
+        let state = AppState::default();
+        reference_model(&state, &tmp, "bogus");
+
+        let result = open_model_inner(
+            &tmp.to_string_lossy(),
+            TessellationOptions::default(),
+            None,
+            |_| {},
+            &state.project,
+            Some(&state.preferences),
+        )
+        .await;
+        let _ = std::fs::remove_file(&tmp);
+
+        assert!(
+            matches!(result, Err(AppError::ModelChanged(_))),
+            "{result:?}"
+        );
+        let project = state.project.read().expect("read lock");
+        assert_eq!(project.source_model.as_ref().unwrap().checksum, "bogus");
+    }
+
+    #[tokio::test]
+    async fn open_model_adds_file_to_recent_files() {
+        let fixture = PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/triangle.3mf",
+        ));
+        let state = AppState::default();
+        open_model_inner(
+            &fixture.to_string_lossy(),
+            TessellationOptions::default(),
+            None,
+            |_| {},
+            &state.project,
+            Some(&state.preferences),
+        )
+        .await
+        .expect("open triangle.3mf");
+
+        let files = get_recent_files_inner(&state.preferences).expect("recent files");
+        assert_eq!(files, vec![fixture]);
+    }
+
+    // ── get_recent_files ──────────────────────────────────────────────────
+
+    #[test]
+    fn get_recent_files_returns_newest_first() {
+        let state = AppState::default();
+        {
+            let mut prefs = state.preferences.write().expect("write lock");
+            prefs.push_recent_file(PathBuf::from("/old.jcam"));
+            prefs.push_recent_file(PathBuf::from("/new.step"));
+        }
+
+        let files = get_recent_files_inner(&state.preferences).expect("should succeed");
+        assert_eq!(
+            files,
+            vec![PathBuf::from("/new.step"), PathBuf::from("/old.jcam")]
+        );
+    }
+
+    // ── export_mesh_stl ───────────────────────────────────────────────────
+
+    #[test]
+    fn export_mesh_stl_without_model_returns_not_found() {
+        let state = AppState::default();
+        let path = std::env::temp_dir().join("jcam_export_mesh_none.stl");
+        let result = export_mesh_stl_inner(&path.to_string_lossy(), true, &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn export_mesh_stl_rejects_invalid_file_name() {
+        let (state, _) = make_export_state();
+        let path = std::env::temp_dir().join("bad?mesh.stl");
+        let result = export_mesh_stl_inner(&path.to_string_lossy(), true, &state.project);
+        assert!(
+            matches!(result, Err(AppError::InvalidPath(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn export_mesh_stl_writes_loaded_mesh() {
+        let state = AppState::default();
+        state.project.write().expect("write lock").source_model = Some(LoadedModel {
+            path: PathBuf::from("/models/part.step"),
+            original_path: None,
+            checksum: String::new(),
+            mesh_data: MeshData {
+                vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                normals: vec![],
+                indices: vec![0, 1, 2],
+            },
+        });
+        let path = std::env::temp_dir().join("jcam_export_mesh_loaded.stl");
+
+        export_mesh_stl_inner(&path.to_string_lossy(), true, &state.project).expect("export");
+
+        let bytes = std::fs::read(&path).expect("read back");
+        assert_eq!(bytes.len(), 84 + 50, "one binary facet");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn refresh_model_reference_missing_file_returns_file_not_found() {
+        let state = AppState::default();
+        state.project.write().expect("write lock").source_model = Some(LoadedModel {
+            path: PathBuf::from("/nonexistent/jamiecam/model.step"),
+            original_path: None,
+            checksum: "stale".to_string(),
+            mesh_data: MeshData {
+                vertices: vec![],
+                normals: vec![],
+                indices: vec![],
+            },
+        });
+        let result = refresh_model_reference_inner(&state.project);
+        assert!(matches!(result, Err(AppError::FileNotFound)), "{result:?}");
+    }
+
+    // ── prepare_output_path ───────────────────────────────────────────────
+
+    #[test]
+    fn prepare_output_path_rejects_missing_parent_without_create() {
+        let dir = std::env::temp_dir().join(format!("jcam_prep_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("out.nc");
+        let result = prepare_output_path_inner(&path.to_string_lossy(), false);
+        assert!(matches!(result, Err(AppError::Io(_))), "{result:?}");
+        assert!(!dir.exists(), "directory must not be created");
+    }
+
+    #[test]
+    fn prepare_output_path_creates_missing_parent_when_requested() {
+        let dir = std::env::temp_dir().join(format!("jcam_prep_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("nested").join("out.nc");
+        let prepared =
+            prepare_output_path_inner(&path.to_string_lossy(), true).expect("should prepare");
+        assert_eq!(prepared, path);
+        assert!(path.parent().unwrap().is_dir());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn prepare_output_path_rejects_illegal_character() {
+        let path = std::env::temp_dir().join("bad\0name.nc");
+        let result = prepare_output_path_inner(&path.to_string_lossy(), false);
+        assert!(
+            matches!(result, Err(AppError::InvalidPath(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn prepare_output_path_rejects_windows_reserved_characters_everywhere() {
+        for name in ["part:1.nc", "what?.nc", "a|b.nc", "back\\slash.nc"] {
+            let path = std::env::temp_dir().join(name);
+            let result = prepare_output_path_inner(&path.to_string_lossy(), false);
+            assert!(
+                matches!(result, Err(AppError::InvalidPath(_))),
+                "{name}: {result:?}"
+            );
+        }
+    }
+
+    // ── export_operations_summary_json ────────────────────────────────────
+
+    #[test]
+    fn export_operations_summary_json_round_trips() {
+        let (state, op_id) = make_export_state();
+        let tmp = std::env::temp_dir().join(format!("jcam_ops_summary_{op_id}.json"));
+
+        export_operations_summary_json_inner(&tmp.to_string_lossy(), &state.project)
+            .expect("export should succeed");
+
+        let text = std::fs::read_to_string(&tmp).expect("read summary");
+        let _ = std::fs::remove_file(&tmp);
+        let value: serde_json::Value = serde_json::from_str(&text).expect("valid JSON");
+        let entries = value.as_array().expect("top-level array");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["type"], "pocket");
+        assert_eq!(entries[0]["tool"]["diameter"], 10.0);
+        assert_eq!(entries[0]["feeds"]["feed"], 500.0);
+    }
+
+    #[test]
+    fn export_operations_summary_json_rejects_invalid_file_name() {
+        let (state, _) = make_export_state();
+        let path = std::env::temp_dir().join("bad:summary.json");
+        let result = export_operations_summary_json_inner(&path.to_string_lossy(), &state.project);
+        assert!(
+            matches!(result, Err(AppError::InvalidPath(_))),
+            "{result:?}"
+        );
+    }
 }
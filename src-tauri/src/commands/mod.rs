@@ -1,28 +1,37 @@
 //! Tauri IPC command handlers.
 //!
 //! Sub-modules are grouped by concern:
+//! - [`demo`]       — bundled demo models for trying the viewer
 //! - [`file`]       — open model, save / load / new project, export G-code
 //! - [`operations`] — machining operation CRUD and reorder
 //! - [`project`]    — lightweight project state queries
 //! - [`stock`]      — stock definition and WCS get/set
 //! - [`toolpath`]   — toolpath queries and post-processor management
 //! - [`tools`]      — tool library CRUD
+//! - [`units`]      — project unit-system conversion
+//! - [`validation`] — project checks and export readiness
 
+pub mod demo;
 pub mod file;
 pub mod operations;
 pub mod project;
 pub mod stock;
 pub mod toolpath;
 pub mod tools;
+pub mod units;
+pub mod validation;
 
+use std::path::{Path, PathBuf};
 use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::geometry::OcctShape;
+use crate::models::operation::{CompensationSide, LeadType, OperationParams};
 use crate::postprocessor::ToolInfo;
-use crate::state::Project;
-use crate::toolpath::Toolpath;
+use crate::state::{Project, UserPreferences};
+use crate::toolpath::{lead, Toolpath};
 
 /// Parse a UUID from a string, returning [`AppError::NotFound`] if the string
 /// is not a valid UUID.
@@ -54,6 +63,106 @@ pub(super) fn read_project(
         .map_err(|e| AppError::Io(format!("project lock poisoned: {e}")))
 }
 
+/// Move `path` to the front of the recent-files list and persist the list.
+///
+/// A failed write is logged rather than returned so that it never fails the
+/// open that triggered it.
+pub(super) fn remember_recent_file(
+    prefs_lock: &RwLock<UserPreferences>,
+    path: &str,
+) -> Result<(), AppError> {
+    let mut prefs = prefs_lock
+        .write()
+        .map_err(|e| AppError::Io(format!("preferences lock poisoned: {e}")))?;
+    prefs.push_recent_file(PathBuf::from(path));
+    if let Err(e) = prefs.save() {
+        tracing::warn!("could not save recent files: {e}");
+    }
+    Ok(())
+}
+
+/// Return the user post-processor directory from `prefs_lock`, mapping a
+/// poisoned-lock failure to [`AppError::Io`].
+pub(super) fn user_post_dir(
+    prefs_lock: &RwLock<UserPreferences>,
+) -> Result<Option<PathBuf>, AppError> {
+    prefs_lock
+        .read()
+        .map(|prefs| prefs.post_processor_dir())
+        .map_err(|e| AppError::Io(format!("preferences lock poisoned: {e}")))
+}
+
+/// Lower-cased extension of `path`, if it has one.
+fn model_extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+}
+
+/// Whether the model at `path` is a STEP or IGES file, the formats that
+/// carry a B-rep.
+#[cfg(cam_geometry_bindings)]
+pub(super) fn has_brep(path: &Path) -> bool {
+    matches!(
+        model_extension(path).as_deref(),
+        Some("step" | "stp" | "iges" | "igs")
+    )
+}
+
+/// The B-rep of the STEP/IGES model at `path`, loaded through OCCT. STL
+/// models carry no B-rep and are rejected with [`AppError::InvalidInput`].
+pub(super) fn load_brep(path: &Path) -> Result<OcctShape, AppError> {
+    match model_extension(path).as_deref() {
+        Some("step") | Some("stp") => Ok(OcctShape::load_step(path)?),
+        Some("iges") | Some("igs") => Ok(OcctShape::load_iges(path)?),
+        _ => Err(AppError::InvalidInput(format!(
+            "'{}' has no B-rep",
+            path.display()
+        ))),
+    }
+}
+
+/// Clone the cached toolpath for `op_uuid`, applying the operation's current
+/// `path_mode`, coolant and feed mode so changing them does not require a
+/// recalculation, its drill parameters so posts can emit canned cycles, and
+/// its stock-to-leave allowance so posts can record it.
+///
+/// A profile or pocket lead-in is inserted ahead of the first cutting pass,
+/// approaching from the compensation side (the left for pockets and
+/// centerline profiles).
+///
+/// Returns [`AppError::NotFound`] if no toolpath is cached for the operation.
+pub(super) fn cached_toolpath(project: &Project, op_uuid: &Uuid) -> Result<Toolpath, AppError> {
+    let mut toolpath = project
+        .toolpaths
+        .get(op_uuid)
+        .ok_or_else(|| AppError::NotFound(format!("no toolpath for operation {op_uuid}")))?
+        .clone();
+    if let Some(op) = project.operations.iter().find(|op| op.id == *op_uuid) {
+        toolpath.path_mode = op.path_mode.or(toolpath.path_mode);
+        toolpath.coolant = Some(op.coolant);
+        toolpath.feed_mode = Some(op.feed_mode);
+        toolpath.drill = match &op.params {
+            OperationParams::Drill(params) => Some(params.clone()),
+            _ => None,
+        };
+        toolpath.stock_to_leave = match &op.params {
+            OperationParams::Profile(p) => Some(p.stock_to_leave),
+            OperationParams::Pocket(p) => Some(p.stock_to_leave),
+            OperationParams::Drill(_) => None,
+        };
+        let (lead_in, from_left) = match &op.params {
+            OperationParams::Profile(p) => {
+                (&p.lead_in, p.compensation_side != CompensationSide::Right)
+            }
+            OperationParams::Pocket(p) => (&p.lead_in, true),
+            OperationParams::Drill(_) => (&LeadType::None, true),
+        };
+        lead::insert_lead_in(&mut toolpath.passes, lead_in, from_left);
+    }
+    Ok(toolpath)
+}
+
 /// Build [`ToolInfo`] entries for each toolpath by cross-referencing project
 /// operations and tools.
 ///
@@ -15,7 +15,7 @@ use std::sync::RwLock;
 use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::models::operation::OperationParams;
+use crate::models::operation::{CoolantMode, FeedMode, OperationParams, PathMode};
 use crate::models::Operation;
 use crate::state::{AppState, Project};
 
@@ -40,6 +40,15 @@ pub struct OperationInput {
     /// Type-discriminated parameters (`"type"` + `"params"` at the same level).
     #[serde(flatten)]
     pub params: OperationParams,
+    /// Corner handling override; absent means the post-processor default.
+    #[serde(default)]
+    pub path_mode: Option<PathMode>,
+    /// Coolant for the operation; absent means flood.
+    #[serde(default)]
+    pub coolant: CoolantMode,
+    /// Feed interpretation for the operation; absent means per minute.
+    #[serde(default)]
+    pub feed_mode: FeedMode,
 }
 
 // ── add_operation ─────────────────────────────────────────────────────────────
@@ -70,6 +79,9 @@ pub(crate) fn add_operation_inner(
         enabled: input.enabled.unwrap_or(true),
         tool_id: tool_uuid,
         params: input.params,
+        path_mode: input.path_mode,
+        coolant: input.coolant,
+        feed_mode: input.feed_mode,
     };
     project.operations.push(op.clone());
     Ok(op)
@@ -112,6 +124,9 @@ pub(crate) fn edit_operation_inner(
     }
     entry.tool_id = tool_uuid;
     entry.params = input.params;
+    entry.path_mode = input.path_mode;
+    entry.coolant = input.coolant;
+    entry.feed_mode = input.feed_mode;
 
     Ok(entry.clone())
 }
@@ -139,6 +154,38 @@ pub(crate) fn delete_operation_inner(
     Ok(())
 }
 
+// ── duplicate_operation ───────────────────────────────────────────────────────
+
+/// Testable inner logic for [`duplicate_operation`].
+///
+/// Copies the operation with the given `id` under a new UUID, appends
+/// `" (copy)"` to its name, inserts it directly after the source, and returns
+/// the copy. Returns [`AppError::NotFound`] if no operation with that ID exists.
+pub(crate) fn duplicate_operation_inner(
+    id: &str,
+    project_lock: &RwLock<Project>,
+) -> Result<Operation, AppError> {
+    let uuid = parse_entity_id(id, "operation")?;
+
+    let mut project = write_project(project_lock)?;
+
+    let index = project
+        .operations
+        .iter()
+        .position(|op| op.id == uuid)
+        .ok_or_else(|| AppError::NotFound(format!("operation {id} not found")))?;
+
+    let source = &project.operations[index];
+    let copy = Operation {
+        id: Uuid::new_v4(),
+        name: format!("{} (copy)", source.name),
+        ..source.clone()
+    };
+    project.operations.insert(index + 1, copy.clone());
+
+    Ok(copy)
+}
+
 // ── reorder_operations ────────────────────────────────────────────────────────
 
 /// Testable inner logic for [`reorder_operations`].
@@ -148,8 +195,9 @@ pub(crate) fn delete_operation_inner(
 /// additions, deletions, or unknown IDs). On success, `project.operations` is
 /// replaced with the new ordering.
 ///
-/// Returns [`AppError::Io`] if the ID count does not match, or
-/// [`AppError::NotFound`] if any submitted ID is not present in the project.
+/// Returns [`AppError::Conflict`] if the list repeats an ID or its count does
+/// not match, or [`AppError::NotFound`] if any submitted ID is not present in
+/// the project.
 pub(crate) fn reorder_operations_inner(
     ids: Vec<String>,
     project_lock: &RwLock<Project>,
@@ -163,7 +211,7 @@ pub(crate) fn reorder_operations_inner(
     {
         let unique: std::collections::HashSet<&Uuid> = uuids.iter().collect();
         if unique.len() != uuids.len() {
-            return Err(AppError::Io(
+            return Err(AppError::Conflict(
                 "reorder list contains duplicate operation IDs".to_string(),
             ));
         }
@@ -172,7 +220,7 @@ pub(crate) fn reorder_operations_inner(
     let mut project = write_project(project_lock)?;
 
     if uuids.len() != project.operations.len() {
-        return Err(AppError::Io(format!(
+        return Err(AppError::Conflict(format!(
             "reorder list has {} IDs but project has {} operations",
             uuids.len(),
             project.operations.len()
@@ -193,16 +241,112 @@ pub(crate) fn reorder_operations_inner(
     Ok(())
 }
 
+// ── move_operation ────────────────────────────────────────────────────────────
+
+/// Direction for [`move_operation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveDirection {
+    /// Towards the start of the list (runs earlier).
+    Up,
+    /// Towards the end of the list (runs later).
+    Down,
+}
+
+/// Testable inner logic for [`move_operation`].
+///
+/// Swaps the operation with its neighbour in `direction` and returns the new
+/// ordered list of operation IDs. Moving the first operation up or the last
+/// one down leaves the order unchanged. Returns [`AppError::NotFound`] if no
+/// operation with that ID exists.
+pub(crate) fn move_operation_inner(
+    id: &str,
+    direction: MoveDirection,
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<Uuid>, AppError> {
+    let uuid = parse_entity_id(id, "operation")?;
+
+    let mut project = write_project(project_lock)?;
+
+    let index = project
+        .operations
+        .iter()
+        .position(|op| op.id == uuid)
+        .ok_or_else(|| AppError::NotFound(format!("operation {id} not found")))?;
+
+    let neighbour = match direction {
+        MoveDirection::Up => index.checked_sub(1),
+        MoveDirection::Down => Some(index + 1).filter(|&i| i < project.operations.len()),
+    };
+    if let Some(neighbour) = neighbour {
+        project.operations.swap(index, neighbour);
+    }
+
+    Ok(project.operations.iter().map(|op| op.id).collect())
+}
+
+// ── get_operation ─────────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`get_operation`].
+///
+/// Returns a clone of the operation with the given `id`, or
+/// [`AppError::NotFound`] if there is none.
+pub(crate) fn get_operation_inner(
+    id: &str,
+    project_lock: &RwLock<Project>,
+) -> Result<Operation, AppError> {
+    let op_uuid = parse_entity_id(id, "operation")?;
+    let project = read_project(project_lock)?;
+    project
+        .operations
+        .iter()
+        .find(|op| op.id == op_uuid)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("operation {id} not found")))
+}
+
 // ── list_operations ───────────────────────────────────────────────────────────
 
+/// Which operations [`list_operations`] returns. The default keeps them all.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OperationFilter {
+    /// Skip disabled operations.
+    pub enabled_only: bool,
+    /// UUID string of a tool; keep only the operations that use it.
+    pub tool_id: Option<String>,
+}
+
+/// Returns a snapshot of the whole operation list; shorthand for
+/// [`list_operations_filtered_inner`] with the default filter.
+pub(crate) fn list_operations_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<Operation>, AppError> {
+    list_operations_filtered_inner(&OperationFilter::default(), project_lock)
+}
+
 /// Testable inner logic for [`list_operations`].
 ///
-/// Returns a snapshot of the current operation list (cloned to release the lock).
-pub(crate) fn list_operations_inner(
+/// Returns a snapshot of the operations matching `filter`, in project order
+/// (cloned to release the lock). Returns [`AppError::NotFound`] if
+/// `filter.tool_id` is not a valid UUID.
+pub(crate) fn list_operations_filtered_inner(
+    filter: &OperationFilter,
     project_lock: &RwLock<Project>,
 ) -> Result<Vec<Operation>, AppError> {
+    let tool_id = filter
+        .tool_id
+        .as_deref()
+        .map(|id| parse_entity_id(id, "tool"))
+        .transpose()?;
     let project = read_project(project_lock)?;
-    Ok(project.operations.clone())
+    Ok(project
+        .operations
+        .iter()
+        .filter(|op| !filter.enabled_only || op.enabled)
+        .filter(|op| tool_id.map_or(true, |id| op.tool_id == id))
+        .cloned()
+        .collect())
 }
 
 // ── Tauri command wrappers ────────────────────────────────────────────────────
@@ -243,10 +387,23 @@ pub async fn delete_operation(
     delete_operation_inner(&id, &state.project)
 }
 
+/// Duplicate an operation, inserting the copy right after the original.
+///
+/// Returns the new [`Operation`], or [`AppError::NotFound`] if `id` does not
+/// match any operation.
+#[tauri::command]
+pub async fn duplicate_operation(
+    id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Operation, AppError> {
+    duplicate_operation_inner(&id, &state.project)
+}
+
 /// Reorder the project's operation list.
 ///
 /// `ids` must contain exactly the same set of UUIDs as the current operation
-/// list. Returns an error if the count or any ID does not match.
+/// list. Returns [`AppError::Conflict`] if the count does not match or an ID
+/// repeats, and [`AppError::NotFound`] for an unknown ID.
 #[tauri::command]
 pub async fn reorder_operations(
     ids: Vec<String>,
@@ -255,12 +412,38 @@ pub async fn reorder_operations(
     reorder_operations_inner(ids, &state.project)
 }
 
-/// Return all operations in the project in their current order.
+/// Move an operation one place up or down the list.
+///
+/// Returns the new ordered operation IDs; moving past either end is a no-op.
+#[tauri::command]
+pub async fn move_operation(
+    id: String,
+    direction: MoveDirection,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Uuid>, AppError> {
+    move_operation_inner(&id, direction, &state.project)
+}
+
+/// Return a single operation by ID.
+#[tauri::command]
+pub async fn get_operation(
+    id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Operation, AppError> {
+    get_operation_inner(&id, &state.project)
+}
+
+/// Return the project's operations in their current order, narrowed by
+/// `filter` when given.
 #[tauri::command]
 pub async fn list_operations(
+    filter: Option<OperationFilter>,
     state: tauri::State<'_, AppState>,
 ) -> Result<Vec<Operation>, AppError> {
-    list_operations_inner(&state.project)
+    match filter {
+        Some(filter) => list_operations_filtered_inner(&filter, &state.project),
+        None => list_operations_inner(&state.project),
+    }
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -268,7 +451,9 @@ pub async fn list_operations(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::operation::{CompensationSide, DrillParams, PocketParams, ProfileParams};
+    use crate::models::operation::{
+        CompensationSide, DrillParams, LeadType, PocketParams, ProfileParams,
+    };
     use crate::models::{Tool, ToolType};
     use crate::state::AppState;
 
@@ -283,6 +468,7 @@ mod tests {
             flute_count: 4,
             default_spindle_speed: None,
             default_feed_rate: None,
+            tip_angle: None,
         };
         let id = tool.id.to_string();
         state.project.write().expect("write lock").tools.push(tool);
@@ -298,7 +484,13 @@ mod tests {
                 depth: 10.0,
                 stepdown: 2.5,
                 compensation_side: CompensationSide::Left,
+                lead_in: LeadType::None,
+                tabs: None,
+                stock_to_leave: 0.0,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         }
     }
 
@@ -311,7 +503,12 @@ mod tests {
                 depth: 15.0,
                 stepdown: 3.0,
                 stepover_percent: 45.0,
+                lead_in: LeadType::None,
+                stock_to_leave: 0.0,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         }
     }
 
@@ -324,6 +521,9 @@ mod tests {
                 depth: 20.0,
                 peck_depth: Some(5.0),
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         }
     }
 
@@ -362,7 +562,12 @@ mod tests {
                     depth: 8.0,
                     stepdown: 2.0,
                     stepover_percent: 50.0,
+                    lead_in: LeadType::None,
+                    stock_to_leave: 0.0,
                 }),
+                path_mode: None,
+                coolant: CoolantMode::Flood,
+                feed_mode: FeedMode::PerMinute,
             },
             &state.project,
         )
@@ -442,6 +647,104 @@ mod tests {
         assert!(ops.is_empty());
     }
 
+    // ── Filtering ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn list_operations_enabled_only_skips_disabled() {
+        let state = AppState::default();
+        let tid = add_test_tool(&state);
+        add_operation_inner(profile_input("On", &tid), &state.project).expect("add");
+        let mut off = pocket_input("Off", &tid);
+        off.enabled = Some(false);
+        add_operation_inner(off, &state.project).expect("add");
+
+        let filter = OperationFilter {
+            enabled_only: true,
+            tool_id: None,
+        };
+        let ops = list_operations_filtered_inner(&filter, &state.project).expect("list");
+        let names: Vec<_> = ops.iter().map(|op| op.name.as_str()).collect();
+        assert_eq!(names, ["On"]);
+        assert_eq!(
+            list_operations_inner(&state.project).expect("list").len(),
+            2
+        );
+    }
+
+    #[test]
+    fn list_operations_tool_id_keeps_only_that_tools_operations() {
+        let state = AppState::default();
+        let first = add_test_tool(&state);
+        let second = add_test_tool(&state);
+        add_operation_inner(profile_input("A", &first), &state.project).expect("add");
+        add_operation_inner(drill_input("B", &second), &state.project).expect("add");
+        add_operation_inner(pocket_input("C", &first), &state.project).expect("add");
+
+        let filter = OperationFilter {
+            enabled_only: false,
+            tool_id: Some(first),
+        };
+        let ops = list_operations_filtered_inner(&filter, &state.project).expect("list");
+        let names: Vec<_> = ops.iter().map(|op| op.name.as_str()).collect();
+        assert_eq!(names, ["A", "C"]);
+    }
+
+    #[test]
+    fn list_operations_invalid_tool_id_returns_not_found() {
+        let state = AppState::default();
+        let filter = OperationFilter {
+            enabled_only: false,
+            tool_id: Some("not-a-uuid".to_string()),
+        };
+        let result = list_operations_filtered_inner(&filter, &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    // ── Duplicate ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn duplicate_operation_inserts_copy_after_source() {
+        let state = AppState::default();
+        let tid = add_test_tool(&state);
+
+        let op1 =
+            add_operation_inner(profile_input("First", &tid), &state.project).expect("add op1");
+        let op2 =
+            add_operation_inner(pocket_input("Second", &tid), &state.project).expect("add op2");
+
+        let copy = duplicate_operation_inner(&op1.id.to_string(), &state.project)
+            .expect("duplicate should succeed");
+
+        let ops = list_operations_inner(&state.project).expect("list");
+        assert_eq!(ops.len(), 3);
+        assert_eq!(ops[0].id, op1.id);
+        assert_eq!(ops[1].id, copy.id);
+        assert_eq!(ops[2].id, op2.id);
+    }
+
+    #[test]
+    fn duplicate_operation_copies_fields_under_new_id() {
+        let state = AppState::default();
+        let tid = add_test_tool(&state);
+        let op = add_operation_inner(drill_input("Spot", &tid), &state.project).expect("add");
+
+        let copy =
+            duplicate_operation_inner(&op.id.to_string(), &state.project).expect("duplicate");
+
+        assert_ne!(copy.id, op.id);
+        assert_eq!(copy.name, "Spot (copy)");
+        assert_eq!(copy.tool_id, op.tool_id);
+        assert_eq!(copy.enabled, op.enabled);
+        assert_eq!(copy.params, op.params);
+    }
+
+    #[test]
+    fn duplicate_unknown_operation_returns_not_found() {
+        let state = AppState::default();
+        let result = duplicate_operation_inner(&Uuid::new_v4().to_string(), &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
     // ── Reorder ───────────────────────────────────────────────────────────────
 
     #[test]
@@ -490,7 +793,7 @@ mod tests {
         let tmp = std::env::temp_dir().join("jcam_test_reorder_round_trip.jcam");
         {
             let project = state.project.read().expect("read");
-            save(&*project, &tmp).expect("save");
+            save(&project, &tmp, false).expect("save");
         }
         let loaded = load(&tmp).expect("load");
         let _ = std::fs::remove_file(&tmp);
@@ -501,6 +804,70 @@ mod tests {
         assert_eq!(loaded.operations[2].id, op2.id, "Beta third");
     }
 
+    // ── Move ──────────────────────────────────────────────────────────────────
+
+    /// Add three operations and return their IDs in list order.
+    fn add_three_operations(state: &AppState) -> [Uuid; 3] {
+        let tid = add_test_tool(state);
+        [
+            add_operation_inner(profile_input("First", &tid), &state.project).expect("add op1"),
+            add_operation_inner(pocket_input("Second", &tid), &state.project).expect("add op2"),
+            add_operation_inner(drill_input("Third", &tid), &state.project).expect("add op3"),
+        ]
+        .map(|op| op.id)
+    }
+
+    #[test]
+    fn move_middle_operation_up_swaps_with_previous() {
+        let state = AppState::default();
+        let [a, b, c] = add_three_operations(&state);
+
+        let ids = move_operation_inner(&b.to_string(), MoveDirection::Up, &state.project)
+            .expect("move should succeed");
+
+        assert_eq!(ids, vec![b, a, c]);
+        let ops = list_operations_inner(&state.project).expect("list");
+        assert_eq!(ops.iter().map(|op| op.id).collect::<Vec<_>>(), ids);
+    }
+
+    #[test]
+    fn move_middle_operation_down_swaps_with_next() {
+        let state = AppState::default();
+        let [a, b, c] = add_three_operations(&state);
+
+        let ids = move_operation_inner(&b.to_string(), MoveDirection::Down, &state.project)
+            .expect("move should succeed");
+
+        assert_eq!(ids, vec![a, c, b]);
+    }
+
+    #[test]
+    fn move_first_up_and_last_down_are_no_ops() {
+        let state = AppState::default();
+        let [a, b, c] = add_three_operations(&state);
+
+        let ids = move_operation_inner(&a.to_string(), MoveDirection::Up, &state.project)
+            .expect("moving first up should succeed");
+        assert_eq!(ids, vec![a, b, c]);
+
+        let ids = move_operation_inner(&c.to_string(), MoveDirection::Down, &state.project)
+            .expect("moving last down should succeed");
+        assert_eq!(ids, vec![a, b, c]);
+    }
+
+    #[test]
+    fn move_unknown_operation_returns_not_found() {
+        let state = AppState::default();
+        add_three_operations(&state);
+
+        let result = move_operation_inner(
+            &Uuid::new_v4().to_string(),
+            MoveDirection::Up,
+            &state.project,
+        );
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
     // ── Tool ID validation ────────────────────────────────────────────────────
 
     #[test]
@@ -528,6 +895,26 @@ mod tests {
         assert!(matches!(result, Err(AppError::NotFound(_))));
     }
 
+    #[test]
+    fn get_operation_returns_the_matching_operation() {
+        let state = AppState::default();
+        let tid = add_test_tool(&state);
+        add_operation_inner(profile_input("First", &tid), &state.project).expect("add");
+        let second =
+            add_operation_inner(pocket_input("Second", &tid), &state.project).expect("add");
+
+        let fetched = get_operation_inner(&second.id.to_string(), &state.project).expect("get");
+        assert_eq!(fetched, second);
+    }
+
+    #[test]
+    fn get_nonexistent_operation_returns_not_found() {
+        let state = AppState::default();
+        let fake_id = Uuid::new_v4().to_string();
+        let result = get_operation_inner(&fake_id, &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
     #[test]
     fn edit_nonexistent_operation_returns_not_found() {
         let state = AppState::default();
@@ -557,7 +944,25 @@ mod tests {
 
         // Submit only one ID for a two-operation list.
         let result = reorder_operations_inner(vec![op1.id.to_string()], &state.project);
-        assert!(matches!(result, Err(AppError::Io(_))));
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[test]
+    fn reorder_count_mismatch_serializes_as_conflict() {
+        let state = AppState::default();
+        let tid = add_test_tool(&state);
+
+        let op1 = add_operation_inner(profile_input("A", &tid), &state.project).expect("add");
+        add_operation_inner(pocket_input("B", &tid), &state.project).expect("add");
+
+        let err = reorder_operations_inner(vec![op1.id.to_string()], &state.project)
+            .expect_err("count mismatch must fail");
+        let value = serde_json::to_value(&err).expect("serialize AppError::Conflict");
+        assert_eq!(value["kind"], "Conflict");
+        assert_eq!(
+            value["message"],
+            "reorder list has 1 IDs but project has 2 operations"
+        );
     }
 
     #[test]
@@ -571,7 +976,7 @@ mod tests {
         // Submit op1 twice — count matches but set is wrong.
         let result =
             reorder_operations_inner(vec![op1.id.to_string(), op1.id.to_string()], &state.project);
-        assert!(matches!(result, Err(AppError::Io(_))));
+        assert!(matches!(result, Err(AppError::Conflict(_))));
     }
 
     #[test]
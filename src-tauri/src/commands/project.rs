@@ -12,7 +12,7 @@ use uuid::Uuid;
 
 use crate::error::AppError;
 use crate::models::operation::OperationParams;
-use crate::models::{StockDefinition, WorkCoordinateSystem};
+use crate::models::{Operation, StockDefinition, WorkCoordinateSystem};
 use crate::state::{AppState, Project};
 
 use super::read_project;
@@ -43,10 +43,28 @@ pub struct OperationSummary {
     pub operation_type: String,
     /// Whether the operation is active in the toolpath.
     pub enabled: bool,
-    /// Placeholder for Phase 1 cache invalidation; always `true` in Phase 0.
+    /// `true` when the operation has no cached toolpath in `project.toolpaths`.
     pub needs_recalculate: bool,
 }
 
+impl OperationSummary {
+    /// Builds the summary for `op`, deriving `needs_recalculate` from the
+    /// toolpath cache in `project`.
+    pub(crate) fn from_operation(op: &Operation, project: &Project) -> Self {
+        Self {
+            id: op.id,
+            name: op.name.clone(),
+            operation_type: match &op.params {
+                OperationParams::Profile(_) => "profile".to_string(),
+                OperationParams::Pocket(_) => "pocket".to_string(),
+                OperationParams::Drill(_) => "drill".to_string(),
+            },
+            enabled: op.enabled,
+            needs_recalculate: !project.toolpaths.contains_key(&op.id),
+        }
+    }
+}
+
 // ── ProjectSnapshot ───────────────────────────────────────────────────────────
 
 /// Serializable snapshot of the current project sent to the frontend.
@@ -62,6 +80,10 @@ pub struct ProjectSnapshot {
     pub model_checksum: Option<String>,
     /// Human-readable project name.
     pub project_name: String,
+    /// Free-form project description.
+    pub description: String,
+    /// Unit system in use (`"mm"` or `"inch"`).
+    pub units: String,
     /// ISO-8601 last-modified timestamp (empty string when not yet saved).
     pub modified_at: String,
     /// Tool library summaries.
@@ -93,17 +115,7 @@ impl From<&Project> for ProjectSnapshot {
         let operations = p
             .operations
             .iter()
-            .map(|op| OperationSummary {
-                id: op.id,
-                name: op.name.clone(),
-                operation_type: match &op.params {
-                    OperationParams::Profile(_) => "profile".to_string(),
-                    OperationParams::Pocket(_) => "pocket".to_string(),
-                    OperationParams::Drill(_) => "drill".to_string(),
-                },
-                enabled: op.enabled,
-                needs_recalculate: true,
-            })
+            .map(|op| OperationSummary::from_operation(op, p))
             .collect();
 
         Self {
@@ -113,6 +125,8 @@ impl From<&Project> for ProjectSnapshot {
                 .map(|m| m.path.to_string_lossy().into_owned()),
             model_checksum: p.source_model.as_ref().map(|m| m.checksum.clone()),
             project_name: p.name.clone(),
+            description: p.description.clone(),
+            units: p.units.clone(),
             modified_at: p.modified_at.clone(),
             tools,
             stock: p.stock.clone(),
@@ -146,11 +160,12 @@ pub async fn get_project_snapshot(
 mod tests {
     use super::*;
     use crate::models::operation::{
-        CompensationSide, OperationParams, PocketParams, ProfileParams,
+        CompensationSide, CoolantMode, FeedMode, LeadType, OperationParams, PocketParams,
+        ProfileParams,
     };
-    use crate::models::stock::{BoxDimensions, Vec3};
+    use crate::models::stock::{BoxDimensions, CylinderDimensions, Vec3};
     use crate::models::wcs::WorkCoordinateSystem;
-    use crate::models::{Operation, StockDefinition, Tool, ToolType};
+    use crate::models::{StockDefinition, Tool, ToolType};
     use crate::state::AppState;
 
     #[test]
@@ -191,6 +206,7 @@ mod tests {
             let mut p = state.project.write().expect("write lock");
             p.source_model = Some(LoadedModel {
                 path: PathBuf::from("/home/user/part.step"),
+                original_path: None,
                 checksum: "deadbeef".to_string(),
                 mesh_data: MeshData {
                     vertices: vec![],
@@ -210,6 +226,8 @@ mod tests {
             model_path: Some("/path/to/model.step".to_string()),
             model_checksum: Some("abc123".to_string()),
             project_name: "Test".to_string(),
+            description: "Bracket".to_string(),
+            units: "mm".to_string(),
             modified_at: "2026-01-01T00:00:00Z".to_string(),
             tools: vec![],
             stock: None,
@@ -233,6 +251,11 @@ mod tests {
             value.get("modifiedAt").is_some(),
             "expected camelCase modifiedAt"
         );
+        assert!(
+            value.get("description").is_some(),
+            "expected description field"
+        );
+        assert!(value.get("units").is_some(), "expected units field");
         assert!(value.get("tools").is_some(), "expected tools field");
         assert!(value.get("wcs").is_some(), "expected wcs field");
         assert!(
@@ -241,6 +264,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn snapshot_carries_description_and_units() {
+        let state = AppState::default();
+        {
+            let mut project = state.project.write().expect("write lock");
+            project.description = "Imported from the shop".to_string();
+            project.units = "inch".to_string();
+        }
+        let snap = get_project_snapshot_inner(&state.project).expect("snapshot");
+        assert_eq!(snap.description, "Imported from the shop");
+        assert_eq!(snap.units, "inch");
+    }
+
     #[test]
     fn snapshot_includes_tool_summaries() {
         let state = AppState::default();
@@ -256,6 +292,7 @@ mod tests {
                 flute_count: 4,
                 default_spindle_speed: None,
                 default_feed_rate: None,
+                tip_angle: None,
             });
         }
 
@@ -266,6 +303,34 @@ mod tests {
         assert_eq!(snap.tools[0].tool_type, "flat_endmill");
     }
 
+    #[test]
+    fn snapshot_stringifies_new_tool_types() {
+        let state = AppState::default();
+        {
+            let mut p = state.project.write().expect("write lock");
+            for (name, tool_type) in [
+                ("90deg Chamfer", ToolType::ChamferMill),
+                ("8mm Slot Drill", ToolType::SlotDrill),
+            ] {
+                p.tools.push(Tool {
+                    id: Uuid::new_v4(),
+                    name: name.to_string(),
+                    tool_type,
+                    material: "carbide".to_string(),
+                    diameter: 8.0,
+                    flute_count: 2,
+                    default_spindle_speed: None,
+                    default_feed_rate: None,
+                    tip_angle: None,
+                });
+            }
+        }
+
+        let snap = get_project_snapshot_inner(&state.project).expect("snapshot");
+        assert_eq!(snap.tools[0].tool_type, "chamfer_mill");
+        assert_eq!(snap.tools[1].tool_type, "slot_drill");
+    }
+
     #[test]
     fn tool_summary_serializes_camel_case_tool_type() {
         let summary = ToolSummary {
@@ -300,10 +365,41 @@ mod tests {
 
         let snap = get_project_snapshot_inner(&state.project).expect("snapshot");
         assert!(snap.stock.is_some());
-        let StockDefinition::Box(b) = snap.stock.unwrap();
+        let StockDefinition::Box(b) = snap.stock.unwrap() else {
+            panic!("expected box stock");
+        };
         assert_eq!(b.width, 100.0);
     }
 
+    #[test]
+    fn snapshot_includes_cylinder_stock() {
+        let state = AppState::default();
+        {
+            let mut p = state.project.write().expect("write lock");
+            p.stock = Some(StockDefinition::Cylinder(CylinderDimensions {
+                origin: Vec3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                axis: Vec3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 1.0,
+                },
+                diameter: 40.0,
+                height: 75.0,
+            }));
+        }
+
+        let snap = get_project_snapshot_inner(&state.project).expect("snapshot");
+        let Some(StockDefinition::Cylinder(c)) = snap.stock else {
+            panic!("expected cylinder stock");
+        };
+        assert_eq!(c.diameter, 40.0);
+        assert_eq!(c.height, 75.0);
+    }
+
     #[test]
     fn snapshot_includes_wcs() {
         let state = AppState::default();
@@ -353,7 +449,12 @@ mod tests {
                     depth: 15.0,
                     stepdown: 3.0,
                     stepover_percent: 45.0,
+                    lead_in: LeadType::None,
+                    stock_to_leave: 0.0,
                 }),
+                path_mode: None,
+                coolant: CoolantMode::Flood,
+                feed_mode: FeedMode::PerMinute,
             });
             p.operations.push(Operation {
                 id: Uuid::new_v4(),
@@ -364,7 +465,13 @@ mod tests {
                     depth: 10.0,
                     stepdown: 2.5,
                     compensation_side: CompensationSide::Left,
+                    lead_in: LeadType::None,
+                    tabs: None,
+                    stock_to_leave: 0.0,
                 }),
+                path_mode: None,
+                coolant: CoolantMode::Flood,
+                feed_mode: FeedMode::PerMinute,
             });
         }
 
@@ -5,13 +5,16 @@
 //!   They are synchronous and directly testable without Tauri.
 //! - `#[tauri::command]` wrappers extract managed state and delegate to `_inner`.
 
+use std::path::Path;
 use std::sync::RwLock;
 
 use crate::error::AppError;
+use crate::geometry::{obb, BoolOp, GeometryError, MeshData, OcctShape, OrientedBoundingBox};
+use crate::models::stock::{BoxDimensions, Vec3};
 use crate::models::{StockDefinition, WorkCoordinateSystem};
 use crate::state::{AppState, Project};
 
-use super::{read_project, write_project};
+use super::{load_brep, read_project, write_project};
 
 // ── set_stock ─────────────────────────────────────────────────────────────────
 
@@ -43,11 +46,24 @@ pub(crate) fn get_stock_inner(
 
 /// Testable inner logic for [`set_wcs`].
 ///
-/// Replaces the entire WCS list for the project.
+/// Replaces the entire WCS list for the project. With `strict`, any entry
+/// whose axes are zero-length or not perpendicular is rejected with
+/// [`AppError::InvalidInput`]; otherwise each entry is orthonormalized via
+/// [`WorkCoordinateSystem::normalize`], which still rejects degenerate axes.
+/// Nothing is stored if any entry fails.
 pub(crate) fn set_wcs_inner(
-    wcs: Vec<WorkCoordinateSystem>,
+    mut wcs: Vec<WorkCoordinateSystem>,
+    strict: bool,
     project_lock: &RwLock<Project>,
 ) -> Result<(), AppError> {
+    for entry in &mut wcs {
+        let checked = if strict {
+            entry.validate()
+        } else {
+            entry.normalize()
+        };
+        checked.map_err(|msg| AppError::InvalidInput(format!("WCS '{}': {msg}", entry.name)))?;
+    }
     let mut project = write_project(project_lock)?;
     project.wcs = wcs;
     Ok(())
@@ -65,6 +81,149 @@ pub(crate) fn get_wcs_inner(
     Ok(project.wcs.clone())
 }
 
+// ── get_oriented_bounding_box ─────────────────────────────────────────────────
+
+/// Testable inner logic for [`get_oriented_bounding_box`].
+///
+/// Fits a PCA-aligned box to the loaded model's mesh vertices. Returns
+/// [`AppError::NotFound`] when no model is loaded or its mesh is empty.
+pub(crate) fn get_oriented_bounding_box_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<OrientedBoundingBox, AppError> {
+    let project = read_project(project_lock)?;
+    let model = project
+        .source_model
+        .as_ref()
+        .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+    obb::from_mesh(&model.mesh_data)
+        .ok_or_else(|| AppError::NotFound("loaded model has no mesh vertices".to_string()))
+}
+
+// ── set_stock_from_model ──────────────────────────────────────────────────────
+
+/// Axis-aligned bounds as `(xmin, ymin, zmin, xmax, ymax, zmax)`.
+pub(super) type Extents = (f64, f64, f64, f64, f64, f64);
+
+/// Exact B-rep bounds, reloading the model's STEP/IGES source through OCCT.
+#[cfg(cam_geometry_bindings)]
+fn brep_extents(path: &Path) -> Option<Extents> {
+    Some(load_brep(path).ok()?.bounding_box())
+}
+
+#[cfg(not(cam_geometry_bindings))]
+fn brep_extents(_path: &Path) -> Option<Extents> {
+    None
+}
+
+/// Bounds of the mesh vertices, or `None` if the mesh has none.
+pub(super) fn mesh_extents(mesh: &MeshData) -> Option<Extents> {
+    let mut points = mesh
+        .vertices
+        .chunks_exact(3)
+        .map(|v| (v[0] as f64, v[1] as f64, v[2] as f64));
+    let (x, y, z) = points.next()?;
+    Some(points.fold((x, y, z, x, y, z), |b, (x, y, z)| {
+        (
+            b.0.min(x),
+            b.1.min(y),
+            b.2.min(z),
+            b.3.max(x),
+            b.4.max(y),
+            b.5.max(z),
+        )
+    }))
+}
+
+/// Testable inner logic for [`set_stock_from_model`].
+///
+/// Sets the stock to a box enclosing the loaded model with `margin` added on
+/// every side, and returns it. Bounds come from the B-rep when OCCT is
+/// available and the source is STEP/IGES, otherwise from the mesh vertices.
+/// Returns [`AppError::NotFound`] when no model is loaded or its mesh is
+/// empty.
+///
+/// The B-rep is reloaded without holding the project lock; the write lock is
+/// taken only to store the result.
+pub(crate) fn set_stock_from_model_inner(
+    margin: f64,
+    project_lock: &RwLock<Project>,
+) -> Result<StockDefinition, AppError> {
+    let (path, mesh_bounds) = {
+        let project = read_project(project_lock)?;
+        let model = project
+            .source_model
+            .as_ref()
+            .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+        (model.path.clone(), mesh_extents(&model.mesh_data))
+    };
+    let (xmin, ymin, zmin, xmax, ymax, zmax) = brep_extents(&path)
+        .or(mesh_bounds)
+        .ok_or_else(|| AppError::NotFound("loaded model has no mesh vertices".to_string()))?;
+
+    let stock = StockDefinition::Box(BoxDimensions {
+        origin: Vec3 {
+            x: xmin - margin,
+            y: ymin - margin,
+            z: zmin - margin,
+        },
+        width: xmax - xmin + 2.0 * margin,
+        depth: ymax - ymin + 2.0 * margin,
+        height: zmax - zmin + 2.0 * margin,
+    });
+    write_project(project_lock)?.stock = Some(stock.clone());
+    Ok(stock)
+}
+
+// ── compute_stock_remainder ───────────────────────────────────────────────────
+
+/// The stock solid described by `stock`.
+fn stock_shape(stock: &StockDefinition) -> Result<OcctShape, GeometryError> {
+    match stock {
+        StockDefinition::Box(b) => OcctShape::make_box(
+            [b.origin.x, b.origin.y, b.origin.z],
+            b.width,
+            b.depth,
+            b.height,
+        ),
+        StockDefinition::Cylinder(c) => OcctShape::make_cylinder(
+            [c.origin.x, c.origin.y, c.origin.z],
+            [c.axis.x, c.axis.y, c.axis.z],
+            c.diameter / 2.0,
+            c.height,
+        ),
+    }
+}
+
+/// Testable inner logic for [`compute_stock_remainder`].
+///
+/// Subtracts the model's B-rep from the stock solid and tessellates what is
+/// left, i.e. the material still to be removed. Returns
+/// [`AppError::NotFound`] when no stock or model is set,
+/// [`AppError::InvalidInput`] for an STL model, and
+/// [`AppError::GeometryImport`] when OCCT is unavailable or the boolean
+/// fails.
+pub(crate) fn compute_stock_remainder_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<MeshData, AppError> {
+    let (stock, model_path) = {
+        let project = read_project(project_lock)?;
+        let stock = project
+            .stock
+            .clone()
+            .ok_or_else(|| AppError::NotFound("no stock defined".to_string()))?;
+        let model_path = project
+            .source_model
+            .as_ref()
+            .map(|m| m.path.clone())
+            .ok_or_else(|| AppError::NotFound("no model loaded".to_string()))?;
+        (stock, model_path)
+    };
+
+    let part = load_brep(&model_path)?;
+    let remainder = stock_shape(&stock)?.boolean(&part, BoolOp::Difference)?;
+    Ok(remainder.tessellate(0.1, 0.5)?.to_mesh_data())
+}
+
 // ── Tauri command wrappers ────────────────────────────────────────────────────
 
 /// Set (or clear) the project stock definition.
@@ -87,12 +246,16 @@ pub async fn get_stock(
 }
 
 /// Replace the project's WCS list.
+///
+/// Axes are orthonormalized unless `strict` is `true`, in which case
+/// non-orthogonal axes are rejected instead.
 #[tauri::command]
 pub async fn set_wcs(
     wcs: Vec<WorkCoordinateSystem>,
+    strict: Option<bool>,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), AppError> {
-    set_wcs_inner(wcs, &state.project)
+    set_wcs_inner(wcs, strict.unwrap_or(false), &state.project)
 }
 
 /// Return the project's WCS list.
@@ -103,13 +266,38 @@ pub async fn get_wcs(
     get_wcs_inner(&state.project)
 }
 
+/// Return the model's oriented bounding box, for setting up rotated stock.
+#[tauri::command]
+pub async fn get_oriented_bounding_box(
+    state: tauri::State<'_, AppState>,
+) -> Result<OrientedBoundingBox, AppError> {
+    get_oriented_bounding_box_inner(&state.project)
+}
+
+/// Set the stock to a box around the loaded model plus `margin` on every side.
+#[tauri::command]
+pub async fn set_stock_from_model(
+    margin: f64,
+    state: tauri::State<'_, AppState>,
+) -> Result<StockDefinition, AppError> {
+    set_stock_from_model_inner(margin, &state.project)
+}
+
+/// Tessellate the stock minus the model, for a view of the material left to
+/// remove.
+#[tauri::command]
+pub async fn compute_stock_remainder(
+    state: tauri::State<'_, AppState>,
+) -> Result<MeshData, AppError> {
+    compute_stock_remainder_inner(&state.project)
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::stock::{BoxDimensions, Vec3};
-    use crate::state::AppState;
+    use crate::state::{AppState, LoadedModel};
     use uuid::Uuid;
 
     fn make_box_stock() -> StockDefinition {
@@ -185,7 +373,7 @@ mod tests {
     fn set_wcs_then_get_returns_same_list() {
         let state = AppState::default();
         let wcs_list = vec![make_wcs(), make_wcs()];
-        set_wcs_inner(wcs_list.clone(), &state.project).expect("set_wcs should succeed");
+        set_wcs_inner(wcs_list.clone(), true, &state.project).expect("set_wcs should succeed");
         let retrieved = get_wcs_inner(&state.project).expect("get_wcs should succeed");
         assert_eq!(retrieved.len(), 2);
         assert_eq!(retrieved[0].name, wcs_list[0].name);
@@ -195,9 +383,172 @@ mod tests {
     #[test]
     fn set_wcs_replaces_previous_list() {
         let state = AppState::default();
-        set_wcs_inner(vec![make_wcs(), make_wcs(), make_wcs()], &state.project).expect("set 3");
-        set_wcs_inner(vec![make_wcs()], &state.project).expect("replace with 1");
+        set_wcs_inner(
+            vec![make_wcs(), make_wcs(), make_wcs()],
+            true,
+            &state.project,
+        )
+        .expect("set 3");
+        set_wcs_inner(vec![make_wcs()], true, &state.project).expect("replace with 1");
         let retrieved = get_wcs_inner(&state.project).expect("get");
         assert_eq!(retrieved.len(), 1);
     }
+
+    #[test]
+    fn strict_set_wcs_rejects_degenerate_axis_and_keeps_previous_list() {
+        let state = AppState::default();
+        let valid = make_wcs();
+        set_wcs_inner(vec![valid.clone()], true, &state.project).expect("set valid");
+
+        let mut bad = make_wcs();
+        bad.x_axis = Vec3::zero();
+        let result = set_wcs_inner(vec![bad], true, &state.project);
+        assert!(
+            matches!(result, Err(AppError::InvalidInput(_))),
+            "{result:?}"
+        );
+        assert_eq!(get_wcs_inner(&state.project).unwrap(), vec![valid]);
+    }
+
+    #[test]
+    fn lenient_set_wcs_normalizes_axes() {
+        let state = AppState::default();
+        let mut wcs = make_wcs();
+        wcs.x_axis = Vec3 {
+            x: 5.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        wcs.z_axis = Vec3 {
+            x: 0.001,
+            y: 0.0,
+            z: 2.0,
+        };
+        set_wcs_inner(vec![wcs], false, &state.project).expect("set_wcs should normalize");
+
+        let stored = &get_wcs_inner(&state.project).unwrap()[0];
+        assert_eq!(stored.x_axis.x, 1.0);
+        assert!(stored.z_axis.x.abs() < 1e-12, "{:?}", stored.z_axis);
+        assert!((stored.z_axis.z - 1.0).abs() < 1e-12, "{:?}", stored.z_axis);
+    }
+
+    #[test]
+    fn oriented_bounding_box_without_model_is_not_found() {
+        let state = AppState::default();
+        let result = get_oriented_bounding_box_inner(&state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[test]
+    fn oriented_bounding_box_uses_loaded_mesh() {
+        let state = AppState::default();
+        state.project.write().expect("write lock").source_model = Some(LoadedModel {
+            path: "part.stl".into(),
+            original_path: None,
+            checksum: String::new(),
+            mesh_data: MeshData {
+                vertices: vec![
+                    0.0, 0.0, 0.0, 10.0, 0.0, 0.0, 0.0, 4.0, 0.0, 10.0, 4.0, 0.0, //
+                    0.0, 0.0, 2.0, 10.0, 0.0, 2.0, 0.0, 4.0, 2.0, 10.0, 4.0, 2.0,
+                ],
+                normals: vec![],
+                indices: vec![],
+            },
+        });
+
+        let obb = get_oriented_bounding_box_inner(&state.project).expect("obb");
+        assert!((obb.half_extents[0] - 5.0).abs() < 1e-6, "{obb:?}");
+        assert!((obb.center[0] - 5.0).abs() < 1e-6, "{obb:?}");
+    }
+
+    #[test]
+    fn stock_from_model_without_model_is_not_found() {
+        let state = AppState::default();
+        let result = set_stock_from_model_inner(5.0, &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[test]
+    fn stock_from_model_wraps_mesh_extents_with_margin() {
+        let state = AppState::default();
+        state.project.write().expect("write lock").source_model = Some(LoadedModel {
+            path: "part.stl".into(),
+            original_path: None,
+            checksum: String::new(),
+            mesh_data: MeshData {
+                vertices: vec![-10.0, 0.0, 0.0, 30.0, 20.0, 0.0, 0.0, 5.0, 15.0],
+                normals: vec![],
+                indices: vec![0, 1, 2],
+            },
+        });
+
+        let stock = set_stock_from_model_inner(2.0, &state.project).expect("stock from model");
+        let StockDefinition::Box(b) = &stock else {
+            panic!("expected box stock");
+        };
+        assert_eq!((b.origin.x, b.origin.y, b.origin.z), (-12.0, -2.0, -2.0));
+        assert_eq!((b.width, b.depth, b.height), (44.0, 24.0, 19.0));
+        assert_eq!(get_stock_inner(&state.project).unwrap(), Some(stock));
+    }
+
+    fn load_model(state: &AppState, path: &str) {
+        state.project.write().expect("write lock").source_model = Some(LoadedModel {
+            path: path.into(),
+            original_path: None,
+            checksum: String::new(),
+            mesh_data: MeshData {
+                vertices: vec![],
+                normals: vec![],
+                indices: vec![],
+            },
+        });
+    }
+
+    #[test]
+    fn stock_remainder_without_stock_is_not_found() {
+        let state = AppState::default();
+        load_model(&state, "part.step");
+        let result = compute_stock_remainder_inner(&state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[test]
+    fn stock_remainder_rejects_stl_model() {
+        let state = AppState::default();
+        set_stock_inner(Some(make_box_stock()), &state.project).unwrap();
+        load_model(&state, "part.stl");
+        let result = compute_stock_remainder_inner(&state.project);
+        assert!(
+            matches!(result, Err(AppError::InvalidInput(_))),
+            "{result:?}"
+        );
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn stock_remainder_around_box_fixture_is_nonempty() {
+        let state = AppState::default();
+        // box.step spans 0..10 on every axis; leave 5 mm on each side.
+        set_stock_inner(
+            Some(StockDefinition::Box(BoxDimensions {
+                origin: Vec3 {
+                    x: -5.0,
+                    y: -5.0,
+                    z: -5.0,
+                },
+                width: 20.0,
+                depth: 20.0,
+                height: 20.0,
+            })),
+            &state.project,
+        )
+        .unwrap();
+        load_model(
+            &state,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../tests/fixtures/box.step"),
+        );
+
+        let mesh = compute_stock_remainder_inner(&state.project).expect("remainder");
+        assert!(!mesh.indices.is_empty());
+    }
 }
@@ -4,21 +4,64 @@
 //! Tauri) wrapped by the `#[tauri::command]` entry point that extracts the
 //! managed state.
 
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
 use crate::error::AppError;
-use crate::postprocessor::{program::GenerateOptions, PostProcessor, PostProcessorMeta};
-use crate::state::{AppState, Project};
+use crate::postprocessor::{
+    config::DEFAULT_RAPID_RATE_MM_MIN,
+    program::{self, CommentLevel, GenerateOptions},
+    GenerateResult, PostProcessor, PostProcessorMeta,
+};
+use crate::state::{AppState, Project, UserPreferences};
+use crate::toolpath::transform::ToolpathTransform;
+use crate::toolpath::types::MoveKind;
 
-use super::{build_tool_infos, parse_entity_id, read_project};
+use super::project::OperationSummary;
+use super::{
+    build_tool_infos, cached_toolpath, parse_entity_id, read_project, user_post_dir, write_project,
+};
 
 // ── list_post_processors ──────────────────────────────────────────────────────
 
 /// Testable inner logic for [`list_post_processors`].
 ///
-/// Returns the metadata for all builtin post-processors.
-pub(crate) fn list_post_processors_inner() -> Result<Vec<PostProcessorMeta>, AppError> {
-    Ok(PostProcessor::list_builtins())
+/// Returns the builtin post-processors merged with the `*.toml` posts in
+/// `user_dir`. A user post whose id matches a builtin replaces it in place;
+/// a missing `user_dir` just yields the builtins.
+pub(crate) fn list_post_processors_inner(
+    user_dir: Option<&Path>,
+) -> Result<Vec<PostProcessorMeta>, AppError> {
+    let mut posts = PostProcessor::list_builtins();
+    let Some(dir) = user_dir.filter(|d| d.is_dir()) else {
+        return Ok(posts);
+    };
+
+    let user_posts = PostProcessor::list_dir(dir)?;
+    for meta in user_posts {
+        match posts.iter_mut().find(|p| p.id == meta.id) {
+            Some(existing) => *existing = meta,
+            None => posts.push(meta),
+        }
+    }
+    Ok(posts)
+}
+
+// ── set_post_processor_dir ────────────────────────────────────────────────────
+
+/// Testable inner logic for [`set_post_processor_dir`].
+///
+/// Stores the directory scanned for user post-processors; `None` restores
+/// the platform default.
+pub(crate) fn set_post_processor_dir_inner(
+    path: Option<String>,
+    prefs_lock: &RwLock<UserPreferences>,
+) -> Result<(), AppError> {
+    let mut prefs = prefs_lock
+        .write()
+        .map_err(|e| AppError::Io(format!("preferences lock poisoned: {e}")))?;
+    prefs.custom_post_dir = path.map(PathBuf::from);
+    Ok(())
 }
 
 // ── get_gcode_preview ─────────────────────────────────────────────────────────
@@ -28,13 +71,14 @@ pub(crate) fn list_post_processors_inner() -> Result<Vec<PostProcessorMeta>, App
 /// 1. Parses `operation_id` as a UUID.
 /// 2. Looks up the toolpath for that operation in `project.toolpaths`.
 /// 3. Builds [`ToolInfo`] from the matching operation and tool in the project.
-/// 4. Loads the named builtin post-processor.
-/// 5. Generates and returns the G-code string.
+/// 4. Loads the named post-processor, preferring a user post in `user_dir`.
+/// 5. Generates and returns the G-code with its warnings and stats.
 pub(crate) fn get_gcode_preview_inner(
     operation_id: &str,
     post_processor_id: &str,
+    user_dir: Option<&Path>,
     project_lock: &RwLock<Project>,
-) -> Result<String, AppError> {
+) -> Result<GenerateResult, AppError> {
     let op_uuid = parse_entity_id(operation_id, "operation")?;
 
     // Extract only the data we need, then release the lock before the
@@ -42,50 +86,349 @@ pub(crate) fn get_gcode_preview_inner(
     let (toolpath, tool_infos) = {
         let project = read_project(project_lock)?;
 
-        let toolpath = project
-            .toolpaths
-            .get(&op_uuid)
-            .ok_or_else(|| AppError::NotFound(format!("no toolpath for operation {op_uuid}")))?
-            .clone();
+        let toolpath = cached_toolpath(&project, &op_uuid)?;
 
         let tool_infos = build_tool_infos(std::slice::from_ref(&toolpath), &project);
 
         (toolpath, tool_infos)
     }; // read lock released here
 
-    let pp = PostProcessor::builtin(post_processor_id)
-        .map_err(|e| AppError::PostProcessor(e.to_string()))?;
+    let pp = PostProcessor::load(post_processor_id, user_dir)?;
 
     pp.generate(
         &[toolpath],
         &tool_infos,
         GenerateOptions {
             program_number: None,
-            include_comments: true,
+            comments: CommentLevel::Verbose,
+            ..Default::default()
         },
     )
-    .map_err(|e| AppError::PostProcessor(e.to_string()))
+    .map_err(AppError::from)
+}
+
+// ── gcode_preview_summary ─────────────────────────────────────────────────────
+
+/// Lines of a preview kept at the start of a [`PreviewSummary`].
+const PREVIEW_HEAD_LINES: usize = 50;
+
+/// Lines of a preview kept at the end of a [`PreviewSummary`].
+const PREVIEW_TAIL_LINES: usize = 10;
+
+/// Totals plus the head and tail of a generated program, for previews too
+/// large to transfer whole.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewSummary {
+    /// Number of lines in the program.
+    pub line_count: usize,
+    /// Size of the program text in bytes.
+    pub byte_len: usize,
+    /// The first 50 lines.
+    pub first_50_lines: Vec<String>,
+    /// The last 10 lines not already in `first_50_lines`; empty for programs
+    /// of 50 lines or fewer.
+    pub last_10_lines: Vec<String>,
+}
+
+/// Summarizes `gcode`, splitting it into lines at `\n` (a trailing `\r` is
+/// dropped, so CRLF output counts the same).
+fn summarize_gcode(gcode: &str) -> PreviewSummary {
+    let lines: Vec<&str> = gcode.lines().collect();
+    let tail_start = lines
+        .len()
+        .saturating_sub(PREVIEW_TAIL_LINES)
+        .max(PREVIEW_HEAD_LINES.min(lines.len()));
+    let owned = |lines: &[&str]| lines.iter().map(|l| l.to_string()).collect();
+    PreviewSummary {
+        line_count: lines.len(),
+        byte_len: gcode.len(),
+        first_50_lines: owned(&lines[..PREVIEW_HEAD_LINES.min(lines.len())]),
+        last_10_lines: owned(&lines[tail_start..]),
+    }
+}
+
+/// Testable inner logic for [`gcode_preview_summary`].
+///
+/// Generates the preview exactly as [`get_gcode_preview_inner`] does, then
+/// returns only its line count, byte length, head and tail.
+pub(crate) fn gcode_preview_summary_inner(
+    operation_id: &str,
+    post_processor_id: &str,
+    user_dir: Option<&Path>,
+    project_lock: &RwLock<Project>,
+) -> Result<PreviewSummary, AppError> {
+    let result = get_gcode_preview_inner(operation_id, post_processor_id, user_dir, project_lock)?;
+    Ok(summarize_gcode(&result.gcode))
+}
+
+// ── invalidate_toolpath ───────────────────────────────────────────────────────
+
+/// Testable inner logic for [`invalidate_toolpath`].
+///
+/// Removes the cached toolpath for `operation_id` from `project.toolpaths`,
+/// leaving every other operation's toolpath untouched, and returns the updated
+/// [`OperationSummary`] (with `needs_recalculate` set). Returns
+/// [`AppError::NotFound`] if no operation with that ID exists.
+pub(crate) fn invalidate_toolpath_inner(
+    operation_id: &str,
+    project_lock: &RwLock<Project>,
+) -> Result<OperationSummary, AppError> {
+    let op_uuid = parse_entity_id(operation_id, "operation")?;
+
+    let mut project = write_project(project_lock)?;
+
+    let index = project
+        .operations
+        .iter()
+        .position(|op| op.id == op_uuid)
+        .ok_or_else(|| AppError::NotFound(format!("operation {operation_id} not found")))?;
+
+    project.toolpaths.remove(&op_uuid);
+
+    Ok(OperationSummary::from_operation(
+        &project.operations[index],
+        &project,
+    ))
+}
+
+// ── transform_toolpath ────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`transform_toolpath`].
+///
+/// Applies `transform` to the cached toolpath of `operation_id` and stores
+/// the result in its place. The operation itself is unchanged, so
+/// recalculating the toolpath discards the transform. Returns
+/// [`AppError::NotFound`] if the operation has no cached toolpath.
+pub(crate) fn transform_toolpath_inner(
+    operation_id: &str,
+    transform: &ToolpathTransform,
+    project_lock: &RwLock<Project>,
+) -> Result<(), AppError> {
+    let op_uuid = parse_entity_id(operation_id, "operation")?;
+    let mut project = write_project(project_lock)?;
+    let toolpath = project
+        .toolpaths
+        .get_mut(&op_uuid)
+        .ok_or_else(|| AppError::NotFound(format!("no toolpath for operation {op_uuid}")))?;
+    transform.apply(toolpath);
+    Ok(())
+}
+
+// ── program_z_range ───────────────────────────────────────────────────────────
+
+/// Lowest and highest Z reached by a set of toolpaths.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZRange {
+    pub z_min: f64,
+    pub z_max: f64,
+}
+
+/// Testable inner logic for [`program_z_range`].
+///
+/// Scans every cut point of the selected operations' cached toolpaths,
+/// including arc endpoints. Operations without a toolpath are skipped; returns
+/// [`AppError::NotFound`] if none of them has one.
+pub(crate) fn program_z_range_inner(
+    operation_ids: &[String],
+    project_lock: &RwLock<Project>,
+) -> Result<ZRange, AppError> {
+    let op_uuids = operation_ids
+        .iter()
+        .map(|id| parse_entity_id(id, "operation"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let project = read_project(project_lock)?;
+    let z_values = op_uuids
+        .iter()
+        .filter_map(|id| project.toolpaths.get(id))
+        .flat_map(|tp| tp.passes.iter())
+        .flat_map(|pass| pass.cuts.iter())
+        .flat_map(|cut| {
+            let end = match &cut.move_kind {
+                MoveKind::Arc { end, .. } | MoveKind::Helix { end, .. } => Some(end.z),
+                _ => None,
+            };
+            std::iter::once(cut.position.z).chain(end)
+        });
+
+    z_values
+        .fold(None, |range: Option<ZRange>, z| {
+            Some(match range {
+                Some(r) => ZRange {
+                    z_min: r.z_min.min(z),
+                    z_max: r.z_max.max(z),
+                },
+                None => ZRange { z_min: z, z_max: z },
+            })
+        })
+        .ok_or_else(|| AppError::NotFound("no toolpaths for the selected operations".to_string()))
+}
+
+// ── toolpath_stats ────────────────────────────────────────────────────────────
+
+/// Motion totals and a cycle-time estimate for one operation's toolpath.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolpathStats {
+    /// Total length of rapid moves.
+    pub rapid_distance: f64,
+    /// Total length of feed moves, arcs included.
+    pub feed_distance: f64,
+    pub arc_count: usize,
+    /// Feed distance at each pass's feed rate (its override, else the
+    /// toolpath's) plus rapid distance at the rapid rate, plus dwells.
+    pub estimated_seconds: f64,
+}
+
+/// Testable inner logic for [`get_toolpath_stats`].
+///
+/// Walks the cached toolpath's cut points with [`program::motion_totals`],
+/// the same walk that estimates program run time. The first move has no
+/// known start and contributes no distance. Rapids are timed at `rapid_rate`
+/// (mm/min) when given, else at the `machine.rapid_rate_mm_min` of the post
+/// named by `post_processor_id` (loaded with `user_dir` as in
+/// [`get_gcode_preview_inner`]), else at 10000. Returns [`AppError::NotFound`] if the operation
+/// has no cached toolpath.
+pub(crate) fn toolpath_stats_inner(
+    operation_id: &str,
+    rapid_rate: Option<f64>,
+    post_processor_id: Option<&str>,
+    user_dir: Option<&Path>,
+    project_lock: &RwLock<Project>,
+) -> Result<ToolpathStats, AppError> {
+    let op_uuid = parse_entity_id(operation_id, "operation")?;
+    let rapid_rate = match (rapid_rate, post_processor_id) {
+        (Some(rate), _) => rate,
+        (None, Some(id)) => PostProcessor::load(id, user_dir)?.rapid_rate(),
+        (None, None) => DEFAULT_RAPID_RATE_MM_MIN,
+    };
+    let project = read_project(project_lock)?;
+    let toolpath = project
+        .toolpaths
+        .get(&op_uuid)
+        .ok_or_else(|| AppError::NotFound(format!("no toolpath for operation {op_uuid}")))?;
+
+    let totals = program::motion_totals(std::slice::from_ref(toolpath), rapid_rate);
+    Ok(ToolpathStats {
+        rapid_distance: totals.rapid_distance,
+        feed_distance: totals.feed_distance,
+        arc_count: totals.arc_count,
+        estimated_seconds: totals.seconds,
+    })
 }
 
 // ── Tauri command wrappers ────────────────────────────────────────────────────
 
-/// List all builtin post-processors, returning their metadata.
+/// List the builtin and user-installed post-processors, returning their
+/// metadata.
 #[tauri::command]
 pub async fn list_post_processors(
-    _state: tauri::State<'_, AppState>,
+    state: tauri::State<'_, AppState>,
 ) -> Result<Vec<PostProcessorMeta>, AppError> {
-    list_post_processors_inner()
+    let user_dir = user_post_dir(&state.preferences)?;
+    list_post_processors_inner(user_dir.as_deref())
+}
+
+/// Set the directory scanned for user post-processors (`null` for the
+/// platform default).
+#[tauri::command]
+pub async fn set_post_processor_dir(
+    path: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    set_post_processor_dir_inner(path, &state.preferences)
 }
 
 /// Generate a G-code preview for the given operation using the named builtin
 /// post-processor.
+///
+/// Returns the program text together with any generation warnings.
 #[tauri::command]
 pub async fn get_gcode_preview(
     operation_id: String,
     post_processor_id: String,
     state: tauri::State<'_, AppState>,
-) -> Result<String, AppError> {
-    get_gcode_preview_inner(&operation_id, &post_processor_id, &state.project)
+) -> Result<GenerateResult, AppError> {
+    let user_dir = user_post_dir(&state.preferences)?;
+    get_gcode_preview_inner(
+        &operation_id,
+        &post_processor_id,
+        user_dir.as_deref(),
+        &state.project,
+    )
+}
+
+/// Summarize the G-code preview for an operation without transferring it:
+/// line count, byte length, the first 50 and the last 10 lines.
+#[tauri::command]
+pub async fn gcode_preview_summary(
+    operation_id: String,
+    post_processor_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<PreviewSummary, AppError> {
+    let user_dir = user_post_dir(&state.preferences)?;
+    gcode_preview_summary_inner(
+        &operation_id,
+        &post_processor_id,
+        user_dir.as_deref(),
+        &state.project,
+    )
+}
+
+/// Discard the cached toolpath for one operation so it is recalculated.
+///
+/// Returns the updated operation summary, or [`AppError::NotFound`] if
+/// `operation_id` does not match any operation.
+#[tauri::command]
+pub async fn invalidate_toolpath(
+    operation_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<OperationSummary, AppError> {
+    invalidate_toolpath_inner(&operation_id, &state.project)
+}
+
+/// Translate, rotate, or mirror one operation's cached toolpath in place.
+///
+/// Returns [`AppError::NotFound`] if the operation has no cached toolpath.
+#[tauri::command]
+pub async fn transform_toolpath(
+    operation_id: String,
+    transform: ToolpathTransform,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    transform_toolpath_inner(&operation_id, &transform, &state.project)
+}
+
+/// Return the Z range reached by the given operations' toolpaths, for
+/// fixturing and setup checks.
+#[tauri::command]
+pub async fn program_z_range(
+    operation_ids: Vec<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<ZRange, AppError> {
+    program_z_range_inner(&operation_ids, &state.project)
+}
+
+/// Return rapid/feed distances, arc count, and an estimated run time for one
+/// operation's toolpath. `rapid_rate` defaults to the rapid rate of the post
+/// named by `post_processor_id`, or 10000 mm/min without one.
+#[tauri::command]
+pub async fn get_toolpath_stats(
+    operation_id: String,
+    rapid_rate: Option<f64>,
+    post_processor_id: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<ToolpathStats, AppError> {
+    let user_dir = user_post_dir(&state.preferences)?;
+    toolpath_stats_inner(
+        &operation_id,
+        rapid_rate,
+        post_processor_id.as_deref(),
+        user_dir.as_deref(),
+        &state.project,
+    )
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -95,39 +438,79 @@ mod tests {
     use uuid::Uuid;
 
     use crate::models::{
-        operation::{OperationParams, PocketParams},
+        operation::{CoolantMode, FeedMode, LeadType, OperationParams, PocketParams},
         tool::ToolType,
         Operation, Tool, Vec3,
     };
     use crate::state::AppState;
-    use crate::toolpath::types::{CutPoint, MoveKind, Pass, PassKind};
+    use crate::toolpath::types::{CutPoint, Pass, PassKind};
     use crate::toolpath::Toolpath;
 
     use super::*;
 
     #[test]
-    fn list_post_processors_inner_returns_four_entries() {
-        let result = list_post_processors_inner().expect("should succeed");
-        assert_eq!(result.len(), 4);
+    fn list_post_processors_inner_returns_six_entries() {
+        let result = list_post_processors_inner(None).expect("should succeed");
+        assert_eq!(result.len(), 6);
         let ids: Vec<&str> = result.iter().map(|m| m.id.as_str()).collect();
         assert!(ids.contains(&"fanuc-0i"));
     }
 
+    #[test]
+    fn list_post_processors_inner_merges_user_dir_preferring_user_posts() {
+        let dir = std::env::temp_dir().join(format!("jcam_user_posts_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let fanuc = crate::postprocessor::FANUC_0I_TOML;
+        std::fs::write(
+            dir.join("shop-fanuc.toml"),
+            fanuc.replace(
+                r#"name        = "Fanuc 0i-MD/MF""#,
+                r#"name = "Shop Fanuc""#,
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("haas-vf2.toml"),
+            fanuc.replace(r#"id          = "fanuc-0i""#, r#"id = "haas-vf2""#),
+        )
+        .unwrap();
+
+        let result = list_post_processors_inner(Some(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+        let posts = result.expect("should succeed");
+
+        assert_eq!(posts.len(), 7, "{posts:?}");
+        let fanuc: Vec<_> = posts.iter().filter(|p| p.id == "fanuc-0i").collect();
+        assert_eq!(fanuc.len(), 1, "duplicate id must not be listed twice");
+        assert_eq!(fanuc[0].name, "Shop Fanuc");
+        assert!(posts.iter().any(|p| p.id == "haas-vf2"));
+    }
+
+    #[test]
+    fn set_post_processor_dir_inner_stores_custom_dir() {
+        let state = AppState::default();
+        set_post_processor_dir_inner(Some("/shop/posts".to_string()), &state.preferences)
+            .expect("should succeed");
+        let prefs = state.preferences.read().expect("read lock");
+        assert_eq!(
+            prefs.post_processor_dir(),
+            Some(PathBuf::from("/shop/posts"))
+        );
+    }
+
     #[test]
     fn get_gcode_preview_inner_returns_not_found_when_no_toolpath() {
         let state = AppState::default();
         let valid_uuid = Uuid::new_v4().to_string();
-        let result = get_gcode_preview_inner(&valid_uuid, "fanuc-0i", &state.project);
+        let result = get_gcode_preview_inner(&valid_uuid, "fanuc-0i", None, &state.project);
         assert!(
             matches!(result, Err(AppError::NotFound(_))),
             "expected NotFound, got: {result:?}"
         );
     }
 
-    #[test]
-    fn get_gcode_preview_inner_returns_gcode_when_toolpath_exists() {
-        let state = AppState::default();
-
+    /// Adds a tool, a pocket operation and a two-move toolpath for it.
+    fn add_pocket_with_toolpath(state: &AppState) -> Uuid {
         let tool_id = Uuid::new_v4();
         let op_id = Uuid::new_v4();
 
@@ -140,6 +523,7 @@ mod tests {
             flute_count: 4,
             default_spindle_speed: None,
             default_feed_rate: None,
+            tip_angle: None,
         };
 
         let operation = Operation {
@@ -151,7 +535,12 @@ mod tests {
                 depth: 10.0,
                 stepdown: 2.0,
                 stepover_percent: 50.0,
+                lead_in: LeadType::None,
+                stock_to_leave: 0.0,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         };
 
         let toolpath = Toolpath {
@@ -181,7 +570,13 @@ mod tests {
                         tool_orientation: None,
                     },
                 ],
+                feed_rate: None,
             }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
         };
 
         {
@@ -191,8 +586,18 @@ mod tests {
             project.toolpaths.insert(op_id, toolpath);
         }
 
-        let gcode = get_gcode_preview_inner(&op_id.to_string(), "fanuc-0i", &state.project)
+        op_id
+    }
+
+    #[test]
+    fn get_gcode_preview_inner_returns_gcode_when_toolpath_exists() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_toolpath(&state);
+
+        let result = get_gcode_preview_inner(&op_id.to_string(), "fanuc-0i", None, &state.project)
             .expect("expected Ok G-code output");
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        let gcode = result.gcode;
         assert!(
             gcode.contains("G00") || gcode.contains("G0 "),
             "expected rapid move (G00/G0) in output, got:\n{}",
@@ -204,4 +609,432 @@ mod tests {
             gcode
         );
     }
+
+    #[test]
+    fn cached_toolpath_inserts_operation_lead_in() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_toolpath(&state);
+        let feed = |x: f64| CutPoint {
+            position: Vec3 { x, y: 0.0, z: -2.0 },
+            move_kind: MoveKind::Feed,
+            tool_orientation: None,
+        };
+        {
+            let mut project = state.project.write().expect("write lock");
+            match &mut project.operations[0].params {
+                OperationParams::Pocket(p) => p.lead_in = LeadType::Line { length: 5.0 },
+                other => panic!("expected pocket, got {other:?}"),
+            }
+            let toolpath = project.toolpaths.get_mut(&op_id).expect("toolpath");
+            toolpath.passes[0].cuts = vec![feed(0.0), feed(10.0)];
+        }
+
+        let project = state.project.read().expect("read lock");
+        let toolpath = cached_toolpath(&project, &op_id).expect("toolpath");
+        assert_eq!(toolpath.passes.len(), 2);
+        assert_eq!(toolpath.passes[0].kind, PassKind::LeadIn);
+        assert_eq!(toolpath.passes[0].cuts[0].position.x, -5.0);
+        assert_eq!(toolpath.passes[1].kind, PassKind::Cutting);
+        // The stored toolpath is left without the lead-in.
+        assert_eq!(project.toolpaths[&op_id].passes.len(), 1);
+    }
+
+    #[test]
+    fn cached_toolpath_copies_operation_stock_to_leave() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_toolpath(&state);
+        {
+            let mut project = state.project.write().expect("write lock");
+            match &mut project.operations[0].params {
+                OperationParams::Pocket(p) => p.stock_to_leave = 0.3,
+                other => panic!("expected pocket, got {other:?}"),
+            }
+        }
+
+        let project = state.project.read().expect("read lock");
+        let toolpath = cached_toolpath(&project, &op_id).expect("toolpath");
+        assert_eq!(toolpath.stock_to_leave, Some(0.3));
+    }
+
+    #[test]
+    fn summarize_gcode_counts_lines_and_keeps_head_and_tail() {
+        let gcode: String = (1..=70).map(|n| format!("N{n} G01 X{n}\n")).collect();
+        let summary = summarize_gcode(&gcode);
+
+        assert_eq!(summary.line_count, gcode.matches('\n').count());
+        assert_eq!(summary.byte_len, gcode.len());
+        assert_eq!(summary.first_50_lines.len(), 50);
+        assert_eq!(summary.first_50_lines[0], "N1 G01 X1");
+        assert_eq!(summary.last_10_lines.len(), 10);
+        assert_eq!(summary.last_10_lines[0], "N61 G01 X61");
+        assert_eq!(summary.last_10_lines[9], "N70 G01 X70");
+    }
+
+    #[test]
+    fn summarize_gcode_tail_does_not_repeat_head_of_short_program() {
+        let summary = summarize_gcode("G90\nG00 X0\nM30\n");
+        assert_eq!(summary.line_count, 3);
+        assert_eq!(summary.first_50_lines, ["G90", "G00 X0", "M30"]);
+        assert!(summary.last_10_lines.is_empty());
+    }
+
+    #[test]
+    fn gcode_preview_summary_line_count_matches_newlines() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_toolpath(&state);
+
+        let full = get_gcode_preview_inner(&op_id.to_string(), "fanuc-0i", None, &state.project)
+            .expect("preview")
+            .gcode;
+        let summary =
+            gcode_preview_summary_inner(&op_id.to_string(), "fanuc-0i", None, &state.project)
+                .expect("summary");
+        assert_eq!(summary.line_count, full.matches('\n').count());
+        assert_eq!(summary.byte_len, full.len());
+        assert_eq!(
+            summary.first_50_lines.first().map(String::as_str),
+            full.lines().next()
+        );
+    }
+
+    #[test]
+    fn invalidate_toolpath_removes_entry_and_flags_recalculate() {
+        let state = AppState::default();
+        let op_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+        {
+            let mut project = state.project.write().expect("write lock");
+            for id in [op_id, other_id] {
+                project.operations.push(Operation {
+                    id,
+                    name: "Pocket".to_string(),
+                    enabled: true,
+                    tool_id: Uuid::new_v4(),
+                    params: OperationParams::Pocket(PocketParams {
+                        depth: 10.0,
+                        stepdown: 2.0,
+                        stepover_percent: 50.0,
+                        lead_in: LeadType::None,
+                        stock_to_leave: 0.0,
+                    }),
+                    path_mode: None,
+                    coolant: CoolantMode::Flood,
+                    feed_mode: FeedMode::PerMinute,
+                });
+                project.toolpaths.insert(
+                    id,
+                    Toolpath {
+                        operation_id: id,
+                        tool_number: 1,
+                        spindle_speed: 8000.0,
+                        feed_rate: 500.0,
+                        passes: vec![],
+                        path_mode: None,
+                        drill: None,
+                        coolant: None,
+                        feed_mode: None,
+                        stock_to_leave: None,
+                    },
+                );
+            }
+        }
+
+        let summary = invalidate_toolpath_inner(&op_id.to_string(), &state.project)
+            .expect("invalidate should succeed");
+        assert_eq!(summary.id, op_id);
+        assert!(summary.needs_recalculate);
+
+        let project = state.project.read().expect("read lock");
+        assert!(!project.toolpaths.contains_key(&op_id));
+        assert!(
+            project.toolpaths.contains_key(&other_id),
+            "other toolpaths must be kept"
+        );
+    }
+
+    #[test]
+    fn invalidate_toolpath_unknown_operation_returns_not_found() {
+        let state = AppState::default();
+        let result = invalidate_toolpath_inner(&Uuid::new_v4().to_string(), &state.project);
+        assert!(
+            matches!(result, Err(AppError::NotFound(_))),
+            "expected NotFound, got: {result:?}"
+        );
+    }
+
+    #[test]
+    fn program_z_range_spans_arc_endpoints() {
+        let state = AppState::default();
+        let op_id = Uuid::new_v4();
+        let at = |z: f64| Vec3 { x: 0.0, y: 0.0, z };
+        let cut = |position: Vec3, move_kind: MoveKind| CutPoint {
+            position,
+            move_kind,
+            tool_orientation: None,
+        };
+        state.project.write().expect("write lock").toolpaths.insert(
+            op_id,
+            Toolpath {
+                operation_id: op_id,
+                tool_number: 1,
+                spindle_speed: 8000.0,
+                feed_rate: 500.0,
+                passes: vec![Pass {
+                    kind: PassKind::Cutting,
+                    cuts: vec![
+                        cut(at(5.0), MoveKind::Rapid),
+                        cut(at(-2.0), MoveKind::Feed),
+                        // Helical arc descending to the final depth.
+                        cut(
+                            at(-2.0),
+                            MoveKind::Arc {
+                                center: Vec3 {
+                                    x: 5.0,
+                                    y: 0.0,
+                                    z: -2.0,
+                                },
+                                end: at(-10.0),
+                                clockwise: true,
+                            },
+                        ),
+                    ],
+                    feed_rate: None,
+                }],
+                path_mode: None,
+                drill: None,
+                coolant: None,
+                feed_mode: None,
+                stock_to_leave: None,
+            },
+        );
+
+        let range = program_z_range_inner(&[op_id.to_string()], &state.project)
+            .expect("range should be found");
+        assert_eq!(
+            range,
+            ZRange {
+                z_min: -10.0,
+                z_max: 5.0
+            }
+        );
+
+        let missing = program_z_range_inner(&[Uuid::new_v4().to_string()], &state.project);
+        assert!(
+            matches!(missing, Err(AppError::NotFound(_))),
+            "expected NotFound, got: {missing:?}"
+        );
+    }
+
+    #[test]
+    fn toolpath_stats_for_sample_feed_toolpath() {
+        let state = AppState::default();
+        let op_id = Uuid::new_v4();
+        let at = |x: f64, y: f64| Vec3 { x, y, z: -5.0 };
+        let cut = |position: Vec3, move_kind: MoveKind| CutPoint {
+            position,
+            move_kind,
+            tool_orientation: None,
+        };
+        state.project.write().expect("write lock").toolpaths.insert(
+            op_id,
+            Toolpath {
+                operation_id: op_id,
+                tool_number: 1,
+                spindle_speed: 12000.0,
+                feed_rate: 1500.0,
+                passes: vec![Pass {
+                    kind: PassKind::Cutting,
+                    cuts: vec![
+                        cut(at(0.0, 0.0), MoveKind::Rapid),
+                        cut(at(50.0, 0.0), MoveKind::Feed),
+                        // Half circle back towards the origin, radius 10.
+                        cut(
+                            at(50.0, 0.0),
+                            MoveKind::Arc {
+                                center: at(40.0, 0.0),
+                                end: at(30.0, 0.0),
+                                clockwise: false,
+                            },
+                        ),
+                        cut(at(30.0, 100.0), MoveKind::Rapid),
+                    ],
+                    feed_rate: None,
+                }],
+                path_mode: None,
+                drill: None,
+                coolant: None,
+                feed_mode: None,
+                stock_to_leave: None,
+            },
+        );
+
+        let stats = toolpath_stats_inner(&op_id.to_string(), None, None, None, &state.project)
+            .expect("stats should be computed");
+        let arc = 10.0 * std::f64::consts::PI;
+        assert_eq!(stats.arc_count, 1);
+        assert_eq!(stats.rapid_distance, 100.0);
+        assert!(
+            (stats.feed_distance - (50.0 + arc)).abs() < 1e-9,
+            "{stats:?}"
+        );
+        let expected = (50.0 + arc) / 1500.0 * 60.0 + 100.0 / 10_000.0 * 60.0;
+        assert!(
+            (stats.estimated_seconds - expected).abs() < 1e-9,
+            "{stats:?}"
+        );
+
+        let slow =
+            toolpath_stats_inner(&op_id.to_string(), Some(1000.0), None, None, &state.project)
+                .expect("stats should be computed");
+        assert!((slow.estimated_seconds - stats.estimated_seconds - 5.4).abs() < 1e-9);
+
+        // Halving the pass's feed doubles its feed time.
+        state
+            .project
+            .write()
+            .expect("write lock")
+            .toolpaths
+            .get_mut(&op_id)
+            .expect("toolpath")
+            .passes[0]
+            .feed_rate = Some(750.0);
+        let overridden = toolpath_stats_inner(&op_id.to_string(), None, None, None, &state.project)
+            .expect("stats should be computed");
+        let expected = (50.0 + arc) / 750.0 * 60.0 + 100.0 / 10_000.0 * 60.0;
+        assert!(
+            (overridden.estimated_seconds - expected).abs() < 1e-9,
+            "{overridden:?}"
+        );
+    }
+
+    #[test]
+    fn toolpath_stats_rapid_rate_falls_back_to_post_machine_rate() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_toolpath(&state);
+        // A 100 mm rapid retract after the feed move.
+        state
+            .project
+            .write()
+            .expect("write lock")
+            .toolpaths
+            .get_mut(&op_id)
+            .expect("toolpath")
+            .passes[0]
+            .cuts
+            .push(CutPoint {
+                position: Vec3 {
+                    x: 10.0,
+                    y: 0.0,
+                    z: 100.0,
+                },
+                move_kind: MoveKind::Rapid,
+                tool_orientation: None,
+            });
+        let dir = std::env::temp_dir().join(format!("jcam_stats_posts_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("fast.toml"),
+            crate::postprocessor::FANUC_0I_TOML
+                .replace(r#"id          = "fanuc-0i""#, r#"id = "fast""#)
+                .replace("[machine]", "[machine]\nrapid_rate_mm_min = 30000"),
+        )
+        .unwrap();
+
+        let stats = |rapid_rate, post: Option<&str>| {
+            toolpath_stats_inner(
+                &op_id.to_string(),
+                rapid_rate,
+                post,
+                Some(&dir),
+                &state.project,
+            )
+        };
+        let default = stats(None, None);
+        let fast = stats(None, Some("fast"));
+        let explicit = stats(Some(10_000.0), Some("fast"));
+        let _ = std::fs::remove_dir_all(&dir);
+        let (default, fast, explicit) = (default.unwrap(), fast.unwrap(), explicit.unwrap());
+
+        // 100 mm at 10 m/min is 0.6 s; at the post's 30 m/min, 0.2 s.
+        assert_eq!(default.rapid_distance, 100.0);
+        assert!(
+            (default.estimated_seconds - fast.estimated_seconds - 0.4).abs() < 1e-9,
+            "{fast:?} vs {default:?}"
+        );
+        // An explicit rate wins over the post's.
+        assert_eq!(explicit, default);
+    }
+
+    #[test]
+    fn toolpath_stats_without_toolpath_is_not_found() {
+        let state = AppState::default();
+        let result = toolpath_stats_inner(
+            &Uuid::new_v4().to_string(),
+            None,
+            None,
+            None,
+            &state.project,
+        );
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[test]
+    fn transform_toolpath_replaces_stored_toolpath() {
+        use crate::toolpath::transform::MirrorPlane;
+
+        let state = AppState::default();
+        let op_id = Uuid::new_v4();
+        let at = |x: f64| Vec3 { x, y: 2.0, z: -1.0 };
+        state.project.write().expect("write lock").toolpaths.insert(
+            op_id,
+            Toolpath {
+                operation_id: op_id,
+                tool_number: 1,
+                spindle_speed: 12000.0,
+                feed_rate: 1500.0,
+                passes: vec![Pass {
+                    kind: PassKind::Cutting,
+                    cuts: vec![CutPoint {
+                        position: at(7.0),
+                        move_kind: MoveKind::Feed,
+                        tool_orientation: None,
+                    }],
+                    feed_rate: None,
+                }],
+                path_mode: None,
+                drill: None,
+                coolant: None,
+                feed_mode: None,
+                stock_to_leave: None,
+            },
+        );
+
+        transform_toolpath_inner(
+            &op_id.to_string(),
+            &ToolpathTransform::Mirror {
+                plane: MirrorPlane::X,
+            },
+            &state.project,
+        )
+        .expect("transform should succeed");
+
+        let project = state.project.read().expect("read lock");
+        assert_eq!(
+            project.toolpaths[&op_id].passes[0].cuts[0].position,
+            at(-7.0)
+        );
+    }
+
+    #[test]
+    fn transform_toolpath_without_toolpath_is_not_found() {
+        let state = AppState::default();
+        let result = transform_toolpath_inner(
+            &Uuid::new_v4().to_string(),
+            &ToolpathTransform::Translate {
+                delta: Vec3::zero(),
+            },
+            &state.project,
+        );
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
 }
@@ -5,14 +5,18 @@
 //!   They are synchronous and directly testable without Tauri.
 //! - `#[tauri::command]` wrappers extract managed state and delegate to `_inner`.
 
+use std::path::Path;
 use std::sync::RwLock;
 
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::models::feeds::{calc_feeds_speeds, FeedSpeed, MaterialClass};
 use crate::models::{Tool, ToolType};
 use crate::state::{AppState, Project};
 
+use super::file::prepare_output_path_inner;
+use super::units::conversion_factor;
 use super::{parse_entity_id, read_project, write_project};
 
 // ── Input type ────────────────────────────────────────────────────────────────
@@ -30,6 +34,7 @@ pub struct ToolInput {
     pub flute_count: u32,
     pub default_spindle_speed: Option<u32>,
     pub default_feed_rate: Option<f64>,
+    pub tip_angle: Option<f64>,
 }
 
 // ── add_tool ──────────────────────────────────────────────────────────────────
@@ -51,6 +56,7 @@ pub(crate) fn add_tool_inner(
         flute_count: input.flute_count,
         default_spindle_speed: input.default_spindle_speed,
         default_feed_rate: input.default_feed_rate,
+        tip_angle: input.tip_angle,
     };
     let mut project = write_project(project_lock)?;
     project.tools.push(tool.clone());
@@ -86,6 +92,7 @@ pub(crate) fn edit_tool_inner(
     entry.flute_count = input.flute_count;
     entry.default_spindle_speed = input.default_spindle_speed;
     entry.default_feed_rate = input.default_feed_rate;
+    entry.tip_angle = input.tip_angle;
 
     Ok(entry.clone())
 }
@@ -96,17 +103,44 @@ pub(crate) fn edit_tool_inner(
 ///
 /// Removes the tool with the given `id`. Returns [`AppError::NotFound`] if no
 /// tool with that ID exists.
-pub(crate) fn delete_tool_inner(id: &str, project_lock: &RwLock<Project>) -> Result<(), AppError> {
+///
+/// If operations still use the tool, returns [`AppError::Conflict`] naming
+/// them, unless `force` is set, in which case those operations and their
+/// cached toolpaths are deleted along with the tool.
+pub(crate) fn delete_tool_inner(
+    id: &str,
+    force: bool,
+    project_lock: &RwLock<Project>,
+) -> Result<(), AppError> {
     let uuid = parse_entity_id(id, "tool")?;
 
     let mut project = write_project(project_lock)?;
 
-    let before = project.tools.len();
-    project.tools.retain(|t| t.id != uuid);
-    if project.tools.len() == before {
+    if !project.tools.iter().any(|t| t.id == uuid) {
         return Err(AppError::NotFound(format!("tool {id} not found")));
     }
 
+    let users: Vec<(Uuid, String)> = project
+        .operations
+        .iter()
+        .filter(|op| op.tool_id == uuid)
+        .map(|op| (op.id, op.name.clone()))
+        .collect();
+    if !users.is_empty() {
+        if !force {
+            let names: Vec<&str> = users.iter().map(|(_, name)| name.as_str()).collect();
+            return Err(AppError::Conflict(format!(
+                "tool {id} is used by operations: {}",
+                names.join(", ")
+            )));
+        }
+        project.operations.retain(|op| op.tool_id != uuid);
+        for (op_id, _) in &users {
+            project.toolpaths.remove(op_id);
+        }
+    }
+
+    project.tools.retain(|t| t.id != uuid);
     Ok(())
 }
 
@@ -120,6 +154,151 @@ pub(crate) fn list_tools_inner(project_lock: &RwLock<Project>) -> Result<Vec<Too
     Ok(project.tools.clone())
 }
 
+// ── get_tool ──────────────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`get_tool`].
+///
+/// Returns a clone of the tool with the given `id`, or
+/// [`AppError::NotFound`] if there is none.
+pub(crate) fn get_tool_inner(id: &str, project_lock: &RwLock<Project>) -> Result<Tool, AppError> {
+    let uuid = parse_entity_id(id, "tool")?;
+    let project = read_project(project_lock)?;
+    project
+        .tools
+        .iter()
+        .find(|t| t.id == uuid)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("tool {id} not found")))
+}
+
+// ── copy_tool ─────────────────────────────────────────────────────────────────
+
+/// Testable inner logic for [`copy_tool`].
+///
+/// Appends a duplicate of the tool with the given `id` under a new UUID and
+/// the name `"<name> (copy)"`, and returns it. Returns [`AppError::NotFound`]
+/// if no tool with that ID exists.
+pub(crate) fn copy_tool_inner(id: &str, project_lock: &RwLock<Project>) -> Result<Tool, AppError> {
+    let uuid = parse_entity_id(id, "tool")?;
+
+    let mut project = write_project(project_lock)?;
+    let mut copy = project
+        .tools
+        .iter()
+        .find(|t| t.id == uuid)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("tool {id} not found")))?;
+    copy.id = Uuid::new_v4();
+    copy.name = format!("{} (copy)", copy.name);
+    project.tools.push(copy.clone());
+    Ok(copy)
+}
+
+// ── export_tool_library / import_tool_library ─────────────────────────────────
+
+/// Testable inner logic for [`export_tool_library`].
+///
+/// Writes `project.tools` to `path` as a pretty-printed JSON array of
+/// [`Tool`]s, the same shape the tools have inside a `.jcam` file. `path`
+/// is checked with [`prepare_output_path_inner`] first.
+pub(crate) fn export_tool_library_inner(
+    path: &Path,
+    project_lock: &RwLock<Project>,
+) -> Result<(), AppError> {
+    let path = prepare_output_path_inner(&path.to_string_lossy(), false)?;
+    let json = {
+        let project = read_project(project_lock)?;
+        serde_json::to_string_pretty(&project.tools)
+            .map_err(|e| AppError::Io(format!("cannot serialize tool library: {e}")))?
+    };
+    std::fs::write(&path, json)?;
+    Ok(())
+}
+
+/// Testable inner logic for [`import_tool_library`].
+///
+/// Reads a tool library written by [`export_tool_library_inner`] and returns
+/// the project's tools afterwards.
+///
+/// - With `merge`, every library tool whose name is not already in the
+///   project (or earlier in the library) is appended under a fresh UUID, so
+///   libraries exported from the same project never collide.
+/// - Without it, the library replaces `project.tools` as-is, keeping its
+///   UUIDs. Returns [`AppError::Conflict`] naming the affected operations if
+///   any would lose their tool.
+///
+/// Returns [`AppError::InvalidInput`] if the file is not a tool library.
+pub(crate) fn import_tool_library_inner(
+    path: &Path,
+    merge: bool,
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<Tool>, AppError> {
+    let json = std::fs::read_to_string(path)?;
+    let library: Vec<Tool> = serde_json::from_str(&json)
+        .map_err(|e| AppError::InvalidInput(format!("invalid tool library: {e}")))?;
+
+    let mut project = write_project(project_lock)?;
+    if merge {
+        for mut tool in library {
+            if project.tools.iter().any(|t| t.name == tool.name) {
+                continue;
+            }
+            tool.id = Uuid::new_v4();
+            project.tools.push(tool);
+        }
+    } else {
+        let orphaned: Vec<&str> = project
+            .operations
+            .iter()
+            .filter(|op| !library.iter().any(|t| t.id == op.tool_id))
+            .map(|op| op.name.as_str())
+            .collect();
+        if !orphaned.is_empty() {
+            return Err(AppError::Conflict(format!(
+                "replacing the tool library would orphan operations: {}",
+                orphaned.join(", ")
+            )));
+        }
+        project.tools = library;
+    }
+    Ok(project.tools.clone())
+}
+
+// ── suggest_feeds_speeds ──────────────────────────────────────────────────────
+
+/// Testable inner logic for [`suggest_feeds_speeds`].
+///
+/// Looks up the tool with the given `id` and runs [`calc_feeds_speeds`] for
+/// `material`. The tool diameter is converted from project units to mm first,
+/// so the result is always RPM and mm/min.
+///
+/// Returns [`AppError::NotFound`] if no tool with that ID exists, or
+/// [`AppError::InvalidInput`] if `material` is not in the built-in table.
+pub(crate) fn suggest_feeds_speeds_inner(
+    id: &str,
+    material: &str,
+    surface_speed_override: Option<f64>,
+    project_lock: &RwLock<Project>,
+) -> Result<FeedSpeed, AppError> {
+    let uuid = parse_entity_id(id, "tool")?;
+    if MaterialClass::from_id(material).is_none() {
+        return Err(AppError::InvalidInput(format!(
+            "no cutting data for material '{material}'"
+        )));
+    }
+
+    let project = read_project(project_lock)?;
+    let mut tool = project
+        .tools
+        .iter()
+        .find(|t| t.id == uuid)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("tool {id} not found")))?;
+    tool.diameter *= conversion_factor(&project.units, "mm")?;
+
+    Ok(calc_feeds_speeds(&tool, material, surface_speed_override))
+}
+
 // ── Tauri command wrappers ────────────────────────────────────────────────────
 
 /// Add a new tool to the project tool library.
@@ -149,10 +328,47 @@ pub async fn edit_tool(
 
 /// Remove a tool from the project tool library.
 ///
-/// Returns [`AppError::NotFound`] if `id` does not match any tool.
+/// Returns [`AppError::NotFound`] if `id` does not match any tool, or
+/// [`AppError::Conflict`] if operations still use it and `force` is not set.
+/// With `force`, those operations are deleted too.
 #[tauri::command]
-pub async fn delete_tool(id: String, state: tauri::State<'_, AppState>) -> Result<(), AppError> {
-    delete_tool_inner(&id, &state.project)
+pub async fn delete_tool(
+    id: String,
+    force: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    delete_tool_inner(&id, force.unwrap_or(false), &state.project)
+}
+
+/// Duplicate a tool under a new ID with `" (copy)"` appended to its name.
+///
+/// Returns the new [`Tool`], or [`AppError::NotFound`] if `id` does not match
+/// any tool in the project library.
+#[tauri::command]
+pub async fn copy_tool(id: String, state: tauri::State<'_, AppState>) -> Result<Tool, AppError> {
+    copy_tool_inner(&id, &state.project)
+}
+
+/// Write the project tool library to `path` as JSON.
+#[tauri::command]
+pub async fn export_tool_library(
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    export_tool_library_inner(Path::new(&path), &state.project)
+}
+
+/// Load a tool library from `path`, merging it into the project's tools
+/// (skipping names already present) or, without `merge`, replacing them.
+///
+/// Returns the resulting tool library.
+#[tauri::command]
+pub async fn import_tool_library(
+    path: String,
+    merge: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Tool>, AppError> {
+    import_tool_library_inner(Path::new(&path), merge, &state.project)
 }
 
 /// Return all tools in the project tool library.
@@ -161,11 +377,34 @@ pub async fn list_tools(state: tauri::State<'_, AppState>) -> Result<Vec<Tool>,
     list_tools_inner(&state.project)
 }
 
+/// Return a single tool by ID.
+#[tauri::command]
+pub async fn get_tool(id: String, state: tauri::State<'_, AppState>) -> Result<Tool, AppError> {
+    get_tool_inner(&id, &state.project)
+}
+
+/// Suggest a spindle speed (RPM) and feed rate (mm/min) for a tool cutting
+/// `material`.
+///
+/// Returns [`AppError::NotFound`] if `tool_id` does not match any tool, or
+/// [`AppError::InvalidInput`] if the material has no built-in cutting data.
+#[tauri::command]
+pub async fn suggest_feeds_speeds(
+    tool_id: String,
+    material: String,
+    surface_speed_override: Option<f64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<FeedSpeed, AppError> {
+    suggest_feeds_speeds_inner(&tool_id, &material, surface_speed_override, &state.project)
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::operation::{CoolantMode, DrillParams, FeedMode, OperationParams};
+    use crate::models::Operation;
     use crate::state::AppState;
 
     fn make_input(name: &str) -> ToolInput {
@@ -177,6 +416,7 @@ mod tests {
             flute_count: 4,
             default_spindle_speed: Some(15000),
             default_feed_rate: Some(2400.0),
+            tip_angle: None,
         }
     }
 
@@ -208,6 +448,7 @@ mod tests {
                 flute_count: 2,
                 default_spindle_speed: None,
                 default_feed_rate: None,
+                tip_angle: None,
             },
             &state.project,
         )
@@ -232,12 +473,73 @@ mod tests {
         let tool =
             add_tool_inner(make_input("To Delete"), &state.project).expect("add should succeed");
 
-        delete_tool_inner(&tool.id.to_string(), &state.project).expect("delete should succeed");
+        delete_tool_inner(&tool.id.to_string(), false, &state.project)
+            .expect("delete should succeed");
 
         let tools = list_tools_inner(&state.project).expect("list should succeed");
         assert!(tools.is_empty());
     }
 
+    /// Add a drill operation named `name` that uses `tool_id`; returns its ID.
+    fn add_operation_using(state: &AppState, name: &str, tool_id: Uuid) -> Uuid {
+        let op = Operation {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            enabled: true,
+            tool_id,
+            params: OperationParams::Drill(DrillParams {
+                depth: 5.0,
+                peck_depth: None,
+            }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
+        };
+        let op_id = op.id;
+        state
+            .project
+            .write()
+            .expect("write lock")
+            .operations
+            .push(op);
+        op_id
+    }
+
+    #[test]
+    fn delete_tool_in_use_is_blocked_with_operation_names() {
+        let state = AppState::default();
+        let tool = add_tool_inner(make_input("Drill"), &state.project).expect("add tool");
+        add_operation_using(&state, "Spot holes", tool.id);
+
+        let result = delete_tool_inner(&tool.id.to_string(), false, &state.project);
+
+        match result {
+            Err(AppError::Conflict(msg)) => assert!(msg.contains("Spot holes"), "{msg}"),
+            other => panic!("expected Conflict, got {other:?}"),
+        }
+        let project = state.project.read().expect("read lock");
+        assert_eq!(project.tools.len(), 1, "tool must survive");
+        assert_eq!(project.operations.len(), 1);
+    }
+
+    #[test]
+    fn force_delete_tool_cascades_to_its_operations() {
+        let state = AppState::default();
+        let tool = add_tool_inner(make_input("Drill"), &state.project).expect("add tool");
+        let other = add_tool_inner(make_input("Endmill"), &state.project).expect("add tool");
+        add_operation_using(&state, "Spot holes", tool.id);
+        let kept = add_operation_using(&state, "Profile", other.id);
+
+        delete_tool_inner(&tool.id.to_string(), true, &state.project)
+            .expect("forced delete should succeed");
+
+        let project = state.project.read().expect("read lock");
+        assert_eq!(project.tools.len(), 1);
+        assert_eq!(project.tools[0].id, other.id);
+        assert_eq!(project.operations.len(), 1);
+        assert_eq!(project.operations[0].id, kept);
+    }
+
     #[test]
     fn add_multiple_tools_have_distinct_ids() {
         let state = AppState::default();
@@ -253,6 +555,24 @@ mod tests {
         assert_eq!(tools.len(), 3);
     }
 
+    #[test]
+    fn get_tool_returns_the_matching_tool() {
+        let state = AppState::default();
+        add_tool_inner(make_input("First"), &state.project).expect("add");
+        let second = add_tool_inner(make_input("Second"), &state.project).expect("add");
+
+        let fetched = get_tool_inner(&second.id.to_string(), &state.project).expect("get");
+        assert_eq!(fetched, second);
+    }
+
+    #[test]
+    fn get_nonexistent_tool_returns_not_found() {
+        let state = AppState::default();
+        let fake_id = Uuid::new_v4().to_string();
+        let result = get_tool_inner(&fake_id, &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
     #[test]
     fn edit_nonexistent_id_returns_not_found() {
         let state = AppState::default();
@@ -265,7 +585,7 @@ mod tests {
     fn delete_nonexistent_id_returns_not_found() {
         let state = AppState::default();
         let fake_id = Uuid::new_v4().to_string();
-        let result = delete_tool_inner(&fake_id, &state.project);
+        let result = delete_tool_inner(&fake_id, false, &state.project);
         assert!(matches!(result, Err(AppError::NotFound(_))));
     }
 
@@ -279,7 +599,132 @@ mod tests {
     #[test]
     fn delete_invalid_uuid_string_returns_not_found() {
         let state = AppState::default();
-        let result = delete_tool_inner("not-a-valid-uuid", &state.project);
+        let result = delete_tool_inner("not-a-valid-uuid", false, &state.project);
         assert!(matches!(result, Err(AppError::NotFound(_))));
     }
+
+    #[test]
+    fn suggest_feeds_speeds_converts_inch_diameter() {
+        let state = AppState::default();
+        let mm_tool = add_tool_inner(make_input("Metric"), &state.project).unwrap();
+        let mut inch_input = make_input("Imperial");
+        inch_input.diameter = 10.0 / 25.4;
+        let inch_tool = add_tool_inner(inch_input, &state.project).unwrap();
+
+        let metric = suggest_feeds_speeds_inner(
+            &mm_tool.id.to_string(),
+            "aluminum-6061",
+            None,
+            &state.project,
+        )
+        .unwrap();
+        state.project.write().unwrap().units = "inch".to_string();
+        let imperial = suggest_feeds_speeds_inner(
+            &inch_tool.id.to_string(),
+            "aluminum-6061",
+            None,
+            &state.project,
+        )
+        .unwrap();
+        assert_eq!(metric.spindle_rpm, imperial.spindle_rpm);
+    }
+
+    #[test]
+    fn suggest_feeds_speeds_rejects_unknown_material_and_tool() {
+        let state = AppState::default();
+        let tool = add_tool_inner(make_input("T"), &state.project).unwrap();
+
+        let result =
+            suggest_feeds_speeds_inner(&tool.id.to_string(), "unobtainium", None, &state.project);
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+
+        let fake_id = Uuid::new_v4().to_string();
+        let result = suggest_feeds_speeds_inner(&fake_id, "wood-mdf", None, &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    // ── copy_tool ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn copy_tool_gets_new_id_and_copy_suffix() {
+        let state = AppState::default();
+        let original = add_tool_inner(make_input("Flat 6"), &state.project).expect("add");
+
+        let copy = copy_tool_inner(&original.id.to_string(), &state.project).expect("copy");
+
+        assert_ne!(copy.id, original.id);
+        assert_eq!(copy.name, "Flat 6 (copy)");
+        assert_eq!(copy.diameter, original.diameter);
+        assert_eq!(list_tools_inner(&state.project).expect("list").len(), 2);
+    }
+
+    #[test]
+    fn copy_unknown_tool_is_not_found() {
+        let state = AppState::default();
+        let result = copy_tool_inner(&Uuid::new_v4().to_string(), &state.project);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    // ── tool library import / export ──────────────────────────────────────────
+
+    #[test]
+    fn import_merge_skips_existing_names_and_assigns_new_ids() {
+        let path = std::env::temp_dir().join("jcam_tool_library_merge.json");
+        let master = AppState::default();
+        let shared = add_tool_inner(make_input("Flat 10"), &master.project).expect("add");
+        add_tool_inner(make_input("Drill 5"), &master.project).expect("add");
+        export_tool_library_inner(&path, &master.project).expect("export");
+
+        let state = AppState::default();
+        add_tool_inner(make_input("Flat 10"), &state.project).expect("add");
+        let tools = import_tool_library_inner(&path, true, &state.project).expect("import");
+        let _ = std::fs::remove_file(&path);
+
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Flat 10", "Drill 5"]);
+        assert!(tools.iter().all(|t| t.id != shared.id));
+    }
+
+    #[test]
+    fn export_tool_library_rejects_invalid_file_name() {
+        let state = AppState::default();
+        let path = std::env::temp_dir().join("bad*library.json");
+        let result = export_tool_library_inner(&path, &state.project);
+        assert!(
+            matches!(result, Err(AppError::InvalidPath(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn import_replace_keeps_library_ids() {
+        let path = std::env::temp_dir().join("jcam_tool_library_replace.json");
+        let master = AppState::default();
+        let tool = add_tool_inner(make_input("Ball 3"), &master.project).expect("add");
+        export_tool_library_inner(&path, &master.project).expect("export");
+
+        let state = AppState::default();
+        add_tool_inner(make_input("Old"), &state.project).expect("add");
+        let tools = import_tool_library_inner(&path, false, &state.project).expect("import");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].id, tool.id);
+    }
+
+    #[test]
+    fn import_replace_refuses_to_orphan_operations() {
+        let path = std::env::temp_dir().join("jcam_tool_library_orphan.json");
+        export_tool_library_inner(&path, &AppState::default().project).expect("export");
+
+        let state = AppState::default();
+        let tool = add_tool_inner(make_input("In use"), &state.project).expect("add");
+        add_operation_using(&state, "Drill holes", tool.id);
+
+        let result = import_tool_library_inner(&path, false, &state.project);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+        assert_eq!(list_tools_inner(&state.project).expect("list").len(), 1);
+    }
 }
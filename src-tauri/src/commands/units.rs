@@ -0,0 +1,535 @@
+//! Project unit-system IPC command handlers.
+//!
+//! All handlers follow the `_inner` + `#[tauri::command]` wrapper pattern:
+//! - `_inner` functions take `&RwLock<Project>` and contain the business logic.
+//!   They are synchronous and directly testable without Tauri.
+//! - `#[tauri::command]` wrappers extract managed state and delegate to `_inner`.
+//!
+//! `Project.units` is either `"mm"` or `"inch"`. Converting between them
+//! scales every length and linear feed rate by 25.4; spindle speeds and
+//! percentages are unit-free and never change.
+
+use std::sync::RwLock;
+
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::models::operation::{LeadType, OperationParams};
+use crate::models::{StockDefinition, Vec3};
+use crate::state::{AppState, Project};
+
+use super::project::ProjectSnapshot;
+use super::{read_project, write_project};
+
+/// Millimetres per inch.
+const MM_PER_INCH: f64 = 25.4;
+
+/// Returns the factor that converts a length in `from` units to `to` units.
+///
+/// Returns [`AppError::UnsupportedFormat`] if either unit string is not
+/// `"mm"` or `"inch"`.
+pub(crate) fn conversion_factor(from: &str, to: &str) -> Result<f64, AppError> {
+    let per_mm = |units: &str| match units {
+        "mm" => Ok(1.0),
+        "inch" => Ok(1.0 / MM_PER_INCH),
+        other => Err(AppError::UnsupportedFormat(format!(
+            "unknown units '{other}'; expected \"mm\" or \"inch\""
+        ))),
+    };
+    Ok(per_mm(to)? / per_mm(from)?)
+}
+
+// ── Length walk ───────────────────────────────────────────────────────────────
+
+/// Call `visit` with a human-readable path and a mutable reference for every
+/// length and linear feed rate in `project`.
+///
+/// Both the preview and the conversion are driven from this walk, so every
+/// value the conversion scales has a matching preview row.
+fn visit_lengths(project: &mut Project, mut visit: impl FnMut(String, &mut f64)) {
+    match &mut project.stock {
+        Some(StockDefinition::Box(b)) => {
+            visit_vec3(&mut visit, "stock.origin", &mut b.origin);
+            visit("stock.width".to_string(), &mut b.width);
+            visit("stock.depth".to_string(), &mut b.depth);
+            visit("stock.height".to_string(), &mut b.height);
+        }
+        Some(StockDefinition::Cylinder(c)) => {
+            visit_vec3(&mut visit, "stock.origin", &mut c.origin);
+            visit("stock.diameter".to_string(), &mut c.diameter);
+            visit("stock.height".to_string(), &mut c.height);
+        }
+        None => {}
+    }
+
+    for wcs in &mut project.wcs {
+        visit_vec3(
+            &mut visit,
+            &format!("wcs '{}'.origin", wcs.name),
+            &mut wcs.origin,
+        );
+    }
+
+    for tool in &mut project.tools {
+        visit(format!("tool '{}'.diameter", tool.name), &mut tool.diameter);
+        if let Some(feed) = &mut tool.default_feed_rate {
+            visit(format!("tool '{}'.defaultFeedRate", tool.name), feed);
+        }
+    }
+
+    for op in &mut project.operations {
+        let label = format!("operation '{}'", op.name);
+        match &mut op.params {
+            OperationParams::Profile(p) => {
+                visit(format!("{label}.depth"), &mut p.depth);
+                visit(format!("{label}.stepdown"), &mut p.stepdown);
+                visit(format!("{label}.stockToLeave"), &mut p.stock_to_leave);
+                visit_lead(&mut visit, &label, &mut p.lead_in);
+                if let Some(tabs) = &mut p.tabs {
+                    visit(format!("{label}.tabs.width"), &mut tabs.width);
+                    visit(format!("{label}.tabs.height"), &mut tabs.height);
+                }
+            }
+            OperationParams::Pocket(p) => {
+                visit(format!("{label}.depth"), &mut p.depth);
+                visit(format!("{label}.stepdown"), &mut p.stepdown);
+                visit(format!("{label}.stockToLeave"), &mut p.stock_to_leave);
+                visit_lead(&mut visit, &label, &mut p.lead_in);
+            }
+            OperationParams::Drill(p) => {
+                visit(format!("{label}.depth"), &mut p.depth);
+                if let Some(peck) = &mut p.peck_depth {
+                    visit(format!("{label}.peckDepth"), peck);
+                }
+            }
+        }
+    }
+}
+
+/// Visit the x, y and z components of `v` as `<prefix>.x` etc.
+fn visit_vec3(visit: &mut dyn FnMut(String, &mut f64), prefix: &str, v: &mut Vec3) {
+    visit(format!("{prefix}.x"), &mut v.x);
+    visit(format!("{prefix}.y"), &mut v.y);
+    visit(format!("{prefix}.z"), &mut v.z);
+}
+
+/// Visit a lead-in's length or radius, if it has one.
+fn visit_lead(visit: &mut dyn FnMut(String, &mut f64), label: &str, lead: &mut LeadType) {
+    match lead {
+        LeadType::None => {}
+        LeadType::Line { length } => visit(format!("{label}.leadIn.length"), length),
+        LeadType::Arc { radius } => visit(format!("{label}.leadIn.radius"), radius),
+    }
+}
+
+/// A copy of just the fields of `project` that [`visit_lengths`] walks.
+///
+/// Cached toolpaths can be large and carry no convertible settings, so they
+/// are left out.
+fn length_fields(project: &Project) -> Project {
+    Project {
+        units: project.units.clone(),
+        stock: project.stock.clone(),
+        wcs: project.wcs.clone(),
+        tools: project.tools.clone(),
+        operations: project.operations.clone(),
+        ..Default::default()
+    }
+}
+
+// ── preview_unit_conversion ───────────────────────────────────────────────────
+
+/// One value that a unit conversion would change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionRow {
+    /// Human-readable path to the value (e.g. `"stock.width"`,
+    /// `"tool 'Flat 10'.diameter"`).
+    pub field: String,
+    /// Value in the project's current units.
+    pub before: f64,
+    /// Value after conversion to the target units.
+    pub after: f64,
+}
+
+/// Testable inner logic for [`preview_unit_conversion`].
+///
+/// Lists stock dimensions and origin, WCS origins, tool diameters and default
+/// feed rates, and operation depths / stepdowns / allowances / lead-ins / tab
+/// sizes / peck depths alongside their values in `to_units`. Acquires only a
+/// read lock; the project is not modified.
+pub(crate) fn preview_unit_conversion_inner(
+    to_units: &str,
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<ConversionRow>, AppError> {
+    let mut lengths = length_fields(&*read_project(project_lock)?);
+    let factor = conversion_factor(&lengths.units, to_units)?;
+
+    let mut rows = Vec::new();
+    visit_lengths(&mut lengths, |field, value| {
+        rows.push(ConversionRow {
+            field,
+            before: *value,
+            after: *value * factor,
+        });
+    });
+    Ok(rows)
+}
+
+// ── convert_project_units ─────────────────────────────────────────────────────
+
+/// Testable inner logic for [`convert_project_units`].
+///
+/// Scales every value listed by [`preview_unit_conversion_inner`] into
+/// `to_units`, then sets `Project.units`. Cached toolpaths were generated in
+/// the old units and are discarded. Converting to the current units is a
+/// no-op.
+pub(crate) fn convert_project_units_inner(
+    to_units: &str,
+    project_lock: &RwLock<Project>,
+) -> Result<ProjectSnapshot, AppError> {
+    let mut project = write_project(project_lock)?;
+    let factor = conversion_factor(&project.units, to_units)?;
+    if project.units == to_units {
+        return Ok(ProjectSnapshot::from(&*project));
+    }
+
+    visit_lengths(&mut project, |_, value| *value *= factor);
+
+    project.toolpaths.clear();
+    project.units = to_units.to_string();
+    Ok(ProjectSnapshot::from(&*project))
+}
+
+// ── Tauri command wrappers ────────────────────────────────────────────────────
+
+/// Preview the values a unit conversion to `to_units` would produce.
+///
+/// Read-only: nothing in the project changes.
+#[tauri::command]
+pub async fn preview_unit_conversion(
+    to_units: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ConversionRow>, AppError> {
+    preview_unit_conversion_inner(&to_units, &state.project)
+}
+
+/// Convert every length and linear feed rate in the project to `to_units`.
+///
+/// Acquires a write lock; spindle speeds and percentages are left unchanged.
+#[tauri::command]
+pub async fn convert_project_units(
+    to_units: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ProjectSnapshot, AppError> {
+    convert_project_units_inner(&to_units, &state.project)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::{
+        CompensationSide, CoolantMode, FeedMode, Operation, PocketParams, ProfileParams, TabParams,
+    };
+    use crate::models::stock::BoxDimensions;
+    use crate::models::{Tool, ToolType, WorkCoordinateSystem};
+    use uuid::Uuid;
+
+    fn state_with_tool_and_stock() -> AppState {
+        let state = AppState::default();
+        {
+            let mut p = state.project.write().expect("write lock");
+            p.tools.push(Tool {
+                id: Uuid::new_v4(),
+                name: "Flat 10".to_string(),
+                tool_type: ToolType::FlatEndmill,
+                material: "carbide".to_string(),
+                diameter: 10.0,
+                flute_count: 4,
+                default_spindle_speed: Some(12000),
+                default_feed_rate: None,
+                tip_angle: None,
+            });
+            p.stock = Some(StockDefinition::Box(BoxDimensions {
+                origin: Vec3::zero(),
+                width: 100.0,
+                depth: 50.0,
+                height: 20.0,
+            }));
+        }
+        state
+    }
+
+    /// Adds a tabbed profile with a line lead-in and a pocket with an arc
+    /// lead-in.
+    fn state_with_operations() -> AppState {
+        let state = state_with_tool_and_stock();
+        {
+            let mut p = state.project.write().expect("write lock");
+            let tool_id = p.tools[0].id;
+            let op = |name: &str, params| Operation {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+                enabled: true,
+                tool_id,
+                params,
+                path_mode: None,
+                coolant: CoolantMode::Flood,
+                feed_mode: FeedMode::PerMinute,
+            };
+            p.operations.push(op(
+                "Outline",
+                OperationParams::Profile(ProfileParams {
+                    depth: 10.0,
+                    stepdown: 2.5,
+                    compensation_side: CompensationSide::Left,
+                    lead_in: LeadType::Line { length: 5.0 },
+                    tabs: Some(TabParams {
+                        count: 4,
+                        width: 6.0,
+                        height: 2.0,
+                    }),
+                    stock_to_leave: 0.0,
+                }),
+            ));
+            p.operations.push(op(
+                "Pocket",
+                OperationParams::Pocket(PocketParams {
+                    depth: 5.0,
+                    stepdown: 1.0,
+                    stepover_percent: 40.0,
+                    lead_in: LeadType::Arc { radius: 2.54 },
+                    stock_to_leave: 0.0,
+                }),
+            ));
+        }
+        state
+    }
+
+    fn row<'a>(rows: &'a [ConversionRow], field: &str) -> &'a ConversionRow {
+        rows.iter()
+            .find(|r| r.field == field)
+            .unwrap_or_else(|| panic!("no row for {field}: {rows:?}"))
+    }
+
+    #[test]
+    fn preview_mm_to_inch_converts_diameter_and_stock() {
+        let state = state_with_tool_and_stock();
+        let rows = preview_unit_conversion_inner("inch", &state.project).expect("preview");
+
+        let diameter = row(&rows, "tool 'Flat 10'.diameter");
+        assert_eq!(diameter.before, 10.0);
+        assert!((diameter.after - 0.3937).abs() < 1e-4, "{diameter:?}");
+
+        let width = row(&rows, "stock.width");
+        assert!((width.after - 3.937).abs() < 1e-3, "{width:?}");
+    }
+
+    #[test]
+    fn preview_lists_lead_in_length_and_radius() {
+        let state = state_with_operations();
+        let rows = preview_unit_conversion_inner("inch", &state.project).expect("preview");
+
+        let length = row(&rows, "operation 'Outline'.leadIn.length");
+        assert!((length.after - 5.0 / 25.4).abs() < 1e-9, "{length:?}");
+        let radius = row(&rows, "operation 'Pocket'.leadIn.radius");
+        assert!((radius.after - 0.1).abs() < 1e-9, "{radius:?}");
+    }
+
+    #[test]
+    fn preview_lists_tab_width_and_height() {
+        let state = state_with_operations();
+        let rows = preview_unit_conversion_inner("inch", &state.project).expect("preview");
+
+        let width = row(&rows, "operation 'Outline'.tabs.width");
+        assert!((width.after - 6.0 / 25.4).abs() < 1e-9, "{width:?}");
+        let height = row(&rows, "operation 'Outline'.tabs.height");
+        assert!((height.after - 2.0 / 25.4).abs() < 1e-9, "{height:?}");
+    }
+
+    #[test]
+    fn preview_lists_stock_origin_and_wcs_origins() {
+        let state = state_with_tool_and_stock();
+        {
+            let mut p = state.project.write().expect("write lock");
+            if let Some(StockDefinition::Box(b)) = &mut p.stock {
+                b.origin = Vec3 {
+                    x: -50.8,
+                    y: 0.0,
+                    z: 0.0,
+                };
+            }
+            p.wcs.push(WorkCoordinateSystem {
+                id: Uuid::new_v4(),
+                name: "G54".to_string(),
+                origin: Vec3 {
+                    x: 0.0,
+                    y: 25.4,
+                    z: 12.7,
+                },
+                x_axis: Vec3 {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                z_axis: Vec3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 1.0,
+                },
+            });
+        }
+        let rows = preview_unit_conversion_inner("inch", &state.project).expect("preview");
+
+        assert!((row(&rows, "stock.origin.x").after + 2.0).abs() < 1e-9);
+        assert!((row(&rows, "wcs 'G54'.origin.y").after - 1.0).abs() < 1e-9);
+        assert!((row(&rows, "wcs 'G54'.origin.z").after - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn preview_rows_match_converted_project() {
+        let state = state_with_operations();
+        state
+            .project
+            .write()
+            .expect("write lock")
+            .wcs
+            .push(WorkCoordinateSystem {
+                id: Uuid::new_v4(),
+                name: "G54".to_string(),
+                origin: Vec3 {
+                    x: 10.0,
+                    y: 20.0,
+                    z: 30.0,
+                },
+                x_axis: Vec3 {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                z_axis: Vec3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 1.0,
+                },
+            });
+        let rows = preview_unit_conversion_inner("inch", &state.project).expect("preview");
+        convert_project_units_inner("inch", &state.project).expect("convert");
+
+        let mut converted = length_fields(&state.project.read().expect("read lock"));
+        let mut values = Vec::new();
+        visit_lengths(&mut converted, |field, value| values.push((field, *value)));
+
+        assert_eq!(rows.len(), values.len());
+        for (row, (field, value)) in rows.iter().zip(&values) {
+            assert_eq!(&row.field, field);
+            assert!((row.after - value).abs() < 1e-9, "{row:?} vs {value}");
+        }
+    }
+
+    #[test]
+    fn preview_does_not_mutate_project() {
+        let state = state_with_tool_and_stock();
+        preview_unit_conversion_inner("inch", &state.project).expect("preview");
+        let p = state.project.read().expect("read lock");
+        assert_eq!(p.units, "mm");
+        assert_eq!(p.tools[0].diameter, 10.0);
+    }
+
+    #[test]
+    fn preview_unknown_units_is_rejected() {
+        let state = AppState::default();
+        let result = preview_unit_conversion_inner("furlong", &state.project);
+        assert!(matches!(result, Err(AppError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn convert_mm_to_inch_scales_tool_diameter_and_sets_units() {
+        let state = state_with_tool_and_stock();
+        let snap = convert_project_units_inner("inch", &state.project).expect("convert");
+        assert_eq!(snap.units, "inch");
+
+        let p = state.project.read().expect("read lock");
+        assert_eq!(p.units, "inch");
+        assert!((p.tools[0].diameter - 0.3937).abs() < 1e-4);
+        assert_eq!(p.tools[0].default_spindle_speed, Some(12000));
+        match &p.stock {
+            Some(StockDefinition::Box(b)) => assert!((b.width - 3.937).abs() < 1e-3),
+            other => panic!("expected box stock, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn convert_round_trip_restores_values() {
+        let state = state_with_operations();
+        convert_project_units_inner("inch", &state.project).expect("to inch");
+        {
+            let p = state.project.read().expect("read lock");
+            match &p.operations[0].params {
+                OperationParams::Profile(profile) => {
+                    let tabs = profile.tabs.as_ref().expect("tabs");
+                    assert!((tabs.width - 6.0 / 25.4).abs() < 1e-9);
+                    assert!((tabs.height - 2.0 / 25.4).abs() < 1e-9);
+                }
+                other => panic!("expected profile, got {other:?}"),
+            }
+        }
+        convert_project_units_inner("mm", &state.project).expect("back to mm");
+        let p = state.project.read().expect("read lock");
+        assert_eq!(p.units, "mm");
+        assert!((p.tools[0].diameter - 10.0).abs() < 1e-9);
+        match &p.operations[0].params {
+            OperationParams::Profile(profile) => {
+                let tabs = profile.tabs.as_ref().expect("tabs");
+                assert_eq!(tabs.count, 4);
+                assert!((tabs.width - 6.0).abs() < 1e-9);
+                assert!((tabs.height - 2.0).abs() < 1e-9);
+            }
+            other => panic!("expected profile, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn convert_scales_lead_ins() {
+        let state = state_with_operations();
+        convert_project_units_inner("inch", &state.project).expect("to inch");
+        {
+            let p = state.project.read().expect("read lock");
+            match &p.operations[1].params {
+                OperationParams::Pocket(pocket) => match pocket.lead_in {
+                    LeadType::Arc { radius } => assert!((radius - 0.1).abs() < 1e-9),
+                    ref other => panic!("expected arc lead-in, got {other:?}"),
+                },
+                other => panic!("expected pocket, got {other:?}"),
+            }
+        }
+
+        convert_project_units_inner("mm", &state.project).expect("back to mm");
+        let p = state.project.read().expect("read lock");
+        match &p.operations[0].params {
+            OperationParams::Profile(profile) => match profile.lead_in {
+                LeadType::Line { length } => assert!((length - 5.0).abs() < 1e-9),
+                ref other => panic!("expected line lead-in, got {other:?}"),
+            },
+            other => panic!("expected profile, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn convert_to_current_units_changes_nothing() {
+        let state = state_with_tool_and_stock();
+        convert_project_units_inner("mm", &state.project).expect("convert");
+        let p = state.project.read().expect("read lock");
+        assert_eq!(p.tools[0].diameter, 10.0);
+    }
+
+    #[test]
+    fn convert_unknown_units_is_rejected() {
+        let state = state_with_tool_and_stock();
+        let result = convert_project_units_inner("furlong", &state.project);
+        assert!(matches!(result, Err(AppError::UnsupportedFormat(_))));
+        assert_eq!(state.project.read().expect("read lock").units, "mm");
+    }
+}
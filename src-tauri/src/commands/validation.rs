@@ -0,0 +1,594 @@
+//! Project validation IPC command handlers.
+//!
+//! Checks report problems as [`ValidationIssue`]s instead of failing, so the
+//! UI can list everything wrong with a project at once. [`check_export_readiness`]
+//! runs every check that matters before G-code is exported;
+//! [`validate_project`] adds project-wide integrity checks on top.
+//! [`validate_profile`] reports the shape of one profile's contours.
+//!
+//! All handlers follow the `_inner` + `#[tauri::command]` wrapper pattern.
+
+use std::sync::RwLock;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::operation::OperationParams;
+use crate::models::tool::ToolType;
+use crate::models::Operation;
+use crate::models::Vec3;
+use crate::state::{AppState, Project};
+use crate::toolpath::geom::{is_closed, self_intersects};
+use crate::toolpath::types::PassKind;
+
+use super::stock::mesh_extents;
+use super::{parse_entity_id, read_project};
+
+/// Distance (model units) a part may poke out of the stock before
+/// [`validate_project`] reports it, absorbing tessellation round-off.
+const STOCK_FIT_TOLERANCE: f64 = 1e-3;
+
+/// Gap (model units) between a contour's first and last points that
+/// [`validate_profile`] still treats as closed.
+const CLOSED_TOLERANCE: f64 = 1e-3;
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// Likely a mistake, but export can proceed.
+    Warning,
+    /// Export cannot produce a usable program.
+    Error,
+}
+
+/// One problem found by a validation check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub severity: IssueSeverity,
+    /// Stable snake_case identifier of the check, e.g. `"missing_tool"`.
+    pub code: String,
+    pub message: String,
+    /// The operation or tool the issue belongs to, if any.
+    pub entity_id: Option<Uuid>,
+}
+
+impl ValidationIssue {
+    fn warning(code: &str, entity_id: Option<Uuid>, message: String) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            code: code.to_string(),
+            message,
+            entity_id,
+        }
+    }
+
+    fn error(code: &str, entity_id: Option<Uuid>, message: String) -> Self {
+        Self {
+            severity: IssueSeverity::Error,
+            code: code.to_string(),
+            message,
+            entity_id,
+        }
+    }
+}
+
+// ── validate_operation_tool_types ─────────────────────────────────────────────
+
+/// Returns `true` if `tool_type` is a sensible choice for `params`: drill
+/// operations want a drill or center drill, profiles and pockets an endmill
+/// or slot drill. Profiles may also use a chamfer mill to break edges.
+fn tool_suits_operation(tool_type: &ToolType, params: &OperationParams) -> bool {
+    let endmill = matches!(
+        tool_type,
+        ToolType::FlatEndmill | ToolType::BallNose | ToolType::BullNose | ToolType::SlotDrill
+    );
+    match params {
+        OperationParams::Drill(_) => matches!(tool_type, ToolType::Drill | ToolType::CenterDrill),
+        OperationParams::Profile(_) => endmill || *tool_type == ToolType::ChamferMill,
+        OperationParams::Pocket(_) => endmill,
+    }
+}
+
+fn operation_type_name(params: &OperationParams) -> &'static str {
+    match params {
+        OperationParams::Profile(_) => "profile",
+        OperationParams::Pocket(_) => "pocket",
+        OperationParams::Drill(_) => "drill",
+    }
+}
+
+fn tool_type_issues(project: &Project) -> Vec<ValidationIssue> {
+    project
+        .operations
+        .iter()
+        .filter(|op| op.enabled)
+        .filter_map(|op| {
+            let tool = project.tools.iter().find(|t| t.id == op.tool_id)?;
+            (!tool_suits_operation(&tool.tool_type, &op.params)).then(|| {
+                ValidationIssue::warning(
+                    "tool_type_mismatch",
+                    Some(op.id),
+                    format!(
+                        "{} operation '{}' uses {:?} tool '{}'",
+                        operation_type_name(&op.params),
+                        op.name,
+                        tool.tool_type,
+                        tool.name
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Testable inner logic for [`validate_operation_tool_types`].
+///
+/// Warns for every enabled operation whose tool type does not suit the
+/// operation type. Operations whose tool is missing are left to
+/// [`check_export_readiness_inner`].
+pub(crate) fn validate_operation_tool_types_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<ValidationIssue>, AppError> {
+    let project = read_project(project_lock)?;
+    Ok(tool_type_issues(&project))
+}
+
+// ── check_export_readiness ────────────────────────────────────────────────────
+
+/// Testable inner logic for [`check_export_readiness`].
+///
+/// For every enabled operation, reports an error if its tool is missing from
+/// the library or it has no cached toolpath, then appends the tool-type
+/// warnings. An empty list means the project is ready to export.
+pub(crate) fn check_export_readiness_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<ValidationIssue>, AppError> {
+    let project = read_project(project_lock)?;
+
+    let mut issues = Vec::new();
+    for op in project.operations.iter().filter(|op| op.enabled) {
+        if !project.tools.iter().any(|t| t.id == op.tool_id) {
+            issues.push(missing_tool(op));
+        }
+        if !project.toolpaths.contains_key(&op.id) {
+            issues.push(missing_toolpath(op));
+        }
+    }
+    issues.extend(tool_type_issues(&project));
+    Ok(issues)
+}
+
+fn missing_tool(op: &Operation) -> ValidationIssue {
+    ValidationIssue::error(
+        "missing_tool",
+        Some(op.id),
+        format!("operation '{}' references a missing tool", op.name),
+    )
+}
+
+fn missing_toolpath(op: &Operation) -> ValidationIssue {
+    ValidationIssue::error(
+        "missing_toolpath",
+        Some(op.id),
+        format!("operation '{}' has no toolpath", op.name),
+    )
+}
+
+// ── validate_project ──────────────────────────────────────────────────────────
+
+/// Warns when a corner of the loaded part's bounding box lies outside the
+/// stock. Nothing is reported without both a stock and a non-empty mesh.
+fn stock_fit_issue(project: &Project) -> Option<ValidationIssue> {
+    let stock = project.stock.as_ref()?;
+    let (xmin, ymin, zmin, xmax, ymax, zmax) =
+        mesh_extents(&project.source_model.as_ref()?.mesh_data)?;
+    let fits = [xmin, xmax].iter().all(|&x| {
+        [ymin, ymax].iter().all(|&y| {
+            [zmin, zmax]
+                .iter()
+                .all(|&z| stock.contains(&Vec3 { x, y, z }, STOCK_FIT_TOLERANCE))
+        })
+    });
+    (!fits).then(|| {
+        ValidationIssue::warning(
+            "stock_too_small",
+            None,
+            "the stock does not enclose the part's bounding box".to_string(),
+        )
+    })
+}
+
+/// Warns for every tool whose name an earlier tool in the library already
+/// uses.
+fn duplicate_tool_name_issues(project: &Project) -> Vec<ValidationIssue> {
+    project
+        .tools
+        .iter()
+        .enumerate()
+        .filter(|(i, tool)| project.tools[..*i].iter().any(|t| t.name == tool.name))
+        .map(|(_, tool)| {
+            ValidationIssue::warning(
+                "duplicate_tool_name",
+                Some(tool.id),
+                format!("tool name '{}' is used by more than one tool", tool.name),
+            )
+        })
+        .collect()
+}
+
+/// Testable inner logic for [`validate_project`].
+///
+/// Collects every integrity issue under a single read lock:
+/// - operations referencing a missing tool (error, including disabled ones);
+/// - enabled operations with a tool but no cached toolpath (error);
+/// - tool types unsuited to their operation (warning);
+/// - stock that does not enclose the part's bounding box (warning);
+/// - tool names shared by several tools (warning).
+///
+/// A missing toolpath is not reported for an operation whose tool is
+/// missing, since none can be generated until the tool is fixed.
+pub(crate) fn validate_project_inner(
+    project_lock: &RwLock<Project>,
+) -> Result<Vec<ValidationIssue>, AppError> {
+    let project = read_project(project_lock)?;
+
+    let mut issues = Vec::new();
+    for op in &project.operations {
+        if !project.tools.iter().any(|t| t.id == op.tool_id) {
+            issues.push(missing_tool(op));
+        } else if op.enabled && !project.toolpaths.contains_key(&op.id) {
+            issues.push(missing_toolpath(op));
+        }
+    }
+    issues.extend(tool_type_issues(&project));
+    issues.extend(stock_fit_issue(&project));
+    issues.extend(duplicate_tool_name_issues(&project));
+    Ok(issues)
+}
+
+// ── validate_profile ──────────────────────────────────────────────────────────
+
+/// Shape of a profile operation's cutting contours, returned by
+/// [`validate_profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileCheck {
+    /// Every cutting pass ends where it started.
+    pub closed: bool,
+    /// Some cutting pass crosses or touches itself.
+    pub self_intersecting: bool,
+}
+
+/// Testable inner logic for [`validate_profile`].
+///
+/// Checks the cutting passes of the operation's cached toolpath. Returns
+/// [`AppError::NotFound`] for an unknown operation or one without a
+/// toolpath, and [`AppError::InvalidInput`] for an operation that is not a
+/// profile. A toolpath without cutting passes is reported as not closed.
+pub(crate) fn validate_profile_inner(
+    operation_id: &str,
+    project_lock: &RwLock<Project>,
+) -> Result<ProfileCheck, AppError> {
+    let op_uuid = parse_entity_id(operation_id, "operation")?;
+    let project = read_project(project_lock)?;
+    let op = project
+        .operations
+        .iter()
+        .find(|op| op.id == op_uuid)
+        .ok_or_else(|| AppError::NotFound(format!("operation {op_uuid} not found")))?;
+    if !matches!(op.params, OperationParams::Profile(_)) {
+        return Err(AppError::InvalidInput(format!(
+            "operation '{}' is not a profile",
+            op.name
+        )));
+    }
+    let toolpath = project
+        .toolpaths
+        .get(&op_uuid)
+        .ok_or_else(|| AppError::NotFound(format!("no toolpath for operation {op_uuid}")))?;
+
+    let mut contours = toolpath
+        .passes
+        .iter()
+        .filter(|pass| pass.kind == PassKind::Cutting)
+        .peekable();
+    let any = contours.peek().is_some();
+    let mut check = ProfileCheck {
+        closed: any,
+        self_intersecting: false,
+    };
+    for pass in contours {
+        check.closed &= is_closed(pass, CLOSED_TOLERANCE);
+        check.self_intersecting |= self_intersects(pass);
+    }
+    Ok(check)
+}
+
+// ── Tauri command wrappers ────────────────────────────────────────────────────
+
+/// Warn about enabled operations whose tool type does not suit the operation.
+#[tauri::command]
+pub async fn validate_operation_tool_types(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ValidationIssue>, AppError> {
+    validate_operation_tool_types_inner(&state.project)
+}
+
+/// Run every pre-export check; an empty list means the project can be exported.
+#[tauri::command]
+pub async fn check_export_readiness(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ValidationIssue>, AppError> {
+    check_export_readiness_inner(&state.project)
+}
+
+/// Report every integrity problem in the project; an empty list means none.
+#[tauri::command]
+pub async fn validate_project(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ValidationIssue>, AppError> {
+    validate_project_inner(&state.project)
+}
+
+/// Report whether a profile operation's contours are closed and simple, so
+/// the UI can warn before offsetting them.
+#[tauri::command]
+pub async fn validate_profile(
+    operation_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ProfileCheck, AppError> {
+    validate_profile_inner(&operation_id, &state.project)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::MeshData;
+    use crate::models::operation::{
+        CompensationSide, CoolantMode, FeedMode, LeadType, PocketParams, ProfileParams,
+    };
+    use crate::models::stock::BoxDimensions;
+    use crate::models::{StockDefinition, Tool};
+    use crate::state::LoadedModel;
+    use crate::toolpath::types::{CutPoint, MoveKind, Pass};
+    use crate::toolpath::Toolpath;
+
+    fn add_pocket_with_tool(state: &AppState, tool_type: ToolType) -> Uuid {
+        let tool_id = Uuid::new_v4();
+        let op_id = Uuid::new_v4();
+        let mut project = state.project.write().expect("write lock");
+        project.tools.push(Tool {
+            id: tool_id,
+            name: "6mm Tool".to_string(),
+            tool_type,
+            material: "hss".to_string(),
+            diameter: 6.0,
+            flute_count: 2,
+            default_spindle_speed: None,
+            default_feed_rate: None,
+            tip_angle: None,
+        });
+        project.operations.push(Operation {
+            id: op_id,
+            name: "Pocket".to_string(),
+            enabled: true,
+            tool_id,
+            params: OperationParams::Pocket(PocketParams {
+                depth: 5.0,
+                stepdown: 1.0,
+                stepover_percent: 40.0,
+                lead_in: LeadType::None,
+                stock_to_leave: 0.0,
+            }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
+        });
+        op_id
+    }
+
+    #[test]
+    fn pocket_with_drill_tool_warns() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_tool(&state, ToolType::Drill);
+
+        let issues = validate_operation_tool_types_inner(&state.project).expect("should succeed");
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].severity, IssueSeverity::Warning);
+        assert_eq!(issues[0].entity_id, Some(op_id));
+        assert_eq!(issues[0].code, "tool_type_mismatch");
+    }
+
+    #[test]
+    fn pocket_with_endmill_has_no_tool_type_issue() {
+        let state = AppState::default();
+        add_pocket_with_tool(&state, ToolType::FlatEndmill);
+
+        let issues = validate_operation_tool_types_inner(&state.project).expect("should succeed");
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn pocket_with_slot_drill_has_no_tool_type_issue() {
+        let state = AppState::default();
+        add_pocket_with_tool(&state, ToolType::SlotDrill);
+
+        let issues = validate_operation_tool_types_inner(&state.project).expect("should succeed");
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn export_readiness_includes_tool_type_warning_and_missing_toolpath() {
+        let state = AppState::default();
+        add_pocket_with_tool(&state, ToolType::Drill);
+
+        let issues = check_export_readiness_inner(&state.project).expect("should succeed");
+        assert!(issues
+            .iter()
+            .any(|i| i.severity == IssueSeverity::Error && i.message.contains("no toolpath")));
+        assert!(issues.iter().any(|i| i.severity == IssueSeverity::Warning));
+    }
+
+    // ── validate_project ──────────────────────────────────────────────────────
+
+    #[test]
+    fn dangling_tool_id_yields_exactly_one_error() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_tool(&state, ToolType::FlatEndmill);
+        state.project.write().expect("write lock").tools.clear();
+
+        let issues = validate_project_inner(&state.project).expect("should succeed");
+        let errors: Vec<_> = issues
+            .iter()
+            .filter(|i| i.severity == IssueSeverity::Error)
+            .collect();
+        assert_eq!(errors.len(), 1, "{issues:?}");
+        assert_eq!(errors[0].code, "missing_tool");
+        assert_eq!(errors[0].entity_id, Some(op_id));
+    }
+
+    #[test]
+    fn validate_project_reports_missing_toolpath_and_duplicate_tool_names() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_tool(&state, ToolType::FlatEndmill);
+        add_pocket_with_tool(&state, ToolType::FlatEndmill);
+
+        let issues = validate_project_inner(&state.project).expect("should succeed");
+        let codes: Vec<_> = issues.iter().map(|i| i.code.as_str()).collect();
+        assert_eq!(
+            codes,
+            [
+                "missing_toolpath",
+                "missing_toolpath",
+                "duplicate_tool_name"
+            ]
+        );
+        assert_eq!(issues[0].entity_id, Some(op_id));
+    }
+
+    #[test]
+    fn validate_project_warns_when_stock_is_smaller_than_part() {
+        let state = AppState::default();
+        {
+            let mut project = state.project.write().expect("write lock");
+            project.source_model = Some(LoadedModel {
+                path: "part.stl".into(),
+                original_path: None,
+                checksum: String::new(),
+                mesh_data: MeshData {
+                    vertices: vec![0.0, 0.0, 0.0, 50.0, 20.0, 10.0],
+                    normals: vec![0.0; 6],
+                    indices: vec![],
+                },
+            });
+            project.stock = Some(StockDefinition::Box(BoxDimensions {
+                origin: Vec3::zero(),
+                width: 40.0,
+                depth: 20.0,
+                height: 10.0,
+            }));
+        }
+
+        let issues = validate_project_inner(&state.project).expect("should succeed");
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].code, "stock_too_small");
+        assert_eq!(issues[0].severity, IssueSeverity::Warning);
+
+        if let Some(StockDefinition::Box(b)) = state.project.write().unwrap().stock.as_mut() {
+            b.width = 50.0;
+        }
+        let issues = validate_project_inner(&state.project).expect("should succeed");
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    /// Adds a profile operation whose cached toolpath cuts one pass through
+    /// `points` at Z −1.
+    fn add_profile_with_contour(state: &AppState, points: &[(f64, f64)]) -> Uuid {
+        let op_id = add_pocket_with_tool(state, ToolType::FlatEndmill);
+        let mut project = state.project.write().expect("write lock");
+        project.operations[0].params = OperationParams::Profile(ProfileParams {
+            depth: 1.0,
+            stepdown: 1.0,
+            compensation_side: CompensationSide::Left,
+            lead_in: LeadType::None,
+            tabs: None,
+            stock_to_leave: 0.0,
+        });
+        let cuts = points
+            .iter()
+            .map(|&(x, y)| CutPoint {
+                position: Vec3 { x, y, z: -1.0 },
+                move_kind: MoveKind::Feed,
+                tool_orientation: None,
+            })
+            .collect();
+        project.toolpaths.insert(
+            op_id,
+            Toolpath {
+                operation_id: op_id,
+                tool_number: 1,
+                spindle_speed: 10000.0,
+                feed_rate: 500.0,
+                passes: vec![Pass {
+                    kind: PassKind::Cutting,
+                    cuts,
+                    feed_rate: None,
+                }],
+                path_mode: None,
+                drill: None,
+                coolant: None,
+                feed_mode: None,
+                stock_to_leave: None,
+            },
+        );
+        op_id
+    }
+
+    #[test]
+    fn validate_profile_flags_figure_eight() {
+        let state = AppState::default();
+        let op_id = add_profile_with_contour(
+            &state,
+            &[
+                (0.0, 0.0),
+                (10.0, 10.0),
+                (10.0, 0.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ],
+        );
+
+        let check = validate_profile_inner(&op_id.to_string(), &state.project).expect("check");
+        assert_eq!(
+            check,
+            ProfileCheck {
+                closed: true,
+                self_intersecting: true,
+            }
+        );
+    }
+
+    #[test]
+    fn validate_profile_reports_open_contour() {
+        let state = AppState::default();
+        let op_id = add_profile_with_contour(&state, &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+        let check = validate_profile_inner(&op_id.to_string(), &state.project).expect("check");
+        assert!(!check.closed);
+        assert!(!check.self_intersecting);
+    }
+
+    #[test]
+    fn validate_profile_rejects_non_profile_operation() {
+        let state = AppState::default();
+        let op_id = add_pocket_with_tool(&state, ToolType::FlatEndmill);
+
+        let err = validate_profile_inner(&op_id.to_string(), &state.project).unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)), "{err:?}");
+    }
+}
@@ -4,6 +4,7 @@
 //! TypeScript frontend can pattern-match on a stable `kind` string.
 
 use crate::geometry::GeometryError;
+use crate::postprocessor::PostProcessorError;
 
 /// Top-level error returned by Tauri command handlers.
 ///
@@ -49,15 +50,50 @@ pub enum AppError {
     /// A post-processor error; the inner message describes the failure.
     #[error("{0}")]
     PostProcessor(String),
+
+    /// A user-supplied path is malformed (e.g. illegal filename characters).
+    #[error("{0}")]
+    InvalidPath(String),
+
+    /// The request conflicts with other project data (e.g. deleting a tool
+    /// that operations still use, or a reorder list that does not match the
+    /// project's operations).
+    #[error("{0}")]
+    Conflict(String),
+
+    /// A user-supplied value is invalid (e.g. a degenerate WCS axis).
+    #[error("{0}")]
+    InvalidInput(String),
+
+    /// The referenced model file no longer matches the checksum stored in the
+    /// project, so toolpaths computed from it may be wrong.
+    #[error("{0}")]
+    ModelChanged(String),
 }
 
 impl From<GeometryError> for AppError {
-    /// Convert a [`GeometryError`] into an [`AppError::GeometryImport`].
+    /// Convert a [`GeometryError`] into an [`AppError::GeometryImport`], or an
+    /// [`AppError::Io`] for [`GeometryError::ExportFailed`].
     ///
     /// The geometry error is stringified here so that the enum variant stores
     /// a plain `String`, keeping the serialized shape as `{ kind, message }`.
     fn from(e: GeometryError) -> Self {
-        Self::GeometryImport(e.to_string())
+        match e {
+            GeometryError::ExportFailed { message } => Self::Io(message),
+            e => Self::GeometryImport(e.to_string()),
+        }
+    }
+}
+
+impl From<PostProcessorError> for AppError {
+    /// Convert a [`PostProcessorError`] into an [`AppError::PostProcessor`], or
+    /// an [`AppError::Io`] for [`PostProcessorError::Io`] so a failed write
+    /// reads like any other I/O failure.
+    fn from(e: PostProcessorError) -> Self {
+        match e {
+            PostProcessorError::Io(e) => Self::from(e),
+            e => Self::PostProcessor(e.to_string()),
+        }
     }
 }
 
@@ -96,6 +132,30 @@ mod tests {
         assert_eq!(value["message"], "tessellation failed");
     }
 
+    #[test]
+    fn conflict_error_serializes_to_kind_message() {
+        let err = AppError::Conflict("tool in use".to_string());
+        let value = serde_json::to_value(&err).expect("serialize AppError::Conflict");
+        assert_eq!(value["kind"], "Conflict");
+        assert_eq!(value["message"], "tool in use");
+    }
+
+    #[test]
+    fn invalid_input_error_serializes_to_kind_message() {
+        let err = AppError::InvalidInput("zero-length axis".to_string());
+        let value = serde_json::to_value(&err).expect("serialize AppError::InvalidInput");
+        assert_eq!(value["kind"], "InvalidInput");
+        assert_eq!(value["message"], "zero-length axis");
+    }
+
+    #[test]
+    fn model_changed_error_serializes_to_kind_message() {
+        let err = AppError::ModelChanged("part.step changed".to_string());
+        let value = serde_json::to_value(&err).expect("serialize AppError::ModelChanged");
+        assert_eq!(value["kind"], "ModelChanged");
+        assert_eq!(value["message"], "part.step changed");
+    }
+
     #[test]
     fn file_not_found_serializes_with_kind() {
         let err = AppError::FileNotFound;
@@ -122,6 +182,15 @@ mod tests {
         assert_eq!(value["kind"], "GeometryImport");
     }
 
+    #[test]
+    fn from_geometry_export_failed_produces_io_variant() {
+        let geo_err = GeometryError::ExportFailed {
+            message: "disk full".to_string(),
+        };
+        let app_err = AppError::from(geo_err);
+        assert!(matches!(app_err, AppError::Io(ref m) if m == "disk full"));
+    }
+
     #[test]
     fn from_io_error_produces_io_variant() {
         let io_err = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
@@ -147,6 +216,33 @@ mod tests {
         assert_eq!(value["message"], "invalid config");
     }
 
+    #[test]
+    fn from_post_processor_error_produces_post_processor_variant() {
+        let pp_err = PostProcessorError::Config("missing [motion]".to_string());
+        let app_err = AppError::from(pp_err);
+        assert!(
+            matches!(app_err, AppError::PostProcessor(ref m) if m == "config error: missing [motion]")
+        );
+        let value = serde_json::to_value(&app_err).expect("serialize");
+        assert_eq!(value["kind"], "PostProcessor");
+        assert_eq!(value["message"], "config error: missing [motion]");
+    }
+
+    #[test]
+    fn from_post_processor_io_error_produces_io_variant() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::StorageFull, "disk full");
+        let app_err = AppError::from(PostProcessorError::Io(io_err));
+        assert!(matches!(app_err, AppError::Io(ref m) if m == "disk full"));
+    }
+
+    #[test]
+    fn invalid_path_error_serializes_to_kind_message() {
+        let err = AppError::InvalidPath("illegal character".to_string());
+        let value = serde_json::to_value(&err).expect("serialize AppError::InvalidPath");
+        assert_eq!(value["kind"], "InvalidPath");
+        assert_eq!(value["message"], "illegal character");
+    }
+
     #[test]
     fn app_error_display_is_human_readable() {
         assert_eq!(AppError::FileNotFound.to_string(), "file not found");
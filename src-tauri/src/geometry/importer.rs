@@ -6,10 +6,61 @@
 
 use std::path::Path;
 
+use super::mesh_cache::{file_checksum, MeshCache};
 use super::safe::{GeometryError, MeshData, OcctMesh, OcctShape};
 
+/// Chord tolerance as a fraction of the shape's bounding-box diagonal, used
+/// when [`TessellationOptions::chord_tol`] is not given.
+const DEFAULT_CHORD_FRACTION: f64 = 0.001;
+
+/// Chord tolerance (mm) for shapes whose bounding box is degenerate.
+const FALLBACK_CHORD_TOL: f64 = 0.1;
+
+/// Angular tolerance (radians) used when [`TessellationOptions::angle_tol`] is
+/// not given.
+const DEFAULT_ANGLE_TOL: f64 = 0.1;
+
+/// Tessellation tolerances for B-rep imports. Fields left `None` fall back to
+/// defaults; STL files are already meshes and ignore both.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TessellationOptions {
+    /// Maximum chord deviation from the true surface (mm). Defaults to 0.1%
+    /// of the shape's bounding-box diagonal.
+    pub chord_tol: Option<f64>,
+    /// Maximum angular deviation (radians).
+    pub angle_tol: Option<f64>,
+}
+
+/// Chord tolerance derived from a bounding box `(xmin, ymin, zmin, xmax, ymax, zmax)`.
+fn default_chord_tol(bbox: (f64, f64, f64, f64, f64, f64)) -> f64 {
+    let (xmin, ymin, zmin, xmax, ymax, zmax) = bbox;
+    let diagonal = ((xmax - xmin).powi(2) + (ymax - ymin).powi(2) + (zmax - zmin).powi(2)).sqrt();
+    if diagonal.is_finite() && diagonal > 0.0 {
+        diagonal * DEFAULT_CHORD_FRACTION
+    } else {
+        FALLBACK_CHORD_TOL
+    }
+}
+
+fn tessellate(
+    shape: &OcctShape,
+    options: &TessellationOptions,
+    progress: &mut (dyn FnMut(f32) + Send),
+) -> Result<MeshData, GeometryError> {
+    let chord_tol = options
+        .chord_tol
+        .unwrap_or_else(|| default_chord_tol(shape.bounding_box()));
+    let angle_tol = options.angle_tol.unwrap_or(DEFAULT_ANGLE_TOL);
+    Ok(shape
+        .tessellate_with_progress(chord_tol, angle_tol, progress)?
+        .to_mesh_data())
+}
+
 /// Load a 3D file and return a tessellated mesh ready for the frontend.
 ///
+/// B-rep formats are tessellated with the tolerances in `options`.
+///
 /// Supported extensions (case-insensitive):
 ///
 /// | Extension    | Path                                  |
@@ -17,6 +68,17 @@ use super::safe::{GeometryError, MeshData, OcctMesh, OcctShape};
 /// | `.step`/`.stp` | B-rep → tessellate → mesh           |
 /// | `.iges`/`.igs` | B-rep → tessellate → mesh           |
 /// | `.stl`       | Triangle mesh (loaded directly)       |
+/// | `.3mf`       | ZIP + XML mesh, read without OCCT     |
+///
+/// With a `cache`, content whose checksum has an entry tessellated with the
+/// same `options` is read from the cache instead of being imported, and a
+/// fresh import is written back to it. Cache write failures are logged, not
+/// returned.
+///
+/// `progress`, when given, receives the fraction done: 0.0 on entry, rising
+/// values while a B-rep is tessellated, and 1.0 when the import ends —
+/// also on failure, so a progress display can always close. It may be called
+/// from mesher worker threads, but never concurrently.
 ///
 /// # Errors
 ///
@@ -24,7 +86,48 @@ use super::safe::{GeometryError, MeshData, OcctMesh, OcctShape};
 /// - [`GeometryError::UnsupportedFormat`] — extension not recognised.
 /// - [`GeometryError::ImportFailed`] — loader rejected the file.
 /// - [`GeometryError::TessellationFailed`] — B-rep produced no triangles.
-pub fn import(path: &Path) -> Result<MeshData, GeometryError> {
+pub fn import(
+    path: &Path,
+    options: &TessellationOptions,
+    cache: Option<&MeshCache>,
+    progress: Option<&mut (dyn FnMut(f32) + Send)>,
+) -> Result<MeshData, GeometryError> {
+    let mut ignore = |_: f32| {};
+    let progress = progress.unwrap_or(&mut ignore);
+    progress(0.0);
+    let result = match cache {
+        Some(cache) => import_cached(path, options, cache, &mut *progress),
+        None => import_with(path, options, &mut *progress),
+    };
+    progress(1.0);
+    result
+}
+
+fn import_cached(
+    path: &Path,
+    options: &TessellationOptions,
+    cache: &MeshCache,
+    progress: &mut (dyn FnMut(f32) + Send),
+) -> Result<MeshData, GeometryError> {
+    // An unreadable file cannot be cached; the loader reports why.
+    let Ok(checksum) = file_checksum(path) else {
+        return import_with(path, options, progress);
+    };
+    if let Some(mesh) = cache.load(&checksum, options) {
+        return Ok(mesh);
+    }
+    let mesh = import_with(path, options, progress)?;
+    if let Err(e) = cache.store(&checksum, options, &mesh) {
+        tracing::warn!("cannot cache mesh for {}: {e}", path.display());
+    }
+    Ok(mesh)
+}
+
+fn import_with(
+    path: &Path,
+    options: &TessellationOptions,
+    progress: &mut (dyn FnMut(f32) + Send),
+) -> Result<MeshData, GeometryError> {
     let ext = path
         .extension()
         .and_then(|e| e.to_str())
@@ -33,18 +136,17 @@ pub fn import(path: &Path) -> Result<MeshData, GeometryError> {
     match ext.as_deref() {
         Some("step") | Some("stp") => {
             let shape = OcctShape::load_step(path)?;
-            let mesh = shape.tessellate(0.1, 0.1)?;
-            Ok(mesh.to_mesh_data())
+            tessellate(&shape, options, progress)
         }
         Some("iges") | Some("igs") => {
             let shape = OcctShape::load_iges(path)?;
-            let mesh = shape.tessellate(0.1, 0.1)?;
-            Ok(mesh.to_mesh_data())
+            tessellate(&shape, options, progress)
         }
         Some("stl") => {
             let mesh = OcctMesh::load_stl(path)?;
             Ok(mesh.to_mesh_data())
         }
+        Some("3mf") => super::threemf::load_3mf(path),
         Some(ext) => Err(GeometryError::UnsupportedFormat {
             extension: ext.to_string(),
         }),
@@ -63,13 +165,34 @@ mod tests {
 
     #[test]
     fn import_missing_step_file_returns_file_not_found() {
-        let result = import(Path::new("/nonexistent/path/model.step"));
+        let result = import(
+            Path::new("/nonexistent/path/model.step"),
+            &TessellationOptions::default(),
+            None,
+            None,
+        );
         assert!(matches!(result, Err(GeometryError::FileNotFound)));
     }
 
     #[test]
     fn import_missing_stl_file_returns_file_not_found() {
-        let result = import(Path::new("/nonexistent/path/model.stl"));
+        let result = import(
+            Path::new("/nonexistent/path/model.stl"),
+            &TessellationOptions::default(),
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(GeometryError::FileNotFound)));
+    }
+
+    #[test]
+    fn import_missing_3mf_file_returns_file_not_found() {
+        let result = import(
+            Path::new("/nonexistent/path/model.3mf"),
+            &TessellationOptions::default(),
+            None,
+            None,
+        );
         assert!(matches!(result, Err(GeometryError::FileNotFound)));
     }
 
@@ -77,7 +200,12 @@ mod tests {
     fn import_unknown_extension_returns_unsupported_format() {
         // Extension check happens before file-existence check, so path need
         // not exist on disk.
-        let result = import(Path::new("model.obj"));
+        let result = import(
+            Path::new("model.obj"),
+            &TessellationOptions::default(),
+            None,
+            None,
+        );
         assert!(matches!(
             result,
             Err(GeometryError::UnsupportedFormat { .. })
@@ -86,7 +214,12 @@ mod tests {
 
     #[test]
     fn import_no_extension_returns_unsupported_format() {
-        let result = import(Path::new("noextension"));
+        let result = import(
+            Path::new("noextension"),
+            &TessellationOptions::default(),
+            None,
+            None,
+        );
         assert!(matches!(
             result,
             Err(GeometryError::UnsupportedFormat { extension })
@@ -98,15 +231,95 @@ mod tests {
     fn import_uppercase_extension_is_unsupported() {
         // Extensions are lowercased before matching, so .OBJ is still
         // unsupported (not a supported format).
-        let result = import(Path::new("model.OBJ"));
+        let result = import(
+            Path::new("model.OBJ"),
+            &TessellationOptions::default(),
+            None,
+            None,
+        );
         assert!(matches!(
             result,
             Err(GeometryError::UnsupportedFormat { .. })
         ));
     }
 
+    #[test]
+    fn import_reports_start_and_terminal_progress_even_on_failure() {
+        let mut seen = Vec::new();
+        let mut record = |fraction: f32| seen.push(fraction);
+        let result = import(
+            Path::new("/nonexistent/path/model.step"),
+            &TessellationOptions::default(),
+            None,
+            Some(&mut record),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(seen.first(), Some(&0.0));
+        assert_eq!(seen.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn second_import_of_same_content_reads_the_cache_entry() {
+        let dir = std::env::temp_dir().join("jcam_import_cache_hit");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let cache = MeshCache::new(dir.join("meshes"));
+        let options = TessellationOptions::default();
+
+        // Content no loader accepts: only a cache hit can return a mesh.
+        let model = dir.join("model.stl");
+        std::fs::write(&model, b"not a real mesh").unwrap();
+        assert!(import(&model, &options, Some(&cache), None).is_err());
+
+        let cached = MeshData {
+            vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            normals: vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+            indices: vec![0, 1, 2],
+        };
+        let checksum = file_checksum(&model).unwrap();
+        cache.store(&checksum, &options, &cached).unwrap();
+
+        let mut seen = Vec::new();
+        let mut record = |fraction: f32| seen.push(fraction);
+        let mesh = import(&model, &options, Some(&cache), Some(&mut record)).expect("cache hit");
+        assert_eq!(mesh.indices, cached.indices);
+        assert_eq!(mesh.vertices, cached.vertices);
+        assert_eq!(seen.last(), Some(&1.0));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     // ── OCCT integration tests ────────────────────────────────────────────
 
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn import_writes_the_cache_and_reuses_it() {
+        let path = std::path::PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/box.step"
+        ));
+        let dir = std::env::temp_dir().join("jcam_import_cache_write");
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = MeshCache::new(&dir);
+        let options = TessellationOptions::default();
+
+        import(&path, &options, Some(&cache), None).expect("import box.step");
+        let checksum = file_checksum(&path).unwrap();
+        assert!(cache.entry_path(&checksum).exists());
+
+        // Replace the entry: the second import must return it, not a fresh
+        // tessellation of the original file.
+        let marker = MeshData {
+            vertices: vec![7.0, 7.0, 7.0],
+            normals: vec![0.0, 0.0, 1.0],
+            indices: vec![0, 0, 0],
+        };
+        cache.store(&checksum, &options, &marker).unwrap();
+        let mesh = import(&path, &options, Some(&cache), None).expect("cache hit");
+        assert_eq!(mesh.vertices, marker.vertices);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[cfg(cam_geometry_bindings)]
     #[test]
     fn import_step_fixture_returns_nonempty_mesh() {
@@ -114,7 +327,8 @@ mod tests {
             env!("CARGO_MANIFEST_DIR"),
             "/../tests/fixtures/box.step"
         ));
-        let mesh = import(&path).expect("import box.step");
+        let mesh =
+            import(&path, &TessellationOptions::default(), None, None).expect("import box.step");
         assert!(!mesh.vertices.is_empty(), "vertices must not be empty");
         assert_eq!(
             mesh.vertices.len(),
@@ -123,4 +337,62 @@ mod tests {
         );
         assert!(!mesh.indices.is_empty(), "indices must not be empty");
     }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn import_step_fixture_reports_rising_progress() {
+        let path = std::path::PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/box.step"
+        ));
+        let seen = std::sync::Mutex::new(Vec::new());
+        let mut record = |fraction: f32| seen.lock().unwrap().push(fraction);
+        import(
+            &path,
+            &TessellationOptions::default(),
+            None,
+            Some(&mut record),
+        )
+        .expect("import box.step");
+
+        let seen = seen.into_inner().unwrap();
+        assert!(seen.windows(2).all(|w| w[0] <= w[1]), "{seen:?}");
+        assert_eq!(seen.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn default_chord_tol_is_fraction_of_diagonal() {
+        let tol = default_chord_tol((0.0, 0.0, 0.0, 30.0, 40.0, 0.0));
+        assert!((tol - 0.05).abs() < 1e-12, "got {tol}");
+    }
+
+    #[test]
+    fn default_chord_tol_falls_back_for_empty_box() {
+        assert_eq!(
+            default_chord_tol((0.0, 0.0, 0.0, 0.0, 0.0, 0.0)),
+            FALLBACK_CHORD_TOL
+        );
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn tighter_chord_tol_never_yields_fewer_vertices() {
+        let path = std::path::PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/box.step"
+        ));
+        let import_with = |chord_tol: f64| {
+            let options = TessellationOptions {
+                chord_tol: Some(chord_tol),
+                angle_tol: Some(0.5),
+            };
+            import(&path, &options, None, None)
+                .expect("import box.step")
+                .vertices
+                .len()
+        };
+        // box.step is all planar faces, so the counts may be equal; a tighter
+        // tolerance must never coarsen the mesh.
+        assert!(import_with(0.001) >= import_with(1.0));
+    }
 }
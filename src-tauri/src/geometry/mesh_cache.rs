@@ -0,0 +1,265 @@
+//! On-disk cache of tessellated meshes keyed by source-file checksum.
+//!
+//! Tessellating a large STEP model takes seconds, and projects re-open their
+//! model on every load because meshes are not persisted in `.jcam` files.
+//! [`MeshCache`] stores each [`MeshData`] as `<checksum>.bin` so that
+//! re-importing unchanged content skips the mesher.
+//!
+//! Entries use a small little-endian format: an 8-byte magic, the
+//! tessellation tolerances the mesh was built with, then the vertex, normal
+//! and index buffers, each prefixed by its element count as a `u64`. An entry
+//! built with different tolerances, or one that fails to decode, is a miss.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use sha2::Digest;
+
+use super::importer::TessellationOptions;
+use super::safe::MeshData;
+
+/// Leading bytes of every cache entry; bump the last byte on format changes.
+const MAGIC: &[u8; 8] = b"JCMESH\x00\x01";
+
+/// File extension of cache entries.
+const ENTRY_EXTENSION: &str = "bin";
+
+/// Returns the lowercase hex SHA-256 digest of the file at `path`.
+pub fn file_checksum(path: &Path) -> io::Result<String> {
+    let bytes = std::fs::read(path)?;
+    Ok(format!("{:x}", sha2::Sha256::digest(&bytes)))
+}
+
+/// A directory of cached meshes.
+#[derive(Debug, Clone)]
+pub struct MeshCache {
+    dir: PathBuf,
+}
+
+impl MeshCache {
+    /// A cache rooted at `dir`. The directory is created on the first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The per-user cache at `<cache dir>/jamiecam/meshes`, if the platform
+    /// has a cache dir.
+    pub fn user() -> Option<Self> {
+        dirs::cache_dir().map(|d| Self::new(d.join("jamiecam").join("meshes")))
+    }
+
+    /// Directory holding the cache entries.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the entry for content with the given `checksum`.
+    pub fn entry_path(&self, checksum: &str) -> PathBuf {
+        self.dir.join(format!("{checksum}.{ENTRY_EXTENSION}"))
+    }
+
+    /// The cached mesh for `checksum`, if one exists that was tessellated
+    /// with `options`.
+    pub fn load(&self, checksum: &str, options: &TessellationOptions) -> Option<MeshData> {
+        let path = self.entry_path(checksum);
+        let bytes = std::fs::read(&path).ok()?;
+        let (cached_options, mesh) = decode(&bytes).or_else(|| {
+            tracing::warn!("ignoring corrupt mesh cache entry {}", path.display());
+            None
+        })?;
+        same_options(&cached_options, options).then_some(mesh)
+    }
+
+    /// Write `mesh`, tessellated with `options`, as the entry for
+    /// `checksum`, replacing any existing entry.
+    pub fn store(
+        &self,
+        checksum: &str,
+        options: &TessellationOptions,
+        mesh: &MeshData,
+    ) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        // Write beside the entry and rename so a reader never sees a partial
+        // file.
+        let path = self.entry_path(checksum);
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, encode(options, mesh))?;
+        std::fs::rename(&tmp, &path)
+    }
+
+    /// Remove every cache entry and return how many were removed. A missing
+    /// cache directory holds no entries.
+    pub fn clear(&self) -> io::Result<usize> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == ENTRY_EXTENSION) {
+                std::fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+fn same_options(a: &TessellationOptions, b: &TessellationOptions) -> bool {
+    let bits = |v: Option<f64>| v.map(f64::to_bits);
+    bits(a.chord_tol) == bits(b.chord_tol) && bits(a.angle_tol) == bits(b.angle_tol)
+}
+
+fn encode(options: &TessellationOptions, mesh: &MeshData) -> Vec<u8> {
+    let words = mesh.vertices.len() + mesh.normals.len() + mesh.indices.len();
+    let mut out = Vec::with_capacity(MAGIC.len() + 2 * 9 + 3 * 8 + 4 * words);
+    out.extend_from_slice(MAGIC);
+    for tol in [options.chord_tol, options.angle_tol] {
+        out.push(u8::from(tol.is_some()));
+        out.extend_from_slice(&tol.unwrap_or(0.0).to_le_bytes());
+    }
+    for buffer in [&mesh.vertices, &mesh.normals] {
+        out.extend_from_slice(&(buffer.len() as u64).to_le_bytes());
+        buffer
+            .iter()
+            .for_each(|v| out.extend_from_slice(&v.to_le_bytes()));
+    }
+    out.extend_from_slice(&(mesh.indices.len() as u64).to_le_bytes());
+    mesh.indices
+        .iter()
+        .for_each(|i| out.extend_from_slice(&i.to_le_bytes()));
+    out
+}
+
+/// Cursor over an entry being decoded; every read fails past the end.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, rest) = self.bytes.split_first_chunk::<N>()?;
+        self.bytes = rest;
+        Some(*head)
+    }
+
+    fn tolerance(&mut self) -> Option<Option<f64>> {
+        let [present] = self.take::<1>()?;
+        let value = f64::from_le_bytes(self.take()?);
+        match present {
+            0 => Some(None),
+            1 => Some(Some(value)),
+            _ => None,
+        }
+    }
+
+    fn words(&mut self) -> Option<Vec<[u8; 4]>> {
+        let count = usize::try_from(u64::from_le_bytes(self.take()?)).ok()?;
+        // Reject counts the remaining bytes cannot hold before allocating.
+        if count > self.bytes.len() / 4 {
+            return None;
+        }
+        (0..count).map(|_| self.take::<4>()).collect()
+    }
+}
+
+fn decode(bytes: &[u8]) -> Option<(TessellationOptions, MeshData)> {
+    let mut reader = Reader { bytes };
+    if &reader.take::<8>()? != MAGIC {
+        return None;
+    }
+    let options = TessellationOptions {
+        chord_tol: reader.tolerance()?,
+        angle_tol: reader.tolerance()?,
+    };
+    let floats = |words: Vec<[u8; 4]>| words.into_iter().map(f32::from_le_bytes).collect();
+    let mesh = MeshData {
+        vertices: floats(reader.words()?),
+        normals: floats(reader.words()?),
+        indices: reader
+            .words()?
+            .into_iter()
+            .map(u32::from_le_bytes)
+            .collect(),
+    };
+    reader.bytes.is_empty().then_some((options, mesh))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> MeshData {
+        MeshData {
+            vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            normals: vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+            indices: vec![0, 1, 2],
+        }
+    }
+
+    fn temp_cache(name: &str) -> MeshCache {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        MeshCache::new(dir)
+    }
+
+    #[test]
+    fn store_then_load_round_trips_the_mesh() {
+        let cache = temp_cache("jcam_mesh_cache_round_trip");
+        let options = TessellationOptions {
+            chord_tol: Some(0.05),
+            angle_tol: None,
+        };
+        cache.store("abc", &options, &triangle()).unwrap();
+
+        let mesh = cache.load("abc", &options).expect("cache hit");
+        assert!(cache.entry_path("abc").ends_with("abc.bin"));
+        assert_eq!(mesh.vertices, triangle().vertices);
+        assert_eq!(mesh.normals, triangle().normals);
+        assert_eq!(mesh.indices, triangle().indices);
+        std::fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn load_misses_for_unknown_checksum_or_other_tolerances() {
+        let cache = temp_cache("jcam_mesh_cache_miss");
+        let options = TessellationOptions::default();
+        cache.store("abc", &options, &triangle()).unwrap();
+
+        assert!(cache.load("def", &options).is_none());
+        let finer = TessellationOptions {
+            chord_tol: Some(0.01),
+            angle_tol: None,
+        };
+        assert!(cache.load("abc", &finer).is_none());
+        std::fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn load_treats_a_corrupt_entry_as_a_miss() {
+        let cache = temp_cache("jcam_mesh_cache_corrupt");
+        let options = TessellationOptions::default();
+        cache.store("abc", &options, &triangle()).unwrap();
+        let path = cache.entry_path("abc");
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 2]).unwrap();
+
+        assert!(cache.load("abc", &options).is_none());
+        std::fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn clear_removes_entries_and_tolerates_a_missing_dir() {
+        let cache = temp_cache("jcam_mesh_cache_clear");
+        assert_eq!(cache.clear().unwrap(), 0);
+
+        let options = TessellationOptions::default();
+        cache.store("a", &options, &triangle()).unwrap();
+        cache.store("b", &options, &triangle()).unwrap();
+        assert_eq!(cache.clear().unwrap(), 2);
+        assert!(cache.load("a", &options).is_none());
+        std::fs::remove_dir_all(cache.dir()).unwrap();
+    }
+}
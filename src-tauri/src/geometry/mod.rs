@@ -4,9 +4,13 @@
 //!
 //! ```text
 //! geometry/
-//! ├── ffi.rs      — raw bindgen-generated extern "C" declarations (private)
-//! ├── safe.rs     — safe Rust wrappers with RAII and Result<T, E> (public API)
-//! └── importer.rs — high-level import dispatcher (STEP/IGES/STL → MeshData)
+//! ├── ffi.rs        — raw bindgen-generated extern "C" declarations (private)
+//! ├── safe.rs       — safe Rust wrappers with RAII and Result<T, E> (public API)
+//! ├── importer.rs   — high-level import dispatcher (STEP/IGES/STL → MeshData)
+//! ├── mesh_cache.rs — on-disk MeshData cache keyed by file checksum
+//! ├── obb.rs        — PCA oriented bounding boxes over mesh vertices
+//! ├── section.rs    — closed loops from Z-section segments
+//! └── threemf.rs    — pure-Rust 3MF (ZIP + XML) mesh loader
 //! ```
 //!
 //! All `unsafe` code lives in `safe.rs`. Code outside the `geometry` module
@@ -16,10 +20,18 @@
 mod ffi;
 
 pub mod importer;
+pub mod mesh_cache;
+pub mod obb;
 pub mod safe;
-
-pub use importer::import;
-pub use safe::{GeometryError, MeshData, OcctMesh, OcctShape};
+pub mod section;
+pub mod threemf;
+
+pub use importer::{import, TessellationOptions};
+pub use mesh_cache::MeshCache;
+pub use obb::OrientedBoundingBox;
+pub use safe::{
+    BoolOp, FaceInfo, GeometryError, HoleInfo, MeshData, OcctMesh, OcctShape, SurfaceType,
+};
 
 #[cfg(test)]
 #[cfg(cam_geometry_bindings)]
@@ -0,0 +1,199 @@
+//! Oriented bounding boxes from principal component analysis.
+//!
+//! An axis-aligned box over-sizes stock for parts that are skewed in the
+//! model's coordinate frame. [`from_mesh`] aligns the box to the principal
+//! axes of the vertex cloud instead, which for prismatic parts recovers the
+//! part's own edges.
+
+use super::safe::MeshData;
+
+/// A box aligned to arbitrary orthonormal axes.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrientedBoundingBox {
+    /// Box center in model coordinates.
+    pub center: [f64; 3],
+    /// Unit box axes, ordered from largest to smallest variance.
+    pub axes: [[f64; 3]; 3],
+    /// Half the box size along each of `axes`.
+    pub half_extents: [f64; 3],
+}
+
+/// Computes the PCA-aligned bounding box of `mesh`'s vertices.
+///
+/// Returns `None` when the mesh has no vertices.
+pub fn from_mesh(mesh: &MeshData) -> Option<OrientedBoundingBox> {
+    let points: Vec<[f64; 3]> = mesh
+        .vertices
+        .chunks_exact(3)
+        .map(|v| [v[0] as f64, v[1] as f64, v[2] as f64])
+        .collect();
+    if points.is_empty() {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mut mean = [0.0; 3];
+    for p in &points {
+        for i in 0..3 {
+            mean[i] += p[i] / n;
+        }
+    }
+
+    let mut cov = [[0.0; 3]; 3];
+    for p in &points {
+        let d = sub(p, &mean);
+        for i in 0..3 {
+            for j in 0..3 {
+                cov[i][j] += d[i] * d[j] / n;
+            }
+        }
+    }
+
+    let axes = principal_axes(cov);
+
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for p in &points {
+        let d = sub(p, &mean);
+        for i in 0..3 {
+            let t = dot(&d, &axes[i]);
+            min[i] = min[i].min(t);
+            max[i] = max[i].max(t);
+        }
+    }
+
+    let mut center = mean;
+    let mut half_extents = [0.0; 3];
+    for i in 0..3 {
+        let mid = (min[i] + max[i]) / 2.0;
+        for k in 0..3 {
+            center[k] += axes[i][k] * mid;
+        }
+        half_extents[i] = (max[i] - min[i]) / 2.0;
+    }
+
+    Some(OrientedBoundingBox {
+        center,
+        axes,
+        half_extents,
+    })
+}
+
+/// Returns the eigenvectors of the symmetric matrix `a`, sorted by
+/// descending eigenvalue, using cyclic Jacobi rotations.
+fn principal_axes(mut a: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    for _ in 0..50 {
+        let off = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+        if off < 1e-24 {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q].abs() < 1e-30 {
+                continue;
+            }
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            for row in a.iter_mut() {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+            let (rp, rq) = (a[p], a[q]);
+            a[p] = [0, 1, 2].map(|k| c * rp[k] - s * rq[k]);
+            a[q] = [0, 1, 2].map(|k| s * rp[k] + c * rq[k]);
+            for row in v.iter_mut() {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+        }
+    }
+
+    // Eigenvectors are the columns of `v`; order them by eigenvalue.
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| a[j][j].total_cmp(&a[i][i]));
+    order.map(|c| [v[0][c], v[1][c], v[2][c]])
+}
+
+fn sub(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Corners of a `w × d × h` box centred on the origin, rotated about Z.
+    fn rotated_box(w: f64, d: f64, h: f64, angle_deg: f64) -> MeshData {
+        let (sin, cos) = angle_deg.to_radians().sin_cos();
+        let mut vertices = Vec::new();
+        for sx in [-0.5, 0.5] {
+            for sy in [-0.5, 0.5] {
+                for sz in [-0.5, 0.5] {
+                    let (x, y, z) = (sx * w, sy * d, sz * h);
+                    vertices.push((x * cos - y * sin) as f32);
+                    vertices.push((x * sin + y * cos) as f32);
+                    vertices.push(z as f32);
+                }
+            }
+        }
+        MeshData {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices: vec![],
+        }
+    }
+
+    #[test]
+    fn empty_mesh_has_no_box() {
+        let mesh = MeshData {
+            vertices: vec![],
+            normals: vec![],
+            indices: vec![],
+        };
+        assert!(from_mesh(&mesh).is_none());
+    }
+
+    #[test]
+    fn rotated_box_obb_is_tighter_than_aabb() {
+        let mesh = rotated_box(40.0, 20.0, 10.0, 30.0);
+        let obb = from_mesh(&mesh).expect("non-empty mesh");
+
+        // Recovered half-extents match the unrotated box.
+        for (got, want) in obb.half_extents.iter().zip([20.0, 10.0, 5.0]) {
+            assert!((got - want).abs() < 1e-4, "{:?}", obb.half_extents);
+        }
+        // The major axis lies along the box's rotated X direction.
+        let (sin, cos) = 30f64.to_radians().sin_cos();
+        let major = obb.axes[0];
+        assert!(
+            (major[0] * cos + major[1] * sin).abs() > 0.9999,
+            "{major:?}"
+        );
+
+        // Axis-aligned half-extents of the same vertices are strictly larger.
+        let xs = mesh.vertices.iter().step_by(3).map(|&v| v as f64);
+        let aabb_half_x = xs.clone().fold(f64::NEG_INFINITY, f64::max);
+        let aabb_half_y = mesh
+            .vertices
+            .iter()
+            .skip(1)
+            .step_by(3)
+            .map(|&v| v as f64)
+            .fold(f64::NEG_INFINITY, f64::max);
+        assert!(obb.half_extents[0] < aabb_half_x);
+        assert!(obb.half_extents[1] < aabb_half_y);
+        for c in obb.center {
+            assert!(c.abs() < 1e-4, "center {:?}", obb.center);
+        }
+    }
+}
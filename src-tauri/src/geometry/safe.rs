@@ -6,6 +6,8 @@
 
 use std::path::Path;
 
+use super::obb::OrientedBoundingBox;
+
 // ── Module-level helpers (OCCT only) ──────────────────────────────────────────
 
 /// Convert a [`Path`] to a null-terminated C string for FFI.
@@ -126,6 +128,114 @@ impl OcctShape {
         })
     }
 
+    /// Build an axis-aligned box solid with minimum corner `origin` and size
+    /// `dx` × `dy` × `dz` mm.
+    ///
+    /// Returns [`GeometryError::ModelingFailed`] for a non-positive size.
+    #[cfg(cam_geometry_bindings)]
+    pub fn make_box(
+        origin: [f64; 3],
+        dx: f64,
+        dy: f64,
+        dz: f64,
+    ) -> Result<OcctShape, GeometryError> {
+        let corner = super::ffi::CgPoint3 {
+            x: origin[0],
+            y: origin[1],
+            z: origin[2],
+        };
+        let id = unsafe { super::ffi::cg_shape_make_box(corner, dx, dy, dz) };
+        Self::from_modeling_result(id)
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    pub fn make_box(
+        _origin: [f64; 3],
+        _dx: f64,
+        _dy: f64,
+        _dz: f64,
+    ) -> Result<OcctShape, GeometryError> {
+        Err(GeometryError::ModelingFailed {
+            message: "OCCT not available".into(),
+        })
+    }
+
+    /// Build a cylinder solid whose bottom face is centred on `origin`,
+    /// extruded `height` mm along `axis` (need not be normalised).
+    ///
+    /// Returns [`GeometryError::ModelingFailed`] for a zero axis or a
+    /// non-positive radius or height.
+    #[cfg(cam_geometry_bindings)]
+    pub fn make_cylinder(
+        origin: [f64; 3],
+        axis: [f64; 3],
+        radius: f64,
+        height: f64,
+    ) -> Result<OcctShape, GeometryError> {
+        let base = super::ffi::CgPoint3 {
+            x: origin[0],
+            y: origin[1],
+            z: origin[2],
+        };
+        let dir = super::ffi::CgVec3 {
+            x: axis[0],
+            y: axis[1],
+            z: axis[2],
+        };
+        let id = unsafe { super::ffi::cg_shape_make_cylinder(base, dir, radius, height) };
+        Self::from_modeling_result(id)
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    pub fn make_cylinder(
+        _origin: [f64; 3],
+        _axis: [f64; 3],
+        _radius: f64,
+        _height: f64,
+    ) -> Result<OcctShape, GeometryError> {
+        Err(GeometryError::ModelingFailed {
+            message: "OCCT not available".into(),
+        })
+    }
+
+    /// Combine this solid with `other`: `self ∪ other`, `self − other`, or
+    /// `self ∩ other`. Both inputs are left unchanged.
+    ///
+    /// Returns [`GeometryError::ModelingFailed`] if OCCT cannot compute the
+    /// result.
+    #[cfg(cam_geometry_bindings)]
+    pub fn boolean(&self, other: &OcctShape, op: BoolOp) -> Result<OcctShape, GeometryError> {
+        let op = match op {
+            BoolOp::Union => super::ffi::CgBoolOp::CG_BOOL_UNION,
+            BoolOp::Difference => super::ffi::CgBoolOp::CG_BOOL_DIFFERENCE,
+            BoolOp::Intersection => super::ffi::CgBoolOp::CG_BOOL_INTERSECTION,
+        };
+        let id = unsafe { super::ffi::cg_shape_boolean(self.id, other.id, op) };
+        Self::from_modeling_result(id)
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    pub fn boolean(&self, _other: &OcctShape, _op: BoolOp) -> Result<OcctShape, GeometryError> {
+        Err(GeometryError::ModelingFailed {
+            message: "OCCT not available".into(),
+        })
+    }
+
+    /// Wrap a handle returned by a modelling call, mapping `CG_NULL_ID` to
+    /// the C layer's error message.
+    #[cfg(cam_geometry_bindings)]
+    fn from_modeling_result(id: u64) -> Result<OcctShape, GeometryError> {
+        if id == 0 {
+            return Err(GeometryError::ModelingFailed {
+                message: last_error_message(),
+            });
+        }
+        Ok(OcctShape {
+            id,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
     /// Tessellate the shape into a triangle mesh.
     ///
     /// - `chord_tol`: maximum chord deviation from the true surface (mm).
@@ -154,6 +264,58 @@ impl OcctShape {
         })
     }
 
+    /// [`tessellate`](Self::tessellate), reporting progress as a fraction
+    /// from 0.0 to 1.0 while the mesher runs.
+    ///
+    /// `progress` may be called from mesher worker threads, though never
+    /// concurrently; the last call is 1.0 when tessellation succeeds.
+    #[cfg(cam_geometry_bindings)]
+    pub fn tessellate_with_progress(
+        &self,
+        chord_tol: f64,
+        angle_tol: f64,
+        mut progress: &mut (dyn FnMut(f32) + Send),
+    ) -> Result<OcctMesh, GeometryError> {
+        unsafe extern "C" fn trampoline(user_data: *mut std::ffi::c_void, fraction: f64) {
+            // SAFETY: `user_data` is the `&mut &mut dyn FnMut` passed below,
+            // which outlives the FFI call; OCCT serialises Show() calls.
+            let progress = unsafe { &mut *(user_data as *mut &mut (dyn FnMut(f32) + Send)) };
+            progress(fraction as f32);
+        }
+
+        let user_data = &mut progress as *mut &mut (dyn FnMut(f32) + Send) as *mut std::ffi::c_void;
+        let id = unsafe {
+            super::ffi::cg_shape_tessellate_progress(
+                self.id,
+                chord_tol,
+                angle_tol,
+                Some(trampoline),
+                user_data,
+            )
+        };
+        if id == 0 {
+            return Err(GeometryError::TessellationFailed {
+                message: last_error_message(),
+            });
+        }
+        Ok(OcctMesh {
+            id,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    pub fn tessellate_with_progress(
+        &self,
+        _chord_tol: f64,
+        _angle_tol: f64,
+        _progress: &mut (dyn FnMut(f32) + Send),
+    ) -> Result<OcctMesh, GeometryError> {
+        Err(GeometryError::TessellationFailed {
+            message: "OCCT not available".into(),
+        })
+    }
+
     /// Return the axis-aligned bounding box as `(xmin, ymin, zmin, xmax, ymax, zmax)`.
     #[cfg(cam_geometry_bindings)]
     pub fn bounding_box(&self) -> (f64, f64, f64, f64, f64, f64) {
@@ -165,6 +327,150 @@ impl OcctShape {
     pub fn bounding_box(&self) -> (f64, f64, f64, f64, f64, f64) {
         (0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
     }
+
+    /// Find the cylindrical holes in the shape, for seeding drill operations.
+    ///
+    /// A hole is a cylindrical face whose wall faces its own axis; the halves
+    /// of a split cylinder are merged into one hole. Returns an empty list
+    /// when the shape has no holes.
+    #[cfg(cam_geometry_bindings)]
+    pub fn detect_holes(&self) -> Result<Vec<HoleInfo>, GeometryError> {
+        let mut raw: *mut super::ffi::CgHoleInfo = std::ptr::null_mut();
+        // SAFETY: `cg_shape_find_holes` writes a `new[]`-allocated array of
+        // `count` entries to `raw` (or null when `count` is 0). We copy the
+        // entries out before releasing the array with `cg_holes_free`.
+        let holes = unsafe {
+            let count = super::ffi::cg_shape_find_holes(self.id, 0.0, f64::INFINITY, &mut raw);
+            if raw.is_null() {
+                return Ok(Vec::new());
+            }
+            let holes = std::slice::from_raw_parts(raw, count)
+                .iter()
+                .map(|h| HoleInfo {
+                    center: [h.center.x, h.center.y, h.center.z],
+                    axis: [h.axis.x, h.axis.y, h.axis.z],
+                    diameter: h.diameter,
+                    depth: h.depth,
+                    is_through: h.is_through != 0,
+                })
+                .collect();
+            super::ffi::cg_holes_free(raw);
+            holes
+        };
+        Ok(holes)
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    pub fn detect_holes(&self) -> Result<Vec<HoleInfo>, GeometryError> {
+        Ok(Vec::new())
+    }
+
+    /// List the shape's faces with their surface type and area, e.g. to pick
+    /// the flat faces a facing operation should cover.
+    ///
+    /// Each face is reported once, in a stable order; [`FaceInfo::id`] is
+    /// its index in that order.
+    ///
+    /// Returns [`GeometryError::TessellationFailed`] if a face's area cannot
+    /// be computed.
+    #[cfg(cam_geometry_bindings)]
+    pub fn faces(&self) -> Result<Vec<FaceInfo>, GeometryError> {
+        // SAFETY: the first call only counts faces. The second writes at most
+        // `count` face handles into `handles`, each of which we own and free
+        // with `cg_face_free` once its type and area are read.
+        unsafe {
+            let count = super::ffi::cg_shape_faces(self.id, std::ptr::null_mut(), 0);
+            let mut handles = vec![0u64; count];
+            let written = super::ffi::cg_shape_faces(self.id, handles.as_mut_ptr(), count);
+            handles.truncate(written.min(count));
+
+            let faces = handles
+                .iter()
+                .enumerate()
+                .map(|(index, &handle)| {
+                    let surface_type =
+                        SurfaceType::from_ffi(super::ffi::cg_face_surface_type(handle));
+                    let area = super::ffi::cg_face_area(handle);
+                    if area < 0.0 {
+                        return Err(GeometryError::TessellationFailed {
+                            message: last_error_message(),
+                        });
+                    }
+                    Ok(FaceInfo {
+                        id: index as u64,
+                        surface_type,
+                        area,
+                    })
+                })
+                .collect();
+            for &handle in &handles {
+                super::ffi::cg_face_free(handle);
+            }
+            faces
+        }
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    pub fn faces(&self) -> Result<Vec<FaceInfo>, GeometryError> {
+        Ok(Vec::new())
+    }
+
+    /// Slice the shape with the plane at height `z` into closed XY loops.
+    ///
+    /// Outer boundaries and holes are returned alike, in no particular
+    /// order; curved edges are split into chords within 0.01 of the curve.
+    /// Returns an empty list when the plane misses the shape.
+    ///
+    /// Returns [`GeometryError::TessellationFailed`] if OCCT cannot compute
+    /// the section.
+    #[cfg(cam_geometry_bindings)]
+    pub fn section_z(&self, z: f64) -> Result<Vec<Vec<(f64, f64)>>, GeometryError> {
+        let mut raw: *mut super::ffi::CgPoint3 = std::ptr::null_mut();
+        let mut count: usize = 0;
+        // SAFETY: on success `cg_shape_section_at_z` writes a `new[]`-allocated
+        // array of `count` points to `raw`. We copy the segments out before
+        // releasing the array with `cg_section_free`.
+        let segments: Vec<[(f64, f64); 2]> = unsafe {
+            match super::ffi::cg_shape_section_at_z(self.id, z, &mut raw, &mut count) {
+                super::ffi::CgError::CG_OK => {}
+                super::ffi::CgError::CG_ERR_NO_RESULT => return Ok(Vec::new()),
+                _ => {
+                    return Err(GeometryError::TessellationFailed {
+                        message: last_error_message(),
+                    })
+                }
+            }
+            let segments = std::slice::from_raw_parts(raw, count)
+                .chunks_exact(2)
+                .map(|pair| [(pair[0].x, pair[0].y), (pair[1].x, pair[1].y)])
+                .collect();
+            super::ffi::cg_section_free(raw);
+            segments
+        };
+        Ok(super::section::chain_loops(
+            &segments,
+            super::section::JOIN_TOLERANCE,
+        ))
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    pub fn section_z(&self, _z: f64) -> Result<Vec<Vec<(f64, f64)>>, GeometryError> {
+        Err(GeometryError::TessellationFailed {
+            message: "OCCT not available".into(),
+        })
+    }
+
+    /// Return the box aligned to the shape's principal axes.
+    ///
+    /// Computed by PCA over a tessellation of the shape (see [`super::obb`]).
+    /// Returns [`GeometryError::TessellationFailed`] if the shape cannot be
+    /// tessellated or yields no vertices.
+    pub fn oriented_bounding_box(&self) -> Result<OrientedBoundingBox, GeometryError> {
+        let mesh = self.tessellate(0.1, 0.5)?.to_mesh_data();
+        super::obb::from_mesh(&mesh).ok_or_else(|| GeometryError::TessellationFailed {
+            message: "tessellation produced no vertices".into(),
+        })
+    }
 }
 
 impl Drop for OcctShape {
@@ -290,6 +596,82 @@ impl Drop for OcctMesh {
 // SAFETY: same reasoning as OcctShape.
 unsafe impl Send for OcctMesh {}
 
+// ── BoolOp ────────────────────────────────────────────────────────────────────
+
+/// Solid boolean performed by [`OcctShape::boolean`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoolOp {
+    Union,
+    Difference,
+    Intersection,
+}
+
+// ── HoleInfo ──────────────────────────────────────────────────────────────────
+
+/// A cylindrical hole found by [`OcctShape::detect_holes`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HoleInfo {
+    /// Center of the hole's opening, in model coordinates (mm).
+    pub center: [f64; 3],
+    /// Unit axis pointing out of the hole through its opening.
+    pub axis: [f64; 3],
+    /// Hole diameter in mm.
+    pub diameter: f64,
+    /// Hole depth along the axis in mm.
+    pub depth: f64,
+    /// `true` if the hole passes through the part.
+    pub is_through: bool,
+}
+
+// ── FaceInfo ──────────────────────────────────────────────────────────────────
+
+/// Geometric type of a B-rep face's underlying surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SurfaceType {
+    Plane,
+    Cylinder,
+    Cone,
+    Sphere,
+    Torus,
+    #[serde(rename = "bspline")]
+    BSpline,
+    Bezier,
+    Offset,
+    Other,
+}
+
+impl SurfaceType {
+    #[cfg(cam_geometry_bindings)]
+    fn from_ffi(surface: super::ffi::CgSurfaceType) -> Self {
+        use super::ffi::CgSurfaceType as Cg;
+        match surface {
+            Cg::CG_SURF_PLANE => Self::Plane,
+            Cg::CG_SURF_CYLINDER => Self::Cylinder,
+            Cg::CG_SURF_CONE => Self::Cone,
+            Cg::CG_SURF_SPHERE => Self::Sphere,
+            Cg::CG_SURF_TORUS => Self::Torus,
+            Cg::CG_SURF_BSPLINE => Self::BSpline,
+            Cg::CG_SURF_BEZIER => Self::Bezier,
+            Cg::CG_SURF_OFFSET => Self::Offset,
+            Cg::CG_SURF_OTHER => Self::Other,
+        }
+    }
+}
+
+/// A face of a B-rep shape, listed by [`OcctShape::faces`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaceInfo {
+    /// Index of the face within its shape.
+    pub id: u64,
+    /// Type of the face's underlying surface.
+    pub surface_type: SurfaceType,
+    /// Face area in mm².
+    pub area: f64,
+}
+
 // ── GeometryError ─────────────────────────────────────────────────────────────
 
 /// Errors produced by the geometry kernel layer.
@@ -313,6 +695,14 @@ pub enum GeometryError {
     /// The file extension is not handled by any available importer.
     #[error("Unsupported format: {extension}")]
     UnsupportedFormat { extension: String },
+
+    /// A primitive solid or boolean operation could not be built.
+    #[error("Modeling failed: {message}")]
+    ModelingFailed { message: String },
+
+    /// A mesh could not be written out.
+    #[error("Export failed: {message}")]
+    ExportFailed { message: String },
 }
 
 // ── MeshData ──────────────────────────────────────────────────────────────────
@@ -332,6 +722,105 @@ pub struct MeshData {
     pub indices: Vec<u32>,
 }
 
+impl MeshData {
+    /// Write the mesh to `path` as an STL file, binary (80-byte header) when
+    /// `binary` is set, ASCII otherwise.
+    ///
+    /// Facet normals are recomputed from each triangle's winding; the
+    /// per-vertex `normals` are not used. Degenerate triangles get a zero
+    /// normal.
+    ///
+    /// Returns [`GeometryError::ExportFailed`] if an index is out of range or
+    /// the file cannot be written.
+    pub fn write_stl(&self, path: &Path, binary: bool) -> Result<(), GeometryError> {
+        let facets = self.facets()?;
+        let export_failed = |e: std::io::Error| GeometryError::ExportFailed {
+            message: format!("{}: {e}", path.display()),
+        };
+        let file = std::fs::File::create(path).map_err(export_failed)?;
+        let mut out = std::io::BufWriter::new(file);
+        if binary {
+            write_binary_stl(&mut out, &facets)
+        } else {
+            write_ascii_stl(&mut out, &facets)
+        }
+        .and_then(|()| std::io::Write::flush(&mut out))
+        .map_err(export_failed)
+    }
+
+    /// Resolve `indices` into `[normal, v0, v1, v2]` facets.
+    fn facets(&self) -> Result<Vec<[[f32; 3]; 4]>, GeometryError> {
+        let vertex = |index: u32| -> Result<[f32; 3], GeometryError> {
+            let start = index as usize * 3;
+            self.vertices
+                .get(start..start + 3)
+                .map(|v| [v[0], v[1], v[2]])
+                .ok_or_else(|| GeometryError::ExportFailed {
+                    message: format!("vertex index {index} out of range"),
+                })
+        };
+        self.indices
+            .chunks_exact(3)
+            .map(|tri| {
+                let (a, b, c) = (vertex(tri[0])?, vertex(tri[1])?, vertex(tri[2])?);
+                Ok([facet_normal(a, b, c), a, b, c])
+            })
+            .collect()
+    }
+}
+
+/// Unit normal of triangle `abc` by the right-hand rule; zero if degenerate.
+fn facet_normal(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> [f32; 3] {
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let n = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if len > f32::EPSILON {
+        [n[0] / len, n[1] / len, n[2] / len]
+    } else {
+        [0.0; 3]
+    }
+}
+
+fn write_binary_stl(
+    out: &mut impl std::io::Write,
+    facets: &[[[f32; 3]; 4]],
+) -> std::io::Result<()> {
+    let mut header = [0u8; 80];
+    let label = b"jamiecam binary STL";
+    header[..label.len()].copy_from_slice(label);
+    out.write_all(&header)?;
+    let count = u32::try_from(facets.len())
+        .map_err(|_| std::io::Error::other("too many triangles for binary STL"))?;
+    out.write_all(&count.to_le_bytes())?;
+    for facet in facets {
+        for value in facet.iter().flatten() {
+            out.write_all(&value.to_le_bytes())?;
+        }
+        // Attribute byte count, unused.
+        out.write_all(&0u16.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_ascii_stl(out: &mut impl std::io::Write, facets: &[[[f32; 3]; 4]]) -> std::io::Result<()> {
+    writeln!(out, "solid jamiecam")?;
+    for [n, a, b, c] in facets {
+        writeln!(out, "  facet normal {} {} {}", n[0], n[1], n[2])?;
+        writeln!(out, "    outer loop")?;
+        for v in [a, b, c] {
+            writeln!(out, "      vertex {} {} {}", v[0], v[1], v[2])?;
+        }
+        writeln!(out, "    endloop")?;
+        writeln!(out, "  endfacet")?;
+    }
+    writeln!(out, "endsolid jamiecam")
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -439,6 +928,65 @@ mod tests {
         assert_eq!(v["indices"][2], 2);
     }
 
+    // ── MeshData::write_stl ───────────────────────────────────────────────
+
+    /// Two triangles forming the unit square in the XY plane, wound CCW.
+    fn unit_square() -> MeshData {
+        MeshData {
+            vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            normals: vec![],
+            indices: vec![0, 1, 2, 0, 2, 3],
+        }
+    }
+
+    #[test]
+    fn write_binary_stl_has_header_count_and_facets() {
+        let path = std::env::temp_dir().join("jcam_write_stl_binary.stl");
+        unit_square().write_stl(&path, true).expect("write stl");
+
+        let bytes = std::fs::read(&path).expect("read back");
+        assert_eq!(bytes.len(), 84 + 2 * 50);
+        assert_eq!(u32::from_le_bytes(bytes[80..84].try_into().unwrap()), 2);
+        let normal_z = f32::from_le_bytes(bytes[92..96].try_into().unwrap());
+        assert_eq!(normal_z, 1.0, "CCW winding must face +Z");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn write_ascii_stl_lists_every_facet() {
+        let path = std::env::temp_dir().join("jcam_write_stl_ascii.stl");
+        unit_square().write_stl(&path, false).expect("write stl");
+
+        let text = std::fs::read_to_string(&path).expect("read back");
+        assert!(text.starts_with("solid jamiecam"));
+        assert_eq!(text.matches("facet normal 0 0 1").count(), 2, "{text}");
+        assert_eq!(text.matches("vertex ").count(), 6);
+        assert!(text.trim_end().ends_with("endsolid jamiecam"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn write_stl_rejects_out_of_range_index() {
+        let mut mesh = unit_square();
+        mesh.indices[5] = 9;
+        let path = std::env::temp_dir().join("jcam_write_stl_bad_index.stl");
+        assert!(matches!(
+            mesh.write_stl(&path, true),
+            Err(GeometryError::ExportFailed { .. })
+        ));
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn binary_stl_round_trips_through_load_stl() {
+        let path = std::env::temp_dir().join("jcam_write_stl_round_trip.stl");
+        unit_square().write_stl(&path, true).expect("write stl");
+
+        let mesh = OcctMesh::load_stl(&path).expect("load stl").to_mesh_data();
+        assert_eq!(mesh.indices.len(), 6, "two triangles");
+        let _ = std::fs::remove_file(&path);
+    }
+
     // ── Handle type properties ────────────────────────────────────────────
 
     /// OcctShape must implement Send (compile-time check).
@@ -528,6 +1076,49 @@ mod tests {
         assert!(data.indices.is_empty());
     }
 
+    /// Without OCCT there is no B-rep to search, so no holes are found.
+    #[cfg(not(cam_geometry_bindings))]
+    #[test]
+    fn detect_holes_stub_returns_empty() {
+        let shape = OcctShape {
+            id: 0,
+            _marker: std::marker::PhantomData,
+        };
+        assert_eq!(shape.detect_holes().unwrap(), Vec::new());
+    }
+
+    /// Without OCCT there is no B-rep, so no faces are listed.
+    #[cfg(not(cam_geometry_bindings))]
+    #[test]
+    fn faces_stub_returns_empty() {
+        let shape = OcctShape {
+            id: 0,
+            _marker: std::marker::PhantomData,
+        };
+        assert_eq!(shape.faces().unwrap(), Vec::new());
+    }
+
+    /// Without OCCT there is no B-rep to slice.
+    #[cfg(not(cam_geometry_bindings))]
+    #[test]
+    fn section_z_stub_returns_tessellation_failed() {
+        let shape = OcctShape {
+            id: 0,
+            _marker: std::marker::PhantomData,
+        };
+        assert!(matches!(
+            shape.section_z(5.0),
+            Err(GeometryError::TessellationFailed { .. })
+        ));
+    }
+
+    #[cfg(not(cam_geometry_bindings))]
+    #[test]
+    fn make_box_stub_returns_modeling_failed() {
+        let result = OcctShape::make_box([0.0; 3], 1.0, 1.0, 1.0);
+        assert!(matches!(result, Err(GeometryError::ModelingFailed { .. })));
+    }
+
     // ── OCCT integration tests ────────────────────────────────────────────
 
     #[cfg(cam_geometry_bindings)]
@@ -554,6 +1145,133 @@ mod tests {
         assert!(zmax > zmin, "zmax > zmin");
     }
 
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn faces_of_box_are_six_planes() {
+        let path = std::path::Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/box.step"
+        ));
+        let shape = OcctShape::load_step(path).expect("load box.step");
+        let faces = shape.faces().expect("list faces");
+
+        assert_eq!(faces.len(), 6, "{faces:?}");
+        assert!(
+            faces
+                .iter()
+                .all(|f| f.surface_type == SurfaceType::Plane && f.area > 0.0),
+            "{faces:?}"
+        );
+        let ids: Vec<u64> = faces.iter().map(|f| f.id).collect();
+        assert_eq!(ids, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn section_z_through_box_is_one_rectangle() {
+        let path = std::path::Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/box.step"
+        ));
+        let shape = OcctShape::load_step(path).expect("load box.step");
+        let (xmin, ymin, zmin, xmax, ymax, zmax) = shape.bounding_box();
+        let loops = shape.section_z((zmin + zmax) / 2.0).expect("section");
+
+        assert_eq!(loops.len(), 1, "{loops:?}");
+        let corners = &loops[0];
+        assert_eq!(corners.len(), 4, "{corners:?}");
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+        for (x, y) in [(xmin, ymin), (xmax, ymin), (xmax, ymax), (xmin, ymax)] {
+            assert!(
+                corners.iter().any(|&(cx, cy)| close(cx, x) && close(cy, y)),
+                "missing corner ({x}, {y}) in {corners:?}"
+            );
+        }
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn section_z_above_box_is_empty() {
+        let path = std::path::Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/box.step"
+        ));
+        let shape = OcctShape::load_step(path).expect("load box.step");
+        let (.., zmax) = shape.bounding_box();
+        assert!(shape.section_z(zmax + 1.0).expect("section").is_empty());
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn detect_holes_finds_through_hole_in_plate() {
+        // 30 × 20 × 10 block with a Ø6 through hole on the vertical axis at
+        // (15, 10).
+        let path = std::path::Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/plate_with_hole.step"
+        ));
+        let shape = OcctShape::load_step(path).expect("load plate_with_hole.step");
+        let holes = shape.detect_holes().expect("detect holes");
+
+        assert_eq!(holes.len(), 1, "{holes:?}");
+        let hole = &holes[0];
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+        assert!(close(hole.diameter, 6.0), "{hole:?}");
+        assert!(close(hole.depth, 10.0), "{hole:?}");
+        assert!(hole.is_through, "{hole:?}");
+        assert!(
+            close(hole.center[0], 15.0)
+                && close(hole.center[1], 10.0)
+                && close(hole.center[2], 10.0),
+            "{hole:?}"
+        );
+        assert!(close(hole.axis[2], 1.0), "{hole:?}");
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn union_of_overlapping_boxes_spans_both() {
+        let a = OcctShape::make_box([0.0, 0.0, 0.0], 10.0, 10.0, 10.0).expect("box a");
+        let b = OcctShape::make_box([5.0, 5.0, 5.0], 10.0, 10.0, 10.0).expect("box b");
+        let union = a.boolean(&b, BoolOp::Union).expect("union");
+
+        let (xmin, ymin, zmin, xmax, ymax, zmax) = union.bounding_box();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+        assert!(close(xmin, 0.0) && close(ymin, 0.0) && close(zmin, 0.0));
+        assert!(close(xmax, 15.0) && close(ymax, 15.0) && close(zmax, 15.0));
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn difference_of_box_and_cylinder_tessellates() {
+        let stock = OcctShape::make_box([0.0, 0.0, 0.0], 20.0, 20.0, 10.0).expect("box");
+        let bore = OcctShape::make_cylinder([10.0, 10.0, -1.0], [0.0, 0.0, 1.0], 3.0, 12.0)
+            .expect("cylinder");
+        let rest = stock
+            .boolean(&bore, BoolOp::Difference)
+            .expect("difference");
+
+        let data = rest
+            .tessellate(0.1, 0.5)
+            .expect("tessellate")
+            .to_mesh_data();
+        assert!(!data.indices.is_empty());
+        // The bore reaches the top face, so the result keeps the box's extent.
+        let (_, _, _, xmax, _, zmax) = rest.bounding_box();
+        assert!((xmax - 20.0).abs() < 1e-6 && (zmax - 10.0).abs() < 1e-6);
+    }
+
+    #[cfg(cam_geometry_bindings)]
+    #[test]
+    fn detect_holes_finds_none_in_box() {
+        let path = std::path::Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/box.step"
+        ));
+        let shape = OcctShape::load_step(path).expect("load box.step");
+        assert!(shape.detect_holes().expect("detect holes").is_empty());
+    }
+
     #[cfg(cam_geometry_bindings)]
     #[test]
     fn tessellate_produces_nonempty_mesh() {
@@ -0,0 +1,114 @@
+//! Closed loops from planar section segments.
+//!
+//! OCCT returns a Z section as unordered line segments, one or more per
+//! section edge. [`chain_loops`] joins segments end to end into the closed
+//! XY polygons that 2.5D pocketing and the cross-section view work with.
+
+/// Endpoint distance within which two segments are treated as joined.
+pub const JOIN_TOLERANCE: f64 = 1e-6;
+
+/// Joins `segments` end to end into closed loops.
+///
+/// Each loop lists its vertices once, without repeating the first point at
+/// the end. Segments may be given in either direction. Zero-length segments
+/// are ignored, and chains that never return to their start (from a section
+/// of an open shell) are dropped.
+pub fn chain_loops(segments: &[[(f64, f64); 2]], tol: f64) -> Vec<Vec<(f64, f64)>> {
+    let near = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).hypot(a.1 - b.1) <= tol;
+    let mut used: Vec<bool> = segments.iter().map(|[a, b]| near(*a, *b)).collect();
+    let mut loops = Vec::new();
+
+    for start in 0..segments.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let [first, mut end] = segments[start];
+        let mut chain = vec![first];
+
+        while !near(end, first) {
+            let next = (0..segments.len()).filter(|&i| !used[i]).find_map(|i| {
+                let [a, b] = segments[i];
+                if near(a, end) {
+                    Some((i, b))
+                } else if near(b, end) {
+                    Some((i, a))
+                } else {
+                    None
+                }
+            });
+            let Some((i, far)) = next else { break };
+            used[i] = true;
+            chain.push(end);
+            end = far;
+        }
+
+        if near(end, first) && chain.len() >= 3 {
+            loops.push(chain);
+        }
+    }
+    loops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_segments_in_mixed_directions_form_one_loop() {
+        let segments = [
+            [(0.0, 0.0), (10.0, 0.0)],
+            [(10.0, 10.0), (10.0, 0.0)],
+            [(0.0, 10.0), (0.0, 0.0)],
+            [(10.0, 10.0), (0.0, 10.0)],
+        ];
+        let loops = chain_loops(&segments, JOIN_TOLERANCE);
+        assert_eq!(
+            loops,
+            vec![vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]]
+        );
+    }
+
+    #[test]
+    fn outer_boundary_and_hole_form_separate_loops() {
+        let segments = [
+            [(0.0, 0.0), (10.0, 0.0)],
+            [(4.0, 4.0), (6.0, 4.0)],
+            [(10.0, 0.0), (5.0, 10.0)],
+            [(6.0, 4.0), (5.0, 6.0)],
+            [(5.0, 10.0), (0.0, 0.0)],
+            [(5.0, 6.0), (4.0, 4.0)],
+        ];
+        let loops = chain_loops(&segments, JOIN_TOLERANCE);
+        assert_eq!(loops.len(), 2, "{loops:?}");
+        assert!(loops.iter().all(|l| l.len() == 3), "{loops:?}");
+    }
+
+    #[test]
+    fn endpoints_within_tolerance_join() {
+        let segments = [
+            [(0.0, 0.0), (1.0, 0.0)],
+            [(1.0 + 1e-9, 0.0), (0.0, 1.0)],
+            [(0.0, 1.0), (0.0, 1e-9)],
+        ];
+        assert_eq!(chain_loops(&segments, JOIN_TOLERANCE).len(), 1);
+    }
+
+    #[test]
+    fn open_chain_is_dropped() {
+        let segments = [[(0.0, 0.0), (1.0, 0.0)], [(1.0, 0.0), (1.0, 1.0)]];
+        assert!(chain_loops(&segments, JOIN_TOLERANCE).is_empty());
+    }
+
+    #[test]
+    fn zero_length_segments_are_ignored() {
+        let segments = [
+            [(0.0, 0.0), (0.0, 0.0)],
+            [(0.0, 0.0), (1.0, 0.0)],
+            [(1.0, 0.0), (0.0, 1.0)],
+            [(0.0, 1.0), (0.0, 0.0)],
+        ];
+        let loops = chain_loops(&segments, JOIN_TOLERANCE);
+        assert_eq!(loops, vec![vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]]);
+    }
+}
@@ -0,0 +1,512 @@
+//! 3MF (3D Manufacturing Format) mesh loader.
+//!
+//! A 3MF file is a ZIP archive whose `3D/3dmodel.model` part holds the mesh
+//! data as XML. [`load_3mf`] reads that part and [`parse_model`] turns it
+//! into a [`MeshData`] in millimetres: every build item's object is placed
+//! with the item transform (and any component transforms), scaled from the
+//! model's `unit`, and appended to one mesh. OCCT is not involved.
+//!
+//! Only elements of the core specification namespace are read. Extension
+//! elements are skipped even when they share a core element's local name,
+//! and extensions that keep geometry in other parts (e.g. the production
+//! extension's `p:path`) are not followed.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::{NsReader, XmlVersion};
+
+use super::safe::{GeometryError, MeshData};
+
+/// Archive path of the model part.
+const MODEL_PART: &str = "3D/3dmodel.model";
+
+/// XML namespace of the 3MF core specification.
+const CORE_NAMESPACE: &str = "http://schemas.microsoft.com/3dmanufacturing/core/2015/02";
+
+/// Deepest chain of components followed before assuming a reference cycle.
+const MAX_COMPONENT_DEPTH: usize = 32;
+
+/// Affine transform in 3MF order: `m00 m01 m02 m10 m11 m12 m20 m21 m22 m30
+/// m31 m32`, applied to row vectors so `m30..m32` is the translation.
+type Transform = [f64; 12];
+
+const IDENTITY: Transform = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0];
+
+fn import_failed(message: impl Into<String>) -> GeometryError {
+    GeometryError::ImportFailed {
+        message: message.into(),
+    }
+}
+
+/// Load the mesh of the 3MF file at `path`.
+///
+/// # Errors
+///
+/// - [`GeometryError::FileNotFound`] — path does not exist.
+/// - [`GeometryError::ImportFailed`] — not a ZIP archive, no model part, or
+///   the model XML is malformed or holds no triangles.
+pub fn load_3mf(path: &Path) -> Result<MeshData, GeometryError> {
+    if !path.exists() {
+        return Err(GeometryError::FileNotFound);
+    }
+    let file = std::fs::File::open(path).map_err(|e| import_failed(e.to_string()))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| import_failed(format!("not a 3MF archive: {e}")))?;
+    let mut xml = String::new();
+    archive
+        .by_name(MODEL_PART)
+        .map_err(|e| import_failed(format!("missing {MODEL_PART}: {e}")))?
+        .read_to_string(&mut xml)
+        .map_err(|e| import_failed(format!("cannot read {MODEL_PART}: {e}")))?;
+    parse_model(&xml)
+}
+
+/// One `<object>`: its own mesh and the objects it places as components.
+#[derive(Default)]
+struct Object {
+    vertices: Vec<[f64; 3]>,
+    triangles: Vec<[usize; 3]>,
+    components: Vec<(String, Transform)>,
+}
+
+/// Convert the XML of a 3MF model part into a single mesh in millimetres.
+///
+/// # Errors
+///
+/// [`GeometryError::ImportFailed`] for malformed XML, bad numbers, triangle
+/// or component references that do not resolve, an unknown `unit`, or a
+/// build that places no triangles.
+pub fn parse_model(xml: &str) -> Result<MeshData, GeometryError> {
+    let mut unit_scale = 1.0;
+    let mut objects: HashMap<String, Object> = HashMap::new();
+    let mut items: Vec<(String, Transform)> = Vec::new();
+    let mut current: Option<String> = None;
+
+    let mut reader = NsReader::from_str(xml);
+    loop {
+        let (namespace, event) = reader
+            .read_resolved_event()
+            .map_err(|e| import_failed(format!("malformed 3MF XML: {e}")))?;
+        // Elements of other namespaces (extensions such as the slice
+        // extension's <s:vertex>) are skipped, whatever their local name.
+        let core = matches!(namespace, ResolveResult::Bound(Namespace(CORE_NAMESPACE)));
+        let (start, kind) = match event {
+            Event::Start(start) => (start, TagKind::Open),
+            Event::Empty(start) => (start, TagKind::Empty),
+            Event::End(end) => {
+                if core && end.local_name().as_ref() == "object" {
+                    current = None;
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        if !core {
+            continue;
+        }
+        let Some(tag) = Tag::new(start) else {
+            continue;
+        };
+        match tag.name {
+            "model" => {
+                if let Some(unit) = tag.attr("unit")? {
+                    unit_scale = unit_to_mm(&unit)?;
+                }
+            }
+            "object" => {
+                let id = tag.required("id")?.into_owned();
+                objects.insert(id.clone(), Object::default());
+                current = (kind == TagKind::Open).then_some(id);
+            }
+            "vertex" => {
+                let object = current_object(&mut objects, &current, "vertex")?;
+                object
+                    .vertices
+                    .push([tag.number("x")?, tag.number("y")?, tag.number("z")?]);
+            }
+            "triangle" => {
+                let object = current_object(&mut objects, &current, "triangle")?;
+                object
+                    .triangles
+                    .push([tag.index("v1")?, tag.index("v2")?, tag.index("v3")?]);
+            }
+            "component" => {
+                let component = (tag.required("objectid")?.into_owned(), tag.transform()?);
+                current_object(&mut objects, &current, "component")?
+                    .components
+                    .push(component);
+            }
+            "item" => {
+                items.push((tag.required("objectid")?.into_owned(), tag.transform()?));
+            }
+            _ => {}
+        }
+    }
+
+    let scale: Transform = [
+        unit_scale, 0.0, 0.0, 0.0, unit_scale, 0.0, 0.0, 0.0, unit_scale, 0.0, 0.0, 0.0,
+    ];
+    let mut mesh = MeshBuilder::default();
+    for (id, transform) in &items {
+        mesh.append(&objects, id, &compose(transform, &scale), 0)?;
+    }
+    if mesh.indices.is_empty() {
+        return Err(import_failed("3MF build contains no triangles"));
+    }
+    Ok(mesh.finish())
+}
+
+fn current_object<'a>(
+    objects: &'a mut HashMap<String, Object>,
+    current: &Option<String>,
+    element: &str,
+) -> Result<&'a mut Object, GeometryError> {
+    current
+        .as_ref()
+        .and_then(|id| objects.get_mut(id))
+        .ok_or_else(|| import_failed(format!("<{element}> outside an <object>")))
+}
+
+/// Millimetres per unit for the 3MF `unit` attribute values.
+fn unit_to_mm(unit: &str) -> Result<f64, GeometryError> {
+    Ok(match unit {
+        "micron" => 0.001,
+        "millimeter" => 1.0,
+        "centimeter" => 10.0,
+        "inch" => 25.4,
+        "foot" => 304.8,
+        "meter" => 1000.0,
+        other => return Err(import_failed(format!("unknown 3MF unit '{other}'"))),
+    })
+}
+
+/// The transform applying `inner`, then `outer`.
+fn compose(inner: &Transform, outer: &Transform) -> Transform {
+    let mut out = [0.0; 12];
+    for row in 0..4 {
+        for col in 0..3 {
+            let mut v = (0..3)
+                .map(|k| inner[row * 3 + k] * outer[k * 3 + col])
+                .sum::<f64>();
+            if row == 3 {
+                v += outer[9 + col];
+            }
+            out[row * 3 + col] = v;
+        }
+    }
+    out
+}
+
+fn apply(t: &Transform, [x, y, z]: [f64; 3]) -> [f64; 3] {
+    [0, 1, 2].map(|c| x * t[c] + y * t[3 + c] + z * t[6 + c] + t[9 + c])
+}
+
+/// Accumulates placed objects into one indexed mesh.
+#[derive(Default)]
+struct MeshBuilder {
+    vertices: Vec<[f64; 3]>,
+    indices: Vec<u32>,
+}
+
+impl MeshBuilder {
+    fn append(
+        &mut self,
+        objects: &HashMap<String, Object>,
+        id: &str,
+        transform: &Transform,
+        depth: usize,
+    ) -> Result<(), GeometryError> {
+        if depth > MAX_COMPONENT_DEPTH {
+            return Err(import_failed("3MF components nest too deeply (cycle?)"));
+        }
+        let object = objects
+            .get(id)
+            .ok_or_else(|| import_failed(format!("3MF references unknown object '{id}'")))?;
+
+        let base = self.vertices.len();
+        self.vertices
+            .extend(object.vertices.iter().map(|&v| apply(transform, v)));
+        for triangle in &object.triangles {
+            for &v in triangle {
+                if v >= object.vertices.len() {
+                    return Err(import_failed(format!(
+                        "triangle vertex {v} out of range in object '{id}'"
+                    )));
+                }
+                self.indices.push((base + v) as u32);
+            }
+        }
+        for (child, child_transform) in &object.components {
+            self.append(
+                objects,
+                child,
+                &compose(child_transform, transform),
+                depth + 1,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Flattens to [`MeshData`] with area-weighted vertex normals.
+    fn finish(self) -> MeshData {
+        let mut normals = vec![[0.0_f64; 3]; self.vertices.len()];
+        for tri in self.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| self.vertices[tri[i] as usize]);
+            let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+            let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+            let n = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            for &i in tri {
+                for k in 0..3 {
+                    normals[i as usize][k] += n[k];
+                }
+            }
+        }
+        let unit = |n: [f64; 3]| {
+            let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            if len > 0.0 {
+                n.map(|v| v / len)
+            } else {
+                n
+            }
+        };
+        MeshData {
+            vertices: self.vertices.iter().flatten().map(|&v| v as f32).collect(),
+            normals: normals
+                .into_iter()
+                .flat_map(unit)
+                .map(|v| v as f32)
+                .collect(),
+            indices: self.indices,
+        }
+    }
+}
+
+// ── Core elements ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagKind {
+    /// `<name ...>`
+    Open,
+    /// `<name ... />`
+    Empty,
+}
+
+/// Local names of the core elements [`parse_model`] reads.
+const ELEMENTS: [&str; 6] = ["model", "object", "vertex", "triangle", "component", "item"];
+
+/// Start tag of one of the [`ELEMENTS`].
+struct Tag<'a> {
+    name: &'static str,
+    start: BytesStart<'a>,
+}
+
+impl<'a> Tag<'a> {
+    /// `None` for elements [`parse_model`] does not read.
+    fn new(start: BytesStart<'a>) -> Option<Self> {
+        let local = start.local_name();
+        let name = ELEMENTS.into_iter().find(|n| *n == local.as_ref())?;
+        Some(Self { name, start })
+    }
+
+    /// The unprefixed attribute `name`, with entity references expanded.
+    fn attr(&self, name: &str) -> Result<Option<Cow<'_, str>>, GeometryError> {
+        let malformed = |e: &dyn std::fmt::Display| {
+            import_failed(format!("malformed attributes in <{}>: {e}", self.name))
+        };
+        let Some(attr) = self
+            .start
+            .try_get_attribute(name)
+            .map_err(|e| malformed(&e))?
+        else {
+            return Ok(None);
+        };
+        attr.normalized_value(XmlVersion::Implicit1_0)
+            .map(Some)
+            .map_err(|e| malformed(&e))
+    }
+
+    fn required(&self, name: &str) -> Result<Cow<'_, str>, GeometryError> {
+        self.attr(name)?
+            .ok_or_else(|| import_failed(format!("<{}> is missing attribute '{name}'", self.name)))
+    }
+
+    fn number(&self, name: &str) -> Result<f64, GeometryError> {
+        let raw = self.required(name)?;
+        raw.trim()
+            .parse()
+            .map_err(|_| import_failed(format!("<{}> {name}=\"{raw}\" is not a number", self.name)))
+    }
+
+    fn index(&self, name: &str) -> Result<usize, GeometryError> {
+        let raw = self.required(name)?;
+        raw.trim()
+            .parse()
+            .map_err(|_| import_failed(format!("<{}> {name}=\"{raw}\" is not an index", self.name)))
+    }
+
+    /// The optional `transform` attribute; identity when absent.
+    fn transform(&self) -> Result<Transform, GeometryError> {
+        let Some(raw) = self.attr("transform")? else {
+            return Ok(IDENTITY);
+        };
+        let values: Vec<f64> = raw
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|_| import_failed(format!("bad transform \"{raw}\"")))?;
+        values
+            .try_into()
+            .map_err(|_| import_failed(format!("transform \"{raw}\" needs 12 values")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLE_MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xml:lang="en-US"
+       xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <!-- one right triangle in the XY plane -->
+  <resources>
+    <object id="1" type="model">
+      <mesh>
+        <vertices>
+          <vertex x="0" y="0" z="0" />
+          <vertex x="10" y="0" z="0" />
+          <vertex x="0" y="10" z="0" />
+        </vertices>
+        <triangles>
+          <triangle v1="0" v2="1" v3="2" />
+        </triangles>
+      </mesh>
+    </object>
+  </resources>
+  <build>
+    <item objectid="1" />
+  </build>
+</model>
+"#;
+
+    #[test]
+    fn parses_single_triangle() {
+        let mesh = parse_model(TRIANGLE_MODEL).expect("parse");
+        assert_eq!(
+            mesh.vertices,
+            [0.0, 0.0, 0.0, 10.0, 0.0, 0.0, 0.0, 10.0, 0.0]
+        );
+        assert_eq!(mesh.indices, [0, 1, 2]);
+        // Counter-clockwise winding seen from +Z gives a +Z normal.
+        assert_eq!(mesh.normals, [0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn applies_unit_and_item_transform() {
+        let xml = TRIANGLE_MODEL
+            .replace("unit=\"millimeter\"", "unit=\"centimeter\"")
+            .replace(
+                "<item objectid=\"1\" />",
+                "<item objectid=\"1\" transform=\"1 0 0 0 1 0 0 0 1 5 0 2\" />",
+            );
+        let mesh = parse_model(&xml).expect("parse");
+        // Translate by (5, 0, 2) cm, then scale every coordinate to mm.
+        assert_eq!(
+            mesh.vertices,
+            [50.0, 0.0, 20.0, 150.0, 0.0, 20.0, 50.0, 100.0, 20.0]
+        );
+    }
+
+    #[test]
+    fn concatenates_build_items_and_components() {
+        let xml = TRIANGLE_MODEL.replace(
+            "  </resources>",
+            r#"    <object id="2" type="model">
+      <components>
+        <component objectid="1" transform="1 0 0 0 1 0 0 0 1 0 0 7" />
+      </components>
+    </object>
+  </resources>"#,
+        );
+        let xml = xml.replace(
+            "<item objectid=\"1\" />",
+            "<item objectid=\"1\" /><item objectid=\"2\" />",
+        );
+        let mesh = parse_model(&xml).expect("parse");
+        assert_eq!(mesh.indices, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(&mesh.vertices[9..12], [0.0, 0.0, 7.0]);
+    }
+
+    #[test]
+    fn rejects_out_of_range_triangle_and_empty_build() {
+        let bad = TRIANGLE_MODEL.replace("v3=\"2\"", "v3=\"3\"");
+        assert!(matches!(
+            parse_model(&bad),
+            Err(GeometryError::ImportFailed { message }) if message.contains("out of range")
+        ));
+        let empty = TRIANGLE_MODEL.replace("<item objectid=\"1\" />", "");
+        assert!(matches!(
+            parse_model(&empty),
+            Err(GeometryError::ImportFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn extension_elements_with_core_local_names_are_skipped() {
+        let xml = TRIANGLE_MODEL
+            .replace(
+                "<model unit",
+                "<model xmlns:s=\"http://schemas.microsoft.com/3dmanufacturing/slice/2015/07\" unit",
+            )
+            .replace(
+                "  </resources>",
+                r#"    <s:slicestack id="9" zbottom="0">
+      <s:slice ztop="0.2">
+        <s:vertices>
+          <s:vertex x="0" y="0" />
+          <s:vertex x="10" y="0" />
+        </s:vertices>
+      </s:slice>
+    </s:slicestack>
+  </resources>"#,
+            );
+        let mesh = parse_model(&xml).expect("parse");
+        assert_eq!(mesh.indices, [0, 1, 2]);
+        assert_eq!(mesh.vertices.len(), 9);
+    }
+
+    #[test]
+    fn unknown_unit_is_rejected() {
+        let xml = TRIANGLE_MODEL.replace("millimeter", "furlong");
+        assert!(matches!(
+            parse_model(&xml),
+            Err(GeometryError::ImportFailed { message }) if message.contains("furlong")
+        ));
+    }
+
+    #[test]
+    fn load_3mf_fixture_returns_one_triangle() {
+        let path = std::path::PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/triangle.3mf"
+        ));
+        let mesh = load_3mf(&path).expect("load triangle.3mf");
+        assert_eq!(mesh.indices, [0, 1, 2]);
+        assert_eq!(mesh.vertices.len(), 9);
+    }
+
+    #[test]
+    fn load_3mf_missing_file_returns_file_not_found() {
+        let result = load_3mf(Path::new("/nonexistent/path/model.3mf"));
+        assert!(matches!(result, Err(GeometryError::FileNotFound)));
+    }
+}
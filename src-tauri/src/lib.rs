@@ -7,7 +7,9 @@ pub mod project;
 pub mod state;
 pub mod toolpath;
 
-use state::AppState;
+use std::sync::RwLock;
+
+use state::{AppState, UserPreferences};
 
 /// JamieCam Tauri application library entry point.
 ///
@@ -48,7 +50,10 @@ pub fn run() {
     tracing::info!("JamieCam starting");
 
     // ── Application state ────────────────────────────────────────────────────
-    let state = AppState::default();
+    let state = AppState {
+        preferences: RwLock::new(UserPreferences::load()),
+        ..AppState::default()
+    };
 
     // ── Tauri builder ────────────────────────────────────────────────────────
     tauri::Builder::default()
@@ -57,26 +62,66 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .invoke_handler(tauri::generate_handler![
             commands::file::open_model,
+            commands::file::refresh_model_reference,
+            commands::file::clear_mesh_cache,
+            commands::file::verify_model_checksum,
+            commands::file::detect_holes,
+            commands::file::list_faces,
+            commands::file::section_model_at_z,
+            commands::file::get_recent_files,
+            commands::demo::list_demo_models,
+            commands::demo::load_demo_model,
             commands::file::save_project,
             commands::file::load_project,
+            commands::file::autosave,
+            commands::file::restore_autosave,
             commands::file::new_project,
+            commands::file::new_project_from_template,
+            commands::file::project_diff,
             commands::project::get_project_snapshot,
             commands::tools::add_tool,
             commands::tools::edit_tool,
             commands::tools::delete_tool,
             commands::tools::list_tools,
+            commands::tools::get_tool,
+            commands::tools::copy_tool,
+            commands::tools::export_tool_library,
+            commands::tools::import_tool_library,
+            commands::tools::suggest_feeds_speeds,
             commands::stock::set_stock,
             commands::stock::get_stock,
             commands::stock::set_wcs,
             commands::stock::get_wcs,
+            commands::stock::get_oriented_bounding_box,
+            commands::stock::set_stock_from_model,
+            commands::stock::compute_stock_remainder,
             commands::operations::add_operation,
             commands::operations::edit_operation,
             commands::operations::delete_operation,
+            commands::operations::duplicate_operation,
+            commands::operations::move_operation,
             commands::operations::reorder_operations,
             commands::operations::list_operations,
+            commands::operations::get_operation,
             commands::toolpath::list_post_processors,
+            commands::toolpath::set_post_processor_dir,
             commands::toolpath::get_gcode_preview,
+            commands::toolpath::gcode_preview_summary,
+            commands::toolpath::invalidate_toolpath,
+            commands::toolpath::transform_toolpath,
+            commands::toolpath::program_z_range,
+            commands::toolpath::get_toolpath_stats,
             commands::file::export_gcode,
+            commands::file::export_gcode_string,
+            commands::file::prepare_output_path,
+            commands::file::export_operations_summary_json,
+            commands::file::export_mesh_stl,
+            commands::units::preview_unit_conversion,
+            commands::units::convert_project_units,
+            commands::validation::validate_operation_tool_types,
+            commands::validation::check_export_readiness,
+            commands::validation::validate_project,
+            commands::validation::validate_profile,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
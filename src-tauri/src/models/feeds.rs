@@ -0,0 +1,195 @@
+//! Feeds-and-speeds calculator.
+//!
+//! [`calc_feeds_speeds`] suggests a starting spindle speed and feed rate for a
+//! [`Tool`] cutting a given workpiece material. The numbers come from a small
+//! built-in table of surface speeds and chip loads per material class; they
+//! are conservative starting points, not a substitute for the tool maker's
+//! data.
+//!
+//! All lengths are millimetres: the tool diameter is read as mm and the feed
+//! rate is returned in mm/min.
+
+use std::f64::consts::PI;
+
+use serde::Serialize;
+
+use crate::models::Tool;
+
+/// Workpiece material class used to look up cutting data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaterialClass {
+    Aluminum,
+    Steel,
+    Stainless,
+    Wood,
+    Plastic,
+}
+
+impl MaterialClass {
+    /// Classify a material identifier such as `"aluminum-6061"` or
+    /// `"steel-stainless-304"` by its prefix.
+    ///
+    /// Returns `None` for materials outside the built-in table.
+    pub fn from_id(material: &str) -> Option<Self> {
+        let material = material.to_ascii_lowercase();
+        // Stainless ids start with "steel-", so check them first.
+        if material.starts_with("steel-stainless") || material.starts_with("stainless") {
+            Some(Self::Stainless)
+        } else if material.starts_with("steel") {
+            Some(Self::Steel)
+        } else if material.starts_with("aluminum") || material.starts_with("aluminium") {
+            Some(Self::Aluminum)
+        } else if material.starts_with("wood") {
+            Some(Self::Wood)
+        } else if material.starts_with("plastic") {
+            Some(Self::Plastic)
+        } else {
+            None
+        }
+    }
+
+    /// Surface speed in m/min for a carbide tool.
+    ///
+    /// Mid-range values from common endmill catalogues: aluminium alloys
+    /// 300, mild steel 100, austenitic stainless 60, MDF and hardwoods 500,
+    /// thermoplastics 250.
+    fn carbide_surface_speed(self) -> f64 {
+        match self {
+            Self::Aluminum => 300.0,
+            Self::Steel => 100.0,
+            Self::Stainless => 60.0,
+            Self::Wood => 500.0,
+            Self::Plastic => 250.0,
+        }
+    }
+
+    /// Chip load per flute as a fraction of the tool diameter.
+    ///
+    /// For a 10 mm tool this gives 0.10 mm/tooth in aluminium, 0.05 in steel,
+    /// 0.04 in stainless, 0.15 in wood, and 0.12 in plastic.
+    fn chip_load_ratio(self) -> f64 {
+        match self {
+            Self::Aluminum => 0.010,
+            Self::Steel => 0.005,
+            Self::Stainless => 0.004,
+            Self::Wood => 0.015,
+            Self::Plastic => 0.012,
+        }
+    }
+}
+
+/// Surface speed multiplier for HSS tools relative to carbide.
+const HSS_SPEED_FACTOR: f64 = 0.4;
+
+/// Suggested cutting parameters returned by [`calc_feeds_speeds`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedSpeed {
+    /// Spindle speed in RPM, rounded to the nearest whole revolution.
+    pub spindle_rpm: u32,
+    /// Feed rate in mm/min.
+    pub feed_mm_min: f64,
+}
+
+/// Compute a starting spindle speed and feed rate for `tool` in `material`.
+///
+/// RPM is `Vc * 1000 / (π * D)`, where `Vc` is the surface speed in m/min
+/// (from the table, or `surface_speed_override` when given) and `D` is the
+/// tool diameter in mm. HSS tools run at [`HSS_SPEED_FACTOR`] of the table
+/// speed; an override is used as-is. Feed is `RPM * flutes * chip load`.
+///
+/// Materials outside the table fall back to the stainless row, the most
+/// conservative one. Callers that need to reject unknown materials should
+/// check [`MaterialClass::from_id`] first.
+pub fn calc_feeds_speeds(
+    tool: &Tool,
+    material: &str,
+    surface_speed_override: Option<f64>,
+) -> FeedSpeed {
+    let class = MaterialClass::from_id(material).unwrap_or(MaterialClass::Stainless);
+    let surface_speed = surface_speed_override.unwrap_or_else(|| {
+        let base = class.carbide_surface_speed();
+        if tool.material.eq_ignore_ascii_case("hss") {
+            base * HSS_SPEED_FACTOR
+        } else {
+            base
+        }
+    });
+
+    if tool.diameter <= 0.0 {
+        return FeedSpeed {
+            spindle_rpm: 0,
+            feed_mm_min: 0.0,
+        };
+    }
+
+    let rpm = (surface_speed * 1000.0 / (PI * tool.diameter)).round();
+    let chip_load = class.chip_load_ratio() * tool.diameter;
+    FeedSpeed {
+        spindle_rpm: rpm as u32,
+        feed_mm_min: rpm * f64::from(tool.flute_count) * chip_load,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::models::ToolType;
+
+    fn make_tool(material: &str, diameter: f64, flutes: u32) -> Tool {
+        Tool {
+            id: Uuid::new_v4(),
+            name: "Test Tool".to_string(),
+            tool_type: ToolType::FlatEndmill,
+            material: material.to_string(),
+            diameter,
+            flute_count: flutes,
+            default_spindle_speed: None,
+            default_feed_rate: None,
+            tip_angle: None,
+        }
+    }
+
+    #[test]
+    fn carbide_endmill_in_aluminum_gives_sane_numbers() {
+        let tool = make_tool("carbide", 10.0, 4);
+        let fs = calc_feeds_speeds(&tool, "aluminum-6061", None);
+        // 300 m/min on a 10 mm tool ≈ 9549 RPM.
+        assert_eq!(fs.spindle_rpm, 9549);
+        assert!(
+            (3000.0..5000.0).contains(&fs.feed_mm_min),
+            "feed {}",
+            fs.feed_mm_min
+        );
+    }
+
+    #[test]
+    fn hss_runs_slower_than_carbide() {
+        let carbide = calc_feeds_speeds(&make_tool("carbide", 10.0, 4), "steel-mild", None);
+        let hss = calc_feeds_speeds(&make_tool("hss", 10.0, 4), "steel-mild", None);
+        assert!(hss.spindle_rpm < carbide.spindle_rpm);
+    }
+
+    #[test]
+    fn surface_speed_override_is_used() {
+        let tool = make_tool("carbide", 10.0, 2);
+        let fs = calc_feeds_speeds(&tool, "plastic-abs", Some(PI * 10.0));
+        assert_eq!(fs.spindle_rpm, 1000);
+        assert!((fs.feed_mm_min - 1000.0 * 2.0 * 0.12).abs() < 1e-9);
+    }
+
+    #[test]
+    fn stainless_is_classified_before_steel() {
+        assert_eq!(
+            MaterialClass::from_id("steel-stainless-304"),
+            Some(MaterialClass::Stainless)
+        );
+        assert_eq!(
+            MaterialClass::from_id("steel-mild"),
+            Some(MaterialClass::Steel)
+        );
+        assert_eq!(MaterialClass::from_id("titanium"), None);
+    }
+}
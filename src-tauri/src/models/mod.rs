@@ -1,3 +1,4 @@
+pub mod feeds;
 pub mod operation;
 pub mod stock;
 pub mod tool;
@@ -21,6 +21,65 @@ pub enum CompensationSide {
     Center,
 }
 
+/// How the tool enters the cut at the start of a profile or pocket pass.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LeadType {
+    /// Enter directly at the start point.
+    #[default]
+    None,
+    /// Straight ramp along the cut direction.
+    Line {
+        /// Lead length in project units.
+        length: f64,
+    },
+    /// Quarter-arc tangent to the cut direction (emitted as G2/G3).
+    Arc {
+        /// Arc radius in project units.
+        radius: f64,
+    },
+}
+
+/// Controller corner handling requested by an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathMode {
+    /// Decelerate to a stop at every corner (`G61`); sharper finish.
+    ExactStop,
+    /// Blend through corners at speed (`G64`); faster roughing.
+    Continuous,
+}
+
+/// Coolant an operation runs with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoolantMode {
+    /// No coolant.
+    Off,
+    /// `coolant.flood`, e.g. `M08`.
+    #[default]
+    Flood,
+    /// `coolant.mist`, e.g. `M07`.
+    Mist,
+    /// `coolant.air`.
+    Air,
+    /// `coolant.through_tool`, e.g. `M88`; posts without it reject the
+    /// operation.
+    ThroughTool,
+}
+
+/// How an operation's F word is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedMode {
+    /// Units per minute (`words.feed_per_min`, e.g. `G94`).
+    #[default]
+    PerMinute,
+    /// Units per spindle revolution (`words.feed_per_rev`, e.g. `G95`), as
+    /// rigid tapping and lathe work need.
+    PerRev,
+}
+
 /// Parameters for a Profile (contour) operation.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +90,29 @@ pub struct ProfileParams {
     pub stepdown: f64,
     /// Which side of the path the tool compensates to.
     pub compensation_side: CompensationSide,
+    /// Entry move shape; defaults to no lead-in.
+    #[serde(default)]
+    pub lead_in: LeadType,
+    /// Holding tabs that keep a cut-out part attached to the stock; `null`
+    /// for none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tabs: Option<TabParams>,
+    /// Material left on the wall and floor for a finishing pass, in project
+    /// units; 0 cuts to size.
+    #[serde(default)]
+    pub stock_to_leave: f64,
+}
+
+/// Holding tabs left along a profile (see [`crate::toolpath::tabs`]).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabParams {
+    /// Number of tabs, spaced evenly along the contour.
+    pub count: u32,
+    /// Length of each tab along the contour, in project units.
+    pub width: f64,
+    /// Tab height above the profile floor, in project units.
+    pub height: f64,
 }
 
 /// Parameters for a Pocket operation.
@@ -43,6 +125,13 @@ pub struct PocketParams {
     pub stepdown: f64,
     /// Radial stepover as a percentage of tool diameter (0–100).
     pub stepover_percent: f64,
+    /// Entry move shape; defaults to no lead-in.
+    #[serde(default)]
+    pub lead_in: LeadType,
+    /// Material left on the walls and floor for a finishing pass, in project
+    /// units; 0 cuts to size.
+    #[serde(default)]
+    pub stock_to_leave: f64,
 }
 
 /// Parameters for a Drill operation.
@@ -90,6 +179,16 @@ pub struct Operation {
     /// Type and parameters specific to this operation kind.
     #[serde(flatten)]
     pub params: OperationParams,
+    /// Corner handling for this operation; `None` uses the post-processor's
+    /// default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_mode: Option<PathMode>,
+    /// Coolant switched on for this operation; defaults to flood.
+    #[serde(default)]
+    pub coolant: CoolantMode,
+    /// Feed interpretation for this operation; defaults to per minute.
+    #[serde(default)]
+    pub feed_mode: FeedMode,
 }
 
 fn default_enabled() -> bool {
@@ -114,7 +213,17 @@ mod tests {
                 depth: 10.0,
                 stepdown: 2.5,
                 compensation_side: CompensationSide::Left,
+                lead_in: LeadType::None,
+                tabs: Some(TabParams {
+                    count: 4,
+                    width: 6.0,
+                    height: 2.0,
+                }),
+                stock_to_leave: 0.5,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         }
     }
 
@@ -128,7 +237,12 @@ mod tests {
                 depth: 15.0,
                 stepdown: 3.0,
                 stepover_percent: 45.0,
+                lead_in: LeadType::None,
+                stock_to_leave: 0.25,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         }
     }
 
@@ -142,6 +256,9 @@ mod tests {
                 depth: 20.0,
                 peck_depth: Some(5.0),
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         }
     }
 
@@ -180,6 +297,9 @@ mod tests {
                 depth: 20.0,
                 peck_depth: None,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         };
         let value = serde_json::to_value(&op).expect("to_value");
         let params = &value["params"];
@@ -202,6 +322,40 @@ mod tests {
         assert!(op.enabled, "enabled should default to true");
     }
 
+    #[test]
+    fn operation_coolant_defaults_to_flood_when_absent() {
+        let json = r#"{
+            "id": "aaaa0000-0000-0000-0000-000000000001",
+            "name": "Test",
+            "toolId": "7f3c1a00-0000-0000-0000-000000000001",
+            "type": "pocket",
+            "params": { "depth": 5.0, "stepdown": 1.0, "stepoverPercent": 50.0 }
+        }"#;
+        let op: Operation = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(op.coolant, CoolantMode::Flood);
+    }
+
+    #[test]
+    fn operation_coolant_serde_round_trip() {
+        let mut op = make_drill_op();
+        op.coolant = CoolantMode::ThroughTool;
+        let value = serde_json::to_value(&op).expect("to_value");
+        assert_eq!(value["coolant"], "through_tool");
+        let recovered: Operation = serde_json::from_value(value).expect("from_value");
+        assert_eq!(recovered, op);
+    }
+
+    #[test]
+    fn operation_feed_mode_serde_round_trip() {
+        let mut op = make_drill_op();
+        assert_eq!(op.feed_mode, FeedMode::PerMinute);
+        op.feed_mode = FeedMode::PerRev;
+        let value = serde_json::to_value(&op).expect("to_value");
+        assert_eq!(value["feedMode"], "per_rev");
+        let recovered: Operation = serde_json::from_value(value).expect("from_value");
+        assert_eq!(recovered, op);
+    }
+
     #[test]
     fn operation_type_field_at_top_level() {
         let op = make_pocket_op();
@@ -217,6 +371,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lead_type_serde_round_trip() {
+        for lead in [
+            LeadType::None,
+            LeadType::Line { length: 3.0 },
+            LeadType::Arc { radius: 2.5 },
+        ] {
+            let json = serde_json::to_string(&lead).expect("serialize");
+            let recovered: LeadType = serde_json::from_str(&json).expect("deserialize");
+            assert_eq!(lead, recovered);
+        }
+        let value = serde_json::to_value(LeadType::Arc { radius: 2.5 }).expect("to_value");
+        assert_eq!(value["type"], "arc");
+        assert_eq!(value["radius"], 2.5);
+    }
+
+    #[test]
+    fn lead_in_defaults_to_none_when_absent() {
+        let json = r#"{ "depth": 5.0, "stepdown": 1.0, "stepoverPercent": 50.0 }"#;
+        let params: PocketParams = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(params.lead_in, LeadType::None);
+    }
+
+    #[test]
+    fn stock_to_leave_defaults_to_zero_when_absent() {
+        let json = r#"{ "depth": 5.0, "stepdown": 1.0, "stepoverPercent": 50.0 }"#;
+        let params: PocketParams = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(params.stock_to_leave, 0.0);
+    }
+
     #[test]
     fn operation_fields_are_camel_case() {
         let op = make_profile_op();
@@ -231,5 +415,9 @@ mod tests {
             params.get("compensationSide").is_some(),
             "compensationSide must be camelCase"
         );
+        assert!(
+            params.get("stockToLeave").is_some(),
+            "stockToLeave must be camelCase"
+        );
     }
 }
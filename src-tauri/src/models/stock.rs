@@ -1,7 +1,7 @@
 //! Stock solid definition data model.
 //!
-//! [`StockDefinition`] is an enum so that future variants (cylinder, mesh)
-//! can be added without breaking the existing `Box` variant on disk.
+//! [`StockDefinition`] is an enum so that new variants (cylinder, and later
+//! mesh) can be added without breaking the existing `Box` variant on disk.
 //! See `docs/project-file-format.md` for the full JSON schema.
 
 use serde::{Deserialize, Serialize};
@@ -46,15 +46,62 @@ pub struct BoxDimensions {
     pub height: f64,
 }
 
+/// Dimensions and position of a cylindrical (round bar) stock solid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CylinderDimensions {
+    /// Centre of the bottom face in WCS coordinates.
+    #[serde(default)]
+    pub origin: Vec3,
+    /// Direction of the cylinder axis from `origin`; need not be normalised.
+    pub axis: Vec3,
+    /// Bar diameter.
+    pub diameter: f64,
+    /// Length along `axis`.
+    pub height: f64,
+}
+
 /// The stock material block for this project.
 ///
-/// Modelled as an internally-tagged enum so future variants (`Cylinder`,
-/// `Mesh`) can be added without a breaking format change. Serializes as
-/// `{ "type": "box", ... }` for the `Box` variant.
+/// Modelled as an internally-tagged enum so new variants (e.g. `Mesh`) can be
+/// added without a breaking format change. Serializes as
+/// `{ "type": "box", ... }` or `{ "type": "cylinder", ... }`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum StockDefinition {
     Box(BoxDimensions),
+    Cylinder(CylinderDimensions),
+}
+
+impl StockDefinition {
+    /// Returns `true` if `point` lies inside the stock or within `tolerance`
+    /// of its surface.
+    pub fn contains(&self, point: &Vec3, tolerance: f64) -> bool {
+        match self {
+            StockDefinition::Box(b) => {
+                let within =
+                    |v: f64, lo: f64, size: f64| v >= lo - tolerance && v <= lo + size + tolerance;
+                within(point.x, b.origin.x, b.width)
+                    && within(point.y, b.origin.y, b.depth)
+                    && within(point.z, b.origin.z, b.height)
+            }
+            StockDefinition::Cylinder(c) => {
+                let len = (c.axis.x.powi(2) + c.axis.y.powi(2) + c.axis.z.powi(2)).sqrt();
+                if len == 0.0 {
+                    return false;
+                }
+                let d = [
+                    point.x - c.origin.x,
+                    point.y - c.origin.y,
+                    point.z - c.origin.z,
+                ];
+                let along = (d[0] * c.axis.x + d[1] * c.axis.y + d[2] * c.axis.z) / len;
+                let radial_sq = d.iter().map(|v| v * v).sum::<f64>() - along * along;
+                let radius = c.diameter / 2.0 + tolerance;
+                along >= -tolerance && along <= c.height + tolerance && radial_sq <= radius * radius
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -74,6 +121,43 @@ mod tests {
         })
     }
 
+    #[test]
+    fn box_contains_interior_and_rejects_outside_points() {
+        let stock = make_box_stock();
+        let inside = Vec3 {
+            x: 100.0,
+            y: 0.0,
+            z: 27.0,
+        };
+        let outside = Vec3 {
+            x: 116.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        assert!(stock.contains(&inside, 0.0));
+        assert!(!stock.contains(&outside, 0.0));
+        assert!(stock.contains(&outside, 1.0));
+    }
+
+    #[test]
+    fn cylinder_contains_checks_radius_and_length() {
+        let stock = StockDefinition::Cylinder(CylinderDimensions {
+            origin: Vec3::zero(),
+            axis: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 2.0,
+            },
+            diameter: 20.0,
+            height: 50.0,
+        });
+        let point = |x, z| Vec3 { x, y: 0.0, z };
+        assert!(stock.contains(&point(9.0, 49.0), 0.0));
+        assert!(!stock.contains(&point(11.0, 10.0), 0.0));
+        assert!(!stock.contains(&point(0.0, 51.0), 0.0));
+        assert!(!stock.contains(&point(0.0, -1.0), 0.0));
+    }
+
     #[test]
     fn stock_serde_round_trip() {
         let original = make_box_stock();
@@ -98,7 +182,30 @@ mod tests {
     fn box_stock_origin_defaults_to_zero() {
         let json = r#"{"type":"box","width":10.0,"depth":10.0,"height":10.0}"#;
         let stock: StockDefinition = serde_json::from_str(json).expect("deserialize");
-        let StockDefinition::Box(b) = stock;
+        let StockDefinition::Box(b) = stock else {
+            panic!("expected box stock");
+        };
         assert_eq!(b.origin, Vec3::zero());
     }
+
+    #[test]
+    fn cylinder_stock_serializes_with_type_tag() {
+        let stock = StockDefinition::Cylinder(CylinderDimensions {
+            origin: Vec3::zero(),
+            axis: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            diameter: 50.0,
+            height: 120.0,
+        });
+        let value = serde_json::to_value(&stock).expect("to_value");
+        assert_eq!(value["type"], "cylinder");
+        assert_eq!(value["diameter"], 50.0);
+        assert_eq!(value["axis"]["z"], 1.0);
+
+        let back: StockDefinition = serde_json::from_value(value).expect("from_value");
+        assert_eq!(back, stock);
+    }
 }
@@ -23,6 +23,8 @@ pub enum ToolType {
     Reamer,
     BoringBar,
     ThreadMill,
+    ChamferMill,
+    SlotDrill,
 }
 
 /// A cutting tool entry in the project-local tool library.
@@ -55,6 +57,9 @@ pub struct Tool {
     /// Default feed rate in mm/min (or inch/min), if specified.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_feed_rate: Option<f64>,
+    /// Included tip angle in degrees, for chamfer mills, V-bits, and drills.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tip_angle: Option<f64>,
 }
 
 #[cfg(test)]
@@ -71,6 +76,7 @@ mod tests {
             flute_count: 4,
             default_spindle_speed: Some(15000),
             default_feed_rate: Some(2400.0),
+            tip_angle: None,
         }
     }
 
@@ -114,10 +120,38 @@ mod tests {
             flute_count: 2,
             default_spindle_speed: None,
             default_feed_rate: None,
+            tip_angle: None,
         };
         let value = serde_json::to_value(&tool).expect("to_value");
         assert!(value.get("defaultSpindleSpeed").is_none());
         assert!(value.get("defaultFeedRate").is_none());
+        assert!(value.get("tipAngle").is_none());
+    }
+
+    #[test]
+    fn new_tool_types_serialize_snake_case() {
+        assert_eq!(
+            serde_json::to_value(ToolType::ChamferMill).unwrap(),
+            "chamfer_mill"
+        );
+        assert_eq!(
+            serde_json::to_value(ToolType::SlotDrill).unwrap(),
+            "slot_drill"
+        );
+    }
+
+    #[test]
+    fn chamfer_mill_tip_angle_round_trips() {
+        let tool = Tool {
+            tool_type: ToolType::ChamferMill,
+            tip_angle: Some(90.0),
+            ..make_tool()
+        };
+        let value = serde_json::to_value(&tool).expect("to_value");
+        assert_eq!(value["type"], "chamfer_mill");
+        assert_eq!(value["tipAngle"], 90.0);
+        let recovered: Tool = serde_json::from_value(value).expect("from_value");
+        assert_eq!(recovered, tool);
     }
 
     #[test]
@@ -133,6 +167,8 @@ mod tests {
             ToolType::Reamer,
             ToolType::BoringBar,
             ToolType::ThreadMill,
+            ToolType::ChamferMill,
+            ToolType::SlotDrill,
         ];
         for tt in &types {
             let json = serde_json::to_string(tt).expect("serialize ToolType");
@@ -33,6 +33,34 @@ fn default_z_axis() -> Vec3 {
     }
 }
 
+/// Axes shorter than this are treated as zero-length.
+const MIN_AXIS_LENGTH: f64 = 1e-9;
+
+/// Largest `|cos θ|` between the X and Z axes accepted as perpendicular.
+const PERPENDICULAR_TOL: f64 = 1e-6;
+
+fn dot(a: &Vec3, b: &Vec3) -> f64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+fn cross(a: &Vec3, b: &Vec3) -> Vec3 {
+    Vec3 {
+        x: a.y * b.z - a.z * b.y,
+        y: a.z * b.x - a.x * b.z,
+        z: a.x * b.y - a.y * b.x,
+    }
+}
+
+/// `v` scaled to unit length, or `None` if it is (nearly) zero-length.
+fn unit(v: &Vec3) -> Option<Vec3> {
+    let len = dot(v, v).sqrt();
+    (len.is_finite() && len > MIN_AXIS_LENGTH).then(|| Vec3 {
+        x: v.x / len,
+        y: v.y / len,
+        z: v.z / len,
+    })
+}
+
 /// A named coordinate frame for positioning machining operations.
 ///
 /// Orientation is defined by two orthogonal unit vectors: `x_axis` and
@@ -56,6 +84,35 @@ pub struct WorkCoordinateSystem {
     pub z_axis: Vec3,
 }
 
+impl WorkCoordinateSystem {
+    /// Check that both axes are non-zero and perpendicular to each other.
+    ///
+    /// Lengths other than 1 are accepted; use [`Self::normalize`] to fix them.
+    pub fn validate(&self) -> Result<(), String> {
+        let x = unit(&self.x_axis).ok_or("x axis has zero length")?;
+        let z = unit(&self.z_axis).ok_or("z axis has zero length")?;
+        if dot(&x, &z).abs() > PERPENDICULAR_TOL {
+            return Err("x and z axes are not perpendicular".to_string());
+        }
+        Ok(())
+    }
+
+    /// Make the axes an orthonormal frame.
+    ///
+    /// `x_axis` is unit-normalized and keeps its direction; Y is derived as
+    /// `z_axis × x_axis`, and `z_axis` is rebuilt as `x × y` so it is exactly
+    /// perpendicular to X. Fails if either axis is zero-length or the two are
+    /// parallel.
+    pub fn normalize(&mut self) -> Result<(), String> {
+        let x = unit(&self.x_axis).ok_or("x axis has zero length")?;
+        unit(&self.z_axis).ok_or("z axis has zero length")?;
+        let y = unit(&cross(&self.z_axis, &x)).ok_or("x and z axes are parallel")?;
+        self.z_axis = cross(&x, &y);
+        self.x_axis = x;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +187,54 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn validate_rejects_zero_length_axis() {
+        let mut wcs = make_wcs();
+        wcs.z_axis = Vec3::zero();
+        let err = wcs.validate().expect_err("zero z axis must be rejected");
+        assert!(err.contains("z axis"), "{err}");
+        assert!(wcs.normalize().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_non_perpendicular_axes() {
+        let mut wcs = make_wcs();
+        wcs.z_axis = Vec3 {
+            x: 0.5,
+            y: 0.0,
+            z: 1.0,
+        };
+        let err = wcs.validate().expect_err("skewed axes must be rejected");
+        assert!(err.contains("perpendicular"), "{err}");
+    }
+
+    #[test]
+    fn normalize_orthonormalizes_near_orthogonal_axes() {
+        let mut wcs = make_wcs();
+        wcs.x_axis = Vec3 {
+            x: 2.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        wcs.z_axis = Vec3 {
+            x: 0.01,
+            y: 0.0,
+            z: 3.0,
+        };
+        wcs.normalize().expect("normalize");
+
+        assert_eq!(
+            wcs.x_axis,
+            Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0
+            }
+        );
+        assert!(dot(&wcs.x_axis, &wcs.z_axis).abs() < 1e-12);
+        assert!((dot(&wcs.z_axis, &wcs.z_axis) - 1.0).abs() < 1e-12);
+        assert!(wcs.z_axis.z > 0.999, "{:?}", wcs.z_axis);
+        wcs.validate().expect("normalized frame is valid");
+    }
 }
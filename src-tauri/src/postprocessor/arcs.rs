@@ -9,6 +9,33 @@ pub fn ijk_from_arc(start: &Vec3, center: &Vec3) -> (f64, f64, f64) {
     (center.x - start.x, center.y - start.y, center.z - start.z)
 }
 
+/// The principal plane an arc lies in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArcPlane {
+    Xy,
+    Xz,
+    Yz,
+}
+
+/// Classifies the plane of the arc through `start`, `end`, and `center`.
+///
+/// Arcs whose points share a Z value are XY (as are helical arcs, whose Z
+/// varies along with X and Y); otherwise a shared Y means XZ and a shared X
+/// means YZ.
+pub fn arc_plane(start: &Vec3, end: &Vec3, center: &Vec3) -> ArcPlane {
+    const EPSILON: f64 = 1e-9;
+    let flat = |a: f64, b: f64, c: f64| (a - b).abs() < EPSILON && (a - c).abs() < EPSILON;
+    if flat(start.z, end.z, center.z) {
+        ArcPlane::Xy
+    } else if flat(start.y, end.y, center.y) {
+        ArcPlane::Xz
+    } else if flat(start.x, end.x, center.x) {
+        ArcPlane::Yz
+    } else {
+        ArcPlane::Xy
+    }
+}
+
 /// Computes the sweep angle (in degrees) traversed by an arc from `start` to
 /// `end` around `center` in the XY plane, in the specified direction.
 ///
@@ -34,6 +61,114 @@ pub fn arc_sweep_degrees(start: &Vec3, center: &Vec3, end: &Vec3, clockwise: boo
     }
 }
 
+/// Returns the path length of an arc: the XY sweep of [`arc_sweep_degrees`]
+/// times the start radius, combined with the Z travel for helical arcs.
+pub fn arc_length(start: &Vec3, center: &Vec3, end: &Vec3, clockwise: bool) -> f64 {
+    let radius = (start.x - center.x).hypot(start.y - center.y);
+    let sweep = arc_sweep_degrees(start, center, end, clockwise);
+    (radius * sweep.to_radians()).hypot(end.z - start.z)
+}
+
+/// Splits a helix into arc segments of at most 90° each, returning
+/// `(start, center, end)` for every segment.
+///
+/// The helix turns from `start` around the vertical axis through `center`
+/// and lands exactly on `end`. Beyond the XY sweep from `start` to `end`
+/// (see [`arc_sweep_degrees`]), whole extra turns are added until the Z drop
+/// per revolution is no more than `pitch`; a non-positive `pitch` adds none.
+/// Each segment's center sits at the segment's start height, so `K` is zero
+/// in IJK output. Keeping segments under a half turn means every one of them
+/// is classified as an XY arc and can be written in R format.
+pub fn helix_segments(
+    start: &Vec3,
+    center: &Vec3,
+    end: &Vec3,
+    clockwise: bool,
+    pitch: f64,
+) -> Vec<(Vec3, Vec3, Vec3)> {
+    const QUARTER_TURN: f64 = 90.0;
+    let base = arc_sweep_degrees(start, center, end, clockwise);
+    let dz = end.z - start.z;
+    let extra_turns = if pitch > 0.0 {
+        ((dz.abs() / pitch * 360.0 - base) / 360.0).ceil().max(0.0)
+    } else {
+        0.0
+    };
+    let total = (base + 360.0 * extra_turns).to_radians();
+    let count = (total / QUARTER_TURN.to_radians()).ceil().max(1.0) as usize;
+
+    let radius = (start.x - center.x).hypot(start.y - center.y);
+    let angle_start = (start.y - center.y).atan2(start.x - center.x);
+    let point = |i: usize| {
+        if i == count {
+            return end.clone();
+        }
+        let t = i as f64 / count as f64;
+        let angle = if clockwise {
+            angle_start - total * t
+        } else {
+            angle_start + total * t
+        };
+        Vec3 {
+            x: center.x + radius * angle.cos(),
+            y: center.y + radius * angle.sin(),
+            z: start.z + dz * t,
+        }
+    };
+
+    let mut segments = Vec::with_capacity(count);
+    let mut from = start.clone();
+    for i in 1..=count {
+        let to = point(i);
+        let axis = Vec3 {
+            x: center.x,
+            y: center.y,
+            z: from.z,
+        };
+        segments.push((from, axis, to.clone()));
+        from = to;
+    }
+    segments
+}
+
+/// Returns the path length of a helix: the sum of its
+/// [`helix_segments`] arc lengths.
+pub fn helix_length(start: &Vec3, center: &Vec3, end: &Vec3, clockwise: bool, pitch: f64) -> f64 {
+    helix_segments(start, center, end, clockwise, pitch)
+        .iter()
+        .map(|(from, axis, to)| arc_length(from, axis, to, clockwise))
+        .sum()
+}
+
+/// Returns `true` if the arc sweeps exactly 180°, the case R format cannot
+/// express.
+pub fn is_semicircle(start: &Vec3, center: &Vec3, end: &Vec3, clockwise: bool) -> bool {
+    const HALF_CIRCLE: f64 = 180.0;
+    const EPSILON: f64 = 1e-9;
+    (arc_sweep_degrees(start, center, end, clockwise) - HALF_CIRCLE).abs() < EPSILON
+}
+
+/// Returns the point halfway along the arc from `start` to `end`.
+///
+/// The point lies on the arc's circle in XY at half the sweep of
+/// [`arc_sweep_degrees`]; Z is the average of the end points so helical
+/// arcs are split evenly.
+pub fn arc_midpoint(start: &Vec3, center: &Vec3, end: &Vec3, clockwise: bool) -> Vec3 {
+    let radius = (start.x - center.x).hypot(start.y - center.y);
+    let half_sweep = arc_sweep_degrees(start, center, end, clockwise).to_radians() / 2.0;
+    let angle_start = (start.y - center.y).atan2(start.x - center.x);
+    let angle = if clockwise {
+        angle_start - half_sweep
+    } else {
+        angle_start + half_sweep
+    };
+    Vec3 {
+        x: center.x + radius * angle.cos(),
+        y: center.y + radius * angle.sin(),
+        z: (start.z + end.z) / 2.0,
+    }
+}
+
 /// Returns the R-format radius for a G-code arc.
 ///
 /// * Minor arcs (sweep < 180°) → positive R.
@@ -53,18 +188,13 @@ pub fn r_from_arc(
         + (center.z - start.z).powi(2))
     .sqrt();
 
-    let sweep = arc_sweep_degrees(start, center, end, clockwise);
-
-    const HALF_CIRCLE: f64 = 180.0;
-    const EPSILON: f64 = 1e-9;
-
-    if (sweep - HALF_CIRCLE).abs() < EPSILON {
+    if is_semicircle(start, center, end, clockwise) {
         return Err(PostProcessorError::ArcError(
             "180\u{b0} arc is ambiguous in R format; use IJK instead".to_string(),
         ));
     }
 
-    if sweep > HALF_CIRCLE {
+    if arc_sweep_degrees(start, center, end, clockwise) > 180.0 {
         Ok(-radius)
     } else {
         Ok(radius)
@@ -219,6 +349,17 @@ mod tests {
         assert!((s - 360.0).abs() < 1e-9, "expected 360°, got {s}");
     }
 
+    #[test]
+    fn arc_length_of_quarter_circle() {
+        let len = arc_length(
+            &v(10.0, 0.0, 0.0),
+            &v(0.0, 0.0, 0.0),
+            &v(0.0, 10.0, 0.0),
+            false,
+        );
+        assert!((len - 5.0 * std::f64::consts::PI).abs() < 1e-9, "got {len}");
+    }
+
     // -------------------------------------------------------------------------
     // r_from_arc
     // -------------------------------------------------------------------------
@@ -273,6 +414,26 @@ mod tests {
         assert!(result.is_err(), "180° CW arc must return Err");
     }
 
+    // -------------------------------------------------------------------------
+    // arc_midpoint
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn midpoint_of_half_arc_follows_direction() {
+        let (start, end, center) = (v(10.0, 0.0, 0.0), v(-10.0, 0.0, -4.0), v(0.0, 0.0, 0.0));
+        let ccw = arc_midpoint(&start, &center, &end, false);
+        assert!(
+            (ccw.x).abs() < 1e-9 && (ccw.y - 10.0).abs() < 1e-9,
+            "{ccw:?}"
+        );
+        assert!(
+            (ccw.z + 2.0).abs() < 1e-9,
+            "helical Z must be halved: {ccw:?}"
+        );
+        let cw = arc_midpoint(&start, &center, &end, true);
+        assert!((cw.x).abs() < 1e-9 && (cw.y + 10.0).abs() < 1e-9, "{cw:?}");
+    }
+
     #[test]
     fn r_three_quarter_arc_ccw_negative() {
         // 270° CCW → major arc → negative R = -10
@@ -300,4 +461,77 @@ mod tests {
         assert!(r < 0.0, "major arc R must be negative, got {r}");
         assert!((r + 10.0).abs() < 1e-9, "expected R=-10, got {r}");
     }
+
+    #[test]
+    fn arc_plane_classifies_principal_planes() {
+        let o = v(0.0, 0.0, 0.0);
+        assert_eq!(
+            arc_plane(&v(1.0, 0.0, 0.0), &v(0.0, 1.0, 0.0), &o),
+            ArcPlane::Xy
+        );
+        assert_eq!(
+            arc_plane(&v(1.0, 0.0, 0.0), &v(0.0, 0.0, 1.0), &o),
+            ArcPlane::Xz
+        );
+        assert_eq!(
+            arc_plane(&v(0.0, 1.0, 0.0), &v(0.0, 0.0, 1.0), &o),
+            ArcPlane::Yz
+        );
+        // Helical: Z changes along an XY arc.
+        assert_eq!(
+            arc_plane(&v(1.0, 0.0, 0.0), &v(0.0, 1.0, -1.0), &o),
+            ArcPlane::Xy
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    // helix_segments
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn one_turn_helix_splits_into_quarters() {
+        let segments = helix_segments(
+            &v(10.0, 0.0, 0.0),
+            &v(0.0, 0.0, 0.0),
+            &v(10.0, 0.0, -2.0),
+            false,
+            2.0,
+        );
+        assert_eq!(segments.len(), 4);
+        assert!((segments[0].2.y - 10.0).abs() < 1e-9, "{:?}", segments[0]);
+        assert!((segments[0].2.z + 0.5).abs() < 1e-9, "{:?}", segments[0]);
+        assert_eq!(segments[3].2, v(10.0, 0.0, -2.0));
+        for (from, axis, _) in &segments {
+            assert_eq!(axis.z, from.z, "K must be zero");
+        }
+    }
+
+    #[test]
+    fn helix_adds_turns_to_respect_pitch() {
+        // 3 mm drop at 1 mm per turn needs three full turns.
+        let segments = helix_segments(
+            &v(5.0, 0.0, 0.0),
+            &v(0.0, 0.0, 0.0),
+            &v(5.0, 0.0, -3.0),
+            true,
+            1.0,
+        );
+        assert_eq!(segments.len(), 12);
+        for (from, axis, to) in &segments {
+            assert_eq!(arc_plane(from, to, axis), ArcPlane::Xy);
+        }
+    }
+
+    #[test]
+    fn helix_length_matches_unrolled_path() {
+        let length = helix_length(
+            &v(10.0, 0.0, 0.0),
+            &v(0.0, 0.0, 0.0),
+            &v(10.0, 0.0, -2.0),
+            false,
+            2.0,
+        );
+        let expected = (2.0 * std::f64::consts::PI * 10.0).hypot(2.0);
+        assert!((length - expected).abs() < 1e-6, "{length} vs {expected}");
+    }
 }
@@ -9,6 +9,17 @@ pub enum WordValue {
     Str(String),
 }
 
+/// Formatting class of a word, used to pick its number of decimal places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordClass {
+    /// Rotary axis words (A, B, C).
+    Rotary,
+    /// The feed rate F word.
+    Feed,
+    /// Everything else: linear axes, arc parameters, spindle speed.
+    Other,
+}
+
 /// A single G-code word: a letter paired with a value.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Word {
@@ -30,18 +41,30 @@ impl Word {
             value: WordValue::Int(value),
         }
     }
+
+    /// Returns the formatting class implied by the word's letter.
+    pub fn class(&self) -> WordClass {
+        match self.letter.to_ascii_uppercase() {
+            'A' | 'B' | 'C' => WordClass::Rotary,
+            'F' => WordClass::Feed,
+            _ => WordClass::Other,
+        }
+    }
 }
 
 /// A single line of G-code, holding words in canonical order and an optional comment.
 pub struct Block {
     words: Vec<Word>,
     comment: Option<String>,
+    block_delete: bool,
 }
 
 impl Block {
     /// Renders the block to a G-code string.
     ///
     /// If `line_number` is `Some`, an N-word is prepended before all other words.
+    /// A block-delete block starts with `fmt.format.block_delete_char`, ahead
+    /// of the N-word.
     /// Coordinate values are formatted using `fmt.format` settings.
     /// Comments are wrapped using `fmt.program.comment_open` / `comment_close`.
     pub fn render(&self, line_number: Option<u32>, fmt: &PostProcessorConfig) -> String {
@@ -49,6 +72,10 @@ impl Block {
         let mut line = String::new();
         let mut needs_sep = false;
 
+        if self.block_delete {
+            line.push_str(&fmt.format.block_delete_char);
+        }
+
         if let Some(n) = line_number {
             line.push_str(&format!("N{}", n));
             needs_sep = true;
@@ -83,7 +110,7 @@ fn render_word(word: &Word, fmt: &PostProcessorConfig) -> String {
             word.letter,
             format_coord(
                 *v,
-                fmt.format.decimal_places,
+                decimal_places(word.class(), fmt),
                 !fmt.format.trailing_zeros,
                 fmt.format.leading_zero_suppression,
             )
@@ -93,10 +120,21 @@ fn render_word(word: &Word, fmt: &PostProcessorConfig) -> String {
     }
 }
 
+/// Number of decimal places for a word of `class`, falling back to the
+/// global `format.decimal_places` when no per-class override is set.
+fn decimal_places(class: WordClass, fmt: &PostProcessorConfig) -> u32 {
+    let places = match class {
+        WordClass::Rotary => fmt.format.decimal_places_rotary,
+        WordClass::Feed => fmt.format.decimal_places_feed,
+        WordClass::Other => None,
+    };
+    places.unwrap_or(fmt.format.decimal_places)
+}
+
 /// Builds a [`Block`] by accumulating words in named slots, then emitting them
 /// in canonical G-code word order on [`build`](BlockBuilder::build):
 ///
-/// motion G → other G-codes → X Y Z A B C → I J K R → F → S → T → coolant M → spindle M
+/// motion G → other G-codes → X Y Z A B C → I J K R Q → F → S → T → coolant M → spindle M
 #[derive(Default)]
 pub struct BlockBuilder {
     motion: Option<String>,
@@ -111,12 +149,14 @@ pub struct BlockBuilder {
     j: Option<f64>,
     k: Option<f64>,
     r: Option<f64>,
+    q: Option<f64>,
     feed_val: Option<f64>,
     spindle_speed: Option<f64>,
     tool_num: Option<u32>,
     coolant: Option<String>,
     spindle_m_code: Option<String>,
     comment_text: Option<String>,
+    block_delete: bool,
 }
 
 impl BlockBuilder {
@@ -162,6 +202,12 @@ impl BlockBuilder {
         self
     }
 
+    /// Sets the canned-cycle peck increment Q word.
+    pub fn peck(mut self, value: f64) -> Self {
+        self.q = Some(value);
+        self
+    }
+
     /// Sets the feed rate F word.
     pub fn feed(mut self, value: f64) -> Self {
         self.feed_val = Some(value);
@@ -198,6 +244,14 @@ impl BlockBuilder {
         self
     }
 
+    /// Marks the block as optional: skipped by the controller while block
+    /// delete is switched on. No effect when `format.block_delete_char` is
+    /// empty.
+    pub fn block_delete(mut self, on: bool) -> Self {
+        self.block_delete = on;
+        self
+    }
+
     /// Consumes the builder and produces a [`Block`] with words in canonical order.
     pub fn build(self) -> Block {
         let mut words: Vec<Word> = Vec::with_capacity(16 + self.g_codes.len());
@@ -229,7 +283,13 @@ impl BlockBuilder {
             }
         }
 
-        for (letter, opt_val) in [('I', self.i), ('J', self.j), ('K', self.k), ('R', self.r)] {
+        for (letter, opt_val) in [
+            ('I', self.i),
+            ('J', self.j),
+            ('K', self.k),
+            ('R', self.r),
+            ('Q', self.q),
+        ] {
             if let Some(v) = opt_val {
                 words.push(Word::coord(letter, v));
             }
@@ -264,6 +324,7 @@ impl BlockBuilder {
         Block {
             words,
             comment: self.comment_text,
+            block_delete: self.block_delete,
         }
     }
 }
@@ -454,6 +515,20 @@ program_stop = "M00"
         assert!(pos("K3") < pos("R5"));
     }
 
+    #[test]
+    fn peck_word_follows_r_and_precedes_feed() {
+        let fmt = default_fmt();
+        let block = BlockBuilder::new()
+            .feed(80.0)
+            .peck(5.0)
+            .arc_param('R', 2.0)
+            .motion("G83")
+            .build();
+
+        let line = block.render(None, &fmt);
+        assert_eq!(line.trim_end(), "G83 R2 Q5 F80");
+    }
+
     // -------------------------------------------------------------------------
     // Line numbers
     // -------------------------------------------------------------------------
@@ -588,6 +663,50 @@ program_stop = "M00"
         assert!(line.contains("X1.500"), "got: {:?}", line);
     }
 
+    #[test]
+    fn rotary_decimal_places_override_applies_only_to_rotary_words() {
+        let toml = base_toml()
+            .replace(
+                "decimal_places = 3",
+                "decimal_places = 4\ndecimal_places_rotary = 3",
+            )
+            .replace("trailing_zeros = false", "trailing_zeros = true");
+        let fmt = config::parse(&toml).unwrap();
+        let block = BlockBuilder::new()
+            .axis('X', 1.5)
+            .axis('A', 1.5)
+            .feed(100.0)
+            .build();
+        let line = block.render(None, &fmt);
+        assert!(line.contains("X1.5000"), "got: {:?}", line);
+        assert!(line.contains("A1.500 "), "got: {:?}", line);
+        assert!(line.contains("F100.0000"), "got: {:?}", line);
+    }
+
+    #[test]
+    fn feed_decimal_places_override_applies_to_f_word() {
+        let toml = base_toml()
+            .replace(
+                "decimal_places = 3",
+                "decimal_places = 3\ndecimal_places_feed = 1",
+            )
+            .replace("trailing_zeros = false", "trailing_zeros = true");
+        let fmt = config::parse(&toml).unwrap();
+        let block = BlockBuilder::new().axis('X', 2.0).feed(250.0).build();
+        let line = block.render(None, &fmt);
+        assert!(line.contains("X2.000"), "got: {:?}", line);
+        assert!(line.contains("F250.0"), "got: {:?}", line);
+        assert!(!line.contains("F250.00"), "got: {:?}", line);
+    }
+
+    #[test]
+    fn word_class_follows_letter() {
+        assert_eq!(Word::coord('B', 0.0).class(), WordClass::Rotary);
+        assert_eq!(Word::coord('F', 0.0).class(), WordClass::Feed);
+        assert_eq!(Word::coord('X', 0.0).class(), WordClass::Other);
+        assert_eq!(Word::coord('S', 0.0).class(), WordClass::Other);
+    }
+
     #[test]
     fn leading_zero_suppression() {
         let toml = base_toml().replace(
@@ -612,6 +731,34 @@ program_stop = "M00"
         assert!(line.trim_end().contains("T7"), "got: {:?}", line);
     }
 
+    // -------------------------------------------------------------------------
+    // Block delete
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn block_delete_char_precedes_line_number() {
+        let toml = base_toml().replace("block_delete_char = \"\"", "block_delete_char = \"/\"");
+        let fmt = config::parse(&toml).unwrap();
+        let block = BlockBuilder::new()
+            .motion("G01")
+            .axis('X', 10.0)
+            .block_delete(true)
+            .build();
+        assert_eq!(block.render(Some(10), &fmt), "/N10 G01 X10\n");
+        assert_eq!(block.render(None, &fmt), "/G01 X10\n");
+    }
+
+    #[test]
+    fn block_delete_is_noop_with_empty_char() {
+        let fmt = default_fmt();
+        let block = BlockBuilder::new()
+            .motion("G01")
+            .axis('X', 10.0)
+            .block_delete(true)
+            .build();
+        assert_eq!(block.render(Some(10), &fmt), "N10 G01 X10\n");
+    }
+
     // -------------------------------------------------------------------------
     // Empty block
     // -------------------------------------------------------------------------
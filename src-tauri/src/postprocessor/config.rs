@@ -1,4 +1,5 @@
 use super::PostProcessorError;
+use crate::models::operation::PathMode;
 
 /// Output units for the generated G-code program.
 #[derive(Debug, serde::Deserialize)]
@@ -27,6 +28,18 @@ pub enum ArcFormat {
     R,
 }
 
+/// Canned-cycle call syntax (`cycles.style`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CycleStyle {
+    /// Modal G-code cycle with Z/R/Q/F words, e.g. `G81 Z-10. R2. F100.`.
+    #[default]
+    Fanuc,
+    /// Siemens cycle call with positional parameters, made modal with
+    /// `MCALL`, e.g. `MCALL CYCLE81(5, 2, 0, -10)`.
+    Siemens,
+}
+
 /// Fully describes one CNC controller. Loaded from a TOML file.
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -43,6 +56,12 @@ pub struct PostProcessorConfig {
     pub coolant: CoolantConfig,
     pub cycles: CyclesConfig,
     pub misc: MiscConfig,
+    #[serde(default)]
+    pub feeds: FeedsConfig,
+    #[serde(default)]
+    pub path_mode: Option<PathModeConfig>,
+    #[serde(default)]
+    pub probe: Option<ProbeConfig>,
 }
 
 /// `[meta]` — identity and display information.
@@ -67,6 +86,25 @@ pub struct MachineConfig {
     /// (RTCP / TCPM). Requires `tool_change.rtcp_on` to be set.
     #[serde(default)]
     pub rtcp_supported: bool,
+    /// Number of tool pockets in the carousel. Tool numbers above this are
+    /// rejected before generation; `None` means unchecked.
+    #[serde(default)]
+    pub tool_capacity: Option<u32>,
+    /// Rapid traverse rate in mm/min used for cycle-time estimates;
+    /// `None` assumes [`DEFAULT_RAPID_RATE_MM_MIN`].
+    #[serde(default)]
+    pub rapid_rate_mm_min: Option<f64>,
+}
+
+/// Rapid traverse rate (mm/min) assumed when a post does not set
+/// `machine.rapid_rate_mm_min`.
+pub const DEFAULT_RAPID_RATE_MM_MIN: f64 = 10_000.0;
+
+impl MachineConfig {
+    /// The machine's rapid traverse rate in mm/min.
+    pub fn rapid_rate(&self) -> f64 {
+        self.rapid_rate_mm_min.unwrap_or(DEFAULT_RAPID_RATE_MM_MIN)
+    }
 }
 
 /// `[format]` — output formatting options.
@@ -76,14 +114,34 @@ pub struct FormatConfig {
     pub line_numbers: bool,
     pub line_number_start: u32,
     pub line_number_increment: u32,
+    /// Highest N-word the controller accepts; numbering wraps back to
+    /// `line_number_start` past it. 0 never wraps.
     pub line_number_max: u32,
     pub decimal_places: u32,
+    /// Decimal places for rotary A/B/C words; `None` uses `decimal_places`.
+    pub decimal_places_rotary: Option<u32>,
+    /// Decimal places for the F word; `None` uses `decimal_places`.
+    pub decimal_places_feed: Option<u32>,
     pub trailing_zeros: bool,
     pub leading_zero_suppression: bool,
     pub word_separator: String,
     pub eol: String,
     pub percent_delimiters: bool,
     pub block_delete_char: String,
+    /// Number-formatting overrides used when `machine.units = "imperial"`.
+    pub inch_profile: Option<FormatProfile>,
+    /// Number-formatting overrides used when `machine.units = "metric"`.
+    pub metric_profile: Option<FormatProfile>,
+}
+
+/// `[format.inch_profile]` / `[format.metric_profile]` — per-unit number
+/// formatting. Absent keys keep the flat `[format]` value.
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FormatProfile {
+    pub decimal_places: Option<u32>,
+    pub trailing_zeros: Option<bool>,
+    pub leading_zero_suppression: Option<bool>,
 }
 
 /// `[axes.limits]` — software limits for rotary axes.
@@ -109,6 +167,18 @@ pub struct AxesConfig {
     pub b: Option<String>,
     pub c: Option<String>,
     pub limits: Option<AxisLimits>,
+    /// When true, rotary axes without `limits` rotate continuously and are
+    /// driven along the shortest angular path (see [`super::rotary`]).
+    #[serde(default)]
+    pub rotary_rollover: bool,
+}
+
+impl AxesConfig {
+    /// Returns `true` when rotary moves should take the shortest path and
+    /// accumulate rotation: rollover is enabled and no travel limits are set.
+    pub fn rollover_active(&self) -> bool {
+        self.rotary_rollover && self.limits.is_none()
+    }
 }
 
 /// `[program]` — program structure: numbering, comments, header/footer.
@@ -122,6 +192,20 @@ pub struct ProgramConfig {
     pub comment_close: String,
     pub header: Vec<String>,
     pub footer: Vec<String>,
+    /// When true, footer lines equal to `spindle.off` are dropped so the
+    /// spindle keeps running into a chained program. Coolant off and the
+    /// end code are still emitted.
+    #[serde(default)]
+    pub keep_spindle_on_end: bool,
+    /// Codes emitted together as one block ahead of the footer so the program
+    /// ends with cutter comp, tool length offset and canned cycles cancelled.
+    /// An empty list omits the block.
+    #[serde(default = "default_end_cancel")]
+    pub end_cancel: Vec<String>,
+}
+
+fn default_end_cancel() -> Vec<String> {
+    ["G40", "G49", "G80"].map(String::from).to_vec()
 }
 
 /// `[tool_change]` — tool-change sequence templates.
@@ -131,11 +215,36 @@ pub struct ToolChangeConfig {
     pub pre: Vec<String>,
     /// Template string for the tool-change block. Must contain `{tool_number}`.
     pub command: String,
+    /// Template for the tool length offset block emitted right after `command`,
+    /// e.g. `"G43 H{tool_number}"`. `None` leaves offset activation to `post`.
+    #[serde(default)]
+    pub length_offset: Option<String>,
     pub post: Vec<String>,
+    /// Skip `pre` and `command` for the program's first toolpath when it
+    /// uses T1, for machines that start with T1 already in the spindle.
+    /// `length_offset` and `post` are still emitted.
     pub suppress_first_if_t1: bool,
-    /// RTCP activation code emitted after a tool change on RTCP-capable machines.
+    /// Random-access ATC: emit the next tool's T-word before the current
+    /// toolpath's final block so the carousel pre-positions during the cut.
+    #[serde(default)]
+    pub preload: bool,
+    /// Z to rapid up to before every tool change that follows cutting, so
+    /// the turret never indexes at cutting depth. `None` falls back to the
+    /// `clearance_z` generate option; with neither, no retract is emitted.
+    #[serde(default)]
+    pub safe_z: Option<f64>,
+    /// XY position to rapid to after the `safe_z` retract, e.g. clear of the
+    /// fixture for a manual change. `None` changes tools in place.
+    #[serde(default)]
+    pub safe_xy: Option<[f64; 2]>,
+    /// RTCP activation template emitted right after `command` (before
+    /// `length_offset`) on RTCP-capable machines, e.g. `"G43.4 H{tool_number}"`.
     /// Required when `machine.rtcp_supported = true`.
     pub rtcp_on: Option<String>,
+    /// Emit `spindle.orient` after `pre` and before `command`, for changers
+    /// that need the spindle keys at a known angle. Requires `spindle.orient`.
+    #[serde(default)]
+    pub orient_before_change: bool,
 }
 
 /// `[motion]` — motion command words and arc configuration.
@@ -150,6 +259,10 @@ pub struct MotionConfig {
     pub plane_xy: String,
     pub plane_xz: String,
     pub plane_yz: String,
+    /// Leave an XZ/YZ plane active after its arc instead of restoring
+    /// `plane_xy` before the next non-arc move.
+    #[serde(default)]
+    pub keep_arc_plane: bool,
 }
 
 /// `[words]` — feed/speed/mode word letters and codes.
@@ -160,7 +273,16 @@ pub struct WordsConfig {
     pub spindle: String,
     pub tool: String,
     pub tool_offset: String,
+    /// Dwell code, e.g. `"G4"`; defaults to `"G4"` when absent.
+    #[serde(default = "default_dwell_code")]
+    pub dwell_code: String,
+    /// Letter carrying the dwell time in the dwell block (`"P"`, or `"F"` on
+    /// Siemens).
     pub dwell: String,
+    /// Write the dwell time in milliseconds instead of seconds, as Fanuc
+    /// does for `G04 P`.
+    #[serde(default)]
+    pub dwell_in_ms: bool,
     pub feed_per_min: String,
     pub feed_per_rev: String,
     pub inverse_time: String,
@@ -168,6 +290,10 @@ pub struct WordsConfig {
     pub incremental: String,
 }
 
+fn default_dwell_code() -> String {
+    "G4".to_string()
+}
+
 /// `[spindle]` — spindle control codes.
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -179,6 +305,39 @@ pub struct SpindleConfig {
     pub max_rpm: u32,
 }
 
+/// `[feeds]` — limits applied to output feed words. Optional section.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FeedsConfig {
+    /// Lowest feed rate ever emitted. Feeds that scaling drives below it are
+    /// raised to it with a warning; `None` disables the floor.
+    #[serde(default)]
+    pub min_feed_rate: Option<f64>,
+}
+
+/// `[path_mode]` — corner-handling codes. Optional section; without it no
+/// path-mode code is emitted.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PathModeConfig {
+    /// Exact-stop mode code (e.g. `"G61"`).
+    pub exact_stop: String,
+    /// Continuous-path mode code (e.g. `"G64"`).
+    pub continuous: String,
+    /// Mode for toolpaths that do not request one; `None` emits nothing for them.
+    #[serde(default)]
+    pub default: Option<PathMode>,
+}
+
+/// `[probe]` — touch-probe motion codes. Optional section; without it
+/// toolpaths containing probe moves are rejected.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ProbeConfig {
+    /// Probe-toward code that stops on contact (e.g. `"G38.2"`, Fanuc `"G31"`).
+    pub toward: String,
+}
+
 /// `[coolant]` — coolant control codes.
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -196,6 +355,9 @@ pub struct CoolantConfig {
 pub struct CyclesConfig {
     /// When `false`, all cycles are expanded to explicit linear moves.
     pub supported: bool,
+    /// Call syntax for the cycle codes below. Defaults to Fanuc G-codes.
+    #[serde(default)]
+    pub style: CycleStyle,
     /// Spot / through-drill cycle code (e.g. `"G81"`). Required when `supported = true`.
     pub drill: Option<String>,
     pub peck: Option<String>,
@@ -219,13 +381,37 @@ pub struct MiscConfig {
 }
 
 /// Parse a TOML string into a [`PostProcessorConfig`], running validation.
+///
+/// The format profile matching `machine.units` is folded into the flat
+/// `[format]` settings, so downstream formatting only reads `format.*`.
 pub fn parse(toml_str: &str) -> Result<PostProcessorConfig, PostProcessorError> {
-    let cfg: PostProcessorConfig =
+    let mut cfg: PostProcessorConfig =
         toml::from_str(toml_str).map_err(|e| PostProcessorError::Config(e.to_string()))?;
     validate(&cfg)?;
+    apply_unit_profile(&mut cfg);
     Ok(cfg)
 }
 
+fn apply_unit_profile(cfg: &mut PostProcessorConfig) {
+    let profile = match cfg.machine.units {
+        Units::Imperial => cfg.format.inch_profile,
+        Units::Metric => cfg.format.metric_profile,
+    };
+    let Some(profile) = profile else {
+        return;
+    };
+    let format = &mut cfg.format;
+    if let Some(places) = profile.decimal_places {
+        format.decimal_places = places;
+    }
+    if let Some(trailing) = profile.trailing_zeros {
+        format.trailing_zeros = trailing;
+    }
+    if let Some(suppress) = profile.leading_zero_suppression {
+        format.leading_zero_suppression = suppress;
+    }
+}
+
 fn validate(cfg: &PostProcessorConfig) -> Result<(), PostProcessorError> {
     // `{tool_number}` must appear in tool_change.command.
     if !cfg.tool_change.command.contains("{tool_number}") {
@@ -259,6 +445,25 @@ fn validate(cfg: &PostProcessorConfig) -> Result<(), PostProcessorError> {
         }
     }
 
+    // Orienting before a change needs an orient code.
+    if cfg.tool_change.orient_before_change {
+        let orient_present = cfg.spindle.orient.as_deref().is_some_and(|s| !s.is_empty());
+        if !orient_present {
+            return Err(PostProcessorError::Config(
+                "spindle.orient must be defined when tool_change.orient_before_change = true"
+                    .to_string(),
+            ));
+        }
+    }
+
+    if let Some(rate) = cfg.machine.rapid_rate_mm_min {
+        if rate.is_nan() || rate <= 0.0 {
+            return Err(PostProcessorError::Config(format!(
+                "machine.rapid_rate_mm_min must be positive, got {rate}"
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -402,6 +607,28 @@ program_stop = "M00"
         assert!(parse(&toml).is_ok());
     }
 
+    #[test]
+    fn orient_before_change_without_orient_code_returns_error() {
+        let toml = minimal_valid_toml().replace(
+            "suppress_first_if_t1 = false",
+            "suppress_first_if_t1 = false\norient_before_change = true",
+        );
+        let err = parse(&toml).unwrap_err();
+        assert!(matches!(err, PostProcessorError::Config(_)));
+        assert!(err.to_string().contains("spindle.orient"));
+    }
+
+    #[test]
+    fn non_positive_rapid_rate_returns_error() {
+        let toml = minimal_valid_toml().replace(
+            "[machine]\nunits = \"metric\"\nmax_axes = 3",
+            "[machine]\nunits = \"metric\"\nmax_axes = 3\nrapid_rate_mm_min = 0.0",
+        );
+        let err = parse(&toml).unwrap_err();
+        assert!(matches!(err, PostProcessorError::Config(_)));
+        assert!(err.to_string().contains("rapid_rate_mm_min"));
+    }
+
     #[test]
     fn rtcp_supported_without_rtcp_on_returns_error() {
         let toml = minimal_valid_toml().replace(
@@ -443,4 +670,33 @@ program_stop = "M00"
         // minimal_valid_toml already has supported = false with no drill code
         assert!(parse(&minimal_valid_toml()).is_ok());
     }
+
+    #[test]
+    fn unit_profile_matching_machine_units_overrides_flat_format() {
+        let toml = minimal_valid_toml().replace(
+            "block_delete_char = \"\"",
+            "block_delete_char = \"\"\n\n[format.inch_profile]\ndecimal_places = 4\n\n\
+             [format.metric_profile]\nleading_zero_suppression = true",
+        );
+        let cfg = parse(&toml).unwrap();
+        // Metric machine: the inch profile is ignored, the metric one applies.
+        assert_eq!(cfg.format.decimal_places, 3);
+        assert!(cfg.format.leading_zero_suppression);
+        assert!(!cfg.format.trailing_zeros, "absent keys keep flat value");
+    }
+
+    #[test]
+    fn rotary_rollover_requires_unlimited_axes() {
+        let toml = minimal_valid_toml().replace("z = \"Z\"", "z = \"Z\"\nrotary_rollover = true");
+        let cfg = parse(&toml).unwrap();
+        assert!(cfg.axes.rollover_active());
+
+        let limited = toml.replace(
+            "rotary_rollover = true",
+            "rotary_rollover = true\n\n[axes.limits]\na_min = -120.0\na_max = 120.0\n\
+             b_min = -120.0\nb_max = 120.0\nc_min = -360.0\nc_max = 360.0",
+        );
+        let cfg = parse(&limited).unwrap();
+        assert!(!cfg.axes.rollover_active());
+    }
 }
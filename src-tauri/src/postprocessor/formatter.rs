@@ -28,6 +28,37 @@ pub fn format_coord(
     s
 }
 
+/// Formats a program number with a printf-style `format` such as `"%04d"`.
+///
+/// Only the integer conversions `%d`, `%Nd` (space-padded to `N`) and `%0Nd`
+/// (zero-padded to `N`) are understood; text around the conversion is kept.
+/// A format without a recognised conversion yields the bare number.
+pub fn format_program_number(format: &str, number: u32) -> String {
+    let Some(start) = format.find('%') else {
+        return number.to_string();
+    };
+    let spec = &format[start + 1..];
+    let Some(end) = spec.find('d') else {
+        return number.to_string();
+    };
+    let flags = &spec[..end];
+    let zero_pad = flags.starts_with('0');
+    let Ok(width) = (if flags.is_empty() {
+        Ok(0)
+    } else {
+        flags.parse::<usize>()
+    }) else {
+        return number.to_string();
+    };
+
+    let digits = if zero_pad {
+        format!("{:0width$}", number, width = width)
+    } else {
+        format!("{:>width$}", number, width = width)
+    };
+    format!("{}{}{}", &format[..start], digits, &spec[end + 1..])
+}
+
 /// Context values available for substitution in G-code template strings.
 pub struct TemplateContext {
     pub tool_number: u32,
@@ -109,6 +140,28 @@ fn expand_token(token: &str, ctx: &TemplateContext) -> String {
 mod tests {
     use super::*;
 
+    // -------------------------------------------------------------------------
+    // format_program_number
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn program_number_zero_padded() {
+        assert_eq!(format_program_number("%04d", 42), "0042");
+        assert_eq!(format_program_number("%04d", 12345), "12345");
+    }
+
+    #[test]
+    fn program_number_plain_and_space_padded() {
+        assert_eq!(format_program_number("%d", 1000), "1000");
+        assert_eq!(format_program_number("%5d", 42), "   42");
+    }
+
+    #[test]
+    fn program_number_unrecognised_format_is_bare_number() {
+        assert_eq!(format_program_number("", 7), "7");
+        assert_eq!(format_program_number("%x", 7), "7");
+    }
+
     // -------------------------------------------------------------------------
     // format_coord — basic formatting
     // -------------------------------------------------------------------------
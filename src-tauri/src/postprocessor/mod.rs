@@ -4,6 +4,10 @@ pub mod config;
 pub mod formatter;
 pub mod modal;
 pub mod program;
+pub mod rotary;
+
+use std::io::Write;
+use std::path::Path;
 
 use crate::toolpath::Toolpath;
 use serde::Serialize;
@@ -20,12 +24,24 @@ pub enum PostProcessorError {
     ArcError(String),
     #[error("program assembly error: {0}")]
     Assembly(String),
+    #[error("validation error: {0}")]
+    Validation(String),
+    #[error("write error: {0}")]
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for PostProcessorError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
 }
 
 pub(crate) const FANUC_0I_TOML: &str = include_str!("builtins/fanuc-0i.toml");
 pub(crate) const LINUXCNC_TOML: &str = include_str!("builtins/linuxcnc.toml");
 pub(crate) const MACH4_TOML: &str = include_str!("builtins/mach4.toml");
 pub(crate) const GRBL_TOML: &str = include_str!("builtins/grbl.toml");
+pub(crate) const HAAS_TOML: &str = include_str!("builtins/haas.toml");
+pub(crate) const SIEMENS_840D_TOML: &str = include_str!("builtins/siemens-840d.toml");
 
 /// Metadata for a post-processor, returned by `list_builtins()`.
 #[derive(Debug, Clone, Serialize)]
@@ -36,6 +52,44 @@ pub struct PostProcessorMeta {
     pub description: String,
 }
 
+impl From<config::PostProcessorConfig> for PostProcessorMeta {
+    fn from(c: config::PostProcessorConfig) -> Self {
+        Self {
+            id: c.meta.id,
+            name: c.meta.name,
+            description: c.meta.description,
+        }
+    }
+}
+
+/// Parses every `*.toml` file in `dir`, in file-name order, skipping (and
+/// logging) the ones that are not valid post-processor configs.
+fn scan_dir(dir: &Path) -> Result<Vec<config::PostProcessorConfig>, PostProcessorError> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| PostProcessorError::Config(format!("{}: {e}", dir.display())))?;
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    Ok(paths
+        .iter()
+        .filter_map(|path| {
+            let parsed = std::fs::read_to_string(path)
+                .map_err(|e| PostProcessorError::Config(e.to_string()))
+                .and_then(|toml| config::parse(&toml));
+            match parsed {
+                Ok(config) => Some(config),
+                Err(e) => {
+                    tracing::warn!("skipping post-processor {}: {e}", path.display());
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
 /// The post-processor engine. Loaded from a config, used to generate G-code.
 pub struct PostProcessor {
     pub(crate) config: config::PostProcessorConfig,
@@ -49,6 +103,8 @@ impl PostProcessor {
             "linuxcnc" => LINUXCNC_TOML,
             "mach4" => MACH4_TOML,
             "grbl" => GRBL_TOML,
+            "haas" => HAAS_TOML,
+            "siemens-840d" => SIEMENS_840D_TOML,
             _ => {
                 return Err(PostProcessorError::Config(format!(
                     "unknown builtin id: {}",
@@ -66,17 +122,58 @@ impl PostProcessor {
         config::parse(&toml).map(|c| Self { config: c })
     }
 
+    /// Load a post-processor by `meta.id`, preferring a matching `.toml` in
+    /// `user_dir` over the builtin with the same id.
+    pub fn load(id: &str, user_dir: Option<&Path>) -> Result<Self, PostProcessorError> {
+        if let Some(dir) = user_dir.filter(|d| d.is_dir()) {
+            if let Some(config) = scan_dir(dir)?.into_iter().find(|c| c.meta.id == id) {
+                return Ok(Self { config });
+            }
+        }
+        Self::builtin(id)
+    }
+
+    /// List the metadata of every `*.toml` post-processor in `dir`, ordered
+    /// by file name.
+    ///
+    /// Files that fail to parse are skipped with a logged warning so one
+    /// broken post does not hide the rest. Returns
+    /// [`PostProcessorError::Config`] if `dir` cannot be read.
+    pub fn list_dir(dir: &Path) -> Result<Vec<PostProcessorMeta>, PostProcessorError> {
+        Ok(scan_dir(dir)?
+            .into_iter()
+            .map(PostProcessorMeta::from)
+            .collect())
+    }
+
     /// List all builtin post-processor metadata (id, name, description).
     pub fn list_builtins() -> Vec<PostProcessorMeta> {
-        [FANUC_0I_TOML, LINUXCNC_TOML, MACH4_TOML, GRBL_TOML]
-            .iter()
-            .filter_map(|toml| config::parse(toml).ok())
-            .map(|c| PostProcessorMeta {
-                id: c.meta.id,
-                name: c.meta.name,
-                description: c.meta.description,
-            })
-            .collect()
+        [
+            FANUC_0I_TOML,
+            LINUXCNC_TOML,
+            MACH4_TOML,
+            GRBL_TOML,
+            HAAS_TOML,
+            SIEMENS_840D_TOML,
+        ]
+        .iter()
+        .filter_map(|toml| config::parse(toml).ok())
+        .map(PostProcessorMeta::from)
+        .collect()
+    }
+
+    /// The machine's rapid traverse rate in mm/min (see
+    /// [`config::MachineConfig::rapid_rate`]).
+    pub fn rapid_rate(&self) -> f64 {
+        self.config.machine.rapid_rate()
+    }
+
+    /// Check `toolpaths` against the machine's limits without generating output.
+    ///
+    /// [`generate`](Self::generate) runs the same checks first; call this
+    /// directly to report problems before an export is attempted.
+    pub fn validate(&self, toolpaths: &[Toolpath]) -> Result<(), PostProcessorError> {
+        program::validate(toolpaths, &self.config)
     }
 
     /// Generate G-code from the given toolpaths.
@@ -84,18 +181,36 @@ impl PostProcessor {
     /// `tool_infos` carries tool library data (diameter, description) used for
     /// template variable substitution in `tool_change.command`. Build it from
     /// `project.tools` before calling. Pass `&[]` if no tool data is needed.
+    ///
+    /// The returned [`GenerateResult`] carries warnings and stats alongside
+    /// the program; use [`GenerateResult::gcode`] when only the text is needed.
     pub fn generate(
         &self,
         toolpaths: &[Toolpath],
         tool_infos: &[program::ToolInfo],
         options: program::GenerateOptions,
-    ) -> Result<String, PostProcessorError> {
+    ) -> Result<GenerateResult, PostProcessorError> {
         program::assemble(toolpaths, tool_infos, &self.config, &options)
     }
+
+    /// Generate G-code like [`generate`](Self::generate), writing each block
+    /// to `writer` as it is produced instead of building the whole program in
+    /// memory.
+    ///
+    /// `writer` is flushed before returning; wrap files in a `BufWriter`.
+    pub fn generate_to_writer<W: Write>(
+        &self,
+        toolpaths: &[Toolpath],
+        tool_infos: &[program::ToolInfo],
+        options: program::GenerateOptions,
+        mut writer: W,
+    ) -> Result<GenerateSummary, PostProcessorError> {
+        program::assemble_to_writer(toolpaths, tool_infos, &self.config, &options, &mut writer)
+    }
 }
 
-/// Re-export so callers can name `ToolInfo` without importing `program` directly.
-pub use program::ToolInfo;
+/// Re-export so callers can name these without importing `program` directly.
+pub use program::{GenerateResult, GenerateSummary, ProgramStats, ToolInfo};
 
 #[cfg(test)]
 mod tests {
@@ -122,14 +237,26 @@ mod tests {
     }
 
     #[test]
-    fn list_builtins_returns_four_entries() {
+    fn haas_builtin_parses_without_error() {
+        config::parse(HAAS_TOML).unwrap();
+    }
+
+    #[test]
+    fn siemens_840d_builtin_parses_without_error() {
+        config::parse(SIEMENS_840D_TOML).unwrap();
+    }
+
+    #[test]
+    fn list_builtins_returns_six_entries() {
         let builtins = PostProcessor::list_builtins();
-        assert_eq!(builtins.len(), 4);
+        assert_eq!(builtins.len(), 6);
         let ids: Vec<&str> = builtins.iter().map(|b| b.id.as_str()).collect();
         assert!(ids.contains(&"fanuc-0i"));
         assert!(ids.contains(&"linuxcnc"));
         assert!(ids.contains(&"mach4"));
         assert!(ids.contains(&"grbl"));
+        assert!(ids.contains(&"haas"));
+        assert!(ids.contains(&"siemens-840d"));
     }
 
     #[test]
@@ -143,6 +270,52 @@ mod tests {
         assert!(matches!(result, Err(PostProcessorError::Config(_))));
     }
 
+    /// Writes a copy of the Fanuc builtin to `dir/file` with `meta.id = id`.
+    fn write_custom_post(dir: &Path, file: &str, id: &str) {
+        let toml = FANUC_0I_TOML.replace(r#"id          = "fanuc-0i""#, &format!(r#"id = "{id}""#));
+        std::fs::write(dir.join(file), toml).unwrap();
+    }
+
+    fn temp_post_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("jcam_posts_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn list_dir_reads_toml_posts_and_skips_others() {
+        let dir = temp_post_dir();
+        write_custom_post(&dir, "haas-vf2.toml", "haas-vf2");
+        std::fs::write(dir.join("notes.txt"), "not a post").unwrap();
+        std::fs::write(dir.join("broken.toml"), "[meta").unwrap();
+
+        let metas = PostProcessor::list_dir(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(metas.len(), 1, "{metas:?}");
+        assert_eq!(metas[0].id, "haas-vf2");
+    }
+
+    #[test]
+    fn list_dir_missing_directory_returns_config_error() {
+        let dir = std::env::temp_dir().join("jcam_posts_does_not_exist");
+        let result = PostProcessor::list_dir(&dir);
+        assert!(matches!(result, Err(PostProcessorError::Config(_))));
+    }
+
+    #[test]
+    fn load_prefers_user_post_and_falls_back_to_builtin() {
+        let dir = temp_post_dir();
+        write_custom_post(&dir, "haas-vf2.toml", "haas-vf2");
+
+        let user = PostProcessor::load("haas-vf2", Some(&dir));
+        let builtin = PostProcessor::load("grbl", Some(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(user.unwrap().config.meta.id, "haas-vf2");
+        assert_eq!(builtin.unwrap().config.meta.id, "grbl");
+    }
+
     #[test]
     fn generate_returns_gcode_string() {
         use crate::models::Vec3;
@@ -176,7 +349,13 @@ mod tests {
                         tool_orientation: None,
                     },
                 ],
+                feed_rate: None,
             }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
         };
 
         let result = PostProcessor::builtin("linuxcnc")
@@ -186,10 +365,12 @@ mod tests {
                 &[],
                 program::GenerateOptions {
                     program_number: Some(1),
-                    include_comments: false,
+                    comments: program::CommentLevel::None,
+                    ..Default::default()
                 },
             )
-            .unwrap();
+            .unwrap()
+            .into_gcode();
 
         assert!(
             result.contains("G01") || result.contains("G1"),
@@ -197,4 +378,168 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn generate_to_writer_matches_generate_byte_for_byte() {
+        use crate::models::Vec3;
+        use crate::toolpath::types::{CutPoint, MoveKind, Pass, PassKind};
+        use uuid::Uuid;
+
+        let point = |x: f64, z: f64, move_kind: MoveKind| CutPoint {
+            position: Vec3 { x, y: 0.0, z },
+            move_kind,
+            tool_orientation: None,
+        };
+        let toolpath = |tool_number: u32| Toolpath {
+            operation_id: Uuid::new_v4(),
+            tool_number,
+            spindle_speed: 8000.0,
+            feed_rate: 500.0,
+            passes: vec![Pass {
+                kind: PassKind::Cutting,
+                cuts: vec![
+                    point(0.0, 5.0, MoveKind::Rapid),
+                    point(0.0, -1.0, MoveKind::Feed),
+                    point(25.0, -1.0, MoveKind::Feed),
+                ],
+                feed_rate: None,
+            }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
+        };
+        let toolpaths = [toolpath(1), toolpath(2)];
+        let options = || program::GenerateOptions {
+            program_number: Some(42),
+            comments: program::CommentLevel::Verbose,
+            ..Default::default()
+        };
+        let pp = PostProcessor::builtin("fanuc-0i").unwrap();
+
+        let result = pp.generate(&toolpaths, &[], options()).unwrap();
+        let mut streamed = Vec::new();
+        let summary = pp
+            .generate_to_writer(&toolpaths, &[], options(), &mut streamed)
+            .unwrap();
+
+        assert_eq!(streamed, result.gcode.as_bytes());
+        assert_eq!(summary.stats, result.stats);
+        assert_eq!(summary.warnings, result.warnings);
+    }
+
+    #[test]
+    fn fanuc_export_is_wrapped_in_percent_delimiters() {
+        let pp = PostProcessor::builtin("fanuc-0i").unwrap();
+        let result = pp
+            .generate(&[], &[], program::GenerateOptions::default())
+            .unwrap();
+
+        let mut lines = result.gcode.lines();
+        assert_eq!(lines.next(), Some("%"), "got:\n{}", result.gcode);
+        assert_eq!(lines.next(), Some("O1000"), "got:\n{}", result.gcode);
+        assert_eq!(
+            result.gcode.lines().last(),
+            Some("%"),
+            "got:\n{}",
+            result.gcode
+        );
+    }
+
+    /// Two holes at (10, 10) and (30, 10) drilled from R2 to Z-10.
+    fn drill_toolpath() -> Toolpath {
+        use crate::models::operation::DrillParams;
+        use crate::models::Vec3;
+        use crate::toolpath::types::{CutPoint, MoveKind, Pass, PassKind};
+
+        let at = |x: f64, z: f64, move_kind: MoveKind| CutPoint {
+            position: Vec3 { x, y: 10.0, z },
+            move_kind,
+            tool_orientation: None,
+        };
+        let cuts = [10.0, 30.0]
+            .into_iter()
+            .flat_map(|x| {
+                [
+                    at(x, 5.0, MoveKind::Rapid),
+                    at(x, 2.0, MoveKind::Rapid),
+                    at(x, -10.0, MoveKind::Feed),
+                    at(x, 5.0, MoveKind::Rapid),
+                ]
+            })
+            .collect();
+        Toolpath {
+            operation_id: uuid::Uuid::nil(),
+            tool_number: 3,
+            spindle_speed: 2000.0,
+            feed_rate: 120.0,
+            passes: vec![Pass {
+                kind: PassKind::Cutting,
+                cuts,
+                feed_rate: None,
+            }],
+            path_mode: None,
+            drill: Some(DrillParams {
+                depth: 12.0,
+                peck_depth: None,
+            }),
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
+        }
+    }
+
+    #[test]
+    fn haas_generate_emits_five_digit_program_number_and_g81() {
+        let result = PostProcessor::builtin("haas")
+            .unwrap()
+            .generate(
+                &[drill_toolpath()],
+                &[],
+                program::GenerateOptions::default(),
+            )
+            .unwrap();
+
+        let gcode = &result.gcode;
+        assert!(gcode.lines().any(|l| l == "O01000"), "got:\n{gcode}");
+        assert!(gcode.contains("G81 Z-10 R2 F120"), "got:\n{gcode}");
+        assert!(gcode.contains("T3 M06"), "got:\n{gcode}");
+    }
+
+    #[test]
+    fn siemens_840d_generate_uses_semicolon_comments_and_cycle81() {
+        let result = PostProcessor::builtin("siemens-840d")
+            .unwrap()
+            .generate(
+                &[drill_toolpath()],
+                &[],
+                program::GenerateOptions {
+                    comments: program::CommentLevel::Verbose,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let gcode = &result.gcode;
+        assert!(!gcode.contains('%'), "got:\n{gcode}");
+        assert!(gcode.contains(";Generated by jamiecam\n"), "got:\n{gcode}");
+
+        let lines: Vec<&str> = gcode.lines().collect();
+        let call = lines
+            .iter()
+            .position(|l| l.ends_with("MCALL CYCLE81(5, 2, 0, -10)"))
+            .unwrap_or_else(|| panic!("no CYCLE81 call:\n{gcode}"));
+        assert!(lines[call + 1].ends_with("X10 Y10"), "got:\n{gcode}");
+        assert!(lines[call + 2].ends_with("X30 Y10"), "got:\n{gcode}");
+        assert!(lines[call + 3].ends_with(" MCALL"), "got:\n{gcode}");
+
+        let cancel = lines
+            .iter()
+            .rposition(|l| l.ends_with(" G40"))
+            .unwrap_or_else(|| panic!("no end cancel block:\n{gcode}"));
+        assert!(lines[cancel + 1].ends_with(" M5"), "got:\n{gcode}");
+        assert!(!gcode.contains("G49"), "got:\n{gcode}");
+        assert!(!gcode.contains("G80"), "got:\n{gcode}");
+    }
 }
@@ -3,8 +3,13 @@
 /// Each modal group holds the last-emitted value. `should_emit_*` returns `true`
 /// (and updates state) when the new value differs from the cached one, or `false`
 /// when it is identical and the word can be omitted.
+///
+/// A state built with [`ModalState::verbose`] still tracks values but answers
+/// `true` from every `should_emit_*` call, disabling suppression entirely.
 #[derive(Default)]
 pub struct ModalState {
+    verbose: bool,
+    absolute_arc_centers: bool,
     motion_code: Option<String>,
     feed: Option<f64>,
     spindle: Option<f64>,
@@ -18,6 +23,7 @@ pub struct ModalState {
     plane: Option<String>,
     distance_mode: Option<String>,
     feed_mode: Option<String>,
+    path_mode: Option<String>,
 }
 
 /// Tolerance for floating-point modal comparisons (coordinates, feed rate, spindle speed).
@@ -49,30 +55,63 @@ impl ModalState {
         Self::default()
     }
 
+    /// Creates a state that never suppresses a word (for verification output).
+    pub fn verbose() -> Self {
+        Self {
+            verbose: true,
+            ..Self::default()
+        }
+    }
+
+    /// Sets whether arc I/J/K words carry the absolute arc center instead of
+    /// the offset from the arc start. Fixed for the whole program.
+    pub fn with_absolute_arc_centers(mut self, absolute: bool) -> Self {
+        self.absolute_arc_centers = absolute;
+        self
+    }
+
+    /// Whether arc I/J/K words carry the absolute arc center.
+    pub fn absolute_arc_centers(&self) -> bool {
+        self.absolute_arc_centers
+    }
+
     /// Returns `true` and caches `code` if it differs from the last emitted motion code.
     pub fn should_emit_motion(&mut self, code: &str) -> bool {
-        update_string_modal(&mut self.motion_code, code)
+        update_string_modal(&mut self.motion_code, code) || self.verbose
     }
 
     /// Returns `true` and caches `feed` if it differs from the last emitted feed rate.
     pub fn should_emit_feed(&mut self, feed: f64) -> bool {
-        update_float_modal(&mut self.feed, feed)
+        update_float_modal(&mut self.feed, feed) || self.verbose
     }
 
     /// Returns `true` and caches `speed` if it differs from the last emitted spindle speed.
     pub fn should_emit_spindle(&mut self, speed: f64) -> bool {
-        update_float_modal(&mut self.spindle, speed)
+        update_float_modal(&mut self.spindle, speed) || self.verbose
     }
 
     /// Returns `true` and caches `number` if it differs from the last emitted tool number.
     pub fn should_emit_tool(&mut self, number: u32) -> bool {
         if self.tool == Some(number) {
-            return false;
+            return self.verbose;
         }
         self.tool = Some(number);
         true
     }
 
+    /// Returns the last emitted value of `axis`, if any.
+    pub fn coord(&self, axis: char) -> Option<f64> {
+        match axis {
+            'X' | 'x' => self.coord_x,
+            'Y' | 'y' => self.coord_y,
+            'Z' | 'z' => self.coord_z,
+            'A' | 'a' => self.coord_a,
+            'B' | 'b' => self.coord_b,
+            'C' | 'c' => self.coord_c,
+            _ => None,
+        }
+    }
+
     /// Returns `true` and caches the coordinate if it differs by more than 1e-6 mm.
     pub fn should_emit_coord(&mut self, axis: char, value: f64) -> bool {
         let slot = match axis {
@@ -84,27 +123,67 @@ impl ModalState {
             'C' | 'c' => &mut self.coord_c,
             _ => return true, // unknown axis — always emit
         };
-        update_float_modal(slot, value)
+        update_float_modal(slot, value) || self.verbose
+    }
+
+    /// Returns the last emitted plane-select code, if any.
+    pub fn plane(&self) -> Option<&str> {
+        self.plane.as_deref()
     }
 
     /// Returns `true` and caches `code` if it differs from the last emitted plane-select code.
     pub fn should_emit_plane(&mut self, code: &str) -> bool {
-        update_string_modal(&mut self.plane, code)
+        update_string_modal(&mut self.plane, code) || self.verbose
     }
 
     /// Returns `true` and caches `code` if it differs from the last emitted distance-mode code.
     pub fn should_emit_distance_mode(&mut self, code: &str) -> bool {
-        update_string_modal(&mut self.distance_mode, code)
+        update_string_modal(&mut self.distance_mode, code) || self.verbose
+    }
+
+    /// Returns the last emitted feed-mode code, if any.
+    pub fn feed_mode(&self) -> Option<&str> {
+        self.feed_mode.as_deref()
+    }
+
+    /// Forgets the cached feed rate so the next feed word is emitted, e.g.
+    /// after a feed-mode change gives F a different meaning.
+    pub fn forget_feed(&mut self) {
+        self.feed = None;
+    }
+
+    /// Forgets the motion code, axis positions and feed rate so the next move
+    /// emits all of them, e.g. after a probe move stops at a position only
+    /// the controller knows.
+    pub fn forget_motion(&mut self) {
+        self.motion_code = None;
+        self.feed = None;
+        self.coord_x = None;
+        self.coord_y = None;
+        self.coord_z = None;
+        self.coord_a = None;
+        self.coord_b = None;
+        self.coord_c = None;
     }
 
     /// Returns `true` and caches `code` if it differs from the last emitted feed-mode code.
     pub fn should_emit_feed_mode(&mut self, code: &str) -> bool {
-        update_string_modal(&mut self.feed_mode, code)
+        update_string_modal(&mut self.feed_mode, code) || self.verbose
+    }
+
+    /// Returns `true` and caches `code` if it differs from the last emitted path-mode code.
+    pub fn should_emit_path_mode(&mut self, code: &str) -> bool {
+        update_string_modal(&mut self.path_mode, code) || self.verbose
     }
 
     /// Clears all modal state (call on tool change or program reset).
+    /// Verbose mode and the arc center mode survive the reset.
     pub fn reset(&mut self) {
-        *self = Self::default();
+        *self = Self {
+            verbose: self.verbose,
+            absolute_arc_centers: self.absolute_arc_centers,
+            ..Self::default()
+        };
     }
 }
 
@@ -283,6 +362,13 @@ mod tests {
 
     // ── distance mode ────────────────────────────────────────────────────────
 
+    #[test]
+    fn reset_keeps_arc_center_mode() {
+        let mut ms = ModalState::new().with_absolute_arc_centers(true);
+        ms.reset();
+        assert!(ms.absolute_arc_centers());
+    }
+
     #[test]
     fn distance_mode_emits_first_time() {
         let mut ms = ModalState::new();
@@ -318,6 +404,28 @@ mod tests {
         assert!(!ms.should_emit_feed_mode("G94"));
     }
 
+    #[test]
+    fn forget_feed_re_emits_same_feed() {
+        let mut ms = ModalState::new();
+        ms.should_emit_feed(500.0);
+        ms.forget_feed();
+        assert!(ms.should_emit_feed(500.0));
+    }
+
+    #[test]
+    fn forget_motion_re_emits_motion_coords_and_feed() {
+        let mut ms = ModalState::new();
+        ms.should_emit_motion("G01");
+        ms.should_emit_coord('X', 1.0);
+        ms.should_emit_feed(500.0);
+        ms.should_emit_spindle(8000.0);
+        ms.forget_motion();
+        assert!(ms.should_emit_motion("G01"));
+        assert!(ms.should_emit_coord('X', 1.0));
+        assert!(ms.should_emit_feed(500.0));
+        assert!(!ms.should_emit_spindle(8000.0));
+    }
+
     #[test]
     fn feed_mode_re_emits_after_change() {
         let mut ms = ModalState::new();
@@ -325,6 +433,16 @@ mod tests {
         assert!(ms.should_emit_feed_mode("G95"));
     }
 
+    // ── path mode ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn path_mode_suppressed_on_repeat() {
+        let mut ms = ModalState::new();
+        assert!(ms.should_emit_path_mode("G64"));
+        assert!(!ms.should_emit_path_mode("G64"));
+        assert!(ms.should_emit_path_mode("G61"));
+    }
+
     // ── reset ────────────────────────────────────────────────────────────────
 
     #[test]
@@ -351,4 +469,18 @@ mod tests {
         assert!(ms.should_emit_distance_mode("G90"));
         assert!(ms.should_emit_feed_mode("G94"));
     }
+
+    // ── verbose ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn verbose_never_suppresses_and_survives_reset() {
+        let mut ms = ModalState::verbose();
+        assert!(ms.should_emit_motion("G01"));
+        assert!(ms.should_emit_motion("G01"));
+        assert!(ms.should_emit_feed(500.0));
+        assert!(ms.should_emit_feed(500.0));
+        ms.reset();
+        ms.should_emit_coord('X', 1.0);
+        assert!(ms.should_emit_coord('X', 1.0));
+    }
 }
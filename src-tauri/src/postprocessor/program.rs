@@ -1,11 +1,19 @@
-use super::block::BlockBuilder;
-use super::config::{ArcFormat, PostProcessorConfig};
+use std::borrow::Cow;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use super::block::{Block, BlockBuilder};
+use super::config::{ArcFormat, CycleStyle, PostProcessorConfig};
 use super::modal::ModalState;
+use super::rotary::{inverse_kinematics, RotaryTracker, SINGULARITY_TILT};
 use super::PostProcessorError;
 use super::{
-    arcs,
-    formatter::{format_coord, render_template, TemplateContext},
+    arcs::{self, ArcPlane},
+    formatter::{format_coord, format_program_number, render_template, TemplateContext},
 };
+use crate::models::operation::{CoolantMode, FeedMode, PathMode};
+use crate::models::Vec3;
 use crate::toolpath::types::{CutPoint, MoveKind, PassKind, ToolOrientation};
 use crate::toolpath::Toolpath;
 
@@ -18,40 +26,307 @@ pub struct ToolInfo {
     pub description: String,
 }
 
+/// How many comments [`assemble`] writes into the program.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentLevel {
+    /// No comments at all.
+    #[default]
+    None,
+    /// Program-level comments only: the generation header and the
+    /// cycle-time estimate.
+    Operations,
+    /// Everything in [`Operations`](Self::Operations) plus a label ahead of
+    /// every pass.
+    Verbose,
+}
+
+impl CommentLevel {
+    /// Whether program-level comments are written.
+    pub fn operations(self) -> bool {
+        self != Self::None
+    }
+
+    /// Whether per-pass labels are written.
+    pub fn verbose(self) -> bool {
+        self == Self::Verbose
+    }
+}
+
+/// Maps the legacy `include_comments` flag: `false` → `None`, `true` →
+/// `Verbose`.
+impl From<bool> for CommentLevel {
+    fn from(include_comments: bool) -> Self {
+        if include_comments {
+            Self::Verbose
+        } else {
+            Self::None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct GenerateOptions {
     pub program_number: Option<u32>,
-    pub include_comments: bool,
+    pub comments: CommentLevel,
+    /// Disable modal suppression so every block repeats its motion code,
+    /// coordinates, and feed. Used to diff against a normal run when
+    /// diagnosing suppression bugs.
+    pub verbose_modals: bool,
+    /// Bracket each toolpath's blocks with `OP_START <uuid>` / `OP_END <uuid>`
+    /// comments so a backplot viewer can segment the file per operation.
+    pub operation_markers: bool,
+    /// Multiplier applied to every toolpath feed rate (e.g. `0.8` for a
+    /// cautious first run). `None` leaves feeds unchanged.
+    pub feed_scale: Option<f64>,
+    /// Emit `spindle.off` and `coolant.off` ahead of the footer, skipping any
+    /// the footer already contains. On by default; turn off for posts whose
+    /// footer hand-writes the shutdown sequence.
+    pub auto_safe_shutdown: bool,
+    /// Emit `misc.optional_stop` as its own block between consecutive
+    /// toolpaths, so the operator can pause for inspection.
+    pub optional_stop_between_ops: bool,
+    /// Write arc I/J/K as offsets from the arc start (the default). When
+    /// `false`, they carry the absolute arc center, for controls running in
+    /// absolute arc center mode (e.g. Haas with setting 33 / `G90.1`).
+    pub incremental_arcs: bool,
+    /// Safe Z above the stock. When set, every pass after the first that
+    /// opens with a rapid first retracts to this height with a Z-only rapid,
+    /// traverses in XY there, then plunges to the pass start. `None` leaves
+    /// rapids exactly as the toolpath gives them.
+    pub clearance_z: Option<f64>,
+    /// Open the program with comments naming the post, the program number,
+    /// the operation count and the estimated run time. Only written when
+    /// `comments` is at least [`CommentLevel::Operations`].
+    pub emit_stats_header: bool,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        Self {
+            program_number: None,
+            comments: CommentLevel::None,
+            verbose_modals: false,
+            operation_markers: false,
+            feed_scale: None,
+            auto_safe_shutdown: true,
+            optional_stop_between_ops: false,
+            incremental_arcs: true,
+            clearance_z: None,
+            emit_stats_header: false,
+        }
+    }
+}
+
+/// Summary figures for a generated program.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramStats {
+    /// Number of output lines, including delimiters and comments.
+    pub line_count: usize,
+    /// Number of tool changes emitted.
+    pub tool_changes: u32,
+    /// Estimated run time in seconds (see [`estimate`]).
+    pub estimated_seconds: Option<f64>,
+}
+
+/// Output of [`assemble`]: the program text plus everything learned while
+/// producing it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateResult {
+    pub gcode: String,
+    /// Non-fatal adjustments made during generation (e.g. feeds raised to
+    /// `feeds.min_feed_rate`, spindle speeds clamped to `spindle.max_rpm`).
+    /// Empty for a clean program.
+    pub warnings: Vec<String>,
+    pub stats: ProgramStats,
+}
+
+/// Output of [`assemble_to_writer`]: everything in a [`GenerateResult`]
+/// except the program text, which went to the writer.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateSummary {
+    pub warnings: Vec<String>,
+    pub stats: ProgramStats,
+}
+
+impl GenerateResult {
+    /// The generated program text.
+    pub fn gcode(&self) -> &str {
+        &self.gcode
+    }
+
+    /// Consumes the result, returning only the program text.
+    pub fn into_gcode(self) -> String {
+        self.gcode
+    }
+}
+
+/// Program text sink for [`assemble_to_writer`].
+///
+/// Counts output lines as they pass through and holds on to the first write
+/// error, so the emit helpers stay infallible; [`Self::finish`] reports it.
+struct ProgramWriter<'a> {
+    inner: &'a mut dyn Write,
+    line_count: usize,
+    /// Whether the last text written ended mid-line.
+    open_line: bool,
+    error: Option<std::io::Error>,
+}
+
+impl<'a> ProgramWriter<'a> {
+    fn new(inner: &'a mut dyn Write) -> Self {
+        Self {
+            inner,
+            line_count: 0,
+            open_line: false,
+            error: None,
+        }
+    }
+
+    fn push_str(&mut self, text: &str) {
+        if text.is_empty() || self.error.is_some() {
+            return;
+        }
+        self.line_count += text.bytes().filter(|&b| b == b'\n').count();
+        self.open_line = !text.ends_with('\n');
+        if let Err(e) = self.inner.write_all(text.as_bytes()) {
+            self.error = Some(e);
+        }
+    }
+
+    /// Flushes the writer and returns the number of lines written, counted
+    /// like [`str::lines`].
+    fn finish(self) -> Result<usize, PostProcessorError> {
+        if let Some(e) = self.error {
+            return Err(e.into());
+        }
+        self.inner.flush()?;
+        Ok(self.line_count + usize::from(self.open_line))
+    }
 }
 
 /// Assembles a complete G-code program from one or more toolpaths.
 ///
-/// Orchestrates all post-processor submodules: modal state tracking, arc
-/// conversion, template substitution, and block formatting.
+/// Buffers the output of [`assemble_to_writer`] into a `String`.
 pub fn assemble(
     toolpaths: &[Toolpath],
     tool_infos: &[ToolInfo],
     config: &PostProcessorConfig,
     options: &GenerateOptions,
-) -> Result<String, PostProcessorError> {
-    let mut out = String::new();
+) -> Result<GenerateResult, PostProcessorError> {
+    let mut buf = Vec::new();
+    let GenerateSummary { warnings, stats } =
+        assemble_to_writer(toolpaths, tool_infos, config, options, &mut buf)?;
+    let gcode = String::from_utf8(buf).map_err(|e| PostProcessorError::Assembly(e.to_string()))?;
+    Ok(GenerateResult {
+        gcode,
+        warnings,
+        stats,
+    })
+}
+
+/// Assembles a complete G-code program from one or more toolpaths, writing
+/// each block to `writer` as it is rendered.
+///
+/// Orchestrates all post-processor submodules: modal state tracking, arc
+/// conversion, template substitution, and block formatting. Returns
+/// [`PostProcessorError::Io`] if writing fails.
+///
+/// Runs of toolpaths that start with a tool change (see [`tool_segments`])
+/// are rendered on a pool of worker threads and written in order as they
+/// finish, with their line numbers stitched together. Programs that move
+/// rotary axes are rendered serially.
+pub fn assemble_to_writer(
+    toolpaths: &[Toolpath],
+    tool_infos: &[ToolInfo],
+    config: &PostProcessorConfig,
+    options: &GenerateOptions,
+    writer: &mut dyn Write,
+) -> Result<GenerateSummary, PostProcessorError> {
+    assemble_program(toolpaths, tool_infos, config, options, writer, true)
+}
+
+/// [`assemble_to_writer`], rendering the tool-change runs in parallel only
+/// when `parallel` is set.
+fn assemble_program(
+    toolpaths: &[Toolpath],
+    tool_infos: &[ToolInfo],
+    config: &PostProcessorConfig,
+    options: &GenerateOptions,
+    writer: &mut dyn Write,
+    parallel: bool,
+) -> Result<GenerateSummary, PostProcessorError> {
+    validate(toolpaths, config)?;
+
+    let mut warnings = Vec::new();
+
+    // An operation without a single cutting move would only produce an empty
+    // tool change. Leave such operations out and say so.
+    let working: Cow<[Toolpath]> = if toolpaths.iter().all(does_work) {
+        Cow::Borrowed(toolpaths)
+    } else {
+        let mut kept = Vec::new();
+        for toolpath in toolpaths {
+            if does_work(toolpath) {
+                kept.push(toolpath.clone());
+            } else {
+                warnings.push(format!(
+                    "operation {}: no cutting moves; skipped",
+                    toolpath.operation_id
+                ));
+            }
+        }
+        Cow::Owned(kept)
+    };
+    let toolpaths: &[Toolpath] = &working;
+
+    let mut out = ProgramWriter::new(writer);
     let mut line_num = config.format.line_number_start;
     let program_number = options.program_number.unwrap_or(config.program.number);
 
     // 1. Opening % delimiter
     if config.format.percent_delimiters {
-        out.push('%');
+        out.push_str("%");
         out.push_str(&config.format.eol);
     }
 
-    // 2. Program number line — never carries an N-word (like the % delimiter)
-    out.push_str(&format!(
-        "{}{}",
-        config.program.number_prefix, program_number
-    ));
-    out.push_str(&config.format.eol);
+    // 2. Program number line — never carries an N-word (like the % delimiter).
+    //    Controllers without O-numbers leave number_prefix empty to omit it.
+    if !config.program.number_prefix.is_empty() {
+        out.push_str(&format!(
+            "{}{}",
+            config.program.number_prefix,
+            format_program_number(&config.program.number_format, program_number)
+        ));
+        out.push_str(&config.format.eol);
+    }
 
-    // 3. Generation comment
-    if options.include_comments {
+    // 3. Stats header, then the generation comment
+    if options.emit_stats_header && options.comments.operations() {
+        let mut stats = vec![
+            format!("Post: {}", config.meta.name),
+            format!(
+                "Program: {}{}",
+                config.program.number_prefix,
+                format_program_number(&config.program.number_format, program_number)
+            ),
+            format!("Operations: {}", toolpaths.len()),
+        ];
+        if let Some(seconds) = estimate(toolpaths, config.machine.rapid_rate()) {
+            stats.push(format!("Estimated runtime: {}", format_hms(seconds)));
+        }
+        for line in stats {
+            let comment = format!(
+                "{}{line}{}",
+                config.program.comment_open, config.program.comment_close
+            );
+            push_raw(&mut out, &comment, &mut line_num, config);
+        }
+    }
+    if options.comments.operations() {
         let comment = format!(
             "{}Generated by jamiecam{}",
             config.program.comment_open, config.program.comment_close
@@ -59,134 +334,1534 @@ pub fn assemble(
         push_raw(&mut out, &comment, &mut line_num, config);
     }
 
-    // 4. Header lines
+    let mut modal = new_modal_state(options);
+
+    // 4. Header lines, then absolute distance mode unless the header already
+    //    left it active — never rely on the controller's power-on default.
     for line in &config.program.header {
         push_raw(&mut out, line, &mut line_num, config);
+        track_distance_mode(line, &mut modal, config);
+    }
+    let absolute = &config.words.absolute;
+    if !absolute.is_empty() && modal.should_emit_distance_mode(absolute) {
+        push_raw(&mut out, absolute, &mut line_num, config);
     }
 
     // 5+6. Tool changes and motion
-    let mut modal = ModalState::new();
-    let mut last_tool: Option<u32> = None;
+    let job = ProgramJob {
+        toolpaths,
+        tool_infos,
+        config,
+        options,
+        program_number,
+    };
+    let mut st = Assembly {
+        modal,
+        rotary: RotaryAxes::new(config),
+        line_num,
+        last_tool: None,
+        tool_changes: 0,
+        moved: false,
+        warnings,
+    };
+    let segments = tool_segments(toolpaths);
+    if parallel && segments.len() > 1 && !toolpaths.iter().any(uses_rotary_axes) {
+        write_segments_in_parallel(&job, &segments, &mut st, &mut out)?;
+    } else {
+        for index in 0..toolpaths.len() {
+            emit_toolpath(&job, index, &mut st, &mut out)?;
+        }
+    }
+    let Assembly {
+        mut line_num,
+        tool_changes,
+        warnings,
+        ..
+    } = st;
 
-    for toolpath in toolpaths {
-        if last_tool != Some(toolpath.tool_number) {
-            modal.reset();
-
-            let tool_info = tool_infos.iter().find(|t| t.number == toolpath.tool_number);
-            let ctx = TemplateContext {
-                tool_number: toolpath.tool_number,
-                tool_diameter: tool_info.map_or(0.0, |t| t.diameter),
-                tool_description: tool_info.map_or_else(String::new, |t| t.description.clone()),
-                spindle_speed: toolpath.spindle_speed,
-                feed_rate: toolpath.feed_rate,
-                program_number,
+    // 7. Cycle-time estimate comment, placed ahead of the end code
+    if options.comments.operations() {
+        if let Some(seconds) = estimate(toolpaths, config.machine.rapid_rate()) {
+            let comment = format!(
+                "{}Estimated cycle time: {}{}",
+                config.program.comment_open,
+                format_hms(seconds),
+                config.program.comment_close
+            );
+            push_raw(&mut out, &comment, &mut line_num, config);
+        }
+    }
+
+    // 8. Mode-cancel block, safe shutdown, then footer lines
+    if !config.program.end_cancel.is_empty() {
+        let block = config
+            .program
+            .end_cancel
+            .join(&config.format.word_separator);
+        push_raw(&mut out, &block, &mut line_num, config);
+    }
+    if options.auto_safe_shutdown && !toolpaths.is_empty() {
+        if let Some(block) = safe_shutdown_block(config) {
+            let ln = next_line_num(&mut line_num, config);
+            out.push_str(&block.render(ln, config));
+        }
+    }
+    for line in &config.program.footer {
+        if config.program.keep_spindle_on_end && line.trim() == config.spindle.off {
+            continue;
+        }
+        push_raw(&mut out, line, &mut line_num, config);
+    }
+
+    // 9. Closing % delimiter
+    if config.format.percent_delimiters {
+        out.push_str("%");
+        out.push_str(&config.format.eol);
+    }
+
+    let stats = ProgramStats {
+        line_count: out.finish()?,
+        tool_changes,
+        estimated_seconds: estimate(toolpaths, config.machine.rapid_rate()),
+    };
+    Ok(GenerateSummary { warnings, stats })
+}
+
+/// Modal state at the start of the program, as `options` configures it.
+fn new_modal_state(options: &GenerateOptions) -> ModalState {
+    if options.verbose_modals {
+        ModalState::verbose()
+    } else {
+        ModalState::new()
+    }
+    .with_absolute_arc_centers(!options.incremental_arcs)
+}
+
+/// Read-only inputs shared by every toolpath of one program.
+#[derive(Clone, Copy)]
+struct ProgramJob<'a> {
+    toolpaths: &'a [Toolpath],
+    tool_infos: &'a [ToolInfo],
+    config: &'a PostProcessorConfig,
+    options: &'a GenerateOptions,
+    program_number: u32,
+}
+
+/// State carried from one toolpath to the next while a program is assembled.
+struct Assembly {
+    modal: ModalState,
+    rotary: RotaryAxes,
+    line_num: u32,
+    last_tool: Option<u32>,
+    tool_changes: u32,
+    /// Whether any motion has been written yet.
+    moved: bool,
+    warnings: Vec<String>,
+}
+
+/// A run of toolpaths rendered on its own by [`render_segment`].
+struct Segment {
+    text: String,
+    warnings: Vec<String>,
+    tool_changes: u32,
+}
+
+/// Splits `toolpaths` into runs that each start with a tool change.
+///
+/// The tool change resets all modal state and the previous toolpath leaves
+/// the XY plane active ahead of it, so each run renders the same with a
+/// fresh [`ModalState`] as it does following the runs before it. Only the
+/// line numbers need fixing up, which [`splice_segment`] does.
+fn tool_segments(toolpaths: &[Toolpath]) -> Vec<std::ops::Range<usize>> {
+    let mut segments: Vec<std::ops::Range<usize>> = Vec::new();
+    for (index, toolpath) in toolpaths.iter().enumerate() {
+        match segments.last_mut() {
+            Some(run) if toolpaths[index - 1].tool_number == toolpath.tool_number => {
+                run.end = index + 1;
+            }
+            _ => segments.push(index..index + 1),
+        }
+    }
+    segments
+}
+
+/// Whether `toolpath` moves the rotary axes, whose unwrapped angles carry
+/// across tool changes and so keep its program from being split.
+fn uses_rotary_axes(toolpath: &Toolpath) -> bool {
+    toolpath.passes.iter().any(|pass| {
+        pass.cuts
+            .iter()
+            .any(|cut| matches!(cut.tool_orientation, Some(ToolOrientation::FiveAxis { .. })))
+    })
+}
+
+/// Renders `segments` on a pool of at most one worker per CPU and writes
+/// each through [`splice_segment`] as soon as it and every segment before it
+/// are done, so only segments finished out of order are held in memory.
+///
+/// Stops at the first segment that fails, in program order.
+fn write_segments_in_parallel(
+    job: &ProgramJob,
+    segments: &[std::ops::Range<usize>],
+    st: &mut Assembly,
+    out: &mut ProgramWriter,
+) -> Result<(), PostProcessorError> {
+    let workers = std::thread::available_parallelism()
+        .map_or(1, std::num::NonZeroUsize::get)
+        .min(segments.len());
+    let next = AtomicUsize::new(0);
+    let (done, finished) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            let done = done.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(range) = segments.get(index) else {
+                    break;
+                };
+                // The receiver is gone once an earlier segment failed.
+                if done
+                    .send((index, render_segment(job, range.clone())))
+                    .is_err()
+                {
+                    break;
+                }
+            });
+        }
+        drop(done);
+
+        let mut pending: Vec<Option<Result<Segment, PostProcessorError>>> =
+            segments.iter().map(|_| None).collect();
+        let mut written = 0;
+        for (index, rendered) in finished {
+            pending[index] = Some(rendered);
+            while let Some(rendered) = pending.get_mut(written).and_then(Option::take) {
+                let segment = rendered?;
+                splice_segment(&segment.text, &mut st.line_num, job.config, out);
+                st.warnings.extend(segment.warnings);
+                st.tool_changes += segment.tool_changes;
+                written += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Renders the toolpaths in `range` as [`emit_toolpath`] would following
+/// the ones before it, numbering lines from `line_number_start`.
+fn render_segment(
+    job: &ProgramJob,
+    range: std::ops::Range<usize>,
+) -> Result<Segment, PostProcessorError> {
+    let config = job.config;
+    let mut st = Assembly {
+        modal: new_modal_state(job.options),
+        rotary: RotaryAxes::new(config),
+        line_num: config.format.line_number_start,
+        last_tool: range
+            .start
+            .checked_sub(1)
+            .map(|i| job.toolpaths[i].tool_number),
+        tool_changes: 0,
+        moved: range.start > 0,
+        warnings: Vec::new(),
+    };
+    let mut buf = Vec::new();
+    let mut out = ProgramWriter::new(&mut buf);
+    for index in range {
+        emit_toolpath(job, index, &mut st, &mut out)?;
+    }
+    out.finish()?;
+    let text = String::from_utf8(buf).map_err(|e| PostProcessorError::Assembly(e.to_string()))?;
+    Ok(Segment {
+        text,
+        warnings: st.warnings,
+        tool_changes: st.tool_changes,
+    })
+}
+
+/// Writes a segment rendered by [`render_segment`], renumbering its blocks
+/// to continue from `line_num`.
+///
+/// With line numbers on, every non-empty line a segment writes starts with
+/// its N-word, after the block-delete character when it has one (see
+/// [`push_raw`] and [`Block::render`]).
+fn splice_segment(
+    text: &str,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) {
+    if !config.format.line_numbers {
+        out.push_str(text);
+        return;
+    }
+    let eol = config.format.eol.as_str();
+    let block_delete = config.format.block_delete_char.as_str();
+    for line in text.split_terminator(eol) {
+        if !line.is_empty() {
+            let prefix = if !block_delete.is_empty() && line.starts_with(block_delete) {
+                block_delete.len()
+            } else {
+                0
             };
+            let numbered = &line[prefix..];
+            debug_assert!(numbered.starts_with('N'), "unnumbered block: {line}");
+            let digits = numbered[1..].bytes().take_while(u8::is_ascii_digit).count();
+            out.push_str(&line[..prefix]);
+            out.push_str(&format!("N{line_num}"));
+            out.push_str(&numbered[1 + digits..]);
+            advance_line_num(line_num, config);
+        }
+        out.push_str(eol);
+    }
+}
+
+/// Emits toolpath `index` of the program: its tool change when the tool
+/// differs from the previous toolpath's, then its motion.
+fn emit_toolpath(
+    job: &ProgramJob,
+    index: usize,
+    st: &mut Assembly,
+    out: &mut ProgramWriter,
+) -> Result<(), PostProcessorError> {
+    let ProgramJob {
+        toolpaths,
+        tool_infos,
+        config,
+        options,
+        program_number,
+    } = *job;
+    let toolpath = &toolpaths[index];
+
+    if index > 0 && options.optional_stop_between_ops {
+        push_raw(out, &config.misc.optional_stop, &mut st.line_num, config);
+    }
 
+    if options.operation_markers {
+        let marker = operation_marker("OP_START", toolpath, config);
+        push_raw(out, &marker, &mut st.line_num, config);
+    }
+    if options.comments.operations() {
+        if let Some(allowance) = toolpath.stock_to_leave.filter(|a| *a != 0.0) {
+            let comment = format!(
+                "{}Stock to leave: {}{}",
+                config.program.comment_open,
+                format_coord(allowance, config.format.decimal_places, true, false),
+                config.program.comment_close
+            );
+            push_raw(out, &comment, &mut st.line_num, config);
+        }
+    }
+
+    let spindle_speed = output_spindle(toolpath, config, &mut st.warnings);
+
+    if st.last_tool != Some(toolpath.tool_number) {
+        let safe_z = config.tool_change.safe_z.or(options.clearance_z);
+        if let (true, Some(z)) = (st.moved, safe_z) {
+            emit_tool_change_retract(
+                z,
+                config.tool_change.safe_xy,
+                &mut st.modal,
+                &mut st.line_num,
+                config,
+                out,
+            );
+        }
+        st.modal.reset();
+        // The tool-change templates carry the S word.
+        st.modal.should_emit_spindle(spindle_speed);
+
+        let tool_info = tool_infos.iter().find(|t| t.number == toolpath.tool_number);
+        let ctx = TemplateContext {
+            tool_number: toolpath.tool_number,
+            tool_diameter: tool_info.map_or(0.0, |t| t.diameter),
+            tool_description: tool_info.map_or_else(String::new, |t| t.description.clone()),
+            spindle_speed,
+            feed_rate: toolpath.feed_rate,
+            program_number,
+        };
+
+        // A machine that starts the program on T1 needs no change to it:
+        // skip `pre` and `command`, but still activate the offset and
+        // start the spindle.
+        let already_loaded = st.last_tool.is_none()
+            && config.tool_change.suppress_first_if_t1
+            && toolpath.tool_number == 1;
+        if !already_loaded {
             for template in &config.tool_change.pre {
                 push_raw(
-                    &mut out,
+                    out,
                     &render_template(template, &ctx),
-                    &mut line_num,
+                    &mut st.line_num,
                     config,
                 );
             }
+            if config.tool_change.orient_before_change {
+                if let Some(orient) = &config.spindle.orient {
+                    push_raw(out, orient, &mut st.line_num, config);
+                }
+            }
             push_raw(
-                &mut out,
+                out,
                 &render_template(&config.tool_change.command, &ctx),
-                &mut line_num,
+                &mut st.line_num,
                 config,
             );
-            for template in &config.tool_change.post {
+            st.tool_changes += 1;
+        }
+        if config.machine.rtcp_supported {
+            if let Some(template) = &config.tool_change.rtcp_on {
                 push_raw(
-                    &mut out,
+                    out,
                     &render_template(template, &ctx),
-                    &mut line_num,
+                    &mut st.line_num,
                     config,
                 );
             }
+        }
+        if let Some(template) = &config.tool_change.length_offset {
+            push_raw(
+                out,
+                &render_template(template, &ctx),
+                &mut st.line_num,
+                config,
+            );
+        }
+        for template in &config.tool_change.post {
+            push_raw(
+                out,
+                &render_template(template, &ctx),
+                &mut st.line_num,
+                config,
+            );
+        }
 
-            last_tool = Some(toolpath.tool_number);
+        st.last_tool = Some(toolpath.tool_number);
+    } else if st.modal.should_emit_spindle(spindle_speed) {
+        let ln = next_line_num(&mut st.line_num, config);
+        out.push_str(
+            &BlockBuilder::new()
+                .spindle(spindle_speed)
+                .build()
+                .render(ln, config),
+        );
+    }
+
+    if let Some(code) = path_mode_code(toolpath, config) {
+        if st.modal.should_emit_path_mode(code) {
+            push_raw(out, code, &mut st.line_num, config);
         }
+    }
 
-        for pass in &toolpath.passes {
-            if options.include_comments {
+    let coolant = match toolpath.coolant {
+        Some(mode) => coolant_code(mode, config)?,
+        None => None,
+    };
+    if let Some(code) = coolant {
+        let ln = next_line_num(&mut st.line_num, config);
+        out.push_str(
+            &BlockBuilder::new()
+                .coolant_m(code)
+                .build()
+                .render(ln, config),
+        );
+    }
+
+    let mut feed_rate = output_feed(
+        toolpath.feed_rate,
+        toolpath,
+        config,
+        options,
+        &mut st.warnings,
+    );
+    let per_rev = toolpath.feed_mode == Some(FeedMode::PerRev);
+    if per_rev {
+        feed_rate = enter_feed_per_rev(
+            toolpath,
+            feed_rate,
+            spindle_speed,
+            &mut st.modal,
+            &mut st.line_num,
+            config,
+            out,
+        )?;
+    }
+
+    // With preload on, stage the next tool ahead of this toolpath's final
+    // block so the carousel is in position when the change comes.
+    let mut preload = toolpaths
+        .get(index + 1)
+        .filter(|next| config.tool_change.preload && next.tool_number != toolpath.tool_number)
+        .map(|next| format!("{}{}", config.words.tool, next.tool_number));
+    let last_pass = toolpath.passes.len().saturating_sub(1);
+
+    if let Some(cycle) = drill_cycle(toolpath, config) {
+        st.moved = true;
+        emit_drill_cycle(
+            &cycle,
+            toolpath,
+            feed_rate,
+            &mut st.modal,
+            &mut st.line_num,
+            config,
+            out,
+        )?;
+    } else if let Some(cuts) = expand_peck_drill(toolpath, config) {
+        st.moved = true;
+        for cut in &cuts {
+            emit_cut(
+                cut,
+                &[],
+                feed_rate,
+                &mut st.modal,
+                &mut st.line_num,
+                config,
+                out,
+            )?;
+        }
+    } else {
+        for (pass_index, pass) in toolpath.passes.iter().enumerate() {
+            if options.comments.verbose() {
                 let label = pass_comment_label(&pass.kind, config);
-                push_raw(&mut out, &label, &mut line_num, config);
+                push_raw(out, &label, &mut st.line_num, config);
             }
 
-            for cut in &pass.cuts {
-                if let Some(ToolOrientation::FiveAxis { .. }) = &cut.tool_orientation {
-                    return Err(PostProcessorError::NotSupported(
-                        "5-axis orientation not supported".to_string(),
-                    ));
+            if let (true, Some(clearance_z), Some(first)) =
+                (st.moved, options.clearance_z, pass.cuts.first())
+            {
+                if first.move_kind == MoveKind::Rapid {
+                    emit_clearance_retract(
+                        &first.position,
+                        clearance_z,
+                        &mut st.modal,
+                        &mut st.line_num,
+                        config,
+                        out,
+                    );
                 }
-                emit_cut(cut, toolpath, &mut modal, &mut line_num, config, &mut out)?;
+            }
+            st.moved |= !pass.cuts.is_empty();
+
+            let pass_feed = match pass.feed_rate {
+                Some(rate) => {
+                    let rate = output_feed(rate, toolpath, config, options, &mut st.warnings);
+                    if per_rev {
+                        rate / spindle_speed
+                    } else {
+                        rate
+                    }
+                }
+                None => feed_rate,
+            };
+            for (cut_index, cut) in pass.cuts.iter().enumerate() {
+                let rotary_words = st.rotary.words(cut, toolpath, config)?;
+                if pass_index == last_pass && cut_index + 1 == pass.cuts.len() {
+                    if let Some(word) = preload.take() {
+                        push_raw(out, &word, &mut st.line_num, config);
+                    }
+                }
+                let cut_feed = if per_rev {
+                    pass_feed
+                } else {
+                    select_feed_mode(
+                        cut,
+                        &rotary_words,
+                        pass_feed,
+                        &mut st.modal,
+                        &mut st.line_num,
+                        config,
+                        out,
+                    )
+                };
+                emit_cut(
+                    cut,
+                    &rotary_words,
+                    cut_feed,
+                    &mut st.modal,
+                    &mut st.line_num,
+                    config,
+                    out,
+                )?;
             }
         }
+        leave_inverse_time(&mut st.modal, &mut st.line_num, config, out);
+    }
+    if let Some(word) = preload {
+        push_raw(out, &word, &mut st.line_num, config);
+    }
+    if per_rev {
+        let code = &config.words.feed_per_min;
+        st.modal.should_emit_feed_mode(code);
+        push_raw(out, code, &mut st.line_num, config);
+        st.modal.forget_feed();
     }
 
-    // 7. Footer lines
-    for line in &config.program.footer {
-        push_raw(&mut out, line, &mut line_num, config);
+    // Leave XZ/YZ before the next tool change, whose modal reset forgets
+    // the active plane.
+    if toolpaths
+        .get(index + 1)
+        .is_some_and(|next| next.tool_number != toolpath.tool_number)
+    {
+        restore_xy_plane(&mut st.modal, &mut st.line_num, config, out);
     }
 
-    // 8. Closing % delimiter
-    if config.format.percent_delimiters {
-        out.push('%');
-        out.push_str(&config.format.eol);
+    if coolant.is_some() {
+        let ln = next_line_num(&mut st.line_num, config);
+        out.push_str(
+            &BlockBuilder::new()
+                .coolant_m(&config.coolant.off)
+                .build()
+                .render(ln, config),
+        );
     }
 
-    Ok(out)
+    if options.operation_markers {
+        let marker = operation_marker("OP_END", toolpath, config);
+        push_raw(out, &marker, &mut st.line_num, config);
+    }
+    Ok(())
 }
 
-fn pass_comment_label(kind: &PassKind, config: &PostProcessorConfig) -> String {
-    let name = match kind {
-        PassKind::SpringPass => return "; *** SPRING PASS ***".to_string(),
-        PassKind::Cutting => "Cutting pass",
-        PassKind::Linking => "Linking pass",
-        PassKind::LeadIn => "Lead-in",
-        PassKind::LeadOut => "Lead-out",
-    };
-    format!(
-        "{}{}{}",
-        config.program.comment_open, name, config.program.comment_close
-    )
+/// Emits the retract ahead of a tool change: a Z-only rapid up to `safe_z`,
+/// then a rapid to `safe_xy` at that height when one is configured.
+fn emit_tool_change_retract(
+    safe_z: f64,
+    safe_xy: Option<[f64; 2]>,
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) {
+    let code = config.motion.rapid.as_str();
+    modal.should_emit_motion(code);
+    let ln = next_line_num(line_num, config);
+    out.push_str(
+        &BlockBuilder::new()
+            .motion(code)
+            .axis('Z', safe_z)
+            .build()
+            .render(ln, config),
+    );
+    if let Some([x, y]) = safe_xy {
+        let ln = next_line_num(line_num, config);
+        out.push_str(
+            &BlockBuilder::new()
+                .axis('X', x)
+                .axis('Y', y)
+                .build()
+                .render(ln, config),
+        );
+    }
 }
 
-/// Emits a raw G-code text line, prepending an N-word if line numbering is enabled.
-fn push_raw(out: &mut String, text: &str, line_num: &mut u32, config: &PostProcessorConfig) {
-    if config.format.line_numbers && !text.is_empty() {
-        out.push_str(&format!("N{}{}", line_num, config.format.word_separator));
-        *line_num = line_num.saturating_add(config.format.line_number_increment);
+/// Emits the safe retract ahead of a rapid to `target`: a Z-only rapid up
+/// to `clearance_z`, then an XY rapid over `target` at that height. The
+/// pass's own rapid then plunges to `target`.
+///
+/// Like every other move, the blocks go through `modal`: the retract is
+/// skipped when the tool is already at `clearance_z`, the traverse when it is
+/// already over `target`, and the rapid code is only written once.
+fn emit_clearance_retract(
+    target: &Vec3,
+    clearance_z: f64,
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) {
+    let code = config.motion.rapid.as_str();
+    let mut rapid = |words: &[(char, f64)], modal: &mut ModalState| {
+        let mut bb = BlockBuilder::new();
+        if modal.should_emit_motion(code) {
+            bb = bb.motion(code);
+        }
+        for &(letter, value) in words {
+            bb = bb.axis(letter, value);
+        }
+        let ln = next_line_num(line_num, config);
+        out.push_str(&bb.build().render(ln, config));
+    };
+
+    if modal.should_emit_coord('Z', clearance_z) {
+        rapid(&[('Z', clearance_z)], modal);
     }
-    out.push_str(text);
-    out.push_str(&config.format.eol);
-}
 
-/// Returns the next line number and advances the counter, or `None` if line numbering is off.
-fn next_line_num(line_num: &mut u32, config: &PostProcessorConfig) -> Option<u32> {
-    if config.format.line_numbers {
-        let n = *line_num;
-        *line_num = line_num.saturating_add(config.format.line_number_increment);
-        Some(n)
-    } else {
-        None
+    let mut traverse = Vec::new();
+    if modal.should_emit_coord('X', target.x) {
+        traverse.push(('X', target.x));
+    }
+    if modal.should_emit_coord('Y', target.y) {
+        traverse.push(('Y', target.y));
+    }
+    if !traverse.is_empty() {
+        rapid(&traverse, modal);
     }
 }
 
-/// Emits one G-code block for a single cut point, applying modal suppression.
-fn emit_cut(
-    cut: &CutPoint,
-    toolpath: &Toolpath,
-    modal: &mut ModalState,
-    line_num: &mut u32,
+/// Checks `toolpaths` against machine limits that would alarm the controller.
+///
+/// Returns [`PostProcessorError::Validation`] naming the first tool number
+/// that exceeds `machine.tool_capacity`.
+pub fn validate(
+    toolpaths: &[Toolpath],
     config: &PostProcessorConfig,
-    out: &mut String,
 ) -> Result<(), PostProcessorError> {
-    let pos = &cut.position;
+    if let Some(capacity) = config.machine.tool_capacity {
+        if let Some(tp) = toolpaths.iter().find(|tp| tp.tool_number > capacity) {
+            return Err(PostProcessorError::Validation(format!(
+                "tool T{} exceeds the machine's tool capacity of {capacity}",
+                tp.tool_number
+            )));
+        }
+    }
+    for tp in toolpaths {
+        if let Some(mode) = tp.coolant {
+            coolant_code(mode, config)?;
+        }
+    }
+    Ok(())
+}
 
-    match &cut.move_kind {
-        MoveKind::Rapid => {
+/// Returns the code that switches on `mode`, or `None` for
+/// [`CoolantMode::Off`].
+///
+/// Returns [`PostProcessorError::NotSupported`] for through-tool coolant on a
+/// post without `coolant.through_tool`.
+fn coolant_code(
+    mode: CoolantMode,
+    config: &PostProcessorConfig,
+) -> Result<Option<&str>, PostProcessorError> {
+    let coolant = &config.coolant;
+    let code = match mode {
+        CoolantMode::Off => return Ok(None),
+        CoolantMode::Flood => coolant.flood.as_str(),
+        CoolantMode::Mist => coolant.mist.as_str(),
+        CoolantMode::Air => coolant.air.as_str(),
+        CoolantMode::ThroughTool => coolant
+            .through_tool
+            .as_deref()
+            .filter(|code| !code.is_empty())
+            .ok_or_else(|| {
+                PostProcessorError::NotSupported(format!(
+                    "through-tool coolant: post '{}' defines no coolant.through_tool code",
+                    config.meta.id
+                ))
+            })?,
+    };
+    Ok(Some(code))
+}
+
+/// Returns `true` if `toolpath` has at least one move that does work at the
+/// part: a feed, arc, helix or probe move. Rapids and dwells alone do not
+/// count.
+fn does_work(toolpath: &Toolpath) -> bool {
+    toolpath
+        .passes
+        .iter()
+        .flat_map(|pass| &pass.cuts)
+        .any(|cut| {
+            matches!(
+                cut.move_kind,
+                MoveKind::Feed
+                    | MoveKind::Arc { .. }
+                    | MoveKind::Helix { .. }
+                    | MoveKind::ProbeToward { .. }
+            )
+        })
+}
+
+/// Move lengths and run time from one walk over a sequence of toolpaths.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MotionTotals {
+    /// Total length of rapid moves.
+    pub rapid_distance: f64,
+    /// Total length of feed, arc and helix moves, and of moves to a probe
+    /// start.
+    pub feed_distance: f64,
+    /// Number of arc and helix moves.
+    pub arc_count: usize,
+    /// Feed time at each pass's feed rate, rapid time at the rapid rate,
+    /// dwells and probe travel at the probe feed.
+    pub seconds: f64,
+}
+
+/// Walks the moves of `toolpaths` in order, summing distances and time.
+///
+/// Feed, arc and helix lengths are timed at each pass's feed rate (its
+/// override, else the toolpath's), rapid lengths at `rapid_rate` (mm/min).
+/// Dwells add their time without moving; probe moves add their full travel
+/// at the probe feed. The first move has no known start and is not timed.
+pub fn motion_totals(toolpaths: &[Toolpath], rapid_rate: f64) -> MotionTotals {
+    let mut totals = MotionTotals::default();
+    let mut last: Option<&Vec3> = None;
+
+    for toolpath in toolpaths {
+        for pass in &toolpath.passes {
+            let feed_rate = pass.feed_rate.unwrap_or(toolpath.feed_rate);
+            for cut in &pass.cuts {
+                let pos = &cut.position;
+                let step = last.map_or(0.0, |from| distance(from, pos));
+                let mut feed = |length: f64| {
+                    totals.feed_distance += length;
+                    totals.seconds += feed_seconds(length, feed_rate);
+                };
+                last = Some(match &cut.move_kind {
+                    MoveKind::Rapid => {
+                        totals.rapid_distance += step;
+                        totals.seconds += feed_seconds(step, rapid_rate);
+                        pos
+                    }
+                    MoveKind::Feed => {
+                        feed(step);
+                        pos
+                    }
+                    MoveKind::Arc {
+                        center,
+                        end,
+                        clockwise,
+                    } => {
+                        feed(step + arcs::arc_length(pos, center, end, *clockwise));
+                        totals.arc_count += 1;
+                        end
+                    }
+                    MoveKind::Helix {
+                        center,
+                        end,
+                        clockwise,
+                        pitch,
+                    } => {
+                        feed(step + arcs::helix_length(pos, center, end, *clockwise, *pitch));
+                        totals.arc_count += 1;
+                        end
+                    }
+                    MoveKind::Dwell { seconds } => {
+                        totals.seconds += seconds;
+                        continue;
+                    }
+                    MoveKind::ProbeToward {
+                        target,
+                        feed: probe_feed,
+                    } => {
+                        feed(step);
+                        totals.seconds += feed_seconds(distance(pos, target), *probe_feed);
+                        target
+                    }
+                });
+            }
+        }
+    }
+    totals
+}
+
+/// Estimates the run time of `toolpaths` in seconds with [`motion_totals`].
+///
+/// Returns `None` when nothing in the program contributes time.
+pub fn estimate(toolpaths: &[Toolpath], rapid_rate: f64) -> Option<f64> {
+    let seconds = motion_totals(toolpaths, rapid_rate).seconds;
+    (seconds > 0.0).then_some(seconds)
+}
+
+/// Builds the coolant-off / spindle-off block that precedes the footer.
+///
+/// Codes the footer already emits are left out, as is spindle off when
+/// `keep_spindle_on_end` is set. `None` when nothing remains.
+fn safe_shutdown_block(config: &PostProcessorConfig) -> Option<Block> {
+    let in_footer = |code: &str| config.program.footer.iter().any(|l| l.trim() == code);
+    let spindle_off = !config.program.keep_spindle_on_end && !in_footer(&config.spindle.off);
+    let coolant_off = !in_footer(&config.coolant.off);
+    if !spindle_off && !coolant_off {
+        return None;
+    }
+
+    let mut bb = BlockBuilder::new();
+    if coolant_off {
+        bb = bb.coolant_m(&config.coolant.off);
+    }
+    if spindle_off {
+        bb = bb.spindle_m(&config.spindle.off);
+    }
+    Some(bb.build())
+}
+
+/// One hole of a canned drilling cycle, in program coordinates.
+struct DrillHole {
+    x: f64,
+    y: f64,
+    /// Deepest Z reached in the hole.
+    bottom: f64,
+    /// Z the first plunge started from; output as the cycle's R plane.
+    retract: f64,
+}
+
+/// A canned drilling cycle ready to emit in place of a drill toolpath's moves.
+struct DrillCycle<'a> {
+    code: &'a str,
+    peck: Option<f64>,
+    holes: Vec<DrillHole>,
+}
+
+/// Returns the canned cycle for `toolpath` when the post supports cycles and
+/// the toolpath came from a drill operation: `cycles.peck` when the operation
+/// has a peck depth, else `cycles.drill`.
+///
+/// `None` (expand to linear moves) when the post lacks the needed code or the
+/// toolpath contains no plunges.
+fn drill_cycle<'a>(toolpath: &Toolpath, config: &'a PostProcessorConfig) -> Option<DrillCycle<'a>> {
+    if !config.cycles.supported {
+        return None;
+    }
+    let params = toolpath.drill.as_ref()?;
+    let code = match params.peck_depth {
+        Some(_) => config.cycles.peck.as_deref()?,
+        None => config.cycles.drill.as_deref()?,
+    };
+    let holes = drill_holes(toolpath);
+    (!holes.is_empty()).then_some(DrillCycle {
+        code,
+        peck: params.peck_depth,
+        holes,
+    })
+}
+
+/// Recovers the holes of a drill toolpath from its plunges: vertical feed
+/// moves downward. Consecutive plunges at the same XY (pecks) form one hole.
+fn drill_holes(toolpath: &Toolpath) -> Vec<DrillHole> {
+    const EPSILON: f64 = 1e-6;
+    let same_xy = |a: &Vec3, x: f64, y: f64| (a.x - x).abs() < EPSILON && (a.y - y).abs() < EPSILON;
+
+    let mut holes: Vec<DrillHole> = Vec::new();
+    let mut from: Option<&Vec3> = None;
+    for cut in toolpath.passes.iter().flat_map(|pass| &pass.cuts) {
+        let pos = &cut.position;
+        if let (MoveKind::Feed, Some(from)) = (&cut.move_kind, from) {
+            if same_xy(from, pos.x, pos.y) && pos.z < from.z {
+                match holes.last_mut() {
+                    Some(hole) if same_xy(pos, hole.x, hole.y) => {
+                        hole.bottom = hole.bottom.min(pos.z);
+                    }
+                    _ => holes.push(DrillHole {
+                        x: pos.x,
+                        y: pos.y,
+                        bottom: pos.z,
+                        retract: from.z,
+                    }),
+                }
+            }
+        }
+        from = Some(match &cut.move_kind {
+            MoveKind::Arc { end, .. } | MoveKind::Helix { end, .. } => end,
+            _ => pos,
+        });
+    }
+    holes
+}
+
+/// Fraction of the peck depth left between the hole bottom so far and the
+/// rapid re-entry before each expanded peck.
+const PECK_REENTRY_FRACTION: f64 = 0.1;
+
+/// Expands a peck-drill toolpath into explicit moves for posts without
+/// canned cycles, replacing its own plunges.
+///
+/// Each hole is approached at the toolpath's starting height and drilled
+/// from its R plane down to the bottom in `peck_depth` steps measured from
+/// the top of the hole (bottom + operation depth): a feed to the next peck
+/// depth, then a rapid back to the R plane to clear chips. Every peck after
+/// the first rapids back down to just above the previous depth first.
+///
+/// `None` (emit the toolpath's moves as-is) when the post supports cycles,
+/// the toolpath is not a peck drill, or it contains no plunges.
+fn expand_peck_drill(toolpath: &Toolpath, config: &PostProcessorConfig) -> Option<Vec<CutPoint>> {
+    if config.cycles.supported {
+        return None;
+    }
+    let params = toolpath.drill.as_ref()?;
+    let peck = params.peck_depth.filter(|&q| q > 0.0)?;
+    let holes = drill_holes(toolpath);
+    let first = holes.first()?;
+    let approach_z = toolpath
+        .passes
+        .iter()
+        .flat_map(|pass| &pass.cuts)
+        .next()
+        .map_or(first.retract, |cut| cut.position.z);
+
+    let point = |x: f64, y: f64, z: f64, move_kind: MoveKind| CutPoint {
+        position: Vec3 { x, y, z },
+        move_kind,
+        tool_orientation: None,
+    };
+    let mut cuts = Vec::new();
+    for hole in &holes {
+        let (x, y) = (hole.x, hole.y);
+        cuts.push(point(x, y, approach_z, MoveKind::Rapid));
+        cuts.push(point(x, y, hole.retract, MoveKind::Rapid));
+        let top = (hole.bottom + params.depth).min(hole.retract);
+        let mut reached: Option<f64> = None;
+        for step in 1.. {
+            let z = (top - peck * step as f64).max(hole.bottom);
+            if let Some(prev) = reached {
+                let reentry = (prev + peck * PECK_REENTRY_FRACTION).min(hole.retract);
+                cuts.push(point(x, y, reentry, MoveKind::Rapid));
+            }
+            cuts.push(point(x, y, z, MoveKind::Feed));
+            cuts.push(point(x, y, hole.retract, MoveKind::Rapid));
+            if z <= hole.bottom {
+                break;
+            }
+            reached = Some(z);
+        }
+        cuts.push(point(x, y, approach_z, MoveKind::Rapid));
+    }
+    Some(cuts)
+}
+
+/// Emits `cycle` for `toolpath`: a rapid over the first hole at the
+/// toolpath's starting height, the cycle itself in the post's
+/// `cycles.style`, then `cycles.cycle_cancel`.
+fn emit_drill_cycle(
+    cycle: &DrillCycle,
+    toolpath: &Toolpath,
+    feed_rate: f64,
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) -> Result<(), PostProcessorError> {
+    // Canned cycles drill along Z, which needs the XY plane.
+    restore_xy_plane(modal, line_num, config, out);
+    let first = &cycle.holes[0];
+    let approach_z = toolpath
+        .passes
+        .iter()
+        .flat_map(|pass| &pass.cuts)
+        .next()
+        .map_or(first.retract, |cut| cut.position.z);
+    let approach = CutPoint {
+        position: Vec3 {
+            x: first.x,
+            y: first.y,
+            z: approach_z,
+        },
+        move_kind: MoveKind::Rapid,
+        tool_orientation: None,
+    };
+    emit_cut(&approach, &[], feed_rate, modal, line_num, config, out)?;
+
+    match config.cycles.style {
+        CycleStyle::Fanuc => emit_fanuc_cycle(cycle, feed_rate, modal, line_num, config, out),
+        CycleStyle::Siemens => {
+            emit_siemens_cycle(cycle, approach_z, feed_rate, line_num, config, out)
+        }
+    }
+
+    if let Some(cancel) = &config.cycles.cycle_cancel {
+        push_raw(out, cancel, line_num, config);
+    }
+    // The cycle leaves motion mode and Z position up to the controller.
+    modal.reset();
+    Ok(())
+}
+
+/// Fanuc-style cycle: the cycle block with Z/R/Q/F at the first hole, then
+/// one XY block per further hole, repeating Z or R only when they change.
+fn emit_fanuc_cycle(
+    cycle: &DrillCycle,
+    feed_rate: f64,
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) {
+    let first = &cycle.holes[0];
+    let mut bb = BlockBuilder::new()
+        .motion(cycle.code)
+        .axis('Z', first.bottom)
+        .arc_param('R', first.retract);
+    if let Some(q) = cycle.peck {
+        bb = bb.peck(q);
+    }
+    let ln = next_line_num(line_num, config);
+    out.push_str(&bb.feed(feed_rate).build().render(ln, config));
+
+    let mut prev = first;
+    for hole in &cycle.holes[1..] {
+        let mut bb = BlockBuilder::new();
+        if modal.should_emit_coord('X', hole.x) {
+            bb = bb.axis('X', hole.x);
+        }
+        if modal.should_emit_coord('Y', hole.y) {
+            bb = bb.axis('Y', hole.y);
+        }
+        if hole.bottom != prev.bottom {
+            bb = bb.axis('Z', hole.bottom);
+        }
+        if hole.retract != prev.retract {
+            bb = bb.arc_param('R', hole.retract);
+        }
+        let ln = next_line_num(line_num, config);
+        out.push_str(&bb.build().render(ln, config));
+        prev = hole;
+    }
+}
+
+/// Siemens-style cycle: the feed on its own block, then an
+/// `MCALL <code>(...)` call and an XY block per hole. A modal call runs after
+/// every positioning block, so the first hole is positioned again, and the
+/// call is re-issued whenever a hole's depth or R plane changes.
+fn emit_siemens_cycle(
+    cycle: &DrillCycle,
+    approach_z: f64,
+    feed_rate: f64,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) {
+    let ln = next_line_num(line_num, config);
+    out.push_str(
+        &BlockBuilder::new()
+            .feed(feed_rate)
+            .build()
+            .render(ln, config),
+    );
+
+    let mut prev: Option<&DrillHole> = None;
+    for hole in &cycle.holes {
+        if prev.map_or(true, |p| {
+            p.bottom != hole.bottom || p.retract != hole.retract
+        }) {
+            let call = siemens_cycle_call(cycle, approach_z, hole, config);
+            push_raw(out, &call, line_num, config);
+        }
+        let block = BlockBuilder::new()
+            .axis('X', hole.x)
+            .axis('Y', hole.y)
+            .build();
+        let ln = next_line_num(line_num, config);
+        out.push_str(&block.render(ln, config));
+        prev = Some(hole);
+    }
+}
+
+/// `MCALL CYCLE81(RTP, RFP, SDIS, DP)` for a plain drill, or
+/// `MCALL CYCLE83(RTP, RFP, SDIS, DP, DPR, FDEP, FDPR, DAM, DTB, DTS, FRF, VARI)`
+/// with a full retract between pecks when the cycle pecks. The return plane
+/// is the approach height and the reference plane the hole's R plane, with
+/// no extra safety distance.
+fn siemens_cycle_call(
+    cycle: &DrillCycle,
+    approach_z: f64,
+    hole: &DrillHole,
+    config: &PostProcessorConfig,
+) -> String {
+    let num = |v: f64| {
+        format_coord(
+            v,
+            config.format.decimal_places,
+            !config.format.trailing_zeros,
+            config.format.leading_zero_suppression,
+        )
+    };
+    let (rtp, rfp, dp) = (num(approach_z), num(hole.retract), num(hole.bottom));
+    let args = match cycle.peck {
+        None => format!("{rtp}, {rfp}, 0, {dp}"),
+        Some(q) => format!(
+            "{rtp}, {rfp}, 0, {dp}, , {}, , {}, 0, 0, 1, 1",
+            num(hole.retract - q),
+            num(q)
+        ),
+    };
+    format!("MCALL {}({args})", cycle.code)
+}
+
+/// Rotary-axis state for 5-axis output: one [`RotaryTracker`] per A/B/C axis.
+struct RotaryAxes {
+    trackers: [RotaryTracker; 3],
+}
+
+impl RotaryAxes {
+    fn new(config: &PostProcessorConfig) -> Self {
+        let rollover = config.axes.rollover_active();
+        Self {
+            trackers: std::array::from_fn(|_| RotaryTracker::new(rollover)),
+        }
+    }
+
+    /// Returns the rotary words that orient the tool for `cut`; empty unless
+    /// it carries a 5-axis tool axis. Within [`SINGULARITY_TILT`] of vertical
+    /// the rotation axis holds its last position instead of swinging.
+    ///
+    /// Fails with [`PostProcessorError::NotSupported`] when the machine has no
+    /// `five_axis_type` or lacks a needed rotary axis, and with
+    /// [`PostProcessorError::Validation`] when an angle falls outside
+    /// `axes.limits`.
+    fn words(
+        &mut self,
+        cut: &CutPoint,
+        toolpath: &Toolpath,
+        config: &PostProcessorConfig,
+    ) -> Result<Vec<(char, f64)>, PostProcessorError> {
+        let Some(ToolOrientation::FiveAxis { tool_axis }) = &cut.tool_orientation else {
+            return Ok(Vec::new());
+        };
+        let kind = config.machine.five_axis_type.as_ref().ok_or_else(|| {
+            PostProcessorError::NotSupported(
+                "5-axis orientation requires machine.five_axis_type".to_string(),
+            )
+        })?;
+
+        let [tilt, (rotation, mut angle)] = inverse_kinematics(kind, tool_axis)?;
+        if tilt.1.abs() < SINGULARITY_TILT {
+            angle = self.trackers[rotation.index()].last().unwrap_or(angle);
+        }
+
+        let mut words = Vec::with_capacity(2);
+        for (axis, angle) in [tilt, (rotation, angle)] {
+            let letter = axis.letter(&config.axes).ok_or_else(|| {
+                PostProcessorError::NotSupported(format!("machine has no {axis:?} axis"))
+            })?;
+            let value = self.trackers[axis.index()].next(angle);
+            if let Some(limits) = &config.axes.limits {
+                let (min, max) = axis.range(limits);
+                if value < min || value > max {
+                    return Err(PostProcessorError::Validation(format!(
+                        "operation {}: {letter}{value} outside axis limits [{min}, {max}]",
+                        toolpath.operation_id
+                    )));
+                }
+            }
+            words.push((letter, value));
+        }
+        Ok(words)
+    }
+}
+
+/// Returns the path-mode code for `toolpath`: its own mode, else the
+/// `[path_mode]` default. `None` when the section is absent or neither is set.
+fn path_mode_code<'a>(toolpath: &Toolpath, config: &'a PostProcessorConfig) -> Option<&'a str> {
+    let codes = config.path_mode.as_ref()?;
+    match toolpath.path_mode.or(codes.default)? {
+        PathMode::ExactStop => Some(&codes.exact_stop),
+        PathMode::Continuous => Some(&codes.continuous),
+    }
+}
+
+/// Computes the feed word value for `feed_rate`, a feed of `toolpath`.
+///
+/// Applies `options.feed_scale`, then raises the result to
+/// `feeds.min_feed_rate` if it fell below it, recording a warning. The floor
+/// is applied last so no transformation can push a feed under it.
+fn output_feed(
+    feed_rate: f64,
+    toolpath: &Toolpath,
+    config: &PostProcessorConfig,
+    options: &GenerateOptions,
+    warnings: &mut Vec<String>,
+) -> f64 {
+    let feed = feed_rate * options.feed_scale.unwrap_or(1.0);
+    match config.feeds.min_feed_rate {
+        Some(min) if feed < min => {
+            warnings.push(format!(
+                "operation {}: feed {feed} raised to minimum feed rate {min}",
+                toolpath.operation_id
+            ));
+            min
+        }
+        _ => feed,
+    }
+}
+
+/// Chooses the feed mode for `cut` and returns the F value to emit with it.
+///
+/// A linear feed that moves rotary axes on a machine without RTCP runs in
+/// inverse-time mode (`words.inverse_time`), with F = 1 / move time in
+/// minutes, where the time comes from the XYZ distance at `feed_rate`. Any
+/// other cutting move returns to units-per-minute and keeps `feed_rate`.
+/// Mode switches go through [`ModalState::should_emit_feed_mode`], so
+/// consecutive inverse-time moves emit `G93` only once.
+fn select_feed_mode(
+    cut: &CutPoint,
+    rotary: &[(char, f64)],
+    feed_rate: f64,
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) -> f64 {
+    match cut.move_kind {
+        MoveKind::Rapid | MoveKind::Dwell { .. } => return feed_rate,
+        MoveKind::Feed => {}
+        MoveKind::Arc { .. } | MoveKind::Helix { .. } | MoveKind::ProbeToward { .. } => {
+            leave_inverse_time(modal, line_num, config, out);
+            return feed_rate;
+        }
+    }
+
+    let inverse_time = &config.words.inverse_time;
+    let from = match (modal.coord('X'), modal.coord('Y'), modal.coord('Z')) {
+        (Some(x), Some(y), Some(z)) => Some(Vec3 { x, y, z }),
+        _ => None,
+    };
+    let length = from.map_or(0.0, |from| distance(&from, &cut.position));
+    if rotary.is_empty()
+        || config.machine.rtcp_supported
+        || inverse_time.is_empty()
+        || length <= 0.0
+        || feed_rate <= 0.0
+    {
+        leave_inverse_time(modal, line_num, config, out);
+        return feed_rate;
+    }
+
+    if modal.should_emit_feed_mode(inverse_time) {
+        push_raw(out, inverse_time, line_num, config);
+    }
+    // Every inverse-time block needs its own F word.
+    modal.forget_feed();
+    feed_rate / length
+}
+
+/// Switches to feed-per-revolution mode (`words.feed_per_rev`) for a
+/// [`FeedMode::PerRev`] toolpath and returns `feed_rate` (units per minute)
+/// restated as units per spindle revolution.
+///
+/// Returns [`PostProcessorError::NotSupported`] when the post defines no
+/// `words.feed_per_rev` code or the toolpath has no spindle speed.
+fn enter_feed_per_rev(
+    toolpath: &Toolpath,
+    feed_rate: f64,
+    spindle_speed: f64,
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) -> Result<f64, PostProcessorError> {
+    let code = &config.words.feed_per_rev;
+    if code.is_empty() {
+        return Err(PostProcessorError::NotSupported(format!(
+            "feed per revolution: post '{}' defines no words.feed_per_rev code",
+            config.meta.id
+        )));
+    }
+    if spindle_speed <= 0.0 {
+        return Err(PostProcessorError::NotSupported(format!(
+            "operation {}: feed per revolution needs a spindle speed",
+            toolpath.operation_id
+        )));
+    }
+    if modal.should_emit_feed_mode(code) {
+        push_raw(out, code, line_num, config);
+    }
+    // F now means units per revolution, so the next feed word must appear.
+    modal.forget_feed();
+    Ok(feed_rate / spindle_speed)
+}
+
+/// Returns to units-per-minute feed (`words.feed_per_min`) if inverse-time
+/// mode is active, and forces the next F word since its meaning changes.
+fn leave_inverse_time(
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) {
+    if modal.feed_mode() != Some(config.words.inverse_time.as_str()) {
+        return;
+    }
+    let code = &config.words.feed_per_min;
+    modal.should_emit_feed_mode(code);
+    push_raw(out, code, line_num, config);
+    modal.forget_feed();
+}
+
+/// Computes the spindle speed for `toolpath`, clamped to `spindle.max_rpm`
+/// (0 means no limit) with a warning when the clamp applies.
+fn output_spindle(
+    toolpath: &Toolpath,
+    config: &PostProcessorConfig,
+    warnings: &mut Vec<String>,
+) -> f64 {
+    let max = f64::from(config.spindle.max_rpm);
+    if max > 0.0 && toolpath.spindle_speed > max {
+        warnings.push(format!(
+            "operation {}: spindle speed {} clamped to max_rpm {max}",
+            toolpath.operation_id, toolpath.spindle_speed
+        ));
+        max
+    } else {
+        toolpath.spindle_speed
+    }
+}
+
+fn feed_seconds(length: f64, feed_rate: f64) -> f64 {
+    if feed_rate > 0.0 {
+        length / feed_rate * 60.0
+    } else {
+        0.0
+    }
+}
+
+fn distance(a: &Vec3, b: &Vec3) -> f64 {
+    ((b.x - a.x).powi(2) + (b.y - a.y).powi(2) + (b.z - a.z).powi(2)).sqrt()
+}
+
+/// Formats a duration in seconds as `HH:MM:SS`, rounded to the nearest second.
+fn format_hms(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total / 3600,
+        total / 60 % 60,
+        total % 60
+    )
+}
+
+fn operation_marker(tag: &str, toolpath: &Toolpath, config: &PostProcessorConfig) -> String {
+    format!(
+        "{}{} {}{}",
+        config.program.comment_open, tag, toolpath.operation_id, config.program.comment_close
+    )
+}
+
+fn pass_comment_label(kind: &PassKind, config: &PostProcessorConfig) -> String {
+    let name = match kind {
+        PassKind::SpringPass => return "; *** SPRING PASS ***".to_string(),
+        PassKind::Cutting => "Cutting pass",
+        PassKind::Linking => "Linking pass",
+        PassKind::LeadIn => "Lead-in",
+        PassKind::LeadOut => "Lead-out",
+    };
+    format!(
+        "{}{}{}",
+        config.program.comment_open, name, config.program.comment_close
+    )
+}
+
+/// Records in `modal` the distance mode a raw `line` leaves active: the last
+/// of its words matching `words.absolute` or `words.incremental`.
+fn track_distance_mode(line: &str, modal: &mut ModalState, config: &PostProcessorConfig) {
+    let words = &config.words;
+    for word in line.split_whitespace() {
+        if word == words.absolute || word == words.incremental {
+            modal.should_emit_distance_mode(word);
+        }
+    }
+}
+
+/// Emits a raw G-code text line, prepending an N-word if line numbering is enabled.
+fn push_raw(out: &mut ProgramWriter, text: &str, line_num: &mut u32, config: &PostProcessorConfig) {
+    if config.format.line_numbers && !text.is_empty() {
+        out.push_str(&format!("N{}{}", line_num, config.format.word_separator));
+        advance_line_num(line_num, config);
+    }
+    out.push_str(text);
+    out.push_str(&config.format.eol);
+}
+
+/// Returns the next line number and advances the counter, or `None` if line numbering is off.
+fn next_line_num(line_num: &mut u32, config: &PostProcessorConfig) -> Option<u32> {
+    if config.format.line_numbers {
+        let n = *line_num;
+        advance_line_num(line_num, config);
+        Some(n)
+    } else {
+        None
+    }
+}
+
+/// Steps the line-number counter by `format.line_number_increment`, wrapping
+/// back to `format.line_number_start` once it would pass
+/// `format.line_number_max`. A max of 0 never wraps.
+fn advance_line_num(line_num: &mut u32, config: &PostProcessorConfig) {
+    let format = &config.format;
+    let next = line_num.saturating_add(format.line_number_increment);
+    *line_num = if format.line_number_max > 0 && next > format.line_number_max {
+        format.line_number_start
+    } else {
+        next
+    };
+}
+
+/// Returns the plane-select code to emit before `cut`, if the plane changes.
+///
+/// Arcs select the plane they lie in. Unless `motion.keep_arc_plane` is set,
+/// any other move restores `plane_xy` after an XZ/YZ arc. XY is never
+/// emitted while no plane has been selected yet, so programs without
+/// XZ/YZ arcs carry no plane words at all.
+fn plane_change(
+    cut: &CutPoint,
+    modal: &mut ModalState,
+    config: &PostProcessorConfig,
+) -> Option<String> {
+    let motion = &config.motion;
+    let code = match &cut.move_kind {
+        MoveKind::Arc { center, end, .. } => match arcs::arc_plane(&cut.position, end, center) {
+            ArcPlane::Xy => &motion.plane_xy,
+            ArcPlane::Xz => &motion.plane_xz,
+            ArcPlane::Yz => &motion.plane_yz,
+        },
+        MoveKind::Helix { .. } => &motion.plane_xy,
+        _ if motion.keep_arc_plane => return None,
+        _ => &motion.plane_xy,
+    };
+    if *code == motion.plane_xy && modal.plane().is_none() {
+        return None;
+    }
+    modal.should_emit_plane(code).then(|| code.clone())
+}
+
+/// Emits `plane_xy` if an XZ/YZ plane is still active.
+///
+/// Called ahead of [`ModalState::reset`], which forgets the plane while the
+/// controller stays in it, so later XY arcs would go out without a G17.
+fn restore_xy_plane(
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) {
+    let code = &config.motion.plane_xy;
+    if modal.plane().is_some_and(|p| p != code) && modal.should_emit_plane(code) {
+        push_raw(out, code, line_num, config);
+    }
+}
+
+/// Emits one G-code block for a single cut point, applying modal suppression.
+///
+/// `rotary` holds the rotary words from [`RotaryAxes::words`], appended to
+/// rapid and feed blocks. `feed_rate` is the final output feed from
+/// [`output_feed`].
+fn emit_cut(
+    cut: &CutPoint,
+    rotary: &[(char, f64)],
+    feed_rate: f64,
+    modal: &mut ModalState,
+    line_num: &mut u32,
+    config: &PostProcessorConfig,
+    out: &mut ProgramWriter,
+) -> Result<(), PostProcessorError> {
+    let pos = &cut.position;
+
+    if !rotary.is_empty() && matches!(cut.move_kind, MoveKind::Arc { .. } | MoveKind::Helix { .. })
+    {
+        return Err(PostProcessorError::NotSupported(
+            "rotary axis motion on arc moves".to_string(),
+        ));
+    }
+    if !rotary.is_empty() && matches!(cut.move_kind, MoveKind::ProbeToward { .. }) {
+        return Err(PostProcessorError::NotSupported(
+            "rotary axis motion on probe moves".to_string(),
+        ));
+    }
+
+    if let Some(code) = plane_change(cut, modal, config) {
+        push_raw(out, &code, line_num, config);
+    }
+
+    match &cut.move_kind {
+        MoveKind::Rapid => {
             let code = config.motion.rapid.as_str();
             let emit_m = modal.should_emit_motion(code);
             let emit_x = modal.should_emit_coord('X', pos.x);
@@ -205,6 +1880,11 @@ fn emit_cut(
             if emit_z {
                 bb = bb.axis('Z', pos.z);
             }
+            for &(letter, value) in rotary {
+                if modal.should_emit_coord(letter, value) {
+                    bb = bb.axis(letter, value);
+                }
+            }
             let ln = next_line_num(line_num, config);
             out.push_str(&bb.build().render(ln, config));
         }
@@ -215,7 +1895,7 @@ fn emit_cut(
             let emit_x = modal.should_emit_coord('X', pos.x);
             let emit_y = modal.should_emit_coord('Y', pos.y);
             let emit_z = modal.should_emit_coord('Z', pos.z);
-            let emit_f = modal.should_emit_feed(toolpath.feed_rate);
+            let emit_f = modal.should_emit_feed(feed_rate);
             let mut bb = BlockBuilder::new();
             if emit_m {
                 bb = bb.motion(code);
@@ -229,13 +1909,41 @@ fn emit_cut(
             if emit_z {
                 bb = bb.axis('Z', pos.z);
             }
+            for &(letter, value) in rotary {
+                if modal.should_emit_coord(letter, value) {
+                    bb = bb.axis(letter, value);
+                }
+            }
             if emit_f {
-                bb = bb.feed(toolpath.feed_rate);
+                bb = bb.feed(feed_rate);
             }
             let ln = next_line_num(line_num, config);
             out.push_str(&bb.build().render(ln, config));
         }
 
+        MoveKind::Arc {
+            center,
+            end,
+            clockwise,
+        } if matches!(config.motion.arc_format, ArcFormat::R)
+            && arcs::is_semicircle(pos, center, end, *clockwise) =>
+        {
+            // R cannot express a semicircle; emit it as two quarter arcs.
+            let mid = arcs::arc_midpoint(pos, center, end, *clockwise);
+            for (start, stop) in [(pos, &mid), (&mid, end)] {
+                let half = CutPoint {
+                    position: start.clone(),
+                    move_kind: MoveKind::Arc {
+                        center: center.clone(),
+                        end: stop.clone(),
+                        clockwise: *clockwise,
+                    },
+                    tool_orientation: cut.tool_orientation.clone(),
+                };
+                emit_cut(&half, rotary, feed_rate, modal, line_num, config, out)?;
+            }
+        }
+
         MoveKind::Arc {
             center,
             end,
@@ -250,7 +1958,7 @@ fn emit_cut(
             let emit_x = modal.should_emit_coord('X', end.x);
             let emit_y = modal.should_emit_coord('Y', end.y);
             let emit_z = modal.should_emit_coord('Z', end.z);
-            let emit_f = modal.should_emit_feed(toolpath.feed_rate);
+            let emit_f = modal.should_emit_feed(feed_rate);
             let mut bb = BlockBuilder::new();
             if emit_m {
                 bb = bb.motion(code);
@@ -266,7 +1974,11 @@ fn emit_cut(
             }
             match &config.motion.arc_format {
                 ArcFormat::Ijk => {
-                    let (i, j, k) = arcs::ijk_from_arc(pos, center);
+                    let (i, j, k) = if modal.absolute_arc_centers() {
+                        (center.x, center.y, center.z)
+                    } else {
+                        arcs::ijk_from_arc(pos, center)
+                    };
                     bb = bb.arc_param('I', i).arc_param('J', j).arc_param('K', k);
                 }
                 ArcFormat::R => {
@@ -275,26 +1987,72 @@ fn emit_cut(
                 }
             }
             if emit_f {
-                bb = bb.feed(toolpath.feed_rate);
+                bb = bb.feed(feed_rate);
             }
             let ln = next_line_num(line_num, config);
             out.push_str(&bb.build().render(ln, config));
         }
 
+        MoveKind::Helix {
+            center,
+            end,
+            clockwise,
+            pitch,
+        } => {
+            // Controllers have no helix word; emit one arc block per segment.
+            for (start, axis, stop) in arcs::helix_segments(pos, center, end, *clockwise, *pitch) {
+                let segment = CutPoint {
+                    position: start,
+                    move_kind: MoveKind::Arc {
+                        center: axis,
+                        end: stop,
+                        clockwise: *clockwise,
+                    },
+                    tool_orientation: cut.tool_orientation.clone(),
+                };
+                emit_cut(&segment, rotary, feed_rate, modal, line_num, config, out)?;
+            }
+        }
+
         MoveKind::Dwell { seconds } => {
             // G4 P{time} — BlockBuilder has no P-word slot, so emit as a raw line.
+            let time = if config.words.dwell_in_ms {
+                (seconds * 1000.0).round()
+            } else {
+                *seconds
+            };
             let p_val = format_coord(
-                *seconds,
+                time,
                 config.format.decimal_places,
                 !config.format.trailing_zeros,
                 config.format.leading_zero_suppression,
             );
             let text = format!(
-                "G4{}{}{}",
-                config.format.word_separator, config.words.dwell, p_val,
+                "{}{}{}{}",
+                config.words.dwell_code, config.format.word_separator, config.words.dwell, p_val,
             );
             push_raw(out, &text, line_num, config);
         }
+
+        MoveKind::ProbeToward { target, feed } => {
+            let probe = config.probe.as_ref().ok_or_else(|| {
+                PostProcessorError::NotSupported(format!(
+                    "probe move: post '{}' has no [probe] section",
+                    config.meta.id
+                ))
+            })?;
+            // A probe block is never shortened: every word goes out, and the
+            // stop position is unknown afterwards, so nothing is cached.
+            let bb = BlockBuilder::new()
+                .motion(&probe.toward)
+                .axis('X', target.x)
+                .axis('Y', target.y)
+                .axis('Z', target.z)
+                .feed(*feed);
+            let ln = next_line_num(line_num, config);
+            out.push_str(&bb.build().render(ln, config));
+            modal.forget_motion();
+        }
     }
 
     Ok(())
@@ -303,7 +2061,7 @@ fn emit_cut(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Vec3;
+    use crate::models::operation::DrillParams;
     use crate::postprocessor::config;
     use crate::toolpath::types::{CutPoint, MoveKind, Pass, PassKind, ToolOrientation, Toolpath};
     use uuid::Uuid;
@@ -432,15 +2190,24 @@ program_stop = "M00"
                         tool_orientation: None,
                     },
                 ],
+                feed_rate: None,
             }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
         };
 
         let opts = GenerateOptions {
             program_number: None,
-            include_comments: false,
+            comments: CommentLevel::None,
+            ..Default::default()
         };
 
-        let result = assemble(&[toolpath], &[], &cfg, &opts).expect("assembly must succeed");
+        let result = assemble(&[toolpath], &[], &cfg, &opts)
+            .expect("assembly must succeed")
+            .gcode;
 
         assert!(
             result.contains("G00"),
@@ -458,17 +2225,16 @@ program_stop = "M00"
     fn tool_change_command_is_emitted() {
         let cfg = default_config();
         let toolpath = Toolpath {
-            operation_id: Uuid::nil(),
             tool_number: 5,
             spindle_speed: 6000.0,
-            feed_rate: 300.0,
-            passes: vec![],
+            ..straight_feed_toolpath(10.0, 300.0)
         };
         let opts = GenerateOptions {
             program_number: None,
-            include_comments: false,
+            comments: CommentLevel::None,
+            ..Default::default()
         };
-        let result = assemble(&[toolpath], &[], &cfg, &opts).unwrap();
+        let result = assemble(&[toolpath], &[], &cfg, &opts).unwrap().gcode;
         assert!(
             result.contains("T5 M06"),
             "expected T5 M06 tool change, got:\n{}",
@@ -476,21 +2242,65 @@ program_stop = "M00"
         );
     }
 
+    #[test]
+    fn operation_without_cutting_moves_is_skipped_with_warning() {
+        let cfg = default_config();
+        let mut idle = straight_feed_toolpath(10.0, 300.0);
+        idle.operation_id = Uuid::from_u128(7);
+        idle.tool_number = 5;
+        idle.passes[0].cuts.truncate(1);
+
+        let result = assemble(&[idle], &[], &cfg, &GenerateOptions::default()).unwrap();
+        assert!(!result.gcode.contains("M06"), "{}", result.gcode);
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert!(
+            result.warnings[0].contains(&Uuid::from_u128(7).to_string()),
+            "{:?}",
+            result.warnings
+        );
+    }
+
     #[test]
     fn program_number_line_is_emitted() {
         let cfg = default_config();
         let opts = GenerateOptions {
             program_number: Some(42),
-            include_comments: false,
+            comments: CommentLevel::None,
+            ..Default::default()
         };
-        let result = assemble(&[], &[], &cfg, &opts).unwrap();
+        let result = assemble(&[], &[], &cfg, &opts).unwrap().gcode;
         assert!(
-            result.contains("O42"),
-            "expected O42 program number, got:\n{}",
+            result.contains("O0042"),
+            "expected O0042 program number, got:\n{}",
             result
         );
     }
 
+    #[test]
+    fn line_numbers_wrap_to_start_after_max() {
+        let mut cfg = config::parse(crate::postprocessor::LINUXCNC_TOML).unwrap();
+        cfg.format.line_numbers = true;
+        cfg.format.line_number_start = 10;
+        cfg.format.line_number_increment = 10;
+        cfg.format.line_number_max = 30;
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        let numbers: Vec<&str> = result
+            .lines()
+            .filter(|l| l.starts_with('N'))
+            .filter_map(|l| l.split_whitespace().next())
+            .take(5)
+            .collect();
+        assert_eq!(numbers, ["N10", "N20", "N30", "N10", "N20"], "{result}");
+    }
+
     #[test]
     fn program_number_line_has_no_line_number_when_line_numbers_enabled() {
         // O-line must never carry an N-word even when line_numbers = true
@@ -498,9 +2308,10 @@ program_stop = "M00"
         let cfg = config::parse(&toml).unwrap();
         let opts = GenerateOptions {
             program_number: Some(1000),
-            include_comments: false,
+            comments: CommentLevel::None,
+            ..Default::default()
         };
-        let result = assemble(&[], &[], &cfg, &opts).unwrap();
+        let result = assemble(&[], &[], &cfg, &opts).unwrap().gcode;
         let first_line = result.lines().next().expect("output must be non-empty");
         assert_eq!(
             first_line, "O1000",
@@ -509,6 +2320,116 @@ program_stop = "M00"
         );
     }
 
+    #[test]
+    fn program_number_uses_number_format() {
+        let cfg = config::parse(&minimal_toml()).unwrap();
+        let opts = GenerateOptions {
+            program_number: Some(42),
+            comments: CommentLevel::None,
+            ..Default::default()
+        };
+        let result = assemble(&[], &[], &cfg, &opts).unwrap().gcode;
+        assert_eq!(result.lines().next(), Some("O0042"), "got:\n{}", result);
+    }
+
+    #[test]
+    fn empty_number_prefix_omits_program_number_line() {
+        let cfg =
+            config::parse(&minimal_toml().replace("number_prefix = \"O\"", "number_prefix = \"\""))
+                .unwrap();
+        let opts = GenerateOptions {
+            program_number: Some(42),
+            comments: CommentLevel::None,
+            ..Default::default()
+        };
+        let result = assemble(&[], &[], &cfg, &opts).unwrap().gcode;
+        assert!(
+            !result.lines().any(|line| line.trim() == "0042"),
+            "got:\n{}",
+            result
+        );
+    }
+
+    #[test]
+    fn fresh_program_selects_absolute_mode_once_near_top() {
+        let cfg = default_config();
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        assert_eq!(result.matches("G90").count(), 1, "{result}");
+        let g90 = result.find("G90").unwrap();
+        let first_move = result.find("G00").expect("rapid move");
+        assert!(g90 < first_move, "G90 must precede all motion:\n{result}");
+    }
+
+    #[test]
+    fn header_that_selects_absolute_mode_is_not_repeated() {
+        let toml = minimal_toml().replace("header = []", "header = [\"G91 G28 Z0\", \"G90 G17\"]");
+        let cfg = config::parse(&toml).unwrap();
+        let result = assemble(&[], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        assert_eq!(result.matches("G90").count(), 1, "{result}");
+
+        // A header that ends in incremental mode gets G90 appended.
+        let toml = minimal_toml().replace("header = []", "header = [\"G90\", \"G91 G28 Z0\"]");
+        let cfg = config::parse(&toml).unwrap();
+        let result = assemble(&[], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        let lines: Vec<&str> = result.lines().collect();
+        let g91 = lines.iter().position(|l| *l == "G91 G28 Z0").unwrap();
+        assert_eq!(lines[g91 + 1], "G90", "{result}");
+    }
+
+    #[test]
+    fn absolute_arc_centers_write_center_as_ijk() {
+        let cfg = default_config();
+        let arc_toolpath = || {
+            let mut toolpath = straight_feed_toolpath(2.0, 500.0);
+            toolpath.passes[0].cuts.push(CutPoint {
+                position: Vec3 {
+                    x: 2.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                move_kind: MoveKind::Arc {
+                    center: Vec3 {
+                        x: 5.0,
+                        y: 2.0,
+                        z: 0.0,
+                    },
+                    end: Vec3 {
+                        x: 8.0,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                    clockwise: true,
+                },
+                tool_orientation: None,
+            });
+            toolpath
+        };
+
+        let relative = assemble(&[arc_toolpath()], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        assert!(relative.contains("I3 J2 K0"), "{relative}");
+
+        let opts = GenerateOptions {
+            incremental_arcs: false,
+            ..Default::default()
+        };
+        let absolute = assemble(&[arc_toolpath()], &[], &cfg, &opts).unwrap().gcode;
+        assert!(absolute.contains("I5 J2 K0"), "{absolute}");
+    }
+
     #[test]
     fn percent_delimiters_wrap_program() {
         let toml =
@@ -516,9 +2437,10 @@ program_stop = "M00"
         let cfg = config::parse(&toml).unwrap();
         let opts = GenerateOptions {
             program_number: None,
-            include_comments: false,
+            comments: CommentLevel::None,
+            ..Default::default()
         };
-        let result = assemble(&[], &[], &cfg, &opts).unwrap();
+        let result = assemble(&[], &[], &cfg, &opts).unwrap().gcode;
         assert!(
             result.starts_with('%'),
             "expected opening %, got:\n{}",
@@ -556,16 +2478,175 @@ program_stop = "M00"
                         },
                     }),
                 }],
+                feed_rate: None,
             }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
         };
         let opts = GenerateOptions {
             program_number: None,
-            include_comments: false,
+            comments: CommentLevel::None,
+            ..Default::default()
         };
         let err = assemble(&[toolpath], &[], &cfg, &opts).unwrap_err();
         assert!(matches!(err, PostProcessorError::NotSupported(_)));
     }
 
+    /// A head-table 5-axis machine: B tilts the head, C turns the table.
+    fn head_table_toml() -> String {
+        minimal_toml()
+            .replace(
+                "max_axes = 3",
+                "max_axes = 5\nfive_axis_type = \"head_table\"",
+            )
+            .replace("z = \"Z\"\n", "z = \"Z\"\nb = \"B\"\nc = \"C\"\n")
+    }
+
+    fn five_axis_feed_toolpath(tool_axes: &[Vec3]) -> Toolpath {
+        Toolpath {
+            operation_id: Uuid::nil(),
+            tool_number: 1,
+            spindle_speed: 8000.0,
+            feed_rate: 500.0,
+            passes: vec![Pass {
+                kind: PassKind::Cutting,
+                cuts: tool_axes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, axis)| CutPoint {
+                        position: Vec3 {
+                            x: 10.0 * i as f64,
+                            y: 0.0,
+                            z: 0.0,
+                        },
+                        move_kind: MoveKind::Feed,
+                        tool_orientation: Some(ToolOrientation::FiveAxis {
+                            tool_axis: axis.clone(),
+                        }),
+                    })
+                    .collect(),
+                feed_rate: None,
+            }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
+        }
+    }
+
+    #[test]
+    fn head_table_emits_b_and_c_words_for_tool_axis() {
+        let cfg = config::parse(&head_table_toml()).unwrap();
+        let toolpath = five_axis_feed_toolpath(&[
+            Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Vec3 {
+                x: 0.0,
+                y: 1.0,
+                z: 1.0,
+            },
+        ]);
+
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        assert!(
+            result
+                .lines()
+                .any(|l| l.starts_with("G01 X0 Y0 Z0 B0 C0 F")),
+            "{result}"
+        );
+        assert!(
+            result.lines().any(|l| l.starts_with("X10 B45 C-90")),
+            "{result}"
+        );
+    }
+
+    #[test]
+    fn head_table_uses_configured_rotary_letters() {
+        let toml = head_table_toml().replace("b = \"B\"", "b = \"A\"");
+        let cfg = config::parse(&toml).unwrap();
+        let toolpath = five_axis_feed_toolpath(&[
+            Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Vec3 {
+                x: 0.0,
+                y: 1.0,
+                z: 1.0,
+            },
+        ]);
+
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        assert!(
+            result.lines().any(|l| l.starts_with("X10 A45 C-90")),
+            "{result}"
+        );
+        assert!(!result.contains('B'), "{result}");
+    }
+
+    #[test]
+    fn tilted_feed_without_rtcp_uses_inverse_time() {
+        let cfg = config::parse(&head_table_toml()).unwrap();
+        let toolpath = five_axis_feed_toolpath(&[
+            Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Vec3 {
+                x: 0.0,
+                y: 1.0,
+                z: 1.0,
+            },
+        ]);
+
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        let lines: Vec<&str> = result.lines().collect();
+
+        // 10 mm at 500 mm/min takes 0.02 min, so F = 1 / 0.02 = 50.
+        let g93 = lines.iter().position(|l| *l == "G93").expect(&result);
+        assert_eq!(lines[g93 + 1], "X10 B45 C-90 F50", "{result}");
+        assert_eq!(lines.iter().filter(|l| **l == "G93").count(), 1);
+        assert_eq!(lines[g93 + 2], "G94", "{result}");
+    }
+
+    #[test]
+    fn rotary_angle_outside_limits_fails() {
+        let toml = format!(
+            "{}\n[axes.limits]\na_min = 0.0\na_max = 0.0\nb_min = -30.0\nb_max = 30.0\n\
+             c_min = -360.0\nc_max = 360.0\n",
+            head_table_toml()
+        );
+        let cfg = config::parse(&toml).unwrap();
+        let toolpath = five_axis_feed_toolpath(&[Vec3 {
+            x: 1.0,
+            y: 0.0,
+            z: 1.0,
+        }]);
+
+        let err = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default()).unwrap_err();
+        match err {
+            PostProcessorError::Validation(msg) => assert!(msg.contains("B45"), "{msg}"),
+            other => panic!("expected Validation, got {other:?}"),
+        }
+    }
+
     #[test]
     fn modal_suppresses_repeated_motion_code() {
         let cfg = default_config();
@@ -596,13 +2677,20 @@ program_stop = "M00"
                         tool_orientation: None,
                     },
                 ],
+                feed_rate: None,
             }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
         };
         let opts = GenerateOptions {
             program_number: None,
-            include_comments: false,
+            comments: CommentLevel::None,
+            ..Default::default()
         };
-        let result = assemble(&[toolpath], &[], &cfg, &opts).unwrap();
+        let result = assemble(&[toolpath], &[], &cfg, &opts).unwrap().gcode;
         // G01 should appear only once (suppressed on second feed move)
         let count = result.matches("G01").count();
         assert_eq!(
@@ -611,4 +2699,1609 @@ program_stop = "M00"
             result
         );
     }
+
+    #[test]
+    fn r_format_semicircle_is_split_into_two_arcs() {
+        let toml = minimal_toml().replace("arc_format = \"ijk\"", "arc_format = \"r\"");
+        let cfg = config::parse(&toml).unwrap();
+        let toolpath = Toolpath {
+            operation_id: Uuid::nil(),
+            tool_number: 1,
+            spindle_speed: 8000.0,
+            feed_rate: 500.0,
+            passes: vec![Pass {
+                kind: PassKind::Cutting,
+                cuts: vec![CutPoint {
+                    position: Vec3 {
+                        x: 10.0,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                    move_kind: MoveKind::Arc {
+                        center: Vec3::zero(),
+                        end: Vec3 {
+                            x: -10.0,
+                            y: 0.0,
+                            z: 0.0,
+                        },
+                        clockwise: false,
+                    },
+                    tool_orientation: None,
+                }],
+                feed_rate: None,
+            }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
+        };
+
+        // Verbose so the second block repeats its motion code.
+        let opts = GenerateOptions {
+            verbose_modals: true,
+            ..Default::default()
+        };
+        let result = assemble(&[toolpath], &[], &cfg, &opts)
+            .expect("semicircle must not abort the export")
+            .gcode;
+
+        let arcs: Vec<&str> = result.lines().filter(|l| l.contains(" R")).collect();
+        assert_eq!(arcs.len(), 2, "{result}");
+        assert!(arcs.iter().all(|l| l.starts_with("G03")), "{result}");
+        assert!(arcs.iter().all(|l| l.contains(" R10")), "{result}");
+        assert!(
+            arcs[0].contains("X0 Y10"),
+            "midpoint on the CCW side:\n{result}"
+        );
+        assert!(arcs[1].contains("X-10 Y0"), "{result}");
+    }
+
+    #[test]
+    fn arc_lead_in_pass_emits_arc_move() {
+        use crate::models::operation::LeadType;
+        use crate::toolpath::lead::lead_in_pass;
+
+        let cfg = default_config();
+        let start = Vec3 {
+            x: 10.0,
+            y: 0.0,
+            z: -2.0,
+        };
+        let lead = lead_in_pass(
+            &LeadType::Arc { radius: 3.0 },
+            start.clone(),
+            Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            true,
+        )
+        .expect("arc lead-in");
+        let mut toolpath = straight_feed_toolpath(20.0, 500.0);
+        toolpath.passes.insert(0, lead);
+
+        let opts = GenerateOptions {
+            comments: CommentLevel::Verbose,
+            ..Default::default()
+        };
+        let result = assemble(&[toolpath], &[], &cfg, &opts).unwrap().gcode;
+        let lead_at = result.find("Lead-in").expect("lead-in comment");
+        let cut_at = result.find("Cutting pass").expect("cutting comment");
+        let arc_at = result[lead_at..]
+            .find("G03")
+            .map(|i| i + lead_at)
+            .expect("G03 after lead-in comment");
+        assert!(
+            arc_at < cut_at,
+            "arc must be in the lead-in pass:\n{result}"
+        );
+    }
+
+    #[test]
+    fn comment_level_controls_comment_block_count() {
+        let cfg = default_config();
+        let run = |comments| {
+            let mut toolpath = straight_feed_toolpath(20.0, 500.0);
+            toolpath.passes.push(toolpath.passes[0].clone());
+            let opts = GenerateOptions {
+                comments,
+                ..Default::default()
+            };
+            let result = assemble(&[toolpath], &[], &cfg, &opts).unwrap();
+            let comment_lines = result.gcode.lines().filter(|l| l.contains('(')).count();
+            (result.stats.line_count, comment_lines)
+        };
+
+        let (none_lines, none_comments) = run(CommentLevel::None);
+        let (ops_lines, ops_comments) = run(CommentLevel::Operations);
+        let (verbose_lines, verbose_comments) = run(CommentLevel::Verbose);
+
+        assert_eq!(none_comments, 0);
+        // Generation header and cycle-time estimate.
+        assert_eq!(ops_comments, 2);
+        assert_eq!(ops_lines, none_lines + 2);
+        // Plus one label per pass.
+        assert_eq!(verbose_comments, 4);
+        assert_eq!(verbose_lines, none_lines + 4);
+    }
+
+    #[test]
+    fn nonzero_stock_to_leave_is_recorded_in_operation_comment() {
+        let cfg = default_config();
+        let run = |stock_to_leave| {
+            let mut toolpath = straight_feed_toolpath(20.0, 500.0);
+            toolpath.stock_to_leave = stock_to_leave;
+            let opts = GenerateOptions {
+                comments: CommentLevel::Operations,
+                ..Default::default()
+            };
+            assemble(&[toolpath], &[], &cfg, &opts).unwrap().gcode
+        };
+
+        let plain = run(Some(0.0));
+        assert!(!plain.contains("Stock to leave"), "{plain}");
+        assert_eq!(plain, run(None));
+
+        let roughing = run(Some(0.5));
+        assert!(roughing.contains("(Stock to leave: 0.5)\n"), "{roughing}");
+        assert_ne!(roughing, plain);
+    }
+
+    #[test]
+    fn comment_level_from_legacy_flag() {
+        assert_eq!(CommentLevel::from(false), CommentLevel::None);
+        assert_eq!(CommentLevel::from(true), CommentLevel::Verbose);
+    }
+
+    #[test]
+    fn comment_level_deserializes_from_snake_case_name() {
+        let parse = |json: &str| serde_json::from_str::<CommentLevel>(json);
+        assert_eq!(parse(r#""operations""#).unwrap(), CommentLevel::Operations);
+        assert_eq!(parse(r#""verbose""#).unwrap(), CommentLevel::Verbose);
+        assert!(parse(r#""some""#).is_err());
+    }
+
+    #[test]
+    fn operation_markers_bracket_each_operation() {
+        let cfg = default_config();
+        let mut first = straight_feed_toolpath(10.0, 500.0);
+        first.operation_id = Uuid::new_v4();
+        let mut second = straight_feed_toolpath(20.0, 500.0);
+        second.operation_id = Uuid::new_v4();
+        second.tool_number = 2;
+
+        let opts = GenerateOptions {
+            operation_markers: true,
+            ..Default::default()
+        };
+        let result = assemble(&[first.clone(), second.clone()], &[], &cfg, &opts)
+            .unwrap()
+            .gcode;
+
+        let mut previous_end = 0;
+        for toolpath in [&first, &second] {
+            let id = toolpath.operation_id;
+            let start = result
+                .find(&format!("OP_START {id}"))
+                .expect("start marker");
+            let end = result.find(&format!("OP_END {id}")).expect("end marker");
+            assert!(previous_end < start && start < end, "{result}");
+            assert!(
+                result[start..end].contains("G01"),
+                "op blocks must be inside its markers:\n{result}"
+            );
+            previous_end = end;
+        }
+        assert_eq!(result.matches("OP_START").count(), 2);
+        assert_eq!(result.matches("OP_END").count(), 2);
+    }
+
+    #[test]
+    fn optional_stop_separates_operations() {
+        let cfg = default_config();
+        let mut first = straight_feed_toolpath(10.0, 500.0);
+        first.operation_id = Uuid::new_v4();
+        let mut second = straight_feed_toolpath(20.0, 500.0);
+        second.operation_id = Uuid::new_v4();
+
+        let opts = GenerateOptions {
+            operation_markers: true,
+            optional_stop_between_ops: true,
+            ..Default::default()
+        };
+        let result = assemble(&[first.clone(), second.clone()], &[], &cfg, &opts)
+            .unwrap()
+            .gcode;
+
+        let stops: Vec<usize> = result.match_indices("M01").map(|(i, _)| i).collect();
+        assert_eq!(stops.len(), 1, "{result}");
+        let first_end = result
+            .find(&format!("OP_END {}", first.operation_id))
+            .unwrap();
+        let second_start = result
+            .find(&format!("OP_START {}", second.operation_id))
+            .unwrap();
+        assert!(first_end < stops[0] && stops[0] < second_start, "{result}");
+        assert!(
+            result.lines().any(|l| l == "M01"),
+            "M01 must be its own block"
+        );
+
+        let without = assemble(&[first, second], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        assert!(!without.contains("M01"), "{without}");
+    }
+
+    #[test]
+    fn tool_number_above_capacity_fails_validation() {
+        let mut cfg = default_config();
+        cfg.machine.tool_capacity = Some(20);
+        let mut toolpath = straight_feed_toolpath(10.0, 500.0);
+        toolpath.tool_number = 25;
+
+        let err = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default()).unwrap_err();
+        match err {
+            PostProcessorError::Validation(msg) => assert!(msg.contains("T25"), "{msg}"),
+            other => panic!("expected Validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn spindle_speed_clamped_to_max_rpm_with_warning() {
+        let mut cfg = default_config();
+        cfg.spindle.max_rpm = 15000;
+        cfg.tool_change.post = vec!["M03 S{spindle_speed}".to_string()];
+        let mut first = straight_feed_toolpath(10.0, 500.0);
+        first.spindle_speed = 24000.0;
+        let second = Toolpath {
+            spindle_speed: 24000.0,
+            ..straight_feed_toolpath(20.0, 500.0)
+        };
+
+        let GenerateResult {
+            gcode, warnings, ..
+        } = assemble(&[first, second], &[], &cfg, &GenerateOptions::default()).unwrap();
+
+        assert!(gcode.contains("M03 S15000"), "{gcode}");
+        assert!(!gcode.contains("S24000"), "{gcode}");
+        assert_eq!(
+            gcode.matches("S15000").count(),
+            1,
+            "clamped repeat must be suppressed:\n{gcode}"
+        );
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("max_rpm 15000"), "{warnings:?}");
+    }
+
+    #[test]
+    fn spindle_change_without_tool_change_emits_s_word() {
+        let mut cfg = default_config();
+        cfg.tool_change.post = vec!["M03 S{spindle_speed}".to_string()];
+        let first = straight_feed_toolpath(10.0, 500.0);
+        let second = Toolpath {
+            spindle_speed: 9000.0,
+            ..straight_feed_toolpath(20.0, 500.0)
+        };
+
+        let result = assemble(&[first, second], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        assert!(result.lines().any(|l| l == "S9000"), "{result}");
+    }
+
+    #[test]
+    fn same_tool_operation_continues_previous_modal_state() {
+        let cfg = default_config();
+        let first = straight_feed_toolpath(10.0, 500.0);
+        let second = straight_feed_toolpath(20.0, 500.0);
+
+        let combined = assemble(
+            &[first, second.clone()],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+        let alone = assemble(&[second], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        // Rendered on its own, the second operation carries its own tool
+        // change and feed word; after the first, both are modal and omitted.
+        let count = |gcode: &str, word: &str| gcode.lines().filter(|l| l.contains(word)).count();
+        assert_eq!(count(&combined, "M06"), 1, "{combined}");
+        assert_eq!(count(&combined, "F500"), 1, "{combined}");
+        assert_eq!(count(&alone, "M06"), 1, "{alone}");
+        assert_eq!(count(&alone, "F500"), 1, "{alone}");
+    }
+
+    #[test]
+    fn scaled_feed_below_minimum_is_floored_with_warning() {
+        let mut cfg = default_config();
+        cfg.feeds.min_feed_rate = Some(5.0);
+        let opts = GenerateOptions {
+            feed_scale: Some(0.4),
+            ..Default::default()
+        };
+
+        let GenerateResult {
+            gcode, warnings, ..
+        } = assemble(&[straight_feed_toolpath(10.0, 1.0)], &[], &cfg, &opts).unwrap();
+
+        assert!(gcode.contains("F5"), "feed must be floored to 5:\n{gcode}");
+        assert!(
+            !gcode.contains("F0.4"),
+            "scaled feed must not be emitted:\n{gcode}"
+        );
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("minimum feed rate"), "{warnings:?}");
+    }
+
+    #[test]
+    fn clean_program_result_has_gcode_and_no_warnings() {
+        let cfg = default_config();
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap();
+
+        assert!(result.gcode().contains("G01"), "{}", result.gcode);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(result.stats.tool_changes, 1);
+        assert_eq!(result.stats.line_count, result.gcode.lines().count());
+    }
+
+    #[test]
+    fn helix_expands_into_descending_arc_blocks() {
+        let cfg = default_config();
+        let v = |x: f64, y: f64, z: f64| Vec3 { x, y, z };
+        let mut toolpath = straight_feed_toolpath(0.0, 500.0);
+        toolpath.passes[0].cuts = vec![
+            CutPoint {
+                position: v(10.0, 0.0, 0.0),
+                move_kind: MoveKind::Rapid,
+                tool_orientation: None,
+            },
+            CutPoint {
+                position: v(10.0, 0.0, 0.0),
+                move_kind: MoveKind::Helix {
+                    center: v(0.0, 0.0, 0.0),
+                    end: v(10.0, 0.0, -2.0),
+                    clockwise: false,
+                    pitch: 2.0,
+                },
+                tool_orientation: None,
+            },
+        ];
+
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let arc_z: Vec<f64> = result
+            .lines()
+            .filter(|line| line.split(' ').any(|w| w.starts_with('I')))
+            .map(|line| {
+                let word = line
+                    .split(' ')
+                    .find(|w| w.starts_with('Z'))
+                    .unwrap_or_else(|| panic!("arc block without Z: {line}"));
+                word[1..].parse().unwrap()
+            })
+            .collect();
+        assert_eq!(arc_z.len(), 4, "{result}");
+        assert!(
+            arc_z.windows(2).all(|pair| pair[1] < pair[0]),
+            "Z must decrease: {arc_z:?}\n{result}"
+        );
+        assert_eq!(arc_z.last(), Some(&-2.0), "{result}");
+        assert!(result.contains("G03"), "{result}");
+    }
+
+    #[test]
+    fn xz_arc_is_preceded_by_g18_block() {
+        let cfg = default_config();
+        let mut toolpath = straight_feed_toolpath(0.0, 500.0);
+        toolpath.passes[0].cuts = vec![
+            CutPoint {
+                position: Vec3 {
+                    x: 10.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                move_kind: MoveKind::Rapid,
+                tool_orientation: None,
+            },
+            // Clockwise quarter circle in XZ: Y is constant across start,
+            // center and end while Z sweeps from 0 to 5.
+            CutPoint {
+                position: Vec3 {
+                    x: 10.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                move_kind: MoveKind::Arc {
+                    center: Vec3 {
+                        x: 5.0,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                    end: Vec3 {
+                        x: 5.0,
+                        y: 0.0,
+                        z: 5.0,
+                    },
+                    clockwise: true,
+                },
+                tool_orientation: None,
+            },
+        ];
+
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let g18 = lines
+            .iter()
+            .position(|l| l.ends_with("G18"))
+            .unwrap_or_else(|| panic!("no G18 block:\n{result}"));
+        let arc = lines
+            .iter()
+            .position(|l| l.contains("G02") || l.contains("G03"))
+            .unwrap_or_else(|| panic!("no arc block:\n{result}"));
+        assert_eq!(arc, g18 + 1, "G18 must directly precede the arc:\n{result}");
+        assert!(lines[arc].contains("G02"), "{result}");
+    }
+
+    #[test]
+    fn xy_plane_is_restored_after_xz_arc() {
+        let cfg = default_config();
+        let v = |x: f64, y: f64, z: f64| Vec3 { x, y, z };
+        let arc = |start: Vec3, center: Vec3, end: Vec3| CutPoint {
+            position: start,
+            move_kind: MoveKind::Arc {
+                center,
+                end,
+                clockwise: false,
+            },
+            tool_orientation: None,
+        };
+        let mut toolpath = straight_feed_toolpath(0.0, 500.0);
+        toolpath.passes[0].cuts = vec![
+            CutPoint {
+                position: v(10.0, 0.0, 0.0),
+                move_kind: MoveKind::Rapid,
+                tool_orientation: None,
+            },
+            // Quarter arc in XZ from (10,0,0) to (5,0,5).
+            arc(v(10.0, 0.0, 0.0), v(5.0, 0.0, 0.0), v(5.0, 0.0, 5.0)),
+            // Quarter arc in XY from (5,0,5) to (0,5,5).
+            arc(v(5.0, 0.0, 5.0), v(0.0, 0.0, 5.0), v(0.0, 5.0, 5.0)),
+        ];
+
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let g18 = result.find("G18").expect("XZ arc selects G18");
+        let g17 = result.find("G17").expect("G17 restored for the XY arc");
+        assert!(g18 < g17, "{result}");
+        assert_eq!(result.matches("G17").count(), 1, "{result}");
+        assert!(
+            result[g17..].contains("Y5"),
+            "XY arc must follow G17:\n{result}"
+        );
+    }
+
+    #[test]
+    fn xy_plane_is_restored_before_tool_change_after_xz_arc() {
+        let mut cfg = default_config();
+        cfg.motion.keep_arc_plane = true;
+        let v = |x: f64, y: f64, z: f64| Vec3 { x, y, z };
+        let arc = |start: Vec3, center: Vec3, end: Vec3| CutPoint {
+            position: start,
+            move_kind: MoveKind::Arc {
+                center,
+                end,
+                clockwise: false,
+            },
+            tool_orientation: None,
+        };
+        let mut first = straight_feed_toolpath(0.0, 500.0);
+        first.passes[0].cuts = vec![
+            CutPoint {
+                position: v(10.0, 0.0, 0.0),
+                move_kind: MoveKind::Rapid,
+                tool_orientation: None,
+            },
+            // First op ends on an XZ arc, leaving the controller in G18.
+            arc(v(10.0, 0.0, 0.0), v(5.0, 0.0, 0.0), v(5.0, 0.0, 5.0)),
+        ];
+        let mut second = straight_feed_toolpath(0.0, 500.0);
+        second.tool_number = 2;
+        second.passes[0].cuts = vec![
+            CutPoint {
+                position: v(5.0, 0.0, 5.0),
+                move_kind: MoveKind::Rapid,
+                tool_orientation: None,
+            },
+            arc(v(5.0, 0.0, 5.0), v(0.0, 0.0, 5.0), v(0.0, 5.0, 5.0)),
+        ];
+
+        let result = assemble(&[first, second], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let g18 = result.find("G18").expect("XZ arc selects G18");
+        let g17 = result.find("G17").expect("G17 restored before the change");
+        let change = result.find("T2 M06").expect("tool change to T2");
+        assert!(g18 < g17 && g17 < change, "{result}");
+    }
+
+    #[test]
+    fn parallel_assembly_matches_serial() {
+        let mut cfg = default_config();
+        cfg.format.line_numbers = true;
+        cfg.format.line_number_increment = 5;
+        cfg.motion.keep_arc_plane = true;
+        cfg.tool_change.preload = true;
+        let v = |x: f64, y: f64, z: f64| Vec3 { x, y, z };
+        let rapid = |position: Vec3| CutPoint {
+            position,
+            move_kind: MoveKind::Rapid,
+            tool_orientation: None,
+        };
+        let mut first = straight_feed_toolpath(20.0, 500.0);
+        first.passes[0].cuts.push(rapid(v(10.0, 0.0, 0.0)));
+        first.passes[0].cuts.push(CutPoint {
+            position: v(10.0, 0.0, 0.0),
+            move_kind: MoveKind::Arc {
+                center: v(5.0, 0.0, 0.0),
+                end: v(5.0, 0.0, 5.0),
+                clockwise: true,
+            },
+            tool_orientation: None,
+        });
+        // Same tool: continues the first run's modal state.
+        let second = straight_feed_toolpath(15.0, 800.0);
+        let mut third = straight_feed_toolpath(10.0, 300.0);
+        third.tool_number = 2;
+        third.coolant = Some(CoolantMode::Flood);
+        let mut fourth = straight_feed_toolpath(5.0, 400.0);
+        fourth.passes[0].cuts.insert(0, rapid(v(0.0, 0.0, 10.0)));
+        let toolpaths = [first, second, third, fourth];
+        let options = GenerateOptions {
+            optional_stop_between_ops: true,
+            operation_markers: true,
+            clearance_z: Some(25.0),
+            ..GenerateOptions::default()
+        };
+
+        let render = |parallel| {
+            let mut buf = Vec::new();
+            let summary =
+                assemble_program(&toolpaths, &[], &cfg, &options, &mut buf, parallel).unwrap();
+            (String::from_utf8(buf).unwrap(), summary)
+        };
+        let (serial, serial_summary) = render(false);
+        let (parallel, parallel_summary) = render(true);
+
+        assert!(
+            serial.contains("T2 M06") && serial.contains("G18"),
+            "{serial}"
+        );
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel_summary, serial_summary);
+        assert_eq!(serial_summary.stats.tool_changes, 3);
+    }
+
+    #[test]
+    fn program_without_off_plane_arcs_has_no_plane_words() {
+        let cfg = default_config();
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+        assert!(!result.contains("G17"), "{result}");
+    }
+
+    #[test]
+    fn suppress_first_if_t1_skips_only_the_initial_t1_change() {
+        let toolpaths = || {
+            let first = straight_feed_toolpath(10.0, 500.0);
+            let mut second = straight_feed_toolpath(20.0, 500.0);
+            second.tool_number = 2;
+            let mut third = straight_feed_toolpath(30.0, 500.0);
+            third.tool_number = 1;
+            [first, second, third]
+        };
+        let run = |suppress: bool| {
+            let mut cfg = default_config();
+            cfg.tool_change.suppress_first_if_t1 = suppress;
+            cfg.tool_change.post = vec!["M03 S{spindle_speed}".to_string()];
+            assemble(&toolpaths(), &[], &cfg, &GenerateOptions::default()).unwrap()
+        };
+
+        let normal = run(false);
+        let suppressed = run(true);
+
+        assert_eq!(
+            normal.gcode.matches("T1 M06").count(),
+            2,
+            "{}",
+            normal.gcode
+        );
+        assert_eq!(normal.stats.tool_changes, 3);
+        // The first T1 change is skipped; the return to T1 later is not.
+        assert_eq!(
+            suppressed.gcode.matches("T1 M06").count(),
+            1,
+            "{}",
+            suppressed.gcode
+        );
+        assert!(suppressed.gcode.contains("T2 M06"), "{}", suppressed.gcode);
+        assert_eq!(suppressed.stats.tool_changes, 2);
+        // The spindle still starts for the first operation.
+        let first_cut = suppressed.gcode.find("X10").unwrap();
+        let spindle = suppressed.gcode.find("M03 S8000").unwrap();
+        assert!(spindle < first_cut, "{}", suppressed.gcode);
+    }
+
+    #[test]
+    fn preload_stages_next_tool_before_final_block() {
+        let mut cfg = default_config();
+        cfg.tool_change.preload = true;
+        let first = straight_feed_toolpath(10.0, 500.0);
+        let mut second = straight_feed_toolpath(20.0, 500.0);
+        second.tool_number = 2;
+
+        let result = assemble(&[first, second], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let preload = result.find("T2\n").expect("preload T2 line");
+        let first_final = result.find("X10").expect("first op's final block");
+        assert!(preload < first_final, "{result}");
+        assert!(
+            result[..preload].matches("T2").count() == 0,
+            "preload must be the first T2:\n{result}"
+        );
+    }
+
+    #[test]
+    fn safe_z_retract_precedes_second_tool_change() {
+        let mut cfg = default_config();
+        cfg.tool_change.safe_z = Some(50.0);
+        let first = straight_feed_toolpath(10.0, 500.0);
+        let mut second = straight_feed_toolpath(20.0, 500.0);
+        second.tool_number = 2;
+
+        let result = assemble(&[first, second], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let change = lines
+            .iter()
+            .position(|l| *l == "T2 M06")
+            .unwrap_or_else(|| panic!("no second tool change:\n{result}"));
+        assert_eq!(lines[change - 1], "G00 Z50", "{result}");
+        assert_eq!(
+            lines.iter().filter(|l| l.contains("Z50")).count(),
+            1,
+            "no retract before the first tool:\n{result}"
+        );
+    }
+
+    #[test]
+    fn tool_change_retract_uses_clearance_z_and_safe_xy() {
+        let mut cfg = default_config();
+        cfg.tool_change.safe_xy = Some([-100.0, 0.0]);
+        let first = straight_feed_toolpath(10.0, 500.0);
+        let mut second = straight_feed_toolpath(20.0, 500.0);
+        second.tool_number = 2;
+        let options = GenerateOptions {
+            clearance_z: Some(25.0),
+            ..GenerateOptions::default()
+        };
+
+        let result = assemble(&[first, second], &[], &cfg, &options)
+            .unwrap()
+            .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let change = lines.iter().position(|l| *l == "T2 M06").unwrap();
+        assert_eq!(
+            lines[change - 2..change],
+            ["G00 Z25", "X-100 Y0"],
+            "{result}"
+        );
+    }
+
+    #[test]
+    fn length_offset_follows_tool_change_command() {
+        let mut cfg = default_config();
+        cfg.tool_change.length_offset = Some("G43 H{tool_number}".to_string());
+        let mut toolpath = straight_feed_toolpath(10.0, 500.0);
+        toolpath.tool_number = 5;
+
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let change = lines
+            .iter()
+            .position(|l| *l == "T5 M06")
+            .unwrap_or_else(|| panic!("no tool change:\n{result}"));
+        assert!(lines[change + 1].contains("G43 H5"), "{result}");
+    }
+
+    fn probe_toolpath() -> Toolpath {
+        let mut toolpath = straight_feed_toolpath(10.0, 500.0);
+        let cuts = &mut toolpath.passes[0].cuts;
+        let start = cuts.last().unwrap().position.clone();
+        cuts.push(CutPoint {
+            position: start.clone(),
+            move_kind: MoveKind::ProbeToward {
+                target: Vec3 {
+                    z: start.z - 20.0,
+                    ..start
+                },
+                feed: 100.0,
+            },
+            tool_orientation: None,
+        });
+        toolpath
+    }
+
+    #[test]
+    fn probe_move_emits_configured_code_with_target_and_feed() {
+        let mut cfg = default_config();
+        cfg.probe = Some(config::ProbeConfig {
+            toward: "G38.2".to_string(),
+        });
+
+        let result = assemble(&[probe_toolpath()], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let probe = result
+            .lines()
+            .find(|l| l.starts_with("G38.2"))
+            .unwrap_or_else(|| panic!("no probe block:\n{result}"));
+        assert!(probe.contains(" X10 "), "{probe}");
+        assert!(probe.contains(" Y0 "), "{probe}");
+        assert!(probe.contains(" Z-20 "), "{probe}");
+        assert!(probe.ends_with("F100"), "{probe}");
+    }
+
+    #[test]
+    fn probe_move_without_probe_section_is_not_supported() {
+        let cfg = default_config();
+        let err =
+            assemble(&[probe_toolpath()], &[], &cfg, &GenerateOptions::default()).unwrap_err();
+        assert!(matches!(err, PostProcessorError::NotSupported(_)));
+    }
+
+    #[test]
+    fn stats_header_opens_program_with_post_name() {
+        let mut cfg = default_config();
+        cfg.program.number_prefix = String::new();
+        let opts = GenerateOptions {
+            comments: CommentLevel::Operations,
+            emit_stats_header: true,
+            ..GenerateOptions::default()
+        };
+
+        let result = assemble(&[straight_feed_toolpath(10.0, 500.0)], &[], &cfg, &opts)
+            .unwrap()
+            .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        assert_eq!(lines[0], "(Post: Test)", "{result}");
+        assert_eq!(lines[1], "(Program: 1000)", "{result}");
+        assert_eq!(lines[2], "(Operations: 1)", "{result}");
+        assert!(lines[3].starts_with("(Estimated runtime: "), "{result}");
+    }
+
+    #[test]
+    fn stats_header_needs_program_level_comments() {
+        let opts = GenerateOptions {
+            emit_stats_header: true,
+            ..GenerateOptions::default()
+        };
+
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &default_config(),
+            &opts,
+        )
+        .unwrap()
+        .gcode;
+
+        assert!(!result.contains("Post:"), "{result}");
+    }
+
+    #[test]
+    fn rtcp_on_follows_tool_change_on_rtcp_machine() {
+        let mut cfg = default_config();
+        cfg.machine.rtcp_supported = true;
+        cfg.tool_change.rtcp_on = Some("G43.4 H{tool_number}".to_string());
+
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let change = lines
+            .iter()
+            .position(|l| *l == "T1 M06")
+            .unwrap_or_else(|| panic!("no tool change:\n{result}"));
+        assert_eq!(lines[change + 1], "G43.4 H1", "{result}");
+    }
+
+    #[test]
+    fn orient_is_emitted_between_spindle_stop_and_tool_change() {
+        let mut cfg = default_config();
+        cfg.tool_change.pre = vec!["M05".to_string()];
+        cfg.spindle.orient = Some("M19".to_string());
+        cfg.tool_change.orient_before_change = true;
+
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let change = lines
+            .iter()
+            .position(|l| *l == "T1 M06")
+            .unwrap_or_else(|| panic!("no tool change:\n{result}"));
+        assert_eq!(lines[change - 2..change], ["M05", "M19"], "{result}");
+    }
+
+    #[test]
+    fn orient_is_not_emitted_unless_requested() {
+        let mut cfg = default_config();
+        cfg.spindle.orient = Some("M19".to_string());
+
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        assert!(!result.contains("M19"), "{result}");
+    }
+
+    #[test]
+    fn rtcp_on_is_not_emitted_without_rtcp_support() {
+        let mut cfg = default_config();
+        cfg.tool_change.rtcp_on = Some("G43.4 H{tool_number}".to_string());
+
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        assert!(!result.contains("G43.4"), "{result}");
+    }
+
+    #[test]
+    fn finishing_op_uses_exact_stop_and_roughing_continuous() {
+        let mut cfg = default_config();
+        cfg.path_mode = Some(config::PathModeConfig {
+            exact_stop: "G61".to_string(),
+            continuous: "G64".to_string(),
+            default: None,
+        });
+        let mut roughing = straight_feed_toolpath(10.0, 500.0);
+        roughing.path_mode = Some(PathMode::Continuous);
+        let mut finishing = straight_feed_toolpath(20.0, 500.0);
+        finishing.path_mode = Some(PathMode::ExactStop);
+
+        let result = assemble(
+            &[roughing.clone(), roughing, finishing],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        let continuous = result.find("G64\n").expect("roughing op emits G64");
+        let exact = result.find("G61\n").expect("finishing op emits G61");
+        assert!(continuous < exact, "{result}");
+        assert_eq!(result.matches("G64").count(), 1, "G64 is modal:\n{result}");
+    }
+
+    fn straight_feed_toolpath(length: f64, feed_rate: f64) -> Toolpath {
+        Toolpath {
+            operation_id: Uuid::nil(),
+            tool_number: 1,
+            spindle_speed: 8000.0,
+            feed_rate,
+            passes: vec![Pass {
+                kind: PassKind::Cutting,
+                cuts: vec![
+                    CutPoint {
+                        position: Vec3::zero(),
+                        move_kind: MoveKind::Rapid,
+                        tool_orientation: None,
+                    },
+                    CutPoint {
+                        position: Vec3 {
+                            x: length,
+                            y: 0.0,
+                            z: 0.0,
+                        },
+                        move_kind: MoveKind::Feed,
+                        tool_orientation: None,
+                    },
+                ],
+                feed_rate: None,
+            }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
+        }
+    }
+
+    /// A feed to the bottom of a bore followed by a 0.5 s dwell there.
+    fn dwell_toolpath() -> Toolpath {
+        let mut toolpath = straight_feed_toolpath(10.0, 500.0);
+        toolpath.passes[0].cuts.push(CutPoint {
+            position: Vec3 {
+                x: 10.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            move_kind: MoveKind::Dwell { seconds: 0.5 },
+            tool_orientation: None,
+        });
+        toolpath
+    }
+
+    #[test]
+    fn clearance_z_retracts_before_traversing_between_passes() {
+        let mut toolpath = straight_feed_toolpath(10.0, 500.0);
+        let mut second = toolpath.passes[0].clone();
+        for cut in &mut second.cuts {
+            cut.position.y = 20.0;
+            cut.position.z = -1.0;
+        }
+        toolpath.passes.push(second);
+
+        let cfg = config::parse(crate::postprocessor::LINUXCNC_TOML).unwrap();
+        let options = GenerateOptions {
+            clearance_z: Some(15.0),
+            ..GenerateOptions::default()
+        };
+        let result = assemble(&[toolpath], &[], &cfg, &options).unwrap().gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let retract = lines
+            .iter()
+            .position(|l| l.ends_with("G00 Z15"))
+            .unwrap_or_else(|| panic!("no clearance retract:\n{result}"));
+        assert!(lines[retract - 1].contains("X10"), "{result}");
+        assert!(lines[retract + 1].ends_with("X0 Y20"), "{result}");
+        assert!(lines[retract + 2].ends_with("Z-1"), "{result}");
+        assert_eq!(
+            lines.iter().filter(|l| l.contains("Z15")).count(),
+            1,
+            "the first pass needs no retract:\n{result}"
+        );
+    }
+
+    #[test]
+    fn clearance_retract_skips_traverse_when_already_over_target() {
+        // A closed contour cut at two depths: the second pass starts where
+        // the first ended, so there is nothing to traverse.
+        let mut toolpath = straight_feed_toolpath(10.0, 500.0);
+        toolpath.passes[0].cuts.push(CutPoint {
+            position: Vec3::zero(),
+            move_kind: MoveKind::Feed,
+            tool_orientation: None,
+        });
+        let mut second = toolpath.passes[0].clone();
+        for cut in &mut second.cuts {
+            cut.position.z = -1.0;
+        }
+        toolpath.passes.push(second);
+
+        let cfg = config::parse(crate::postprocessor::LINUXCNC_TOML).unwrap();
+        let options = GenerateOptions {
+            clearance_z: Some(15.0),
+            ..GenerateOptions::default()
+        };
+        let result = assemble(&[toolpath], &[], &cfg, &options).unwrap().gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let retract = lines
+            .iter()
+            .position(|l| l.ends_with("G00 Z15"))
+            .unwrap_or_else(|| panic!("no clearance retract:\n{result}"));
+        assert!(lines[retract + 1].ends_with("Z-1"), "{result}");
+        assert!(
+            lines.iter().all(|l| !l.trim().is_empty()),
+            "no empty blocks:\n{result}"
+        );
+    }
+
+    #[test]
+    fn successive_feeds_omit_unchanged_axis_words() {
+        let mut toolpath = straight_feed_toolpath(10.0, 500.0);
+        for cut in &mut toolpath.passes[0].cuts {
+            cut.position.y = 5.0;
+        }
+        toolpath.passes[0].cuts.push(CutPoint {
+            position: Vec3 {
+                x: 20.0,
+                y: 5.0,
+                z: 0.0,
+            },
+            move_kind: MoveKind::Feed,
+            tool_orientation: None,
+        });
+
+        let cfg = config::parse(crate::postprocessor::LINUXCNC_TOML).unwrap();
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let first = lines
+            .iter()
+            .position(|l| l.contains("X10"))
+            .unwrap_or_else(|| panic!("no first feed:\n{result}"));
+        assert!(lines[first].contains("G01"), "{result}");
+        let second = lines[first + 1];
+        assert!(second.ends_with("X20"), "{result}");
+        assert!(!second.contains('Y'), "Y is unchanged:\n{result}");
+        assert!(!second.contains("G01"), "G01 is still modal:\n{result}");
+    }
+
+    #[test]
+    fn dwell_emits_g4_block_in_seconds_on_linuxcnc() {
+        let cfg = config::parse(crate::postprocessor::LINUXCNC_TOML).unwrap();
+        let result = assemble(&[dwell_toolpath()], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let dwell = lines
+            .iter()
+            .position(|l| l.ends_with("G4 P0.5"))
+            .unwrap_or_else(|| panic!("no dwell block:\n{result}"));
+        assert!(lines[dwell - 1].contains("X10"), "{result}");
+    }
+
+    #[test]
+    fn dwell_in_ms_emits_milliseconds_on_fanuc() {
+        let result = assemble_fanuc(dwell_toolpath());
+        assert!(result.lines().any(|l| l.ends_with("G4 P500")), "{result}");
+    }
+
+    #[test]
+    fn dwell_uses_configured_dwell_code() {
+        let mut cfg = config::parse(crate::postprocessor::LINUXCNC_TOML).unwrap();
+        cfg.words.dwell_code = "G04".to_string();
+        let result = assemble(&[dwell_toolpath()], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        assert!(result.lines().any(|l| l.ends_with("G04 P0.5")), "{result}");
+        assert!(!result.contains("G4 "), "{result}");
+    }
+
+    /// Two holes at (10, 10) and (30, 10), drilled from R2 to Z-10, with the
+    /// plunge split at Z-5 when `peck_depth` is set.
+    fn drill_toolpath(peck_depth: Option<f64>) -> Toolpath {
+        let at = |x: f64, z: f64, move_kind: MoveKind| CutPoint {
+            position: Vec3 { x, y: 10.0, z },
+            move_kind,
+            tool_orientation: None,
+        };
+        let mut cuts = Vec::new();
+        for x in [10.0, 30.0] {
+            cuts.push(at(x, 5.0, MoveKind::Rapid));
+            cuts.push(at(x, 2.0, MoveKind::Rapid));
+            if peck_depth.is_some() {
+                cuts.push(at(x, -5.0, MoveKind::Feed));
+                cuts.push(at(x, 2.0, MoveKind::Rapid));
+            }
+            cuts.push(at(x, -10.0, MoveKind::Feed));
+            cuts.push(at(x, 5.0, MoveKind::Rapid));
+        }
+        Toolpath {
+            operation_id: Uuid::nil(),
+            tool_number: 1,
+            spindle_speed: 1500.0,
+            feed_rate: 100.0,
+            passes: vec![Pass {
+                kind: PassKind::Cutting,
+                cuts,
+                feed_rate: None,
+            }],
+            path_mode: None,
+            drill: Some(DrillParams {
+                depth: 10.0,
+                peck_depth,
+            }),
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
+        }
+    }
+
+    fn assemble_fanuc(toolpath: Toolpath) -> String {
+        let cfg = config::parse(crate::postprocessor::FANUC_0I_TOML).unwrap();
+        assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode
+    }
+
+    #[test]
+    fn drill_op_emits_g81_cycle_on_fanuc() {
+        let result = assemble_fanuc(drill_toolpath(None));
+
+        let cycle = result
+            .lines()
+            .find(|l| l.contains("G81"))
+            .unwrap_or_else(|| panic!("no G81 block:\n{result}"));
+        assert!(cycle.ends_with("G81 Z-10 R2 F100"), "{cycle}");
+        assert!(
+            !result.contains("G01 Z-10"),
+            "plunges must not be expanded:\n{result}"
+        );
+
+        // The second hole is a bare XY block, followed by the cycle cancel.
+        let lines: Vec<&str> = result.lines().collect();
+        let hole = lines
+            .iter()
+            .position(|l| l.ends_with(" X30"))
+            .unwrap_or_else(|| panic!("no second hole:\n{result}"));
+        assert!(lines[hole + 1].ends_with(" G80"), "{result}");
+    }
+
+    #[test]
+    fn peck_drill_op_emits_g83_cycle_with_q_on_fanuc() {
+        let result = assemble_fanuc(drill_toolpath(Some(5.0)));
+
+        let cycle = result
+            .lines()
+            .find(|l| l.contains("G83"))
+            .unwrap_or_else(|| panic!("no G83 block:\n{result}"));
+        assert!(cycle.ends_with("G83 Z-10 R2 Q5 F100"), "{cycle}");
+        assert_eq!(result.matches("G83").count(), 1, "{result}");
+        assert!(result.lines().any(|l| l.ends_with("G80")), "{result}");
+    }
+
+    #[test]
+    fn peck_drill_op_emits_modal_cycle83_call_on_siemens() {
+        let cfg = config::parse(crate::postprocessor::SIEMENS_840D_TOML).unwrap();
+        let result = assemble(
+            &[drill_toolpath(Some(5.0))],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        assert!(
+            result.contains("MCALL CYCLE83(5, 2, 0, -10, , -3, , 5, 0, 0, 1, 1)"),
+            "{result}"
+        );
+        assert_eq!(result.matches("CYCLE83").count(), 1, "{result}");
+        assert!(
+            !result.contains("G1 Z-5"),
+            "pecks must not be expanded:\n{result}"
+        );
+    }
+
+    #[test]
+    fn through_tool_coolant_brackets_the_operation_on_fanuc() {
+        let mut toolpath = drill_toolpath(None);
+        toolpath.coolant = Some(CoolantMode::ThroughTool);
+        let result = assemble_fanuc(toolpath);
+
+        let lines: Vec<&str> = result.lines().collect();
+        let on = lines
+            .iter()
+            .position(|l| l.ends_with(" M88"))
+            .unwrap_or_else(|| panic!("no through-tool coolant:\n{result}"));
+        let cycle = lines.iter().position(|l| l.contains("G81")).unwrap();
+        let off = lines.iter().position(|l| l.ends_with(" M09")).unwrap();
+        assert!(on < cycle && cycle < off, "{result}");
+        assert!(!result.contains("M08"), "{result}");
+    }
+
+    #[test]
+    fn through_tool_coolant_without_post_code_is_rejected() {
+        let cfg = config::parse(crate::postprocessor::MACH4_TOML).unwrap();
+        let mut toolpath = drill_toolpath(None);
+        toolpath.coolant = Some(CoolantMode::ThroughTool);
+
+        let err = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default()).unwrap_err();
+        match err {
+            PostProcessorError::NotSupported(msg) => {
+                assert!(msg.contains("coolant.through_tool"), "{msg}");
+                assert!(msg.contains("mach4"), "{msg}");
+            }
+            other => panic!("expected NotSupported, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lead_in_pass_feed_override_emits_its_own_feed_word() {
+        let mut toolpath = straight_feed_toolpath(10.0, 800.0);
+        let cutting = toolpath.passes[0].clone();
+        toolpath.passes.insert(
+            0,
+            Pass {
+                kind: PassKind::LeadIn,
+                feed_rate: Some(200.0),
+                ..cutting
+            },
+        );
+        let result = assemble_fanuc(toolpath);
+
+        let feeds: Vec<&str> = result
+            .lines()
+            .flat_map(str::split_whitespace)
+            .filter(|w| w.starts_with('F'))
+            .collect();
+        assert_eq!(feeds.len(), 2, "{result}");
+        assert!(feeds[0].starts_with("F200"), "{result}");
+        assert!(feeds[1].starts_with("F800"), "{result}");
+    }
+
+    #[test]
+    fn per_rev_operation_emits_g95_and_restores_g94() {
+        let mut tapping = straight_feed_toolpath(10.0, 800.0);
+        tapping.feed_mode = Some(FeedMode::PerRev);
+        let result = assemble_fanuc(tapping);
+
+        let lines: Vec<&str> = result.lines().collect();
+        let per_rev = lines
+            .iter()
+            .position(|l| l.ends_with("G95"))
+            .unwrap_or_else(|| panic!("no G95:\n{result}"));
+        let feed = lines.iter().position(|l| l.contains("G01")).unwrap();
+        let per_min = lines
+            .iter()
+            .rposition(|l| l.ends_with("G94"))
+            .unwrap_or_else(|| panic!("no G94:\n{result}"));
+        assert!(per_rev < feed && feed < per_min, "{result}");
+        // 800 mm/min at 8000 rpm is 0.1 mm/rev.
+        assert!(lines[feed].contains("F0.1"), "{result}");
+    }
+
+    #[test]
+    fn per_rev_operation_without_post_code_is_rejected() {
+        let mut cfg = config::parse(crate::postprocessor::FANUC_0I_TOML).unwrap();
+        cfg.words.feed_per_rev.clear();
+        let mut toolpath = straight_feed_toolpath(10.0, 800.0);
+        toolpath.feed_mode = Some(FeedMode::PerRev);
+
+        let err = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default()).unwrap_err();
+        match err {
+            PostProcessorError::NotSupported(msg) => {
+                assert!(msg.contains("words.feed_per_rev"), "{msg}");
+            }
+            other => panic!("expected NotSupported, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn drill_op_expands_to_linear_moves_without_cycle_support() {
+        let mut cfg = config::parse(crate::postprocessor::FANUC_0I_TOML).unwrap();
+        cfg.cycles.supported = false;
+        let result = assemble(
+            &[drill_toolpath(None)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        assert!(!result.contains("G81"), "{result}");
+        assert!(result.contains("G01 Z-10"), "{result}");
+    }
+
+    #[test]
+    fn peck_drill_expands_to_plunge_retract_cycles_on_grbl() {
+        let at = |z: f64, move_kind: MoveKind| CutPoint {
+            position: Vec3 {
+                x: 10.0,
+                y: 10.0,
+                z,
+            },
+            move_kind,
+            tool_orientation: None,
+        };
+        let toolpath = Toolpath {
+            passes: vec![Pass {
+                kind: PassKind::Cutting,
+                cuts: vec![
+                    at(5.0, MoveKind::Rapid),
+                    at(2.0, MoveKind::Rapid),
+                    at(-20.0, MoveKind::Feed),
+                    at(5.0, MoveKind::Rapid),
+                ],
+                feed_rate: None,
+            }],
+            drill: Some(DrillParams {
+                depth: 20.0,
+                peck_depth: Some(5.0),
+            }),
+            ..drill_toolpath(None)
+        };
+        let cfg = config::parse(crate::postprocessor::GRBL_TOML).unwrap();
+        let result = assemble(&[toolpath], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let plunges: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|l| l.starts_with("G01 Z"))
+            .collect();
+        assert_eq!(
+            plunges,
+            ["G01 Z-5 F100", "G01 Z-10", "G01 Z-15", "G01 Z-20"],
+            "{result}"
+        );
+        assert_eq!(
+            lines.iter().filter(|l| **l == "G00 Z2").count(),
+            4,
+            "{result}"
+        );
+        // Later pecks rapid back to just above the previous depth.
+        assert!(lines.contains(&"Z-4.5"), "{result}");
+    }
+
+    #[test]
+    fn estimate_times_feed_moves_from_feed_rate() {
+        // 1000 mm at 500 mm/min = 2 minutes; the rapid contributes nothing.
+        let seconds = estimate(&[straight_feed_toolpath(1000.0, 500.0)], 10_000.0).unwrap();
+        assert!((seconds - 120.0).abs() < 1e-9, "got {seconds}");
+        assert_eq!(estimate(&[], 10_000.0), None);
+    }
+
+    #[test]
+    fn estimate_uses_pass_feed_override() {
+        let mut toolpath = straight_feed_toolpath(1000.0, 500.0);
+        toolpath.passes[0].feed_rate = Some(250.0);
+        // 1000 mm at the pass's 250 mm/min = 4 minutes.
+        let seconds = estimate(&[toolpath], 10_000.0).unwrap();
+        assert!((seconds - 240.0).abs() < 1e-9, "got {seconds}");
+    }
+
+    #[test]
+    fn estimate_times_rapids_inversely_to_rapid_rate() {
+        let mut toolpath = straight_feed_toolpath(1000.0, 500.0);
+        for cut in &mut toolpath.passes[0].cuts {
+            cut.move_kind = MoveKind::Rapid;
+        }
+        // 1000 mm at 10 m/min = 6 s; at 30 m/min a third of that.
+        let slow = estimate(std::slice::from_ref(&toolpath), 10_000.0).unwrap();
+        let fast = estimate(&[toolpath], 30_000.0).unwrap();
+        assert!((slow - 6.0).abs() < 1e-9, "got {slow}");
+        assert!((fast - slow / 3.0).abs() < 1e-9, "got {fast}");
+    }
+
+    #[test]
+    fn footer_includes_cycle_time_comment_before_end_code() {
+        let toml = minimal_toml().replace("footer = []", "footer = [\"M30\"]");
+        let cfg = config::parse(&toml).unwrap();
+        let opts = GenerateOptions {
+            program_number: None,
+            comments: CommentLevel::Verbose,
+            ..Default::default()
+        };
+        let result = assemble(&[straight_feed_toolpath(1000.0, 500.0)], &[], &cfg, &opts)
+            .unwrap()
+            .gcode;
+        let comment = result
+            .lines()
+            .position(|l| l.starts_with("(Estimated cycle time:"))
+            .expect("cycle-time comment must be emitted");
+        let end = result.lines().position(|l| l == "M30").unwrap();
+        assert!(comment < end, "comment must precede end code:\n{result}");
+        assert!(
+            result.contains("(Estimated cycle time: 00:02:00)"),
+            "got:\n{result}"
+        );
+        assert!(!result.contains("00:00:00"));
+    }
+
+    #[test]
+    fn end_cancel_block_precedes_end_code() {
+        let toml = minimal_toml().replace("footer = []", "footer = [\"M30\"]");
+        let cfg = config::parse(&toml).unwrap();
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+
+        let lines: Vec<&str> = result.lines().collect();
+        let cancel = lines
+            .iter()
+            .position(|l| *l == "G40 G49 G80")
+            .expect("cancel block");
+        let last_cut = lines.iter().rposition(|l| l.contains("X10")).unwrap();
+        let end = lines.iter().position(|l| *l == "M30").unwrap();
+        assert!(last_cut < cancel && cancel < end, "{result}");
+
+        let toml = toml.replace("footer = [", "end_cancel = []\nfooter = [");
+        let cfg = config::parse(&toml).unwrap();
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+        assert!(
+            !result.contains("G40"),
+            "empty end_cancel disables the block:\n{result}"
+        );
+    }
+
+    #[test]
+    fn imperial_output_uses_inch_format_profile() {
+        let toml = minimal_toml()
+            .replace("units = \"metric\"", "units = \"imperial\"")
+            .replace(
+                "block_delete_char = \"\"",
+                "block_delete_char = \"\"\n\n[format.inch_profile]\ndecimal_places = 4\n\
+                 trailing_zeros = true\nleading_zero_suppression = true",
+            );
+        let cfg = config::parse(&toml).unwrap();
+        let opts = GenerateOptions {
+            program_number: None,
+            comments: CommentLevel::None,
+            ..Default::default()
+        };
+        let result = assemble(&[straight_feed_toolpath(0.25, 10.0)], &[], &cfg, &opts)
+            .unwrap()
+            .gcode;
+        assert!(
+            result.contains("X.2500"),
+            "expected 4 decimals without leading zero, got:\n{result}"
+        );
+    }
+
+    #[test]
+    fn verbose_modals_repeats_words_that_default_run_suppresses() {
+        let cfg = default_config();
+        let mut toolpath = straight_feed_toolpath(10.0, 500.0);
+        toolpath.passes[0].cuts.push(CutPoint {
+            position: Vec3 {
+                x: 20.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            move_kind: MoveKind::Feed,
+            tool_orientation: None,
+        });
+        let toolpaths = [toolpath];
+
+        let default_run = assemble(&toolpaths, &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        assert_eq!(default_run.matches("G01").count(), 1, "{default_run}");
+        assert_eq!(default_run.matches("F500").count(), 1, "{default_run}");
+
+        let opts = GenerateOptions {
+            verbose_modals: true,
+            ..Default::default()
+        };
+        let verbose_run = assemble(&toolpaths, &[], &cfg, &opts).unwrap().gcode;
+        assert_eq!(verbose_run.matches("G01").count(), 2, "{verbose_run}");
+        assert_eq!(verbose_run.matches("F500").count(), 2, "{verbose_run}");
+        assert!(verbose_run.contains("G01 X20 Y0 Z0 F500"), "{verbose_run}");
+    }
+
+    #[test]
+    fn auto_safe_shutdown_precedes_footer() {
+        let toml = minimal_toml().replace("footer = []", "footer = [\"M30\"]");
+        let cfg = config::parse(&toml).unwrap();
+        let toolpaths = [straight_feed_toolpath(10.0, 500.0)];
+
+        let result = assemble(&toolpaths, &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        let tail: Vec<&str> = result.lines().rev().take(2).collect();
+        assert_eq!(tail, ["M30", "M09 M05"], "{result}");
+
+        let opts = GenerateOptions {
+            auto_safe_shutdown: false,
+            ..Default::default()
+        };
+        let manual = assemble(&toolpaths, &[], &cfg, &opts).unwrap().gcode;
+        assert!(!manual.contains("M09 M05"), "{manual}");
+        assert_eq!(manual.lines().rev().nth(1), Some("G40 G49 G80"), "{manual}");
+    }
+
+    #[test]
+    fn auto_safe_shutdown_skips_codes_already_in_footer() {
+        let toml = minimal_toml().replace("footer = []", "footer = [\"M05\", \"M30\"]");
+        let cfg = config::parse(&toml).unwrap();
+        let result = assemble(
+            &[straight_feed_toolpath(10.0, 500.0)],
+            &[],
+            &cfg,
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+        .gcode;
+        assert_eq!(result.matches("M05").count(), 1, "{result}");
+        assert!(result.contains("M09\nM05\nM30"), "{result}");
+    }
+
+    #[test]
+    fn keep_spindle_on_end_drops_spindle_off_from_footer() {
+        let toml = minimal_toml().replace("footer = []", "footer = [\"M05\", \"M09\", \"M30\"]");
+        let cfg = config::parse(&toml).unwrap();
+        let default_run = assemble(&[], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        assert!(default_run.contains("M05"), "{default_run}");
+
+        let cfg =
+            config::parse(&toml.replace("footer = [", "keep_spindle_on_end = true\nfooter = ["))
+                .unwrap();
+        let chained = assemble(&[], &[], &cfg, &GenerateOptions::default())
+            .unwrap()
+            .gcode;
+        assert!(!chained.contains("M05"), "{chained}");
+        assert!(
+            chained.contains("M09"),
+            "coolant off must remain:\n{chained}"
+        );
+        assert!(chained.trim_end().ends_with("M30"), "{chained}");
+    }
 }
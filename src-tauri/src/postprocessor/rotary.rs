@@ -0,0 +1,212 @@
+//! Rotary-axis position tracking for continuous-rotation tables.
+//!
+//! CAM output naturally produces rotary angles wrapped to `[0°, 360°)`. On an
+//! axis with no travel limits, emitting `C359.` followed by `C1.` would make the
+//! controller unwind 358° instead of advancing 2°. [`RotaryTracker`] turns each
+//! wrapped target into an accumulated position reached by the shortest path.
+//!
+//! [`inverse_kinematics`] turns a 5-axis tool-axis vector into the rotary
+//! angles that produce it on a given machine layout.
+
+use super::config::{AxesConfig, AxisLimits, FiveAxisType};
+use super::PostProcessorError;
+use crate::models::Vec3;
+
+/// Tool axes shorter than this cannot be normalised.
+const MIN_AXIS_LENGTH: f64 = 1e-9;
+
+/// Tilt (degrees) within which the tool is treated as vertical: the rotation
+/// axis is then undefined, so callers hold it at its last position.
+pub const SINGULARITY_TILT: f64 = 0.5;
+
+/// Returns the signed shortest angular move from `from` to `to`, in degrees.
+///
+/// The result lies in `(-180°, 180°]`; a move of exactly half a turn is taken
+/// in the positive direction.
+pub fn shortest_delta(from: f64, to: f64) -> f64 {
+    let delta = (to - from).rem_euclid(360.0);
+    if delta > 180.0 {
+        delta - 360.0
+    } else {
+        delta
+    }
+}
+
+/// Converts wrapped rotary targets into the axis values to emit.
+///
+/// With rollover disabled, targets pass through unchanged. With rollover
+/// enabled, each target is reached from the previous emitted position by the
+/// shortest path, so the emitted value accumulates beyond ±360°.
+#[derive(Debug, Default)]
+pub struct RotaryTracker {
+    rollover: bool,
+    last: Option<f64>,
+}
+
+impl RotaryTracker {
+    pub fn new(rollover: bool) -> Self {
+        Self {
+            rollover,
+            last: None,
+        }
+    }
+
+    /// The last value returned by [`Self::next`], if any.
+    pub fn last(&self) -> Option<f64> {
+        self.last
+    }
+
+    /// Returns the axis value to emit for a move to `target` degrees.
+    pub fn next(&mut self, target: f64) -> f64 {
+        let value = match self.last {
+            Some(last) if self.rollover => last + shortest_delta(last, target),
+            _ => target,
+        };
+        self.last = Some(value);
+        value
+    }
+}
+
+/// One of the machine's rotary axes, independent of the letter it is given in
+/// the post-processor's `[axes]` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotaryAxis {
+    A,
+    B,
+    C,
+}
+
+impl RotaryAxis {
+    /// Index of this axis in per-axis `[A, B, C]` arrays.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The letter `axes` assigns this axis, or `None` if the machine lacks it.
+    pub fn letter(self, axes: &AxesConfig) -> Option<char> {
+        let name = match self {
+            RotaryAxis::A => &axes.a,
+            RotaryAxis::B => &axes.b,
+            RotaryAxis::C => &axes.c,
+        };
+        name.as_deref()?.chars().next()
+    }
+
+    /// The `(min, max)` travel of this axis.
+    pub fn range(self, limits: &AxisLimits) -> (f64, f64) {
+        match self {
+            RotaryAxis::A => (limits.a_min, limits.a_max),
+            RotaryAxis::B => (limits.b_min, limits.b_max),
+            RotaryAxis::C => (limits.c_min, limits.c_max),
+        }
+    }
+}
+
+/// Maps a tool-axis vector `(i, j, k)` in workpiece coordinates to the two
+/// rotary angles that orient the tool along it. The first angle is the tilt,
+/// the second the rotation that is undefined when the tool is vertical.
+///
+/// For [`FiveAxisType::HeadTable`] (B head, C table):
+/// `B = atan2(sqrt(i² + j²), k)` and `C = atan2(-j, -i)`, with C in
+/// `(-180°, 180°]` and `C0` for an exactly vertical tool.
+///
+/// Returns [`PostProcessorError::Validation`] for a zero-length axis and
+/// [`PostProcessorError::NotSupported`] for other kinematic families.
+pub fn inverse_kinematics(
+    kind: &FiveAxisType,
+    tool_axis: &Vec3,
+) -> Result<[(RotaryAxis, f64); 2], PostProcessorError> {
+    let len = (tool_axis.x.powi(2) + tool_axis.y.powi(2) + tool_axis.z.powi(2)).sqrt();
+    if !len.is_finite() || len < MIN_AXIS_LENGTH {
+        return Err(PostProcessorError::Validation(
+            "tool axis has zero length".to_string(),
+        ));
+    }
+    let (x, y, z) = (tool_axis.x / len, tool_axis.y / len, tool_axis.z / len);
+
+    match kind {
+        FiveAxisType::HeadTable => {
+            let b = x.hypot(y).atan2(z).to_degrees();
+            let c = if x == 0.0 && y == 0.0 {
+                0.0
+            } else {
+                // atan2 yields -180 for (-0, -i); fold it onto +180.
+                let c = (-y).atan2(-x).to_degrees();
+                if c <= -180.0 {
+                    c + 360.0
+                } else {
+                    c
+                }
+            };
+            Ok([(RotaryAxis::B, b), (RotaryAxis::C, c)])
+        }
+        other => Err(PostProcessorError::NotSupported(format!(
+            "{other:?} inverse kinematics"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    #[test]
+    fn head_table_vertical_tool_is_b0_c0() {
+        let angles = inverse_kinematics(&FiveAxisType::HeadTable, &v(0.0, 0.0, 1.0)).unwrap();
+        assert_eq!(angles, [(RotaryAxis::B, 0.0), (RotaryAxis::C, 0.0)]);
+    }
+
+    #[test]
+    fn head_table_tilted_tool_maps_to_b_tilt_and_c_direction() {
+        let [(_, b), (_, c)] =
+            inverse_kinematics(&FiveAxisType::HeadTable, &v(0.0, 1.0, 1.0)).unwrap();
+        assert!((b - 45.0).abs() < 1e-9, "B = {b}");
+        assert!((c + 90.0).abs() < 1e-9, "C = {c}");
+
+        // Unnormalised input gives the same tilt; C folds onto +180.
+        let [(_, b), (_, c)] =
+            inverse_kinematics(&FiveAxisType::HeadTable, &v(3.0, 0.0, 3.0)).unwrap();
+        assert!((b - 45.0).abs() < 1e-9, "B = {b}");
+        assert_eq!(c, 180.0);
+    }
+
+    #[test]
+    fn zero_tool_axis_is_rejected() {
+        let result = inverse_kinematics(&FiveAxisType::HeadTable, &v(0.0, 0.0, 0.0));
+        assert!(matches!(result, Err(PostProcessorError::Validation(_))));
+    }
+
+    #[test]
+    fn unsupported_kinematics_are_rejected() {
+        let result = inverse_kinematics(&FiveAxisType::TableTable, &v(0.0, 0.0, 1.0));
+        assert!(matches!(result, Err(PostProcessorError::NotSupported(_))));
+    }
+
+    #[test]
+    fn shortest_delta_wraps_across_zero() {
+        assert_eq!(shortest_delta(359.0, 1.0), 2.0);
+        assert_eq!(shortest_delta(1.0, 359.0), -2.0);
+        assert_eq!(shortest_delta(0.0, 180.0), 180.0);
+    }
+
+    #[test]
+    fn rollover_takes_short_path_from_359_to_1() {
+        let mut c = RotaryTracker::new(true);
+        let first = c.next(359.0);
+        let second = c.next(1.0);
+        assert_eq!(first, 359.0);
+        assert_eq!(second - first, 2.0, "expected +2° move, not -358°");
+        assert_eq!(second, 361.0, "position accumulates past 360°");
+    }
+
+    #[test]
+    fn without_rollover_targets_pass_through() {
+        let mut c = RotaryTracker::new(false);
+        c.next(359.0);
+        assert_eq!(c.next(1.0), 1.0);
+    }
+}
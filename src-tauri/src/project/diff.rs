@@ -0,0 +1,242 @@
+//! Structural comparison of two projects for reviewing `.jcam` changes.
+//!
+//! Tools and operations are matched by UUID, so a rename shows up as a
+//! change rather than a remove + add. Metadata fields are compared by value.
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::state::Project;
+
+/// A project-level field whose value differs between the two projects.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// camelCase field name (e.g. `"name"`, `"units"`, `"stock"`).
+    pub field: String,
+    /// Value in the first project; `None` when the field is unset there.
+    pub before: Option<String>,
+    /// Value in the second project; `None` when the field is unset there.
+    pub after: Option<String>,
+}
+
+/// Identifies one tool or operation in a diff.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityRef {
+    pub id: Uuid,
+    /// Name in the second project (or the first, for removed entities).
+    pub name: String,
+}
+
+/// Added, removed, and changed entities of one kind, in document order.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityChanges {
+    pub added: Vec<EntityRef>,
+    pub removed: Vec<EntityRef>,
+    pub changed: Vec<EntityRef>,
+}
+
+impl EntityChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Structured difference between two projects, ready for the UI to render.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDiff {
+    pub metadata: Vec<FieldChange>,
+    pub tools: EntityChanges,
+    pub operations: EntityChanges,
+}
+
+impl ProjectDiff {
+    /// Returns `true` when the two projects are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty() && self.tools.is_empty() && self.operations.is_empty()
+    }
+}
+
+/// Compares `a` (before) against `b` (after).
+///
+/// Timestamps are ignored: they change on every save and would drown out the
+/// edits a reviewer cares about.
+pub fn diff(a: &Project, b: &Project) -> ProjectDiff {
+    let mut metadata = Vec::new();
+    push_change(&mut metadata, "name", Some(&a.name), Some(&b.name));
+    push_change(
+        &mut metadata,
+        "description",
+        Some(&a.description),
+        Some(&b.description),
+    );
+    push_change(&mut metadata, "units", Some(&a.units), Some(&b.units));
+    push_change(
+        &mut metadata,
+        "sourceModel",
+        a.source_model
+            .as_ref()
+            .map(|m| m.path.to_string_lossy().into_owned())
+            .as_ref(),
+        b.source_model
+            .as_ref()
+            .map(|m| m.path.to_string_lossy().into_owned())
+            .as_ref(),
+    );
+    push_change(
+        &mut metadata,
+        "stock",
+        a.stock.as_ref().map(to_json).as_ref(),
+        b.stock.as_ref().map(to_json).as_ref(),
+    );
+    if a.wcs != b.wcs {
+        metadata.push(FieldChange {
+            field: "wcs".to_string(),
+            before: Some(to_json(&a.wcs)),
+            after: Some(to_json(&b.wcs)),
+        });
+    }
+
+    ProjectDiff {
+        metadata,
+        tools: diff_entities(&a.tools, &b.tools, |t| (t.id, &t.name)),
+        operations: diff_entities(&a.operations, &b.operations, |op| (op.id, &op.name)),
+    }
+}
+
+fn push_change(
+    out: &mut Vec<FieldChange>,
+    field: &str,
+    before: Option<&String>,
+    after: Option<&String>,
+) {
+    if before != after {
+        out.push(FieldChange {
+            field: field.to_string(),
+            before: before.cloned(),
+            after: after.cloned(),
+        });
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn diff_entities<T: PartialEq>(
+    before: &[T],
+    after: &[T],
+    key: impl Fn(&T) -> (Uuid, &String),
+) -> EntityChanges {
+    let mut changes = EntityChanges::default();
+
+    for item in after {
+        let (id, name) = key(item);
+        let entity = EntityRef {
+            id,
+            name: name.clone(),
+        };
+        match before.iter().find(|old| key(old).0 == id) {
+            None => changes.added.push(entity),
+            Some(old) if old != item => changes.changed.push(entity),
+            Some(_) => {}
+        }
+    }
+
+    for item in before {
+        let (id, name) = key(item);
+        if !after.iter().any(|new| key(new).0 == id) {
+            changes.removed.push(EntityRef {
+                id,
+                name: name.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::{CoolantMode, DrillParams, FeedMode, OperationParams};
+    use crate::models::{Operation, Tool, ToolType};
+
+    fn tool(name: &str) -> Tool {
+        Tool {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            tool_type: ToolType::Drill,
+            material: "hss".to_string(),
+            diameter: 6.0,
+            flute_count: 2,
+            default_spindle_speed: None,
+            default_feed_rate: None,
+            tip_angle: None,
+        }
+    }
+
+    fn drill_op(name: &str, tool_id: Uuid) -> Operation {
+        Operation {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            enabled: true,
+            tool_id,
+            params: OperationParams::Drill(DrillParams {
+                depth: 10.0,
+                peck_depth: None,
+            }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
+        }
+    }
+
+    #[test]
+    fn identical_projects_have_empty_diff() {
+        let a = Project {
+            tools: vec![tool("Drill")],
+            ..Default::default()
+        };
+        let b = Project {
+            tools: a.tools.clone(),
+            ..Default::default()
+        };
+        assert!(diff(&a, &b).is_empty());
+    }
+
+    #[test]
+    fn detects_added_removed_and_changed_entities() {
+        let kept = tool("Kept");
+        let dropped = tool("Dropped");
+        let a = Project {
+            tools: vec![kept.clone(), dropped.clone()],
+            operations: vec![drill_op("Spot", kept.id)],
+            ..Default::default()
+        };
+
+        let b = Project {
+            name: "Renamed".to_string(),
+            tools: vec![Tool {
+                diameter: 8.0,
+                ..kept.clone()
+            }],
+            operations: vec![a.operations[0].clone(), drill_op("Through", kept.id)],
+            ..Default::default()
+        };
+
+        let d = diff(&a, &b);
+        assert_eq!(d.metadata.len(), 1);
+        assert_eq!(d.metadata[0].field, "name");
+        assert_eq!(d.tools.changed.len(), 1);
+        assert_eq!(d.tools.changed[0].id, kept.id);
+        assert_eq!(d.tools.removed.len(), 1);
+        assert_eq!(d.tools.removed[0].id, dropped.id);
+        assert!(d.tools.added.is_empty());
+        assert_eq!(d.operations.added.len(), 1);
+        assert_eq!(d.operations.added[0].name, "Through");
+    }
+}
@@ -0,0 +1,107 @@
+//! Schema migrations for `project.json`.
+//!
+//! Older manifests are upgraded one version at a time on the raw JSON value,
+//! before deserialization into [`super::types::ProjectFile`], so the typed
+//! structs only ever describe the current schema.
+//!
+//! To bump the schema: append a migration to [`MIGRATIONS`] and increment
+//! [`CURRENT_SCHEMA_VERSION`]; the compile-time check below keeps the two in
+//! step.
+
+use serde_json::Value;
+
+use crate::error::AppError;
+
+/// Schema version written by this build and the newest one it can read.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades a manifest by exactly one schema version.
+type Migration = fn(Value) -> Result<Value, AppError>;
+
+/// `MIGRATIONS[i]` upgrades a manifest from version `i + 1` to `i + 2`.
+const MIGRATIONS: &[Migration] = &[];
+
+const _: () = assert!(MIGRATIONS.len() as u32 + 1 == CURRENT_SCHEMA_VERSION);
+
+/// Upgrade `json`, a manifest at schema version `from`, to
+/// [`CURRENT_SCHEMA_VERSION`].
+///
+/// Returns [`AppError::ProjectLoad`] if `from` is 0 or newer than this build
+/// supports, or if a migration rejects the manifest.
+pub fn migrate(json: Value, from: u32) -> Result<Value, AppError> {
+    apply(json, from, MIGRATIONS)
+}
+
+/// Run `migrations` (indexed as [`MIGRATIONS`]) from version `from` onward,
+/// stamping each step's `schema_version` into the manifest.
+fn apply(mut json: Value, from: u32, migrations: &[Migration]) -> Result<Value, AppError> {
+    let newest = migrations.len() as u32 + 1;
+    if from == 0 || from > newest {
+        return Err(AppError::ProjectLoad(format!(
+            "unsupported schema version {from}; this build reads schema versions 1 to {newest}"
+        )));
+    }
+    for (index, migration) in migrations.iter().enumerate().skip(from as usize - 1) {
+        json = migration(json)?;
+        json["schema_version"] = Value::from(index as u32 + 2);
+    }
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::types::ProjectFile;
+
+    fn v1_manifest() -> Value {
+        serde_json::json!({
+            "schema_version": 1,
+            "app_version": "0.1.0",
+            "created_at": "",
+            "modified_at": "",
+            "project": { "name": "Migrated", "description": "", "units": "mm" }
+        })
+    }
+
+    #[test]
+    fn v1_manifest_loads_through_noop_v1_to_v2_migration() {
+        let noop: Migration = Ok;
+        let json = apply(v1_manifest(), 1, &[noop]).expect("migrate");
+
+        assert_eq!(json["schema_version"], 2);
+        let pf: ProjectFile = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(pf.project.name, "Migrated");
+    }
+
+    #[test]
+    fn migrations_run_in_order_from_the_file_version() {
+        let to_v2: Migration = |mut json| {
+            json["steps"] = Value::from("a");
+            Ok(json)
+        };
+        let to_v3: Migration = |mut json| {
+            let steps = json["steps"].as_str().unwrap_or_default().to_string();
+            json["steps"] = Value::from(steps + "b");
+            Ok(json)
+        };
+
+        let from_v1 = apply(v1_manifest(), 1, &[to_v2, to_v3]).unwrap();
+        assert_eq!(from_v1["steps"], "ab");
+        assert_eq!(from_v1["schema_version"], 3);
+
+        let from_v2 = apply(v1_manifest(), 2, &[to_v2, to_v3]).unwrap();
+        assert_eq!(from_v2["steps"], "b", "v1→v2 must be skipped");
+    }
+
+    #[test]
+    fn current_version_is_unchanged() {
+        let json = migrate(v1_manifest(), CURRENT_SCHEMA_VERSION).unwrap();
+        assert_eq!(json, v1_manifest());
+    }
+
+    #[test]
+    fn version_above_current_errors() {
+        let result = migrate(v1_manifest(), CURRENT_SCHEMA_VERSION + 1);
+        assert!(matches!(result, Err(AppError::ProjectLoad(msg)) if msg.contains("schema")));
+    }
+}
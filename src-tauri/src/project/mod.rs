@@ -5,6 +5,10 @@
 //!
 //! - [`types`] — serializable types that mirror the `project.json` schema
 //! - [`serialization`] — atomic save and validated load functions
+//! - [`migrations`] — upgrades older `project.json` schemas on load
+//! - [`diff`] — structural comparison of two projects
 
+pub mod diff;
+pub mod migrations;
 pub mod serialization;
 pub mod types;
@@ -10,17 +10,21 @@
 //!
 //! # Load
 //! 1. Open the ZIP and read `project.json`.
-//! 2. Validate `schema_version == 1`; reject anything else with a clear error.
+//! 2. Check `schema_version` is at most [`CURRENT_SCHEMA_VERSION`] and run the
+//!    [`migrations`] chain to upgrade older manifests; reject newer ones.
 //! 3. Reconstruct the in-memory [`Project`].  [`LoadedModel::mesh_data`] is
 //!    initialised empty — the IPC `open_model` command re-tessellates when the
 //!    viewport needs geometry.
+//! 4. If the model is embedded, extract `model/source.*` to a temp file and
+//!    point [`LoadedModel::path`] at it.
 
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use zip::write::SimpleFileOptions;
 use zip::CompressionMethod;
 
+use super::migrations::{self, CURRENT_SCHEMA_VERSION};
 use super::types::{ProjectFile, ProjectMeta, SourceModelRef};
 use crate::error::AppError;
 use crate::geometry::MeshData;
@@ -29,6 +33,9 @@ use crate::state::{LoadedModel, Project};
 /// Name of the project manifest inside every `.jcam` ZIP.
 const PROJECT_JSON: &str = "project.json";
 
+/// Prefix of the embedded model entry; the model file's extension follows.
+const MODEL_ENTRY_PREFIX: &str = "model/source";
+
 /// JamieCam version embedded in every saved file.
 const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -37,7 +44,10 @@ const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// The ZIP is written to `<path>.tmp` in the same directory (guaranteeing
 /// same-filesystem placement), then renamed over `path`.  On any error the
 /// temp file is removed and `path` is left unchanged.
-pub fn save(project: &Project, path: &Path) -> Result<(), AppError> {
+///
+/// With `embed_model`, the source model file is copied into the archive as
+/// `model/source.<ext>` so the project opens without the original on disk.
+pub fn save(project: &Project, path: &Path, embed_model: bool) -> Result<(), AppError> {
     let file_name = path
         .file_name()
         .unwrap_or_default()
@@ -45,7 +55,7 @@ pub fn save(project: &Project, path: &Path) -> Result<(), AppError> {
         .into_owned();
     let tmp_path = path.with_file_name(format!("{file_name}.tmp"));
 
-    if let Err(e) = write_archive(project, &tmp_path) {
+    if let Err(e) = write_archive(project, &tmp_path, embed_model) {
         let _ = std::fs::remove_file(&tmp_path);
         return Err(e);
     }
@@ -59,8 +69,8 @@ pub fn save(project: &Project, path: &Path) -> Result<(), AppError> {
 /// Load a `.jcam` file from `path` and return the reconstructed [`Project`].
 ///
 /// Returns [`AppError::ProjectLoad`] if the file cannot be read, is not a
-/// valid ZIP, contains no `project.json`, or has an unsupported
-/// `schema_version`.
+/// valid ZIP, contains no `project.json`, has a `schema_version` newer than
+/// [`CURRENT_SCHEMA_VERSION`], or marks its model embedded but cannot extract it.
 pub fn load(path: &Path) -> Result<Project, AppError> {
     let file = std::fs::File::open(path)
         .map_err(|e| AppError::ProjectLoad(format!("cannot open file: {e}")))?;
@@ -69,7 +79,7 @@ pub fn load(path: &Path) -> Result<Project, AppError> {
         .map_err(|e| AppError::ProjectLoad(format!("not a valid ZIP archive: {e}")))?;
 
     // Read project.json inside a block so the borrow on `archive` is released
-    // before we might need it again (e.g. for embedded model extraction later).
+    // before embedded model extraction needs it again.
     let json_str = {
         let mut entry = archive.by_name(PROJECT_JSON).map_err(|e| {
             AppError::ProjectLoad(format!("{PROJECT_JSON} not found in archive: {e}"))
@@ -81,27 +91,42 @@ pub fn load(path: &Path) -> Result<Project, AppError> {
         s
     };
 
-    let pf: ProjectFile = serde_json::from_str(&json_str)
+    let json: serde_json::Value = serde_json::from_str(&json_str)
         .map_err(|e| AppError::ProjectLoad(format!("cannot parse {PROJECT_JSON}: {e}")))?;
+    let version = json["schema_version"]
+        .as_u64()
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| {
+            AppError::ProjectLoad(format!("{PROJECT_JSON} has no valid schema_version"))
+        })?;
+    let json = migrations::migrate(json, version)?;
 
-    if pf.schema_version != 1 {
-        return Err(AppError::ProjectLoad(format!(
-            "unsupported schema version {}; only schema version 1 is supported",
-            pf.schema_version
-        )));
-    }
+    let pf: ProjectFile = serde_json::from_value(json)
+        .map_err(|e| AppError::ProjectLoad(format!("cannot parse {PROJECT_JSON}: {e}")))?;
 
-    let source_model = pf.source_model.map(|r| LoadedModel {
-        path: std::path::PathBuf::from(&r.path),
-        checksum: r.checksum,
-        // Mesh data is not persisted in the project file.  The IPC
-        // `open_model` command re-tessellates the geometry when needed.
-        mesh_data: MeshData {
-            vertices: vec![],
-            normals: vec![],
-            indices: vec![],
-        },
-    });
+    let source_model = match pf.source_model {
+        Some(r) => {
+            let (path, original_path) = if r.embedded {
+                let extracted = extract_embedded_model(&mut archive, &r.checksum)?;
+                (extracted, Some(PathBuf::from(&r.path)))
+            } else {
+                (PathBuf::from(&r.path), None)
+            };
+            Some(LoadedModel {
+                path,
+                original_path,
+                checksum: r.checksum,
+                // Mesh data is not persisted in the project file.  The IPC
+                // `open_model` command re-tessellates the geometry when needed.
+                mesh_data: MeshData {
+                    vertices: vec![],
+                    normals: vec![],
+                    indices: vec![],
+                },
+            })
+        }
+        None => None,
+    };
 
     Ok(Project {
         name: pf.project.name,
@@ -110,6 +135,7 @@ pub fn load(path: &Path) -> Result<Project, AppError> {
         schema_version: pf.schema_version,
         created_at: pf.created_at,
         modified_at: pf.modified_at,
+        path: None,
         source_model,
         stock: pf.stock,
         wcs: pf.wcs,
@@ -119,11 +145,73 @@ pub fn load(path: &Path) -> Result<Project, AppError> {
     })
 }
 
+/// File-name stem for an extracted model: `checksum` when it is a lowercase
+/// hex digest, so reloading the same project reuses the file, else a fresh
+/// UUID. The checksum comes from the untrusted project file and must never
+/// reach the path unchecked.
+fn embedded_model_stem(checksum: &str) -> String {
+    let is_hex = |c: char| c.is_ascii_digit() || ('a'..='f').contains(&c);
+    if !checksum.is_empty() && checksum.chars().all(is_hex) {
+        checksum.to_string()
+    } else {
+        uuid::Uuid::new_v4().to_string()
+    }
+}
+
+/// Extension of the embedded model entry `entry_name` (e.g. `".step"`), or
+/// an empty string when it has none.
+///
+/// Returns [`AppError::ProjectLoad`] unless the extension is a `.` followed
+/// by ASCII letters and digits, so a crafted entry name cannot steer the
+/// extracted file out of the temp directory.
+fn embedded_model_ext(entry_name: &str) -> Result<&str, AppError> {
+    let ext = &entry_name[MODEL_ENTRY_PREFIX.len()..];
+    let valid = ext.is_empty()
+        || ext.strip_prefix('.').is_some_and(|rest| {
+            !rest.is_empty() && rest.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+    if valid {
+        Ok(ext)
+    } else {
+        Err(AppError::ProjectLoad(format!(
+            "embedded model entry {entry_name:?} has an invalid extension"
+        )))
+    }
+}
+
+/// Extract the `model/source.*` entry of `archive` to a temp file and return
+/// its path. The file is named after `checksum` so reloading the same
+/// project reuses it.
+fn extract_embedded_model<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    checksum: &str,
+) -> Result<PathBuf, AppError> {
+    let entry_name = archive
+        .file_names()
+        .find(|name| name.starts_with(MODEL_ENTRY_PREFIX))
+        .map(str::to_owned)
+        .ok_or_else(|| {
+            AppError::ProjectLoad("model is marked embedded but the archive has none".to_string())
+        })?;
+    let ext = embedded_model_ext(&entry_name)?;
+    let stem = embedded_model_stem(checksum);
+    let path = std::env::temp_dir().join(format!("jamiecam-model-{stem}{ext}"));
+
+    let mut bytes = Vec::new();
+    archive
+        .by_name(&entry_name)
+        .and_then(|mut entry| Ok(entry.read_to_end(&mut bytes)?))
+        .map_err(|e| AppError::ProjectLoad(format!("cannot read embedded model: {e}")))?;
+    std::fs::write(&path, bytes)
+        .map_err(|e| AppError::ProjectLoad(format!("cannot extract embedded model: {e}")))?;
+    Ok(path)
+}
+
 /// Write the ZIP archive to `path` (the temp file location).
 ///
 /// Separated from [`save`] so that cleanup on error is handled entirely by
 /// the caller.
-fn write_archive(project: &Project, path: &Path) -> Result<(), AppError> {
+fn write_archive(project: &Project, path: &Path, embed_model: bool) -> Result<(), AppError> {
     let file = std::fs::File::create(path)
         .map_err(|e| AppError::ProjectSave(format!("cannot create temp file: {e}")))?;
 
@@ -131,15 +219,19 @@ fn write_archive(project: &Project, path: &Path) -> Result<(), AppError> {
     let opts = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
 
     // Build the on-disk SourceModelRef from the in-memory LoadedModel.
-    // Phase 0: embedding is always false; the toggle is added in a later phase.
     let source_model_ref = project.source_model.as_ref().map(|m| SourceModelRef {
-        path: m.path.to_string_lossy().into_owned(),
+        path: m
+            .original_path
+            .as_ref()
+            .unwrap_or(&m.path)
+            .to_string_lossy()
+            .into_owned(),
         checksum: m.checksum.clone(),
-        embedded: false,
+        embedded: embed_model,
     });
 
     let pf = ProjectFile {
-        schema_version: 1,
+        schema_version: CURRENT_SCHEMA_VERSION,
         app_version: APP_VERSION.to_string(),
         created_at: project.created_at.clone(),
         modified_at: project.modified_at.clone(),
@@ -164,9 +256,7 @@ fn write_archive(project: &Project, path: &Path) -> Result<(), AppError> {
     zip.write_all(json.as_bytes())
         .map_err(|e| AppError::ProjectSave(format!("cannot write {PROJECT_JSON}: {e}")))?;
 
-    // Embed model if requested (Phase 0: embedded is always false, so this
-    // branch never executes — it is here for correctness when the toggle is
-    // wired up in a later bead).
+    // Embed model if requested.
     if let Some(model_ref) = &source_model_ref {
         if model_ref.embedded {
             if let Some(loaded) = &project.source_model {
@@ -175,7 +265,7 @@ fn write_archive(project: &Project, path: &Path) -> Result<(), AppError> {
                     .extension()
                     .map(|e| format!(".{}", e.to_string_lossy()))
                     .unwrap_or_default();
-                let entry_name = format!("model/source{ext}");
+                let entry_name = format!("{MODEL_ENTRY_PREFIX}{ext}");
 
                 let model_bytes = std::fs::read(&loaded.path).map_err(|e| {
                     AppError::ProjectSave(format!("cannot read model file for embedding: {e}"))
@@ -214,26 +304,29 @@ mod tests {
             flute_count: 4,
             default_spindle_speed: Some(15000),
             default_feed_rate: Some(2400.0),
+            tip_angle: None,
         }
     }
 
     fn make_project_with_model() -> Project {
-        let mut p = Project::default();
-        p.name = "Test Project".to_string();
-        p.description = "A test description".to_string();
-        p.created_at = "2026-01-01T00:00:00Z".to_string();
-        p.modified_at = "2026-01-02T12:00:00Z".to_string();
-        p.source_model = Some(LoadedModel {
-            path: PathBuf::from("/home/user/model.step"),
-            checksum: "abc123def456abc123def456abc123def456abc123def456abc123def456abc1"
-                .to_string(),
-            mesh_data: MeshData {
-                vertices: vec![],
-                normals: vec![],
-                indices: vec![],
-            },
-        });
-        p
+        Project {
+            name: "Test Project".to_string(),
+            description: "A test description".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-02T12:00:00Z".to_string(),
+            source_model: Some(LoadedModel {
+                path: PathBuf::from("/home/user/model.step"),
+                original_path: None,
+                checksum: "abc123def456abc123def456abc123def456abc123def456abc123def456abc1"
+                    .to_string(),
+                mesh_data: MeshData {
+                    vertices: vec![],
+                    normals: vec![],
+                    indices: vec![],
+                },
+            }),
+            ..Default::default()
+        }
     }
 
     #[test]
@@ -241,7 +334,7 @@ mod tests {
         let project = make_project_with_model();
         let tmp = std::env::temp_dir().join("jcam_test_round_trip_model.jcam");
 
-        save(&project, &tmp).expect("save should succeed");
+        save(&project, &tmp, false).expect("save should succeed");
         let loaded = load(&tmp).expect("load should succeed");
         let _ = std::fs::remove_file(&tmp);
 
@@ -261,12 +354,88 @@ mod tests {
         assert_eq!(got.checksum, orig.checksum);
     }
 
+    #[test]
+    fn round_trip_embedded_model_survives_deleting_original() {
+        let model_path = std::env::temp_dir().join("jcam_test_embed_source.stl");
+        std::fs::write(&model_path, b"solid embedded").expect("write model");
+        let mut project = make_project_with_model();
+        project.source_model.as_mut().unwrap().path = model_path.clone();
+        let tmp = std::env::temp_dir().join("jcam_test_round_trip_embedded.jcam");
+
+        save(&project, &tmp, true).expect("save should succeed");
+        std::fs::remove_file(&model_path).expect("delete original model");
+        let loaded = load(&tmp).expect("load should succeed");
+        let _ = std::fs::remove_file(&tmp);
+
+        let model = loaded.source_model.expect("source_model should survive");
+        assert_ne!(model.path, model_path, "must point at the extracted copy");
+        assert_eq!(model.original_path.as_ref(), Some(&model_path));
+        assert_eq!(model.path.extension().unwrap(), "stl");
+        let bytes = std::fs::read(&model.path).expect("extracted model must exist");
+        assert_eq!(bytes, b"solid embedded");
+        let _ = std::fs::remove_file(&model.path);
+    }
+
+    #[test]
+    fn resaving_extracted_model_keeps_original_reference() {
+        let model_path = std::env::temp_dir().join("jcam_test_resave_source.stl");
+        std::fs::write(&model_path, b"solid resave").expect("write model");
+        let mut project = make_project_with_model();
+        project.source_model.as_mut().unwrap().path = model_path.clone();
+        let tmp = std::env::temp_dir().join("jcam_test_resave_embedded.jcam");
+
+        save(&project, &tmp, true).expect("save embedded");
+        let loaded = load(&tmp).expect("load embedded");
+        save(&loaded, &tmp, false).expect("save by reference");
+        let reloaded = load(&tmp).expect("load by reference");
+        let _ = std::fs::remove_file(&tmp);
+        let _ = std::fs::remove_file(&model_path);
+        if let Some(model) = &loaded.source_model {
+            let _ = std::fs::remove_file(&model.path);
+        }
+
+        let model = reloaded.source_model.expect("source_model should survive");
+        assert_eq!(
+            model.path, model_path,
+            "reference must not name the temp copy"
+        );
+        assert_eq!(model.original_path, None);
+    }
+
+    #[test]
+    fn embedded_model_stem_accepts_only_lowercase_hex() {
+        assert_eq!(embedded_model_stem("abc123"), "abc123");
+        for hostile in ["../../home/u/.bashrc", "ABC123", "abc/123", ""] {
+            let stem = embedded_model_stem(hostile);
+            assert!(Uuid::parse_str(&stem).is_ok(), "{hostile:?} gave {stem:?}");
+        }
+    }
+
+    #[test]
+    fn embedded_model_ext_rejects_path_components() {
+        assert_eq!(embedded_model_ext("model/source.step").unwrap(), ".step");
+        assert_eq!(embedded_model_ext("model/source").unwrap(), "");
+        for hostile in [
+            "model/source/../../x",
+            "model/source.st/p",
+            "model/source.\\x",
+            "model/source..",
+            "model/source.",
+            "model/sourcex",
+        ] {
+            assert!(
+                matches!(embedded_model_ext(hostile), Err(AppError::ProjectLoad(_))),
+                "{hostile:?} must be rejected"
+            );
+        }
+    }
+
     #[test]
     fn round_trip_no_model() {
         let project = Project::default();
         let tmp = std::env::temp_dir().join("jcam_test_round_trip_empty.jcam");
 
-        save(&project, &tmp).expect("save should succeed");
+        save(&project, &tmp, false).expect("save should succeed");
         let loaded = load(&tmp).expect("load should succeed");
         let _ = std::fs::remove_file(&tmp);
 
@@ -321,7 +490,7 @@ mod tests {
         let project = make_project_with_model();
         let tmp = std::env::temp_dir().join("jcam_test_zip_valid.jcam");
 
-        save(&project, &tmp).expect("save should succeed");
+        save(&project, &tmp, false).expect("save should succeed");
 
         // Verify the file is a readable ZIP containing project.json.
         let file = std::fs::File::open(&tmp).unwrap();
@@ -336,15 +505,17 @@ mod tests {
 
     #[test]
     fn round_trip_project_with_tool() {
-        let mut project = Project::default();
-        project.name = "Tool Round-Trip Test".to_string();
-        project.created_at = "2026-01-01T00:00:00Z".to_string();
-        project.modified_at = "2026-01-02T00:00:00Z".to_string();
+        let mut project = Project {
+            name: "Tool Round-Trip Test".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-02T00:00:00Z".to_string(),
+            ..Default::default()
+        };
         let tool = make_tool();
         project.tools.push(tool.clone());
 
         let tmp = std::env::temp_dir().join("jcam_test_round_trip_tool.jcam");
-        save(&project, &tmp).expect("save should succeed");
+        save(&project, &tmp, false).expect("save should succeed");
         let loaded = load(&tmp).expect("load should succeed");
         let _ = std::fs::remove_file(&tmp);
 
@@ -401,15 +572,56 @@ mod tests {
         assert_eq!(project.name, "Phase0 Project");
     }
 
+    #[test]
+    fn round_trip_project_with_cylinder_stock() {
+        use crate::models::stock::{CylinderDimensions, Vec3};
+        use crate::models::StockDefinition;
+
+        let mut project = Project {
+            name: "Cylinder Stock Round-Trip Test".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-02T00:00:00Z".to_string(),
+            ..Default::default()
+        };
+
+        let cylinder = StockDefinition::Cylinder(CylinderDimensions {
+            origin: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: -60.0,
+            },
+            axis: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            diameter: 50.0,
+            height: 60.0,
+        });
+        project.stock = Some(cylinder.clone());
+
+        let tmp = std::env::temp_dir().join("jcam_test_round_trip_cylinder_stock.jcam");
+        save(&project, &tmp, false).expect("save should succeed");
+        let loaded = load(&tmp).expect("load should succeed");
+        let _ = std::fs::remove_file(&tmp);
+
+        assert_eq!(
+            loaded.stock.expect("stock should survive round-trip"),
+            cylinder
+        );
+    }
+
     #[test]
     fn round_trip_project_with_stock_and_wcs() {
         use crate::models::stock::{BoxDimensions, Vec3};
         use crate::models::{StockDefinition, WorkCoordinateSystem};
 
-        let mut project = Project::default();
-        project.name = "Stock/WCS Round-Trip Test".to_string();
-        project.created_at = "2026-01-01T00:00:00Z".to_string();
-        project.modified_at = "2026-01-02T00:00:00Z".to_string();
+        let mut project = Project {
+            name: "Stock/WCS Round-Trip Test".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-02T00:00:00Z".to_string(),
+            ..Default::default()
+        };
 
         project.stock = Some(StockDefinition::Box(BoxDimensions {
             origin: Vec3 {
@@ -444,13 +656,15 @@ mod tests {
         });
 
         let tmp = std::env::temp_dir().join("jcam_test_round_trip_stock_wcs.jcam");
-        save(&project, &tmp).expect("save should succeed");
+        save(&project, &tmp, false).expect("save should succeed");
         let loaded = load(&tmp).expect("load should succeed");
         let _ = std::fs::remove_file(&tmp);
 
         // Stock round-trip
         let stock = loaded.stock.expect("stock should survive round-trip");
-        let StockDefinition::Box(b) = stock;
+        let StockDefinition::Box(b) = stock else {
+            panic!("expected box stock");
+        };
         assert_eq!(b.width, 120.0);
         assert_eq!(b.depth, 80.0);
         assert_eq!(b.height, 30.0);
@@ -465,7 +679,8 @@ mod tests {
     #[test]
     fn round_trip_project_with_operations() {
         use crate::models::operation::{
-            CompensationSide, DrillParams, OperationParams, PocketParams, ProfileParams,
+            CompensationSide, CoolantMode, DrillParams, FeedMode, LeadType, OperationParams,
+            PocketParams, ProfileParams,
         };
         use crate::models::Operation;
 
@@ -480,7 +695,13 @@ mod tests {
                 depth: 10.0,
                 stepdown: 2.5,
                 compensation_side: CompensationSide::Left,
+                lead_in: LeadType::None,
+                tabs: None,
+                stock_to_leave: 0.0,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         };
         let op_pocket = Operation {
             id: Uuid::parse_str("bbbb0000-0000-0000-0000-000000000002").unwrap(),
@@ -491,7 +712,12 @@ mod tests {
                 depth: 15.0,
                 stepdown: 3.0,
                 stepover_percent: 45.0,
+                lead_in: LeadType::None,
+                stock_to_leave: 0.0,
             }),
+            path_mode: None,
+            coolant: CoolantMode::Flood,
+            feed_mode: FeedMode::PerMinute,
         };
         let op_drill = Operation {
             id: Uuid::parse_str("cccc0000-0000-0000-0000-000000000003").unwrap(),
@@ -502,18 +728,23 @@ mod tests {
                 depth: 20.0,
                 peck_depth: Some(5.0),
             }),
+            path_mode: None,
+            coolant: CoolantMode::ThroughTool,
+            feed_mode: FeedMode::PerMinute,
         };
 
-        let mut project = Project::default();
-        project.name = "Operations Round-Trip Test".to_string();
-        project.created_at = "2026-01-01T00:00:00Z".to_string();
-        project.modified_at = "2026-01-02T00:00:00Z".to_string();
+        let mut project = Project {
+            name: "Operations Round-Trip Test".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-02T00:00:00Z".to_string(),
+            ..Default::default()
+        };
         project.operations.push(op_profile.clone());
         project.operations.push(op_pocket.clone());
         project.operations.push(op_drill.clone());
 
         let tmp = std::env::temp_dir().join("jcam_test_round_trip_operations.jcam");
-        save(&project, &tmp).expect("save should succeed");
+        save(&project, &tmp, false).expect("save should succeed");
         let loaded = load(&tmp).expect("load should succeed");
         let _ = std::fs::remove_file(&tmp);
 
@@ -535,5 +766,7 @@ mod tests {
             "enabled=false must round-trip"
         );
         assert_eq!(loaded.operations[2].params, op_drill.params);
+        assert_eq!(loaded.operations[2].coolant, CoolantMode::ThroughTool);
+        assert_eq!(loaded.operations[0].coolant, CoolantMode::Flood);
     }
 }
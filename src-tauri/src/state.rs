@@ -5,11 +5,12 @@
 
 use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
 use uuid::Uuid;
 
+use crate::error::AppError;
 use crate::geometry::MeshData;
 use crate::models::{Operation, StockDefinition, Tool, WorkCoordinateSystem};
 
@@ -18,6 +19,9 @@ use crate::models::{Operation, StockDefinition, Tool, WorkCoordinateSystem};
 pub struct LoadedModel {
     /// Absolute path to the source file on disk.
     pub path: PathBuf,
+    /// Path recorded in the project file when `path` is a temp copy
+    /// extracted from an embedded model; `None` when `path` is the original.
+    pub original_path: Option<PathBuf>,
     /// SHA-256 hex digest of the file at load time (for cache validation).
     pub checksum: String,
     /// Tessellated mesh ready for rendering.
@@ -41,6 +45,9 @@ pub struct Project {
     pub created_at: String,
     /// ISO-8601 last-modified timestamp (empty string when not yet persisted).
     pub modified_at: String,
+    /// The `.jcam` file this project was last saved to or loaded from;
+    /// `None` until it is first saved.
+    pub path: Option<PathBuf>,
     /// The geometry model currently loaded, if any.
     pub source_model: Option<LoadedModel>,
     // ── Scaffolding — remaining types will be replaced in later beads ────────
@@ -62,9 +69,10 @@ impl Default for Project {
             name: String::new(),
             description: String::new(),
             units: "mm".to_string(),
-            schema_version: 1,
+            schema_version: crate::project::migrations::CURRENT_SCHEMA_VERSION,
             created_at: String::new(),
             modified_at: String::new(),
+            path: None,
             source_model: None,
             stock: None,
             wcs: Vec::new(),
@@ -75,14 +83,101 @@ impl Default for Project {
     }
 }
 
-/// In-memory user preferences.
+/// Most recent files kept in [`UserPreferences::recent_files`].
+pub const MAX_RECENT_FILES: usize = 10;
+
+/// User preferences.
 ///
-/// Phase 0: no disk persistence.  The list is rebuilt from scratch each
-/// session.  A persistence layer will be added in a future phase.
-#[derive(Default)]
+/// The recent-files list persists across sessions in
+/// `<local data dir>/jamiecam/preferences.json` (see [`UserPreferences::load`]
+/// and [`UserPreferences::save`]); other fields live for the session only.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct UserPreferences {
-    /// Most-recently-used file paths, newest first.
+    /// Most-recently-used file paths, newest first, without duplicates and at
+    /// most [`MAX_RECENT_FILES`] long.
     pub recent_files: VecDeque<PathBuf>,
+    /// Directory scanned for user-installed post-processors; `None` uses
+    /// the platform default (see [`UserPreferences::post_processor_dir`]).
+    #[serde(skip)]
+    pub custom_post_dir: Option<PathBuf>,
+    /// File [`UserPreferences::save`] writes to; set by
+    /// [`UserPreferences::load`]. Preferences built in memory have none.
+    #[serde(skip)]
+    pub file: Option<PathBuf>,
+}
+
+impl UserPreferences {
+    /// Location of the preferences file, if the platform has a local data dir.
+    pub fn file_path() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|d| d.join("jamiecam").join("preferences.json"))
+    }
+
+    /// Load the saved preferences, falling back to defaults when there is no
+    /// file or it cannot be read. Either way they are saved back to
+    /// [`UserPreferences::file_path`].
+    pub fn load() -> Self {
+        let Some(path) = Self::file_path() else {
+            return Self::default();
+        };
+        let mut prefs = if path.exists() {
+            Self::load_from(&path).unwrap_or_else(|e| {
+                tracing::warn!("ignoring unreadable preferences {}: {e}", path.display());
+                Self::default()
+            })
+        } else {
+            Self::default()
+        };
+        prefs.file = Some(path);
+        prefs
+    }
+
+    /// Write the preferences back to the file they were loaded from. Without
+    /// one (e.g. [`AppState::default`]) nothing is written.
+    pub fn save(&self) -> Result<(), AppError> {
+        match &self.file {
+            Some(path) => self.save_to(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Read preferences from `path`, normalising the recent-files list.
+    pub fn load_from(path: &Path) -> Result<Self, AppError> {
+        let json = std::fs::read_to_string(path)?;
+        let mut prefs: Self = serde_json::from_str(&json)
+            .map_err(|e| AppError::Io(format!("cannot parse preferences: {e}")))?;
+        let saved = std::mem::take(&mut prefs.recent_files);
+        for file in saved.into_iter().rev() {
+            prefs.push_recent_file(file);
+        }
+        Ok(prefs)
+    }
+
+    /// Write preferences to `path`, creating its parent directory.
+    pub fn save_to(&self, path: &Path) -> Result<(), AppError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| AppError::Io(format!("cannot serialize preferences: {e}")))?;
+        std::fs::write(path, json).map_err(AppError::from)
+    }
+
+    /// Move `path` to the front of the recent-files list, dropping any earlier
+    /// entry for it and the oldest entries beyond [`MAX_RECENT_FILES`].
+    pub fn push_recent_file(&mut self, path: PathBuf) {
+        self.recent_files.retain(|p| *p != path);
+        self.recent_files.push_front(path);
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    /// Directory scanned for user-installed `.toml` post-processors: the
+    /// configured one, else `<config dir>/jamiecam/postprocessors`.
+    pub fn post_processor_dir(&self) -> Option<PathBuf> {
+        self.custom_post_dir
+            .clone()
+            .or_else(|| dirs::config_dir().map(|d| d.join("jamiecam").join("postprocessors")))
+    }
 }
 
 /// Root application state managed by Tauri.
@@ -152,6 +247,60 @@ mod tests {
         assert!(prefs.recent_files.is_empty());
     }
 
+    #[test]
+    fn push_recent_file_moves_duplicate_to_front() {
+        let mut prefs = UserPreferences::default();
+        prefs.push_recent_file(PathBuf::from("/a.jcam"));
+        prefs.push_recent_file(PathBuf::from("/b.jcam"));
+        prefs.push_recent_file(PathBuf::from("/a.jcam"));
+
+        assert_eq!(
+            prefs.recent_files,
+            [PathBuf::from("/a.jcam"), PathBuf::from("/b.jcam")]
+        );
+    }
+
+    #[test]
+    fn push_recent_file_caps_list_dropping_oldest() {
+        let mut prefs = UserPreferences::default();
+        for i in 0..MAX_RECENT_FILES + 3 {
+            prefs.push_recent_file(PathBuf::from(format!("/part{i}.step")));
+        }
+
+        assert_eq!(prefs.recent_files.len(), MAX_RECENT_FILES);
+        let newest = format!("/part{}.step", MAX_RECENT_FILES + 2);
+        assert_eq!(prefs.recent_files[0], PathBuf::from(newest));
+        assert!(!prefs.recent_files.contains(&PathBuf::from("/part0.step")));
+    }
+
+    #[test]
+    fn preferences_round_trip_through_file() {
+        let path = std::env::temp_dir()
+            .join("jcam_prefs_test")
+            .join("preferences.json");
+        let mut prefs = UserPreferences::default();
+        prefs.push_recent_file(PathBuf::from("/old.jcam"));
+        prefs.push_recent_file(PathBuf::from("/new.jcam"));
+
+        prefs.save_to(&path).expect("save");
+        let loaded = UserPreferences::load_from(&path).expect("load");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.recent_files, prefs.recent_files);
+    }
+
+    #[test]
+    fn custom_post_dir_overrides_platform_default() {
+        let prefs = UserPreferences {
+            custom_post_dir: Some(PathBuf::from("/shop/posts")),
+            ..UserPreferences::default()
+        };
+        assert_eq!(
+            prefs.post_processor_dir(),
+            Some(PathBuf::from("/shop/posts"))
+        );
+    }
+
     #[test]
     fn app_state_project_lock_allows_write() {
         let state = AppState::default();
@@ -0,0 +1,312 @@
+//! Polyline simplification by arc and line fitting.
+//!
+//! Dense polylines — imported curves, or contours sampled at a fine step —
+//! turn into thousands of short G01 blocks that bloat the program and make
+//! the machine stutter. [`fit_arcs`] replaces runs of feed moves that lie on
+//! a common circle with a single arc move, and runs that lie on a common
+//! line with a single feed move.
+
+use std::f64::consts::TAU;
+
+use crate::models::Vec3;
+
+use super::types::{CutPoint, MoveKind, Pass};
+
+/// Fewest feed moves worth replacing with one arc. Any three points lie on a
+/// circle, so shorter runs would turn ordinary corners into arcs.
+const MIN_ARC_MOVES: usize = 3;
+
+/// Returns a copy of `pass` with runs of feed moves simplified.
+///
+/// A run of two or more feed moves whose points all lie within `tolerance`
+/// of the straight line from the run's start to its end, advancing along it,
+/// becomes one feed move. A run of at least [`MIN_ARC_MOVES`] feed moves at a
+/// constant Z whose points lie within `tolerance` of a common circle, turning
+/// steadily one way by less than a full turn, becomes one arc move; each
+/// original segment must also stay within `tolerance` of the arc. Where both
+/// fit, the longer run wins, with ties going to the line.
+///
+/// Only feed moves without a tool orientation are merged, so fitting never
+/// crosses a change of move kind and never touches 5-axis moves. Start and
+/// end points of every replaced run are kept exactly.
+pub fn fit_arcs(pass: &Pass, tolerance: f64) -> Pass {
+    let cuts = &pass.cuts;
+    let mut fitted = Vec::with_capacity(cuts.len());
+    let mut i = 0;
+
+    while i < cuts.len() {
+        // The tool position before `cuts[i]`, known once one cut has passed.
+        let start = (i > 0).then(|| landing(&cuts[i - 1]));
+        let run = cuts[i..].iter().take_while(|c| mergeable(c)).count();
+
+        if let (Some(start), true) = (start, run >= 2) {
+            let points: Vec<&Vec3> = std::iter::once(start)
+                .chain(cuts[i..i + run].iter().map(|c| &c.position))
+                .collect();
+            let line = longest_line(&points, tolerance);
+            let arc = longest_arc(&points, tolerance);
+
+            match arc {
+                Some((moves, center, clockwise)) if moves > line => {
+                    fitted.push(CutPoint {
+                        position: start.clone(),
+                        move_kind: MoveKind::Arc {
+                            center,
+                            end: points[moves].clone(),
+                            clockwise,
+                        },
+                        tool_orientation: None,
+                    });
+                    i += moves;
+                    continue;
+                }
+                _ if line >= 2 => {
+                    fitted.push(cuts[i + line - 1].clone());
+                    i += line;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        fitted.push(cuts[i].clone());
+        i += 1;
+    }
+
+    Pass {
+        kind: pass.kind.clone(),
+        cuts: fitted,
+        feed_rate: pass.feed_rate,
+    }
+}
+
+/// Whether `cut` may be merged into a fitted run.
+fn mergeable(cut: &CutPoint) -> bool {
+    cut.move_kind == MoveKind::Feed && cut.tool_orientation.is_none()
+}
+
+/// Where the tool is after executing `cut`.
+fn landing(cut: &CutPoint) -> &Vec3 {
+    match &cut.move_kind {
+        MoveKind::Arc { end, .. } | MoveKind::Helix { end, .. } => end,
+        _ => &cut.position,
+    }
+}
+
+/// Number of moves, from the start of `points`, that one straight feed can
+/// replace. `points[0]` is the start position.
+fn longest_line(points: &[&Vec3], tolerance: f64) -> usize {
+    (2..points.len())
+        .rev()
+        .find(|&last| is_line(&points[..=last], tolerance))
+        .unwrap_or(1)
+}
+
+fn is_line(points: &[&Vec3], tolerance: f64) -> bool {
+    let (start, end) = (points[0], points[points.len() - 1]);
+    let axis = sub(end, start);
+    let length = norm(&axis);
+    if length <= tolerance {
+        return false;
+    }
+    let dir = scale(&axis, 1.0 / length);
+
+    let mut along = 0.0;
+    for p in &points[1..] {
+        let offset = sub(p, start);
+        let t = dot(&offset, &dir);
+        if t <= along || norm(&sub(&offset, &scale(&dir, t))) > tolerance {
+            return false;
+        }
+        along = t;
+    }
+    true
+}
+
+/// Longest arc, from the start of `points`, covering at least
+/// [`MIN_ARC_MOVES`] moves: `(moves, center, clockwise)`.
+fn longest_arc(points: &[&Vec3], tolerance: f64) -> Option<(usize, Vec3, bool)> {
+    (MIN_ARC_MOVES..points.len())
+        .rev()
+        .find_map(|last| fit_circle(&points[..=last], tolerance).map(|(c, cw)| (last, c, cw)))
+}
+
+/// Fits a circle through the first, middle and last of `points` and checks
+/// that the rest follow it (see [`fit_arcs`]).
+fn fit_circle(points: &[&Vec3], tolerance: f64) -> Option<(Vec3, bool)> {
+    let z = points[0].z;
+    if points.iter().any(|p| (p.z - z).abs() > tolerance) {
+        return None;
+    }
+
+    let (a, b, c) = (
+        points[0],
+        points[points.len() / 2],
+        points[points.len() - 1],
+    );
+    let center = circumcenter(a, b, c)?;
+    let radius = (a.x - center.x).hypot(a.y - center.y);
+
+    let angle = |p: &Vec3| (p.y - center.y).atan2(p.x - center.x);
+    let mut sweep = 0.0;
+    for pair in points.windows(2) {
+        let (p, q) = (pair[0], pair[1]);
+        if ((q.x - center.x).hypot(q.y - center.y) - radius).abs() > tolerance {
+            return None;
+        }
+        let step = (angle(q) - angle(p) + TAU / 2.0).rem_euclid(TAU) - TAU / 2.0;
+        if step == 0.0 || (sweep != 0.0 && step.signum() != f64::signum(sweep)) {
+            return None;
+        }
+        // Sagitta: how far the arc bulges away from the original segment.
+        let half_chord = (q.x - p.x).hypot(q.y - p.y) / 2.0;
+        if radius - (radius * radius - half_chord * half_chord).max(0.0).sqrt() > tolerance {
+            return None;
+        }
+        sweep += step;
+    }
+    if sweep.abs() >= TAU {
+        return None;
+    }
+
+    Some((Vec3 { z, ..center }, sweep < 0.0))
+}
+
+/// Center of the XY circle through `a`, `b` and `c`; `None` if collinear.
+fn circumcenter(a: &Vec3, b: &Vec3, c: &Vec3) -> Option<Vec3> {
+    let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    if d.abs() < 1e-12 {
+        return None;
+    }
+    let sq = |p: &Vec3| p.x * p.x + p.y * p.y;
+    Some(Vec3 {
+        x: (sq(a) * (b.y - c.y) + sq(b) * (c.y - a.y) + sq(c) * (a.y - b.y)) / d,
+        y: (sq(a) * (c.x - b.x) + sq(b) * (a.x - c.x) + sq(c) * (b.x - a.x)) / d,
+        z: a.z,
+    })
+}
+
+fn sub(a: &Vec3, b: &Vec3) -> Vec3 {
+    Vec3 {
+        x: a.x - b.x,
+        y: a.y - b.y,
+        z: a.z - b.z,
+    }
+}
+
+fn scale(v: &Vec3, k: f64) -> Vec3 {
+    Vec3 {
+        x: v.x * k,
+        y: v.y * k,
+        z: v.z * k,
+    }
+}
+
+fn dot(a: &Vec3, b: &Vec3) -> f64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+fn norm(v: &Vec3) -> f64 {
+    dot(v, v).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toolpath::types::PassKind;
+
+    fn v(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    fn pass(points: &[Vec3]) -> Pass {
+        let cuts = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| CutPoint {
+                position: p.clone(),
+                move_kind: if i == 0 {
+                    MoveKind::Rapid
+                } else {
+                    MoveKind::Feed
+                },
+                tool_orientation: None,
+            })
+            .collect();
+        Pass {
+            kind: PassKind::Cutting,
+            cuts,
+            feed_rate: None,
+        }
+    }
+
+    #[test]
+    fn quarter_circle_polyline_fits_one_arc() {
+        let points: Vec<Vec3> = (0..=10)
+            .map(|i| {
+                let a = std::f64::consts::FRAC_PI_2 * i as f64 / 10.0;
+                v(10.0 * a.cos(), 10.0 * a.sin(), -1.0)
+            })
+            .collect();
+        let fitted = fit_arcs(&pass(&points), 0.05);
+
+        assert_eq!(fitted.cuts.len(), 2, "{:?}", fitted.cuts);
+        assert_eq!(fitted.cuts[0], pass(&points).cuts[0]);
+        let arc = &fitted.cuts[1];
+        assert_eq!(arc.position, points[0]);
+        match &arc.move_kind {
+            MoveKind::Arc {
+                center,
+                end,
+                clockwise,
+            } => {
+                assert_eq!(end, &points[10], "end point must be exact");
+                assert!(center.x.abs() < 1e-9 && center.y.abs() < 1e-9);
+                assert_eq!(center.z, -1.0);
+                assert!(!clockwise);
+            }
+            other => panic!("expected arc, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn zig_zag_fits_nothing() {
+        let points: Vec<Vec3> = (0..8)
+            .map(|i| v(i as f64, if i % 2 == 0 { 0.0 } else { 1.0 }, 0.0))
+            .collect();
+        let original = pass(&points);
+        assert_eq!(fit_arcs(&original, 0.01), original);
+    }
+
+    #[test]
+    fn collinear_run_collapses_to_one_feed() {
+        let points: Vec<Vec3> = (0..6).map(|i| v(i as f64 * 2.0, 1.0, -2.0)).collect();
+        let fitted = fit_arcs(&pass(&points), 0.001);
+
+        assert_eq!(fitted.cuts.len(), 2);
+        assert_eq!(fitted.cuts[1].position, points[5]);
+        assert_eq!(fitted.cuts[1].move_kind, MoveKind::Feed);
+    }
+
+    #[test]
+    fn fitting_stops_at_move_kind_change() {
+        let points: Vec<Vec3> = (0..6).map(|i| v(i as f64, 0.0, 0.0)).collect();
+        let mut original = pass(&points);
+        original.cuts[3].move_kind = MoveKind::Rapid;
+        let fitted = fit_arcs(&original, 0.001);
+
+        // Feeds 1–2 merge, the rapid stays, feeds 4–5 merge.
+        let kinds: Vec<&MoveKind> = fitted.cuts.iter().map(|c| &c.move_kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &MoveKind::Rapid,
+                &MoveKind::Feed,
+                &MoveKind::Rapid,
+                &MoveKind::Feed
+            ]
+        );
+        assert_eq!(fitted.cuts[1].position, points[2]);
+        assert_eq!(fitted.cuts[3].position, points[5]);
+    }
+}
@@ -0,0 +1,216 @@
+//! Contour checks for toolpath passes.
+//!
+//! Offsetting a profile only makes sense for a contour that is closed and
+//! simple. [`is_closed`] and [`self_intersects`] let callers check a pass
+//! before trusting it, and warn the user when it is neither.
+//!
+//! Both work on the polyline from the pass's first position through the
+//! point each move lands on; arc and helix moves are taken as their chords.
+
+use crate::models::Vec3;
+
+use super::types::{CutPoint, MoveKind, Pass};
+
+/// Returns `true` if the point the last move of `pass` lands on lies within
+/// `tol` of the first position. An empty pass is not closed.
+pub fn is_closed(pass: &Pass, tol: f64) -> bool {
+    outline_is_closed(&outline(pass), tol)
+}
+
+fn outline_is_closed(points: &[&Vec3], tol: f64) -> bool {
+    match (points.first(), points.last()) {
+        (Some(a), Some(b)) if points.len() > 2 => {
+            ((b.x - a.x).powi(2) + (b.y - a.y).powi(2) + (b.z - a.z).powi(2)).sqrt() <= tol
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if any two non-adjacent segments of `pass` cross or touch
+/// in XY.
+///
+/// Segments are swept in order of their lowest X, so each is only tested
+/// against the segments whose X extents overlap it. Neighbouring segments
+/// share an end point and are never tested against each other — nor are the
+/// first and last segments of a closed pass.
+pub fn self_intersects(pass: &Pass) -> bool {
+    let points = outline(pass);
+    if points.len() < 4 {
+        return false;
+    }
+    let segments: Vec<(&Vec3, &Vec3)> = points.windows(2).map(|w| (w[0], w[1])).collect();
+    let last = segments.len() - 1;
+    let closed = outline_is_closed(&points, 0.0);
+
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by(|&i, &j| min_x(segments[i]).total_cmp(&min_x(segments[j])));
+
+    for (k, &i) in order.iter().enumerate() {
+        let reach = max_x(segments[i]);
+        for &j in order[k + 1..]
+            .iter()
+            .take_while(|&&j| min_x(segments[j]) <= reach)
+        {
+            let (lo, hi) = (i.min(j), i.max(j));
+            let adjacent = hi - lo == 1 || (closed && lo == 0 && hi == last);
+            if !adjacent && segments_touch(segments[i], segments[j]) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// The first cut's position, then where each move lands. A straight first
+/// move lands on its own position, so it adds no second point.
+fn outline(pass: &Pass) -> Vec<&Vec3> {
+    let Some(first) = pass.cuts.first() else {
+        return Vec::new();
+    };
+    let mut points = vec![&first.position];
+    points.extend(
+        pass.cuts
+            .iter()
+            .enumerate()
+            .filter(|(i, cut)| *i > 0 || turns(cut))
+            .map(|(_, cut)| landing(cut)),
+    );
+    points
+}
+
+fn turns(cut: &CutPoint) -> bool {
+    matches!(cut.move_kind, MoveKind::Arc { .. } | MoveKind::Helix { .. })
+}
+
+/// Where the tool is after executing `cut`.
+fn landing(cut: &CutPoint) -> &Vec3 {
+    match &cut.move_kind {
+        MoveKind::Arc { end, .. } | MoveKind::Helix { end, .. } => end,
+        _ => &cut.position,
+    }
+}
+
+fn min_x((a, b): (&Vec3, &Vec3)) -> f64 {
+    a.x.min(b.x)
+}
+
+fn max_x((a, b): (&Vec3, &Vec3)) -> f64 {
+    a.x.max(b.x)
+}
+
+/// Sign of the XY cross product `(b − a) × (c − a)`: positive when `c` lies
+/// to the left of `a → b`, zero when the three points are collinear.
+fn orientation(a: &Vec3, b: &Vec3, c: &Vec3) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Returns `true` if collinear point `p` lies within the XY bounding box of
+/// `a → b`.
+fn within(a: &Vec3, b: &Vec3, p: &Vec3) -> bool {
+    p.x >= a.x.min(b.x) && p.x <= a.x.max(b.x) && p.y >= a.y.min(b.y) && p.y <= a.y.max(b.y)
+}
+
+/// Returns `true` if segments `p` and `q` share at least one XY point.
+fn segments_touch((p1, p2): (&Vec3, &Vec3), (q1, q2): (&Vec3, &Vec3)) -> bool {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+    (d1 == 0.0 && within(q1, q2, p1))
+        || (d2 == 0.0 && within(q1, q2, p2))
+        || (d3 == 0.0 && within(p1, p2, q1))
+        || (d4 == 0.0 && within(p1, p2, q2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toolpath::types::PassKind;
+
+    fn pass_through(points: &[(f64, f64)]) -> Pass {
+        Pass {
+            kind: PassKind::Cutting,
+            cuts: points
+                .iter()
+                .map(|&(x, y)| CutPoint {
+                    position: Vec3 { x, y, z: -1.0 },
+                    move_kind: MoveKind::Feed,
+                    tool_orientation: None,
+                })
+                .collect(),
+            feed_rate: None,
+        }
+    }
+
+    #[test]
+    fn square_is_closed_and_simple() {
+        let square = pass_through(&[
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]);
+        assert!(is_closed(&square, 1e-6));
+        assert!(!self_intersects(&square));
+    }
+
+    #[test]
+    fn figure_eight_self_intersects() {
+        let eight = pass_through(&[
+            (0.0, 0.0),
+            (10.0, 10.0),
+            (10.0, 0.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]);
+        assert!(is_closed(&eight, 1e-6));
+        assert!(self_intersects(&eight));
+    }
+
+    #[test]
+    fn open_polyline_is_not_closed() {
+        let open = pass_through(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+        assert!(!is_closed(&open, 1e-6));
+        assert!(is_closed(&open, 10.0));
+        assert!(!self_intersects(&open));
+    }
+
+    #[test]
+    fn pass_closed_by_an_arc_end_is_closed() {
+        let mut pass = pass_through(&[(0.0, 0.0), (10.0, 0.0)]);
+        pass.cuts.push(CutPoint {
+            position: Vec3 {
+                x: 10.0,
+                y: 0.0,
+                z: -1.0,
+            },
+            move_kind: MoveKind::Arc {
+                center: Vec3 {
+                    x: 5.0,
+                    y: 0.0,
+                    z: -1.0,
+                },
+                end: Vec3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: -1.0,
+                },
+                clockwise: false,
+            },
+            tool_orientation: None,
+        });
+        assert!(is_closed(&pass, 1e-6));
+        assert!(!self_intersects(&pass));
+    }
+
+    #[test]
+    fn path_touching_itself_at_a_vertex_self_intersects() {
+        let touching = pass_through(&[(0.0, 0.0), (10.0, 0.0), (5.0, 5.0), (5.0, 0.0)]);
+        assert!(self_intersects(&touching));
+    }
+}
@@ -0,0 +1,479 @@
+//! Lead-in pass construction.
+//!
+//! A lead-in brings the tool onto the cut path gradually instead of plunging
+//! sideways into the wall at the start point, which leaves a witness mark.
+//! [`lead_in_pass`] turns an operation's [`LeadType`] into a
+//! [`PassKind::LeadIn`] pass that ends exactly at the first cut point, and
+//! [`insert_lead_in`] places it ahead of a toolpath's first cutting pass.
+//! [`add_tangent_arc_lead`] instead splices a quarter-arc lead into an
+//! existing pass, on either end.
+
+use crate::models::operation::LeadType;
+use crate::models::Vec3;
+
+use super::types::{CutPoint, MoveKind, Pass, PassKind};
+
+/// Builds the lead-in pass that arrives at `start` travelling along
+/// `direction` (the XY cut direction at `start`; need not be normalised).
+///
+/// `from_left` selects which side of the path the lead approaches from —
+/// the tool's compensation side, so the entry stays clear of the finished
+/// wall. Arc leads on the left run counter-clockwise (G3), on the right
+/// clockwise (G2).
+///
+/// The pass starts with a feed move to the lead start point at `start.z`.
+/// Returns `None` for [`LeadType::None`] or a zero-length direction.
+pub fn lead_in_pass(
+    lead: &LeadType,
+    start: Vec3,
+    direction: Vec3,
+    from_left: bool,
+) -> Option<Pass> {
+    let len = direction.x.hypot(direction.y);
+    if len == 0.0 {
+        return None;
+    }
+    let (dx, dy) = (direction.x / len, direction.y / len);
+    let at = |x: f64, y: f64| Vec3 { x, y, z: start.z };
+    let feed_to = |position: Vec3| CutPoint {
+        position,
+        move_kind: MoveKind::Feed,
+        tool_orientation: None,
+    };
+
+    let cuts = match *lead {
+        LeadType::None => return None,
+        LeadType::Line { length } => {
+            let entry = at(start.x - dx * length, start.y - dy * length);
+            vec![feed_to(entry), feed_to(start.clone())]
+        }
+        LeadType::Arc { radius } => {
+            // Unit normal pointing to the chosen side of the path.
+            let (nx, ny) = if from_left { (-dy, dx) } else { (dy, -dx) };
+            let center = at(start.x + nx * radius, start.y + ny * radius);
+            let entry = at(center.x - dx * radius, center.y - dy * radius);
+            vec![
+                feed_to(entry.clone()),
+                // Arc cut points carry the arc's start; `end` is where it lands.
+                CutPoint {
+                    position: entry,
+                    move_kind: MoveKind::Arc {
+                        center,
+                        end: start,
+                        clockwise: !from_left,
+                    },
+                    tool_orientation: None,
+                },
+            ]
+        }
+    };
+
+    Some(Pass {
+        kind: PassKind::LeadIn,
+        cuts,
+        feed_rate: None,
+    })
+}
+
+/// Inserts the lead-in for `lead` ahead of the first cutting pass in
+/// `passes`, arriving at its first cut point along its starting direction.
+///
+/// `passes` is left unchanged for [`LeadType::None`], when there is no
+/// cutting pass, or when that pass has no XY direction of travel.
+pub fn insert_lead_in(passes: &mut Vec<Pass>, lead: &LeadType, from_left: bool) {
+    let Some(i) = passes
+        .iter()
+        .position(|p| p.kind == PassKind::Cutting && !p.cuts.is_empty())
+    else {
+        return;
+    };
+    let Some((start, (dx, dy))) = start_tangent(&passes[i].cuts) else {
+        return;
+    };
+    let direction = Vec3 {
+        x: dx,
+        y: dy,
+        z: 0.0,
+    };
+    if let Some(pass) = lead_in_pass(lead, start, direction, from_left) {
+        passes.insert(i, pass);
+    }
+}
+
+/// Adds a tangent quarter-arc of `radius` to the start of `pass` or, with
+/// `lead_out`, to its end, so the tool eases onto (or off) the contour
+/// instead of plunging into the wall.
+///
+/// The arc turns in the same direction as the contour: clockwise contours get
+/// a G2 lead, counter-clockwise ones a G3 lead. Direction comes from the
+/// signed area of the pass in XY; an open pass with no area uses its first arc
+/// move, or counter-clockwise if it has none.
+///
+/// A lead-in replaces the move that reached the first cut point with the same
+/// move to the arc's entry point, followed by the arc onto the contour. A
+/// lead-out appends the arc leaving the last point. Passes without a
+/// well-defined tangent at that end (fewer than two distinct XY points) are
+/// left unchanged, as is every pass when `radius` is not positive.
+pub fn add_tangent_arc_lead(pass: &mut Pass, radius: f64, lead_out: bool) {
+    if radius <= 0.0 || pass.cuts.is_empty() {
+        return;
+    }
+    let clockwise = is_clockwise(&pass.cuts);
+    let tangent = if lead_out {
+        end_tangent(&pass.cuts)
+    } else {
+        start_tangent(&pass.cuts)
+    };
+    let Some((point, (dx, dy))) = tangent else {
+        return;
+    };
+
+    // Unit normal towards the arc center: left of travel for CCW, right for CW.
+    let (nx, ny) = if clockwise { (dy, -dx) } else { (-dy, dx) };
+    let at = |x: f64, y: f64| Vec3 { x, y, z: point.z };
+    let center = at(point.x + nx * radius, point.y + ny * radius);
+
+    if lead_out {
+        let exit = at(center.x + dx * radius, center.y + dy * radius);
+        pass.cuts.push(CutPoint {
+            position: point,
+            move_kind: MoveKind::Arc {
+                center,
+                end: exit,
+                clockwise,
+            },
+            tool_orientation: None,
+        });
+    } else {
+        let entry = at(center.x - dx * radius, center.y - dy * radius);
+        let arc = CutPoint {
+            position: entry.clone(),
+            move_kind: MoveKind::Arc {
+                center,
+                end: point,
+                clockwise,
+            },
+            tool_orientation: None,
+        };
+        let first = &mut pass.cuts[0];
+        if matches!(
+            first.move_kind,
+            MoveKind::Arc { .. } | MoveKind::Helix { .. }
+        ) {
+            // The pass starts mid-contour with an arc; feed out to the entry.
+            let approach = CutPoint {
+                position: entry,
+                move_kind: MoveKind::Feed,
+                tool_orientation: None,
+            };
+            pass.cuts.splice(0..0, [approach, arc]);
+        } else {
+            first.position = entry;
+            pass.cuts.insert(1, arc);
+        }
+    }
+}
+
+/// XY point where the tool is after reaching `cut`.
+fn landing(cut: &CutPoint) -> &Vec3 {
+    match &cut.move_kind {
+        MoveKind::Arc { end, .. } | MoveKind::Helix { end, .. } => end,
+        _ => &cut.position,
+    }
+}
+
+/// Center and direction of an arc or helix move; `None` for straight moves.
+fn turning(cut: &CutPoint) -> Option<(&Vec3, bool)> {
+    match &cut.move_kind {
+        MoveKind::Arc {
+            center, clockwise, ..
+        }
+        | MoveKind::Helix {
+            center, clockwise, ..
+        } => Some((center, *clockwise)),
+        _ => None,
+    }
+}
+
+/// Unit XY tangent of the arc around `center` at `point`.
+fn arc_tangent(point: &Vec3, center: &Vec3, clockwise: bool) -> Option<(f64, f64)> {
+    let (rx, ry) = (point.x - center.x, point.y - center.y);
+    if clockwise {
+        unit_xy(ry, -rx)
+    } else {
+        unit_xy(-ry, rx)
+    }
+}
+
+fn unit_xy(x: f64, y: f64) -> Option<(f64, f64)> {
+    let len = x.hypot(y);
+    (len > 1e-12).then(|| (x / len, y / len))
+}
+
+/// First cut point and the unit XY direction of travel leaving it.
+fn start_tangent(cuts: &[CutPoint]) -> Option<(Vec3, (f64, f64))> {
+    if let Some((center, clockwise)) = turning(&cuts[0]) {
+        return Some((
+            cuts[0].position.clone(),
+            arc_tangent(&cuts[0].position, center, clockwise)?,
+        ));
+    }
+    let start = cuts[0].position.clone();
+    for cut in &cuts[1..] {
+        if let Some((center, clockwise)) = turning(cut) {
+            return Some((start.clone(), arc_tangent(&start, center, clockwise)?));
+        }
+        if let Some(dir) = unit_xy(cut.position.x - start.x, cut.position.y - start.y) {
+            return Some((start, dir));
+        }
+    }
+    None
+}
+
+/// Last tool position and the unit XY direction of travel arriving there.
+fn end_tangent(cuts: &[CutPoint]) -> Option<(Vec3, (f64, f64))> {
+    let last = cuts.last()?;
+    let end = landing(last).clone();
+    if let Some((center, clockwise)) = turning(last) {
+        return Some((end.clone(), arc_tangent(&end, center, clockwise)?));
+    }
+    for cut in cuts[..cuts.len() - 1].iter().rev() {
+        let from = landing(cut);
+        if let Some(dir) = unit_xy(end.x - from.x, end.y - from.y) {
+            return Some((end, dir));
+        }
+    }
+    None
+}
+
+/// Whether the pass runs clockwise in XY (see [`add_tangent_arc_lead`]).
+fn is_clockwise(cuts: &[CutPoint]) -> bool {
+    let points: Vec<&Vec3> = cuts.iter().map(landing).collect();
+    let twice_area: f64 = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum();
+    if twice_area.abs() > 1e-9 {
+        return twice_area < 0.0;
+    }
+    cuts.iter()
+        .find_map(|cut| turning(cut).map(|(_, clockwise)| clockwise))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    #[test]
+    fn no_lead_produces_no_pass() {
+        assert!(lead_in_pass(&LeadType::None, v(0.0, 0.0, -2.0), v(1.0, 0.0, 0.0), true).is_none());
+    }
+
+    #[test]
+    fn line_lead_starts_behind_the_start_point() {
+        let pass = lead_in_pass(
+            &LeadType::Line { length: 5.0 },
+            v(10.0, 0.0, -2.0),
+            v(2.0, 0.0, 0.0),
+            true,
+        )
+        .expect("line lead");
+        assert_eq!(pass.kind, PassKind::LeadIn);
+        assert_eq!(pass.cuts[0].position, v(5.0, 0.0, -2.0));
+        assert_eq!(pass.cuts[1].position, v(10.0, 0.0, -2.0));
+        assert_eq!(pass.cuts[1].move_kind, MoveKind::Feed);
+    }
+
+    #[test]
+    fn arc_lead_ends_with_tangent_arc_move() {
+        let pass = lead_in_pass(
+            &LeadType::Arc { radius: 3.0 },
+            v(10.0, 0.0, -2.0),
+            v(1.0, 0.0, 0.0),
+            true,
+        )
+        .expect("arc lead");
+        assert_eq!(pass.kind, PassKind::LeadIn);
+        assert_eq!(pass.cuts[0].position, v(7.0, 3.0, -2.0));
+        assert_eq!(pass.cuts[1].position, v(7.0, 3.0, -2.0));
+        assert_eq!(
+            pass.cuts[1].move_kind,
+            MoveKind::Arc {
+                center: v(10.0, 3.0, -2.0),
+                end: v(10.0, 0.0, -2.0),
+                clockwise: false,
+            }
+        );
+    }
+
+    #[test]
+    fn lead_in_is_inserted_before_first_cutting_pass() {
+        let feed = |x: f64| CutPoint {
+            position: v(x, 0.0, -2.0),
+            move_kind: MoveKind::Feed,
+            tool_orientation: None,
+        };
+        let pass = |kind: PassKind, cuts: Vec<CutPoint>| Pass {
+            kind,
+            cuts,
+            feed_rate: None,
+        };
+        let mut passes = vec![
+            pass(PassKind::Linking, vec![feed(0.0)]),
+            pass(PassKind::Cutting, vec![feed(10.0), feed(20.0)]),
+            pass(PassKind::Cutting, vec![feed(20.0), feed(10.0)]),
+        ];
+
+        insert_lead_in(&mut passes, &LeadType::Line { length: 4.0 }, true);
+
+        let kinds: Vec<&PassKind> = passes.iter().map(|p| &p.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &PassKind::Linking,
+                &PassKind::LeadIn,
+                &PassKind::Cutting,
+                &PassKind::Cutting
+            ]
+        );
+        assert_eq!(passes[1].cuts[0].position, v(6.0, 0.0, -2.0));
+        assert_eq!(passes[1].cuts[1].position, v(10.0, 0.0, -2.0));
+    }
+
+    #[test]
+    fn no_lead_leaves_passes_unchanged() {
+        let mut passes = vec![Pass {
+            kind: PassKind::Cutting,
+            cuts: vec![
+                cut(0.0, 0.0, MoveKind::Feed),
+                cut(10.0, 0.0, MoveKind::Feed),
+            ],
+            feed_rate: None,
+        }];
+        let before = passes.clone();
+        insert_lead_in(&mut passes, &LeadType::None, true);
+        assert_eq!(passes, before);
+    }
+
+    fn cut(x: f64, y: f64, move_kind: MoveKind) -> CutPoint {
+        CutPoint {
+            position: v(x, y, -2.0),
+            move_kind,
+            tool_orientation: None,
+        }
+    }
+
+    /// A 20 mm square traversed counter-clockwise from (0, 0), or clockwise
+    /// when `clockwise` is set.
+    fn square(clockwise: bool) -> Pass {
+        let mut corners = [
+            (0.0, 0.0),
+            (20.0, 0.0),
+            (20.0, 20.0),
+            (0.0, 20.0),
+            (0.0, 0.0),
+        ];
+        if clockwise {
+            corners.reverse();
+        }
+        let cuts = corners
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, y))| {
+                let kind = if i == 0 {
+                    MoveKind::Rapid
+                } else {
+                    MoveKind::Feed
+                };
+                cut(x, y, kind)
+            })
+            .collect();
+        Pass {
+            kind: PassKind::Cutting,
+            cuts,
+            feed_rate: None,
+        }
+    }
+
+    fn arc_parts(cut: &CutPoint) -> (Vec3, Vec3, bool) {
+        match &cut.move_kind {
+            MoveKind::Arc {
+                center,
+                end,
+                clockwise,
+            } => (center.clone(), end.clone(), *clockwise),
+            other => panic!("expected arc, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lead_in_arc_is_tangent_to_first_segment() {
+        let mut pass = square(false);
+        add_tangent_arc_lead(&mut pass, 3.0, false);
+
+        // First segment runs +X from the origin; CCW contour → center on the left.
+        let (center, end, clockwise) = arc_parts(&pass.cuts[1]);
+        assert_eq!(end, v(0.0, 0.0, -2.0));
+        assert_eq!(center, v(0.0, 3.0, -2.0));
+        assert!(!clockwise);
+        // Center offset is perpendicular to the segment direction (1, 0).
+        let (ox, oy) = (center.x - end.x, center.y - end.y);
+        assert!((ox * 1.0 + oy * 0.0).abs() < 1e-12);
+        assert!((ox.hypot(oy) - 3.0).abs() < 1e-12);
+
+        // The rapid now goes to the arc's entry point instead of the contour.
+        assert_eq!(pass.cuts[0].move_kind, MoveKind::Rapid);
+        assert_eq!(pass.cuts[0].position, v(-3.0, 3.0, -2.0));
+        assert_eq!(pass.cuts[1].position, pass.cuts[0].position);
+        assert_eq!(pass.cuts[2].position, v(20.0, 0.0, -2.0));
+    }
+
+    #[test]
+    fn clockwise_contour_gets_clockwise_lead() {
+        let mut pass = square(true);
+        add_tangent_arc_lead(&mut pass, 2.0, false);
+
+        // First segment runs +Y from the origin; CW contour → center on the right.
+        let (center, _, clockwise) = arc_parts(&pass.cuts[1]);
+        assert!(clockwise);
+        assert_eq!(center, v(2.0, 0.0, -2.0));
+    }
+
+    #[test]
+    fn lead_out_arc_is_tangent_to_last_segment() {
+        let mut pass = square(false);
+        let before = pass.cuts.len();
+        add_tangent_arc_lead(&mut pass, 3.0, true);
+
+        assert_eq!(pass.cuts.len(), before + 1);
+        let last = pass.cuts.last().unwrap();
+        // Last segment runs -Y into the origin; left of that is +X.
+        let (center, end, clockwise) = arc_parts(last);
+        assert_eq!(last.position, v(0.0, 0.0, -2.0));
+        assert_eq!(center, v(3.0, 0.0, -2.0));
+        assert_eq!(end, v(3.0, -3.0, -2.0));
+        assert!(!clockwise);
+    }
+
+    #[test]
+    fn degenerate_pass_is_unchanged() {
+        let mut pass = Pass {
+            kind: PassKind::Cutting,
+            cuts: vec![
+                cut(1.0, 1.0, MoveKind::Rapid),
+                cut(1.0, 1.0, MoveKind::Feed),
+            ],
+            feed_rate: None,
+        };
+        let before = pass.clone();
+        add_tangent_arc_lead(&mut pass, 3.0, false);
+        add_tangent_arc_lead(&mut pass, 3.0, true);
+        assert_eq!(pass, before);
+    }
+}
@@ -1,2 +1,7 @@
+pub mod fit;
+pub mod geom;
+pub mod lead;
+pub mod tabs;
+pub mod transform;
 pub mod types;
 pub use types::Toolpath;
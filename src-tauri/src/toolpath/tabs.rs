@@ -0,0 +1,263 @@
+//! Holding tabs for profile passes.
+//!
+//! A profile that cuts a part free of the stock lets the part shift or fly
+//! out on the last pass. [`add_tabs`] leaves short bridges of material by
+//! lifting the tool over evenly spaced spans of a closed contour: the pass is
+//! split at each span's ends, climbs vertically to the tab top, crosses the
+//! span there, and drops back to the contour's own Z.
+
+use crate::models::operation::TabParams;
+use crate::models::Vec3;
+
+use super::types::{CutPoint, MoveKind, Pass};
+
+/// Distance under which two contour points are treated as the same point.
+const EPSILON: f64 = 1e-6;
+
+/// Raises `pass` over `tabs.count` tabs of length `tabs.width`, their tops
+/// `tabs.height` above `floor_z` (the operation's final depth).
+///
+/// `pass` must be a closed contour: a move to the start point followed by
+/// straight feed moves that return to it. Tab centers sit at
+/// `(i + 0.5) × perimeter / count` along the contour, measured in XY from the
+/// start. Cut points inside a tab are lifted to the tab top, never lowered;
+/// passes that already run at or above it are left as they are. After each tab
+/// the tool re-enters at the contour's Z at the same XY.
+///
+/// Open passes, passes with arc, helix, dwell or probe moves, and tab
+/// settings that are not positive or do not fit the perimeter leave `pass`
+/// unchanged.
+pub fn add_tabs(pass: &mut Pass, tabs: &TabParams, floor_z: f64) {
+    let cuts = &pass.cuts;
+    let (Some(first), Some(last)) = (cuts.first(), cuts.last()) else {
+        return;
+    };
+    let straight = cuts[1..].iter().all(|c| c.move_kind == MoveKind::Feed);
+    if !straight || cuts.len() < 3 || xy_distance(&first.position, &last.position) > EPSILON {
+        return;
+    }
+    if tabs.count == 0 || tabs.width <= 0.0 || tabs.height <= 0.0 {
+        return;
+    }
+
+    let perimeter: f64 = cuts
+        .windows(2)
+        .map(|w| xy_distance(&w[0].position, &w[1].position))
+        .sum();
+    let spacing = perimeter / f64::from(tabs.count);
+    if tabs.width >= spacing {
+        return;
+    }
+    let spans: Vec<(f64, f64)> = (0..tabs.count)
+        .map(|i| {
+            let center = (f64::from(i) + 0.5) * spacing;
+            (center - tabs.width / 2.0, center + tabs.width / 2.0)
+        })
+        .collect();
+    let tab_z = floor_z + tabs.height;
+    if cuts.iter().all(|c| c.position.z >= tab_z) {
+        return;
+    }
+    let lift = |p: &Vec3| Vec3 {
+        z: p.z.max(tab_z),
+        ..p.clone()
+    };
+
+    let mut out = vec![first.clone()];
+    let mut travelled = 0.0;
+    for w in cuts.windows(2) {
+        let (a, b) = (&w[0].position, &w[1].position);
+        let length = xy_distance(a, b);
+
+        // Tab edges crossed on this segment, in travel order: `true` where a
+        // tab starts, `false` where it ends.
+        let mut edges: Vec<(f64, bool)> = spans
+            .iter()
+            .flat_map(|&(start, end)| [(start, true), (end, false)])
+            .filter(|&(at, _)| at >= travelled && at < travelled + length)
+            .collect();
+        edges.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+        for (at, entering) in edges {
+            let point = lerp(a, b, (at - travelled) / length);
+            let (from, to) = if entering {
+                (point.clone(), lift(&point))
+            } else {
+                (lift(&point), point)
+            };
+            push_feed(&mut out, from);
+            push_feed(&mut out, to);
+        }
+
+        travelled += length;
+        let in_tab = spans
+            .iter()
+            .any(|&(start, end)| travelled > start && travelled <= end);
+        push_feed(&mut out, if in_tab { lift(b) } else { b.clone() });
+    }
+
+    pass.cuts = out;
+}
+
+/// Appends a feed move to `position` unless the pass is already there.
+fn push_feed(cuts: &mut Vec<CutPoint>, position: Vec3) {
+    if cuts
+        .last()
+        .is_some_and(|c| distance(&c.position, &position) <= EPSILON)
+    {
+        return;
+    }
+    cuts.push(CutPoint {
+        position,
+        move_kind: MoveKind::Feed,
+        tool_orientation: None,
+    });
+}
+
+fn lerp(a: &Vec3, b: &Vec3, t: f64) -> Vec3 {
+    Vec3 {
+        x: a.x + (b.x - a.x) * t,
+        y: a.y + (b.y - a.y) * t,
+        z: a.z + (b.z - a.z) * t,
+    }
+}
+
+fn xy_distance(a: &Vec3, b: &Vec3) -> f64 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+fn distance(a: &Vec3, b: &Vec3) -> f64 {
+    ((b.x - a.x).powi(2) + (b.y - a.y).powi(2) + (b.z - a.z).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toolpath::types::PassKind;
+
+    fn v(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    /// A 40 × 40 square at `z`, entered with a rapid to the origin.
+    fn square_pass(z: f64) -> Pass {
+        let corners = [
+            v(0.0, 0.0, z),
+            v(40.0, 0.0, z),
+            v(40.0, 40.0, z),
+            v(0.0, 40.0, z),
+            v(0.0, 0.0, z),
+        ];
+        Pass {
+            kind: PassKind::Cutting,
+            cuts: corners
+                .into_iter()
+                .enumerate()
+                .map(|(i, position)| CutPoint {
+                    position,
+                    move_kind: if i == 0 {
+                        MoveKind::Rapid
+                    } else {
+                        MoveKind::Feed
+                    },
+                    tool_orientation: None,
+                })
+                .collect(),
+            feed_rate: None,
+        }
+    }
+
+    fn four_tabs() -> TabParams {
+        TabParams {
+            count: 4,
+            width: 6.0,
+            height: 2.0,
+        }
+    }
+
+    /// Runs of consecutive cut points at `z`, as (first, last) index pairs.
+    fn runs_at(pass: &Pass, z: f64) -> Vec<(usize, usize)> {
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        for (i, cut) in pass.cuts.iter().enumerate() {
+            if (cut.position.z - z).abs() > EPSILON {
+                continue;
+            }
+            match runs.last_mut() {
+                Some(run) if run.1 + 1 == i => run.1 = i,
+                _ => runs.push((i, i)),
+            }
+        }
+        runs
+    }
+
+    #[test]
+    fn four_tab_square_has_four_raised_spans() {
+        let mut pass = square_pass(-5.0);
+        add_tabs(&mut pass, &four_tabs(), -5.0);
+
+        let runs = runs_at(&pass, -3.0);
+        assert_eq!(runs.len(), 4, "{:?}", pass.cuts);
+        for (start, end) in runs {
+            let (a, b) = (&pass.cuts[start].position, &pass.cuts[end].position);
+            assert!((xy_distance(a, b) - 6.0).abs() < EPSILON, "{a:?} → {b:?}");
+            // Re-entry drops straight back down to the floor.
+            let after = &pass.cuts[end + 1].position;
+            assert_eq!(after.z, -5.0);
+            assert!(xy_distance(b, after) < EPSILON);
+        }
+    }
+
+    #[test]
+    fn tabs_are_centered_on_each_side_of_the_square() {
+        let mut pass = square_pass(-5.0);
+        add_tabs(&mut pass, &four_tabs(), -5.0);
+
+        let lifted: Vec<&Vec3> = pass
+            .cuts
+            .iter()
+            .map(|c| &c.position)
+            .filter(|p| p.z == -3.0)
+            .collect();
+        let near = |p: &Vec3, q: Vec3| distance(p, &q) < EPSILON;
+        assert!(near(lifted[0], v(17.0, 0.0, -3.0)), "{lifted:?}");
+        assert!(near(lifted[1], v(23.0, 0.0, -3.0)), "{lifted:?}");
+        assert!(near(lifted[7], v(0.0, 17.0, -3.0)), "{lifted:?}");
+    }
+
+    #[test]
+    fn tabbed_pass_stays_closed() {
+        let mut pass = square_pass(-5.0);
+        add_tabs(&mut pass, &four_tabs(), -5.0);
+
+        let first = &pass.cuts.first().unwrap().position;
+        let last = &pass.cuts.last().unwrap().position;
+        assert_eq!(first, last);
+        assert!(pass.cuts[1..].iter().all(|c| c.move_kind == MoveKind::Feed));
+    }
+
+    #[test]
+    fn pass_above_tab_top_is_unchanged() {
+        let mut pass = square_pass(-2.0);
+        let original = pass.clone();
+        add_tabs(&mut pass, &four_tabs(), -5.0);
+        assert_eq!(pass, original);
+    }
+
+    #[test]
+    fn open_pass_or_oversized_tabs_are_left_alone() {
+        let mut open = square_pass(-5.0);
+        open.cuts.pop();
+        let original = open.clone();
+        add_tabs(&mut open, &four_tabs(), -5.0);
+        assert_eq!(open, original);
+
+        let mut pass = square_pass(-5.0);
+        let original = pass.clone();
+        let wide = TabParams {
+            width: 40.0,
+            ..four_tabs()
+        };
+        add_tabs(&mut pass, &wide, -5.0);
+        assert_eq!(pass, original);
+    }
+}
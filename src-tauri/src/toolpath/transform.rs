@@ -0,0 +1,244 @@
+//! Rigid transforms of generated toolpaths.
+//!
+//! [`translate`], [`rotate_z`] and [`mirror`] move every point of a
+//! [`Toolpath`] — cut positions, arc and helix centers and end points — and
+//! turn 5-axis tool axes along with them. Mirroring reverses the direction
+//! of every arc and helix, since a reflected clockwise arc runs
+//! counter-clockwise.
+
+use serde::Deserialize;
+
+use crate::models::Vec3;
+
+use super::types::{MoveKind, ToolOrientation};
+use super::Toolpath;
+
+/// Plane to mirror a toolpath through, named by the axis it negates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MirrorPlane {
+    /// Mirror through the plane `x = 0`, negating X.
+    X,
+    /// Mirror through the plane `y = 0`, negating Y.
+    Y,
+}
+
+/// A transform request from the frontend, tagged by `"type"`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolpathTransform {
+    Translate { delta: Vec3 },
+    RotateZ { degrees: f64, about: Vec3 },
+    Mirror { plane: MirrorPlane },
+}
+
+impl ToolpathTransform {
+    /// Applies this transform to `toolpath` in place.
+    pub fn apply(&self, toolpath: &mut Toolpath) {
+        match self {
+            Self::Translate { delta } => translate(toolpath, delta.clone()),
+            Self::RotateZ { degrees, about } => rotate_z(toolpath, *degrees, about.clone()),
+            Self::Mirror { plane } => mirror(toolpath, *plane),
+        }
+    }
+}
+
+/// Moves every point of `toolpath` by `delta`. Tool axes are unchanged.
+pub fn translate(toolpath: &mut Toolpath, delta: Vec3) {
+    map_points(
+        toolpath,
+        |p| Vec3 {
+            x: p.x + delta.x,
+            y: p.y + delta.y,
+            z: p.z + delta.z,
+        },
+        |v| v.clone(),
+        false,
+    );
+}
+
+/// Rotates `toolpath` by `degrees` (counter-clockwise seen from +Z) about the
+/// vertical axis through `about`.
+pub fn rotate_z(toolpath: &mut Toolpath, degrees: f64, about: Vec3) {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let turn = |x: f64, y: f64| (x * cos - y * sin, x * sin + y * cos);
+    map_points(
+        toolpath,
+        |p| {
+            let (x, y) = turn(p.x - about.x, p.y - about.y);
+            Vec3 {
+                x: about.x + x,
+                y: about.y + y,
+                z: p.z,
+            }
+        },
+        |v| {
+            let (x, y) = turn(v.x, v.y);
+            Vec3 { x, y, z: v.z }
+        },
+        false,
+    );
+}
+
+/// Mirrors `toolpath` through `plane`, reversing every arc and helix.
+pub fn mirror(toolpath: &mut Toolpath, plane: MirrorPlane) {
+    let reflect = |v: &Vec3| match plane {
+        MirrorPlane::X => Vec3 { x: -v.x, ..*v },
+        MirrorPlane::Y => Vec3 { y: -v.y, ..*v },
+    };
+    map_points(toolpath, reflect, reflect, true);
+}
+
+/// Replaces every point with `point(p)` and every tool axis with
+/// `direction(v)`; `reverse` flips the direction of arcs and helices.
+fn map_points(
+    toolpath: &mut Toolpath,
+    point: impl Fn(&Vec3) -> Vec3,
+    direction: impl Fn(&Vec3) -> Vec3,
+    reverse: bool,
+) {
+    for cut in toolpath.passes.iter_mut().flat_map(|pass| &mut pass.cuts) {
+        cut.position = point(&cut.position);
+        match &mut cut.move_kind {
+            MoveKind::Arc {
+                center,
+                end,
+                clockwise,
+            }
+            | MoveKind::Helix {
+                center,
+                end,
+                clockwise,
+                ..
+            } => {
+                *center = point(center);
+                *end = point(end);
+                *clockwise ^= reverse;
+            }
+            MoveKind::ProbeToward { target, .. } => *target = point(target),
+            MoveKind::Rapid | MoveKind::Feed | MoveKind::Dwell { .. } => {}
+        }
+        if let Some(ToolOrientation::FiveAxis { tool_axis }) = &mut cut.tool_orientation {
+            *tool_axis = direction(tool_axis);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::toolpath::types::{CutPoint, Pass, PassKind};
+
+    fn v(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    fn close(a: &Vec3, b: &Vec3) -> bool {
+        (a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9 && (a.z - b.z).abs() < 1e-9
+    }
+
+    /// A feed to (10, 0) followed by a CCW quarter arc to (0, 10) about the
+    /// origin.
+    fn arc_toolpath() -> Toolpath {
+        Toolpath {
+            operation_id: Uuid::nil(),
+            tool_number: 1,
+            spindle_speed: 10000.0,
+            feed_rate: 500.0,
+            passes: vec![Pass {
+                kind: PassKind::Cutting,
+                cuts: vec![
+                    CutPoint {
+                        position: v(10.0, 0.0, -1.0),
+                        move_kind: MoveKind::Feed,
+                        tool_orientation: None,
+                    },
+                    CutPoint {
+                        position: v(10.0, 0.0, -1.0),
+                        move_kind: MoveKind::Arc {
+                            center: v(0.0, 0.0, -1.0),
+                            end: v(0.0, 10.0, -1.0),
+                            clockwise: false,
+                        },
+                        tool_orientation: None,
+                    },
+                ],
+                feed_rate: None,
+            }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
+        }
+    }
+
+    fn arc_of(toolpath: &Toolpath) -> (Vec3, Vec3, bool) {
+        match &toolpath.passes[0].cuts[1].move_kind {
+            MoveKind::Arc {
+                center,
+                end,
+                clockwise,
+            } => (center.clone(), end.clone(), *clockwise),
+            other => panic!("expected arc, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mirror_across_x_negates_x_and_flips_arcs() {
+        let mut toolpath = arc_toolpath();
+        mirror(&mut toolpath, MirrorPlane::X);
+
+        assert_eq!(toolpath.passes[0].cuts[0].position, v(-10.0, 0.0, -1.0));
+        assert_eq!(toolpath.passes[0].cuts[1].position, v(-10.0, 0.0, -1.0));
+        let (center, end, clockwise) = arc_of(&toolpath);
+        assert_eq!(center, v(0.0, 0.0, -1.0));
+        assert_eq!(end, v(0.0, 10.0, -1.0));
+        assert!(clockwise, "mirrored CCW arc must run clockwise");
+    }
+
+    #[test]
+    fn translate_moves_arc_center_and_end() {
+        let mut toolpath = arc_toolpath();
+        translate(&mut toolpath, v(5.0, -5.0, 1.0));
+
+        assert_eq!(toolpath.passes[0].cuts[0].position, v(15.0, -5.0, 0.0));
+        let (center, end, clockwise) = arc_of(&toolpath);
+        assert_eq!(center, v(5.0, -5.0, 0.0));
+        assert_eq!(end, v(5.0, 5.0, 0.0));
+        assert!(!clockwise);
+    }
+
+    #[test]
+    fn rotate_z_turns_about_point_and_keeps_direction() {
+        let mut toolpath = arc_toolpath();
+        rotate_z(&mut toolpath, 90.0, v(0.0, 0.0, 0.0));
+
+        assert!(close(
+            &toolpath.passes[0].cuts[0].position,
+            &v(0.0, 10.0, -1.0)
+        ));
+        let (center, end, clockwise) = arc_of(&toolpath);
+        assert!(close(&center, &v(0.0, 0.0, -1.0)));
+        assert!(close(&end, &v(-10.0, 0.0, -1.0)));
+        assert!(!clockwise);
+    }
+
+    #[test]
+    fn rotate_z_turns_five_axis_tool_axis() {
+        let mut toolpath = arc_toolpath();
+        toolpath.passes[0].cuts[0].tool_orientation = Some(ToolOrientation::FiveAxis {
+            tool_axis: v(1.0, 0.0, 1.0),
+        });
+        rotate_z(&mut toolpath, 90.0, v(50.0, 50.0, 0.0));
+
+        match &toolpath.passes[0].cuts[0].tool_orientation {
+            Some(ToolOrientation::FiveAxis { tool_axis }) => {
+                assert!(close(tool_axis, &v(0.0, 1.0, 1.0)), "{tool_axis:?}");
+            }
+            other => panic!("expected 5-axis orientation, got {other:?}"),
+        }
+    }
+}
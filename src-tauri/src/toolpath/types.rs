@@ -6,6 +6,7 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::operation::{CoolantMode, DrillParams, FeedMode, PathMode};
 use crate::models::Vec3;
 
 /// A complete toolpath for one machining operation.
@@ -22,6 +23,27 @@ pub struct Toolpath {
     pub feed_rate: f64,
     /// Ordered list of passes that make up this toolpath.
     pub passes: Vec<Pass>,
+    /// Corner handling copied from the operation; `None` uses the
+    /// post-processor's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_mode: Option<PathMode>,
+    /// Parameters of the drill operation this toolpath came from, if any.
+    /// Posts with canned cycles use them to emit `G81`/`G83` instead of the
+    /// expanded plunge moves.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drill: Option<DrillParams>,
+    /// Coolant copied from the operation. `None` leaves coolant alone, for
+    /// toolpaths not tied to a project operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coolant: Option<CoolantMode>,
+    /// Feed interpretation copied from the operation. `None` means per
+    /// minute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed_mode: Option<FeedMode>,
+    /// Finishing allowance copied from a profile or pocket operation, in
+    /// project units. Recorded in the operation's G-code comments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stock_to_leave: Option<f64>,
 }
 
 /// A single pass within a toolpath (e.g. one depth step, one linking move).
@@ -32,6 +54,10 @@ pub struct Pass {
     pub kind: PassKind,
     /// Ordered list of cut points that define the path geometry.
     pub cuts: Vec<CutPoint>,
+    /// Feed rate for this pass, overriding [`Toolpath::feed_rate`] (e.g. a
+    /// slower plunge or lead-in).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed_rate: Option<f64>,
 }
 
 /// Classification of a toolpath pass.
@@ -74,11 +100,34 @@ pub enum MoveKind {
         /// `true` → clockwise (G2); `false` → counter-clockwise (G3).
         clockwise: bool,
     },
+    /// Helical ramp around a vertical axis, expanded into arc blocks (G2/G3)
+    /// on output. Used to ramp down into pockets and profiles instead of
+    /// plunging straight.
+    Helix {
+        /// Helix axis position in the work coordinate system; only X and Y
+        /// are used.
+        center: Vec3,
+        /// Helix end point, normally below the start.
+        end: Vec3,
+        /// `true` → clockwise (G2); `false` → counter-clockwise (G3).
+        clockwise: bool,
+        /// Maximum Z change per full revolution.
+        pitch: f64,
+    },
     /// Dwell (G4).
     Dwell {
         /// Duration in seconds.
         seconds: f64,
     },
+    /// Protected probing move (e.g. G38.2) from the [`CutPoint`] position
+    /// toward `target`, stopping on contact. Where the tool stops is only
+    /// known to the controller.
+    ProbeToward {
+        /// Furthest point the probe may travel to.
+        target: Vec3,
+        /// Probing feed rate, independent of the toolpath feed.
+        feed: f64,
+    },
 }
 
 /// Tool orientation for multi-axis moves.
@@ -126,7 +175,13 @@ mod tests {
                         tool_orientation: Some(ToolOrientation::ThreeAxis),
                     },
                 ],
+                feed_rate: None,
             }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
         }
     }
 
@@ -165,7 +220,13 @@ mod tests {
                         },
                     }),
                 }],
+                feed_rate: None,
             }],
+            path_mode: None,
+            drill: None,
+            coolant: None,
+            feed_mode: None,
+            stock_to_leave: None,
         }
     }
 
@@ -185,6 +246,67 @@ mod tests {
         assert_eq!(original, recovered);
     }
 
+    #[test]
+    fn pass_feed_rate_serde_round_trip() {
+        let mut original = sample_arc_toolpath();
+        original.passes[0].feed_rate = Some(200.0);
+        let value = serde_json::to_value(&original).expect("serialize toolpath");
+        assert_eq!(value["passes"][0]["feedRate"], 200.0);
+        let recovered: Toolpath = serde_json::from_value(value).expect("deserialize toolpath");
+        assert_eq!(original, recovered);
+    }
+
+    #[test]
+    fn helix_toolpath_serde_round_trip() {
+        let mut original = sample_feed_toolpath();
+        original.passes[0].cuts.push(CutPoint {
+            position: Vec3 {
+                x: 10.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            move_kind: MoveKind::Helix {
+                center: Vec3::zero(),
+                end: Vec3 {
+                    x: 10.0,
+                    y: 0.0,
+                    z: -2.0,
+                },
+                clockwise: false,
+                pitch: 1.0,
+            },
+            tool_orientation: None,
+        });
+        let json = serde_json::to_string(&original).expect("serialize helix toolpath");
+        let recovered: Toolpath = serde_json::from_str(&json).expect("deserialize helix toolpath");
+        assert_eq!(original, recovered);
+    }
+
+    #[test]
+    fn probe_toolpath_serde_round_trip() {
+        let mut original = sample_feed_toolpath();
+        original.passes[0].cuts.push(CutPoint {
+            position: Vec3 {
+                x: 50.0,
+                y: 0.0,
+                z: 5.0,
+            },
+            move_kind: MoveKind::ProbeToward {
+                target: Vec3 {
+                    x: 50.0,
+                    y: 0.0,
+                    z: -10.0,
+                },
+                feed: 100.0,
+            },
+            tool_orientation: None,
+        });
+        let json = serde_json::to_string(&original).expect("serialize probe toolpath");
+        assert!(json.contains(r#""type":"probe_toward""#), "{json}");
+        let recovered: Toolpath = serde_json::from_str(&json).expect("deserialize probe toolpath");
+        assert_eq!(original, recovered);
+    }
+
     #[test]
     fn move_kind_rapid_serializes_with_type_tag() {
         let mk = MoveKind::Rapid;
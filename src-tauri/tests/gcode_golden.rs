@@ -1,4 +1,7 @@
-use jamiecam_lib::postprocessor::{program::GenerateOptions, PostProcessor, ToolInfo};
+use jamiecam_lib::postprocessor::{
+    program::{CommentLevel, GenerateOptions},
+    PostProcessor, ToolInfo,
+};
 use jamiecam_lib::toolpath::Toolpath;
 use std::path::PathBuf;
 
@@ -35,10 +38,12 @@ fn fanuc_0i_golden_matches() {
             &[tool_info],
             GenerateOptions {
                 program_number: Some(1000),
-                include_comments: false,
+                comments: CommentLevel::None,
+                ..Default::default()
             },
         )
-        .expect("generate");
+        .expect("generate")
+        .into_gcode();
     assert_eq!(
         output,
         load_golden("fanuc-0i"),
@@ -61,10 +66,12 @@ fn linuxcnc_golden_matches() {
             &[tool_info],
             GenerateOptions {
                 program_number: Some(1000),
-                include_comments: false,
+                comments: CommentLevel::None,
+                ..Default::default()
             },
         )
-        .expect("generate");
+        .expect("generate")
+        .into_gcode();
     assert_eq!(
         output,
         load_golden("linuxcnc"),
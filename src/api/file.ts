@@ -7,18 +7,32 @@
  *    callers always receive a typed error object.
  */
 
-import type { MeshData, ProjectSnapshot } from './types'
+import type {
+  FaceInfo,
+  HoleInfo,
+  MeshData,
+  ModelChecksumStatus,
+  ProjectSnapshot,
+  TessellationOptions,
+} from './types'
 import { typedInvoke } from './errors'
 
 /**
  * Open a 3D model file, tessellate it, and store it in the active project.
  *
- * @param path Absolute path to the model file (.step, .iges, or .stl).
+ * While importing, the backend emits `tessellation-progress` events whose
+ * payload is the fraction done (0.0–1.0).
+ *
+ * @param path Absolute path to the model file (.step, .iges, .stl, or .3mf).
+ * @param tessellation Optional B-rep tessellation tolerances.
  * @returns Tessellated MeshData ready for the viewport.
  * @throws AppError on import failure or if the path is not found.
  */
-export async function openModel(path: string): Promise<MeshData> {
-  return typedInvoke<MeshData>('open_model', { path })
+export async function openModel(
+  path: string,
+  tessellation?: TessellationOptions,
+): Promise<MeshData> {
+  return typedInvoke<MeshData>('open_model', { path, tessellation })
 }
 
 /**
@@ -31,14 +45,29 @@ export async function newProject(): Promise<ProjectSnapshot> {
   return typedInvoke<ProjectSnapshot>('new_project')
 }
 
+/**
+ * Start a new project from a .jcam template.
+ *
+ * Keeps the template's tools, work offsets, and stock but drops its model
+ * reference and timestamps.
+ *
+ * @param path Absolute path to the template .jcam file.
+ * @returns A ProjectSnapshot reflecting the new project.
+ * @throws AppError if the template is missing or cannot be parsed.
+ */
+export async function newProjectFromTemplate(path: string): Promise<ProjectSnapshot> {
+  return typedInvoke<ProjectSnapshot>('new_project_from_template', { path })
+}
+
 /**
  * Save the active project to a .jcam file.
  *
  * @param path Absolute path for the output file.
+ * @param embedModel Copy the source model into the file so it opens elsewhere.
  * @throws AppError if the file cannot be written.
  */
-export async function saveProject(path: string): Promise<void> {
-  return typedInvoke<void>('save_project', { path })
+export async function saveProject(path: string, embedModel?: boolean): Promise<void> {
+  return typedInvoke<void>('save_project', { path, embedModel })
 }
 
 /**
@@ -52,6 +81,28 @@ export async function loadProject(path: string): Promise<ProjectSnapshot> {
   return typedInvoke<ProjectSnapshot>('load_project', { path })
 }
 
+/**
+ * Write the active project to its newest autosave, rotating older autosaves.
+ *
+ * Does not change the project's timestamps or save path.
+ *
+ * @returns The path of the autosave written.
+ * @throws AppError if the autosave cannot be written.
+ */
+export async function autosave(): Promise<string> {
+  return typedInvoke<string>('autosave')
+}
+
+/**
+ * Replace the active project with its newest autosave.
+ *
+ * @returns A ProjectSnapshot reflecting the restored project.
+ * @throws AppError (`FileNotFound`) if the project has no autosave.
+ */
+export async function restoreAutosave(): Promise<ProjectSnapshot> {
+  return typedInvoke<ProjectSnapshot>('restore_autosave')
+}
+
 /**
  * Return a lightweight snapshot of the current project state.
  *
@@ -63,3 +114,57 @@ export async function loadProject(path: string): Promise<ProjectSnapshot> {
 export async function getProjectSnapshot(): Promise<ProjectSnapshot> {
   return typedInvoke<ProjectSnapshot>('get_project_snapshot')
 }
+
+/**
+ * Check whether the loaded model's file still matches the checksum stored in
+ * the project.
+ *
+ * @returns "match", "mismatch" (the file was edited), or "missing".
+ * @throws AppError (kind "NotFound") if no model is loaded.
+ */
+export async function verifyModelChecksum(): Promise<ModelChecksumStatus> {
+  return typedInvoke<ModelChecksumStatus>('verify_model_checksum')
+}
+
+/**
+ * List the cylindrical holes in the loaded model, for seeding drill operations.
+ *
+ * @returns One entry per hole; empty for STL models or builds without OCCT.
+ * @throws AppError (kind "NotFound") if no model is loaded.
+ */
+export async function detectHoles(): Promise<HoleInfo[]> {
+  return typedInvoke<HoleInfo[]>('detect_holes')
+}
+
+/**
+ * List the faces of the loaded model, for picking flat faces to face.
+ *
+ * @returns One entry per face with its surface type and area; empty for STL
+ *   models or builds without OCCT.
+ * @throws AppError (kind "NotFound") if no model is loaded.
+ */
+export async function listFaces(): Promise<FaceInfo[]> {
+  return typedInvoke<FaceInfo[]>('list_faces')
+}
+
+/**
+ * Slice the loaded model at height `z`, for drawing its cross-section.
+ *
+ * @returns Closed XY loops (outer boundaries and holes), each listing its
+ *   vertices once; empty when the plane misses the model.
+ * @throws AppError (kind "NotFound") if no model is loaded, (kind
+ *   "InvalidInput") for STL models, or (kind "GeometryImport") without OCCT.
+ */
+export async function sectionModelAtZ(z: number): Promise<[number, number][][]> {
+  return typedInvoke<[number, number][][]>('section_model_at_z', { z })
+}
+
+/**
+ * Delete all cached tessellations so the next import re-meshes its model.
+ *
+ * @returns The number of cache entries removed.
+ * @throws AppError if the cache directory cannot be read.
+ */
+export async function clearMeshCache(): Promise<number> {
+  return typedInvoke<number>('clear_mesh_cache')
+}
@@ -13,6 +13,7 @@
 import type {
   MeshData,
   Operation,
+  OperationFilter,
   OperationInput,
   ProjectSnapshot,
   StockDefinition,
@@ -31,6 +32,8 @@ const DEFAULT_SNAPSHOT: ProjectSnapshot = {
   modelPath: null,
   modelChecksum: null,
   projectName: '',
+  description: '',
+  units: 'mm',
   modifiedAt: '',
   tools: [],
   stock: null,
@@ -50,6 +53,11 @@ export async function newProject(): Promise<ProjectSnapshot> {
   return { ...DEFAULT_SNAPSHOT }
 }
 
+/** Mock: returns a default ProjectSnapshot without reading any template. */
+export async function newProjectFromTemplate(_path: string): Promise<ProjectSnapshot> {
+  return { ...DEFAULT_SNAPSHOT }
+}
+
 /** Mock: no-op save (always succeeds). */
 export async function saveProject(_path: string): Promise<void> {
   // no-op
@@ -60,6 +68,16 @@ export async function loadProject(_path: string): Promise<ProjectSnapshot> {
   return { ...DEFAULT_SNAPSHOT }
 }
 
+/** Mock: returns a fixed autosave path without writing anything. */
+export async function autosave(): Promise<string> {
+  return '/tmp/jamiecam-untitled.jcam.autosave.jcam'
+}
+
+/** Mock: returns a default ProjectSnapshot without reading any autosave. */
+export async function restoreAutosave(): Promise<ProjectSnapshot> {
+  return { ...DEFAULT_SNAPSHOT }
+}
+
 /** Mock: returns a default ProjectSnapshot. */
 export async function getProjectSnapshot(): Promise<ProjectSnapshot> {
   return { ...DEFAULT_SNAPSHOT }
@@ -101,6 +119,16 @@ export async function listTools(): Promise<Tool[]> {
   return []
 }
 
+/** Mock: always rejects, since the mock tool library is empty. */
+export async function getTool(id: string): Promise<Tool> {
+  throw { kind: 'NotFound', message: `tool ${id} not found` }
+}
+
+/** Mock: returns a stub copy of the tool with a placeholder ID. */
+export async function copyTool(id: string): Promise<Tool> {
+  return { ...(await addTool({} as ToolInput)), id: `${id}-copy` }
+}
+
 // ── Stock / WCS commands ──────────────────────────────────────────────────────
 
 /** Mock: no-op set stock (always succeeds). */
@@ -114,7 +142,7 @@ export async function getStock(): Promise<StockDefinition | null> {
 }
 
 /** Mock: no-op set WCS (always succeeds). */
-export async function setWcs(_wcs: WorkCoordinateSystem[]): Promise<void> {
+export async function setWcs(_wcs: WorkCoordinateSystem[], _strict?: boolean): Promise<void> {
   // no-op
 }
 
@@ -160,6 +188,11 @@ export async function reorderOperations(_ids: string[]): Promise<void> {
 }
 
 /** Mock: returns an empty operation list. */
-export async function listOperations(): Promise<Operation[]> {
+export async function listOperations(_filter?: OperationFilter): Promise<Operation[]> {
   return []
 }
+
+/** Mock: always rejects, since the mock operation list is empty. */
+export async function getOperation(id: string): Promise<Operation> {
+  throw { kind: 'NotFound', message: `operation ${id} not found` }
+}
@@ -2,7 +2,7 @@
  * Typed wrappers around Tauri's invoke() for operation CRUD IPC commands.
  */
 
-import type { Operation, OperationInput } from './types'
+import type { Operation, OperationFilter, OperationInput } from './types'
 import { typedInvoke } from './errors'
 
 /**
@@ -40,6 +40,29 @@ export async function deleteOperation(id: string): Promise<void> {
   return typedInvoke<void>('delete_operation', { id })
 }
 
+/**
+ * Duplicate an operation; the copy is inserted right after the original.
+ *
+ * @param id UUID string of the operation to copy.
+ * @returns The new Operation with a fresh ID and " (copy)" appended to its name.
+ * @throws AppError if the operation ID is not found.
+ */
+export async function duplicateOperation(id: string): Promise<Operation> {
+  return typedInvoke<Operation>('duplicate_operation', { id })
+}
+
+/**
+ * Move an operation one place up or down the list.
+ *
+ * @param id UUID string of the operation to move.
+ * @param direction 'up' runs it earlier, 'down' later; past either end is a no-op.
+ * @returns The operation IDs in their new order.
+ * @throws AppError if the operation ID is not found.
+ */
+export async function moveOperation(id: string, direction: 'up' | 'down'): Promise<string[]> {
+  return typedInvoke<string[]>('move_operation', { id, direction })
+}
+
 /**
  * Reorder the project's operation list.
  *
@@ -54,11 +77,23 @@ export async function reorderOperations(ids: string[]): Promise<void> {
 }
 
 /**
- * Return all operations in the project, in program order.
+ * Return the operations in the project, in program order.
  *
+ * @param filter Optional narrowing by enabled state or tool; all by default.
  * @returns Array of Operation objects.
- * @throws AppError on unexpected backend failure.
+ * @throws AppError (kind "NotFound") if filter.toolId is not a valid UUID.
+ */
+export async function listOperations(filter?: OperationFilter): Promise<Operation[]> {
+  return typedInvoke<Operation[]>('list_operations', { filter })
+}
+
+/**
+ * Return a single operation, e.g. to load it into an edit form.
+ *
+ * @param id UUID string of the operation.
+ * @returns The Operation.
+ * @throws AppError (kind "NotFound") if the operation ID is not found.
  */
-export async function listOperations(): Promise<Operation[]> {
-  return typedInvoke<Operation[]>('list_operations')
+export async function getOperation(id: string): Promise<Operation> {
+  return typedInvoke<Operation>('get_operation', { id })
 }
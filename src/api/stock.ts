@@ -2,7 +2,7 @@
  * Typed wrappers around Tauri's invoke() for stock and WCS IPC commands.
  */
 
-import type { StockDefinition, WorkCoordinateSystem } from './types'
+import type { MeshData, StockDefinition, WorkCoordinateSystem } from './types'
 import { typedInvoke } from './errors'
 
 /**
@@ -25,14 +25,38 @@ export async function getStock(): Promise<StockDefinition | null> {
   return typedInvoke<StockDefinition | null>('get_stock')
 }
 
+/**
+ * Set the stock to a box enclosing the loaded model.
+ *
+ * @param margin Extra material added on every side of the model bounds.
+ * @returns The new stock definition.
+ * @throws AppError when no model is loaded.
+ */
+export async function setStockFromModel(margin: number): Promise<StockDefinition> {
+  return typedInvoke<StockDefinition>('set_stock_from_model', { margin })
+}
+
+/**
+ * Tessellate the stock minus the loaded model: the material left to remove.
+ *
+ * @returns The remainder mesh.
+ * @throws AppError when no stock or model is set, the model is an STL, or
+ *   OCCT is unavailable.
+ */
+export async function computeStockRemainder(): Promise<MeshData> {
+  return typedInvoke<MeshData>('compute_stock_remainder')
+}
+
 /**
  * Replace the project's WCS list.
  *
  * @param wcs The complete replacement WCS list.
- * @throws AppError on unexpected backend failure.
+ * @param strict Reject non-orthogonal axes instead of orthonormalizing them.
+ * @throws AppError with kind "InvalidInput" if an axis is zero-length, the
+ *   axes are parallel, or (with `strict`) they are not perpendicular.
  */
-export async function setWcs(wcs: WorkCoordinateSystem[]): Promise<void> {
-  return typedInvoke<void>('set_wcs', { wcs })
+export async function setWcs(wcs: WorkCoordinateSystem[], strict?: boolean): Promise<void> {
+  return typedInvoke<void>('set_wcs', { wcs, strict })
 }
 
 /**
@@ -2,7 +2,15 @@
  * Typed wrappers around Tauri's invoke() for toolpath and G-code IPC commands.
  */
 
-import type { PostProcessorMeta, ExportParams } from './types'
+import type {
+  PostProcessorMeta,
+  ExportParams,
+  GenerateResult,
+  PreviewSummary,
+  ProgramParams,
+  ToolpathStats,
+  ToolpathTransform,
+} from './types'
 import { typedInvoke } from './errors'
 
 /**
@@ -18,18 +26,81 @@ export async function listPostProcessors(): Promise<PostProcessorMeta[]> {
  * Generate a G-code preview for the given operation using the specified post-processor.
  * @param operationId UUID string of the operation whose toolpath to preview.
  * @param postProcessorId Builtin post-processor ID (e.g. "fanuc-0i", "linuxcnc").
- * @returns G-code string.
+ * @returns Generated G-code with warnings and stats.
  * @throws AppError (kind "NotFound") if no toolpath has been computed for the operation.
  */
-export async function getGcodePreview(operationId: string, postProcessorId: string): Promise<string> {
-  return typedInvoke<string>('get_gcode_preview', { operationId, postProcessorId })
+export async function getGcodePreview(
+  operationId: string,
+  postProcessorId: string,
+): Promise<GenerateResult> {
+  return typedInvoke<GenerateResult>('get_gcode_preview', { operationId, postProcessorId })
+}
+
+/**
+ * Summarize the G-code preview for an operation without transferring the full text.
+ * @param operationId UUID string of the operation whose toolpath to preview.
+ * @param postProcessorId Post-processor ID (e.g. "fanuc-0i", "linuxcnc").
+ * @returns Line count, byte length, the first 50 and the last 10 lines.
+ * @throws AppError (kind "NotFound") if no toolpath has been computed for the operation.
+ */
+export async function gcodePreviewSummary(
+  operationId: string,
+  postProcessorId: string,
+): Promise<PreviewSummary> {
+  return typedInvoke<PreviewSummary>('gcode_preview_summary', { operationId, postProcessorId })
 }
 
 /**
  * Export G-code for the specified operations to a file on disk.
  * @param params Export configuration including operation IDs, post-processor, and output path.
+ * @returns Generation warnings (empty for a clean program).
  * @throws AppError on post-processor error, missing toolpath, or I/O failure.
  */
-export async function exportGcode(params: ExportParams): Promise<void> {
-  return typedInvoke<void>('export_gcode', { params })
+export async function exportGcode(params: ExportParams): Promise<string[]> {
+  return typedInvoke<string[]>('export_gcode', { params })
+}
+
+/**
+ * Generate G-code for the specified operations without writing a file, e.g.
+ * to preview a multi-operation program before saving it.
+ * @param params Operation IDs, post-processor and output options.
+ * @returns The complete program, identical to what exportGcode would write.
+ * @throws AppError on post-processor error or missing toolpath.
+ */
+export async function exportGcodeString(params: ProgramParams): Promise<string> {
+  return typedInvoke<string>('export_gcode_string', { params })
+}
+
+/**
+ * Return rapid/feed distances, arc count, and an estimated run time for an operation's toolpath.
+ * @param operationId UUID string of the operation.
+ * @param rapidRate Rapid traverse rate in mm/min; defaults to the rapid rate of
+ *   `postProcessorId`'s machine, or 10000 without a post.
+ * @param postProcessorId Post whose `machine.rapid_rate_mm_min` is the fallback rapid rate.
+ * @throws AppError (kind "NotFound") if no toolpath has been computed for the operation.
+ */
+export async function getToolpathStats(
+  operationId: string,
+  rapidRate?: number,
+  postProcessorId?: string,
+): Promise<ToolpathStats> {
+  return typedInvoke<ToolpathStats>('get_toolpath_stats', {
+    operationId,
+    rapidRate,
+    postProcessorId,
+  })
+}
+
+/**
+ * Translate, rotate, or mirror an operation's cached toolpath in place.
+ * The transform is lost when the toolpath is recalculated.
+ * @param operationId UUID string of the operation.
+ * @param transform The transform to apply.
+ * @throws AppError (kind "NotFound") if no toolpath has been computed for the operation.
+ */
+export async function transformToolpath(
+  operationId: string,
+  transform: ToolpathTransform,
+): Promise<void> {
+  return typedInvoke<void>('transform_toolpath', { operationId, transform })
 }
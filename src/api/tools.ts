@@ -2,7 +2,7 @@
  * Typed wrappers around Tauri's invoke() for tool CRUD IPC commands.
  */
 
-import type { Tool, ToolInput } from './types'
+import type { FeedSpeed, Tool, ToolInput } from './types'
 import { typedInvoke } from './errors'
 
 /**
@@ -32,10 +32,12 @@ export async function editTool(id: string, input: ToolInput): Promise<Tool> {
  * Remove a tool from the project tool library.
  *
  * @param id UUID string of the tool to remove.
- * @throws AppError if the tool ID is not found.
+ * @param force Also delete the operations that use the tool.
+ * @throws AppError if the tool ID is not found, or with kind "Conflict" if
+ *   operations still use the tool and `force` is not set.
  */
-export async function deleteTool(id: string): Promise<void> {
-  return typedInvoke<void>('delete_tool', { id })
+export async function deleteTool(id: string, force?: boolean): Promise<void> {
+  return typedInvoke<void>('delete_tool', { id, force })
 }
 
 /**
@@ -47,3 +49,71 @@ export async function deleteTool(id: string): Promise<void> {
 export async function listTools(): Promise<Tool[]> {
   return typedInvoke<Tool[]>('list_tools')
 }
+
+/**
+ * Return a single tool from the project tool library.
+ *
+ * @param id UUID string of the tool.
+ * @returns The Tool.
+ * @throws AppError (kind "NotFound") if the tool ID is not found.
+ */
+export async function getTool(id: string): Promise<Tool> {
+  return typedInvoke<Tool>('get_tool', { id })
+}
+
+/**
+ * Duplicate a tool under a new ID, appending " (copy)" to its name.
+ *
+ * @param id UUID string of the tool to copy.
+ * @returns The new Tool.
+ * @throws AppError if the tool ID is not found.
+ */
+export async function copyTool(id: string): Promise<Tool> {
+  return typedInvoke<Tool>('copy_tool', { id })
+}
+
+/**
+ * Write the project tool library to a JSON file.
+ *
+ * @param path Absolute path of the file to write.
+ * @throws AppError if the file cannot be written.
+ */
+export async function exportToolLibrary(path: string): Promise<void> {
+  return typedInvoke<void>('export_tool_library', { path })
+}
+
+/**
+ * Load a tool library JSON file into the project.
+ *
+ * @param path Absolute path of a file written by `exportToolLibrary`.
+ * @param merge Add tools whose names are new (with fresh IDs) instead of
+ *   replacing the whole library.
+ * @returns The project's tools after the import.
+ * @throws AppError with kind "InvalidInput" if the file is not a tool library,
+ *   or "Conflict" if replacing would leave operations without their tool.
+ */
+export async function importToolLibrary(path: string, merge: boolean): Promise<Tool[]> {
+  return typedInvoke<Tool[]>('import_tool_library', { path, merge })
+}
+
+/**
+ * Suggest a spindle speed and feed rate for a tool cutting a material.
+ *
+ * @param toolId UUID string of the tool.
+ * @param material Workpiece material id (e.g. `"aluminum-6061"`).
+ * @param surfaceSpeedOverride Surface speed in m/min, replacing the table value.
+ * @returns RPM and feed in mm/min.
+ * @throws AppError if the tool is not found, or with kind "InvalidInput" if
+ *   the material has no built-in cutting data.
+ */
+export async function suggestFeedsSpeeds(
+  toolId: string,
+  material: string,
+  surfaceSpeedOverride?: number,
+): Promise<FeedSpeed> {
+  return typedInvoke<FeedSpeed>('suggest_feeds_speeds', {
+    toolId,
+    material,
+    surfaceSpeedOverride,
+  })
+}
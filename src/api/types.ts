@@ -15,6 +15,14 @@ export interface MeshData {
   indices: number[]
 }
 
+/** Tessellation tolerances for B-rep imports; omitted fields are derived from the part. */
+export interface TessellationOptions {
+  /** Maximum chord deviation from the true surface (mm). */
+  chordTol?: number
+  /** Maximum angular deviation (radians). */
+  angleTol?: number
+}
+
 /** Reference to the source geometry model stored in a .jcam file. */
 export interface SourceModelRef {
   /** Absolute path to the model file at last save. */
@@ -53,6 +61,15 @@ export interface Tool {
   fluteCount: number
   defaultSpindleSpeed?: number
   defaultFeedRate?: number
+  /** Included tip angle in degrees (chamfer mills, V-bits, drills). */
+  tipAngle?: number
+}
+
+/** Suggested cutting parameters from `suggest_feeds_speeds`. */
+export interface FeedSpeed {
+  spindleRpm: number
+  /** Feed rate in mm/min, regardless of project units. */
+  feedMmMin: number
 }
 
 /**
@@ -68,6 +85,8 @@ export interface ToolInput {
   fluteCount: number
   defaultSpindleSpeed?: number
   defaultFeedRate?: number
+  /** Included tip angle in degrees (chamfer mills, V-bits, drills). */
+  tipAngle?: number
 }
 
 /** A compact tool summary included in ProjectSnapshot. */
@@ -93,12 +112,25 @@ export interface BoxStock {
   height: number
 }
 
+/** A cylindrical (round bar) stock solid. */
+export interface CylinderStock {
+  type: 'cylinder'
+  /** Centre of the bottom face in WCS coordinates. */
+  origin: Vec3
+  /** Direction of the cylinder axis; need not be normalised. */
+  axis: Vec3
+  /** Bar diameter. */
+  diameter: number
+  /** Length along the axis. */
+  height: number
+}
+
 /**
  * Stock material block for the project.
  *
  * Internally-tagged enum matching `StockDefinition` in Rust.
  */
-export type StockDefinition = BoxStock
+export type StockDefinition = BoxStock | CylinderStock
 
 // ── WCS types ─────────────────────────────────────────────────────────────────
 
@@ -122,6 +154,18 @@ export interface ProfileParams {
   depth: number
   stepdown: number
   compensationSide: 'left' | 'right' | 'center'
+  /** Holding tabs; absent for none. */
+  tabs?: TabParams
+  /** Wall and floor allowance for a finishing pass; defaults to 0. */
+  stockToLeave?: number
+}
+
+/** Holding tabs left along a profile. Lengths are in project units. */
+export interface TabParams {
+  count: number
+  width: number
+  /** Tab height above the profile floor. */
+  height: number
 }
 
 /** Parameters for a Pocket operation. */
@@ -129,6 +173,8 @@ export interface PocketParams {
   depth: number
   stepdown: number
   stepoverPercent: number
+  /** Wall and floor allowance for a finishing pass; defaults to 0. */
+  stockToLeave?: number
 }
 
 /** Parameters for a Drill operation. */
@@ -137,6 +183,15 @@ export interface DrillParams {
   peckDepth?: number
 }
 
+/** Controller corner handling requested by an operation. */
+export type PathMode = 'exact_stop' | 'continuous'
+
+/** Coolant an operation runs with; each maps to a `[coolant]` post code. */
+export type CoolantMode = 'off' | 'flood' | 'mist' | 'air' | 'through_tool'
+
+/** How an operation's F word is read: units per minute or per spindle revolution. */
+export type FeedMode = 'per_minute' | 'per_rev'
+
 /**
  * A machining operation returned by the backend.
  *
@@ -150,6 +205,11 @@ export interface Operation {
   toolId: string
   type: 'profile' | 'pocket' | 'drill'
   params: ProfileParams | PocketParams | DrillParams
+  pathMode?: PathMode
+  /** Defaults to `'flood'` when absent. */
+  coolant?: CoolantMode
+  /** Defaults to `'per_minute'` when absent. */
+  feedMode?: FeedMode
 }
 
 /**
@@ -164,6 +224,19 @@ export interface OperationInput {
   toolId: string
   type: 'profile' | 'pocket' | 'drill'
   params: ProfileParams | PocketParams | DrillParams
+  pathMode?: PathMode
+  /** Defaults to `'flood'` when absent. */
+  coolant?: CoolantMode
+  /** Defaults to `'per_minute'` when absent. */
+  feedMode?: FeedMode
+}
+
+/** Narrows the result of listOperations; omitted fields keep everything. */
+export interface OperationFilter {
+  /** Skip disabled operations. */
+  enabledOnly?: boolean
+  /** Keep only the operations using this tool. */
+  toolId?: string
 }
 
 /** A compact operation summary included in ProjectSnapshot. */
@@ -189,6 +262,10 @@ export interface ProjectSnapshot {
   modelChecksum: string | null
   /** Human-readable project name. */
   projectName: string
+  /** Free-form project description. */
+  description: string
+  /** Unit system in use ("mm" or "inch"). */
+  units: string
   /** ISO-8601 last-modified timestamp (empty string when not yet saved). */
   modifiedAt: string
   /** Tool library summaries. */
@@ -201,6 +278,41 @@ export interface ProjectSnapshot {
   operations: OperationSummary[]
 }
 
+/** Result of `verify_model_checksum`. */
+export type ModelChecksumStatus = 'match' | 'mismatch' | 'missing'
+
+/** A cylindrical hole found in the loaded model by `detect_holes`. */
+export interface HoleInfo {
+  /** Center of the hole's opening, in model coordinates (mm). */
+  center: [number, number, number]
+  /** Unit axis pointing out of the hole through its opening. */
+  axis: [number, number, number]
+  diameter: number
+  depth: number
+  isThrough: boolean
+}
+
+/** Geometric type of a face's underlying surface. */
+export type SurfaceType =
+  | 'plane'
+  | 'cylinder'
+  | 'cone'
+  | 'sphere'
+  | 'torus'
+  | 'bspline'
+  | 'bezier'
+  | 'offset'
+  | 'other'
+
+/** A face of the loaded model listed by `list_faces`. */
+export interface FaceInfo {
+  /** Index of the face within the model. */
+  id: number
+  surfaceType: SurfaceType
+  /** Face area in mm². */
+  area: number
+}
+
 /**
  * Error payload produced by all Rust command handlers.
  *
@@ -226,13 +338,91 @@ export interface PostProcessorMeta {
 }
 
 /**
- * Parameters for exporting G-code to a file.
- * Mirrors the Rust `ExportParams` struct.
+ * Summary figures for a generated program.
+ * Mirrors the Rust `ProgramStats` struct.
  */
-export interface ExportParams {
+export interface ProgramStats {
+  lineCount: number
+  toolChanges: number
+  estimatedSeconds: number | null
+}
+
+/**
+ * Motion totals and estimated run time for one operation's toolpath.
+ * Mirrors the Rust `ToolpathStats` struct.
+ */
+export interface ToolpathStats {
+  rapidDistance: number
+  /** Feed moves including arcs. */
+  feedDistance: number
+  arcCount: number
+  estimatedSeconds: number
+}
+
+/**
+ * A rigid transform applied to a cached toolpath.
+ * Mirrors the Rust `ToolpathTransform` enum (tagged by `type`).
+ */
+export type ToolpathTransform =
+  | { type: 'translate'; delta: Vec3 }
+  /** Counter-clockwise (seen from +Z) about the vertical axis through `about`. */
+  | { type: 'rotate_z'; degrees: number; about: Vec3 }
+  /** `x` negates X, `y` negates Y; arcs reverse direction. */
+  | { type: 'mirror'; plane: 'x' | 'y' }
+
+/**
+ * Generated G-code plus warnings and stats.
+ * Mirrors the Rust `GenerateResult` struct.
+ */
+export interface GenerateResult {
+  gcode: string
+  warnings: string[]
+  stats: ProgramStats
+}
+
+/**
+ * Totals plus head and tail of a generated program.
+ * Mirrors the Rust `PreviewSummary` struct.
+ */
+export interface PreviewSummary {
+  lineCount: number
+  byteLen: number
+  first50Lines: string[]
+  /** Last 10 lines not already in first50Lines. */
+  last10Lines: string[]
+}
+
+/**
+ * How many comments generated G-code carries.
+ * Mirrors the Rust `CommentLevel` enum.
+ */
+export type CommentLevel = 'none' | 'operations' | 'verbose'
+
+/**
+ * What to generate: operations, post-processor and output options.
+ * Mirrors the Rust `ProgramParams` struct.
+ */
+export interface ProgramParams {
   operationIds: string[]
   postProcessorId: string
-  outputPath: string
   programNumber?: number
-  includeComments: boolean
+  /** Comment density; defaults to `'none'`. */
+  comments?: CommentLevel
+  /** @deprecated Use `comments`; `true` maps to `'verbose'`. */
+  includeComments?: boolean
+  /** Insert an optional stop (M01) between operations. */
+  optionalStopBetweenOps?: boolean
+  /** Safe Z for retracts between passes and operations. */
+  clearanceZ?: number
+  /** Open the program with post, program number and runtime comments. */
+  emitStatsHeader?: boolean
+}
+
+/**
+ * Parameters for exporting G-code to a file.
+ * Mirrors the Rust `ExportParams` struct.
+ */
+export interface ExportParams extends ProgramParams {
+  outputPath: string
+  createDirs?: boolean
 }
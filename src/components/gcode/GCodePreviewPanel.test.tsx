@@ -9,6 +9,7 @@
 import { render, screen, fireEvent, waitFor } from '@testing-library/react'
 import { GCodePreviewPanel } from './GCodePreviewPanel'
 import { useProjectStore } from '../../store/projectStore'
+import type { GenerateResult } from '../../api/types'
 
 // ── Module mocks ──────────────────────────────────────────────────────────────
 
@@ -34,14 +35,18 @@ const PP_LIST = [
   { id: 'fanuc-0i', name: 'Fanuc 0i', description: '' },
 ]
 
+function preview(gcode: string): GenerateResult {
+  return { gcode, warnings: [], stats: { lineCount: 0, toolChanges: 0, estimatedSeconds: null } }
+}
+
 // ── Setup ─────────────────────────────────────────────────────────────────────
 
 beforeEach(() => {
   vi.clearAllMocks()
   useProjectStore.setState({ snapshot: null, selectedOperationId: null, notifications: [] })
   vi.mocked(toolpathApi.listPostProcessors).mockResolvedValue(PP_LIST)
-  vi.mocked(toolpathApi.getGcodePreview).mockResolvedValue('')
-  vi.mocked(toolpathApi.exportGcode).mockResolvedValue(undefined)
+  vi.mocked(toolpathApi.getGcodePreview).mockResolvedValue(preview(''))
+  vi.mocked(toolpathApi.exportGcode).mockResolvedValue([])
 })
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -64,7 +69,7 @@ describe('GCodePreviewPanel', () => {
 
   it('renders gcode text when preview available', async () => {
     vi.mocked(toolpathApi.listPostProcessors).mockResolvedValue([{ id: 'linuxcnc', name: 'LinuxCNC', description: '' }])
-    vi.mocked(toolpathApi.getGcodePreview).mockResolvedValue('G00 X0 Y0\nG01 X10')
+    vi.mocked(toolpathApi.getGcodePreview).mockResolvedValue(preview('G00 X0 Y0\nG01 X10'))
     useProjectStore.setState({ selectedOperationId: OP_ID, notifications: [] })
     render(<GCodePreviewPanel />)
     await waitFor(() => {
@@ -74,8 +79,8 @@ describe('GCodePreviewPanel', () => {
 
   it('calls exportGcode when Export button clicked', async () => {
     vi.mocked(dialogApi.save).mockResolvedValue('/tmp/output.nc')
-    vi.mocked(toolpathApi.getGcodePreview).mockResolvedValue('G00 X0 Y0')
-    vi.mocked(toolpathApi.exportGcode).mockResolvedValue(undefined)
+    vi.mocked(toolpathApi.getGcodePreview).mockResolvedValue(preview('G00 X0 Y0'))
+    vi.mocked(toolpathApi.exportGcode).mockResolvedValue([])
     useProjectStore.setState({ selectedOperationId: OP_ID, notifications: [] })
     render(<GCodePreviewPanel />)
 
@@ -93,6 +98,18 @@ describe('GCodePreviewPanel', () => {
     })
   })
 
+  it('pushes preview warnings as notifications', async () => {
+    vi.mocked(toolpathApi.getGcodePreview).mockResolvedValue({
+      ...preview('G01 X10 F5'),
+      warnings: ['feed raised to minimum'],
+    })
+    useProjectStore.setState({ selectedOperationId: OP_ID, notifications: [] })
+    render(<GCodePreviewPanel />)
+    await waitFor(() => {
+      expect(useProjectStore.getState().notifications).toContain('feed raised to minimum')
+    })
+  })
+
   it('post-processor selector populated from listPostProcessors', async () => {
     useProjectStore.setState({ selectedOperationId: OP_ID, notifications: [] })
     render(<GCodePreviewPanel />)
@@ -46,8 +46,9 @@ export function GCodePreviewPanel() {
     }
     setLoading(true)
     getGcodePreview(selectedOperationId, selectedPpId)
-      .then((text) => {
-        setGcode(text)
+      .then((result) => {
+        setGcode(result.gcode)
+        result.warnings.forEach((w) => pushNotification(w))
       })
       .catch((err: unknown) => {
         const e = toAppError(err)
@@ -65,12 +66,13 @@ export function GCodePreviewPanel() {
     const path = await save({ filters: [{ name: 'NC Files', extensions: ['nc'] }] })
     if (!path) return
     try {
-      await exportGcode({
+      const warnings = await exportGcode({
         operationIds: [selectedOperationId],
         postProcessorId: selectedPpId,
         outputPath: path,
-        includeComments: true,
+        comments: 'verbose',
       })
+      warnings.forEach((w) => pushNotification(w))
     } catch (err: unknown) {
       const e = toAppError(err)
       pushNotification(`Export failed: ${e.message ?? e.kind}`)
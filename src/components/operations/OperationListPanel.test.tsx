@@ -37,6 +37,8 @@ const SNAPSHOT_WITH_OPS: ProjectSnapshot = {
   modelPath: null,
   modelChecksum: null,
   projectName: 'Test',
+  description: '',
+  units: 'mm',
   modifiedAt: '',
   tools: [{ id: TOOL_ID, name: '10mm Flat Endmill', toolType: 'flat_endmill' }],
   stock: null,
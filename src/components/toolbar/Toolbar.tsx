@@ -48,7 +48,7 @@ export function Toolbar() {
 
   async function handleOpenModel() {
     const path = await open({
-      filters: [{ name: 'CAD Files', extensions: ['step', 'stp', 'stl'] }],
+      filters: [{ name: 'CAD Files', extensions: ['step', 'stp', 'stl', '3mf'] }],
     })
     if (!path) return
     try {
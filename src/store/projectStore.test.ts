@@ -13,6 +13,8 @@ const SNAPSHOT: ProjectSnapshot = {
   modelPath: '/home/user/part.step',
   modelChecksum: 'abc123def456',
   projectName: 'Test Project',
+  description: '',
+  units: 'mm',
   modifiedAt: '2026-01-01T00:00:00Z',
   tools: [],
   stock: null,
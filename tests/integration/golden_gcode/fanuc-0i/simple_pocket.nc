@@ -18,9 +18,11 @@ N150 Y30
 N160 X0
 N170 Y0
 N180 G00 Z10
-N190 M05
-N200 G28 G91 Z0.
-N210 G90
-N220 G28 X0. Y0.
-N230 M30
+N190 G40 G49 G80
+N200 M09
+N210 M05
+N220 G28 G91 Z0.
+N230 G90
+N240 G28 X0. Y0.
+N250 M30
 %
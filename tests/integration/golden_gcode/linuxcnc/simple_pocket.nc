@@ -1,4 +1,3 @@
-1000
 N10 G90 G94 G17
 N20 G21
 N30 M05
@@ -14,6 +13,7 @@ N120 Y30
 N130 X0
 N140 Y0
 N150 G00 Z10
-N160 M05
-N170 M09
-N180 M30
+N160 G40 G49 G80
+N170 M05
+N180 M09
+N190 M30